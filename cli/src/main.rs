@@ -50,7 +50,8 @@ The following options are supported:
 
     -l, --legends PATH  Parse a legends export
         This can be included multiple times to parse multiple legends
-        exports. These should be 'legends-plus' exports from DFHack.
+        exports. Include both the '-legends.xml' and the DFHack
+        '-legends_plus.xml' export of a world to merge them.
 
     -m, --module PATH   Parse a raw module
         This can be included multiple times to parse multiple raw modules
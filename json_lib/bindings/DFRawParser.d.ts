@@ -1,5 +1,77 @@
 // This file has been generated by Specta. DO NOT EDIT.
 
+/**
+ * The manifest of an atlas, which maps each sprite to its place in the atlas image
+ */
+export type AtlasManifest = { 
+/**
+ * The file name of the atlas image
+ */
+image: string; 
+/**
+ * The width of the atlas image
+ */
+width: number; 
+/**
+ * The height of the atlas image
+ */
+height: number; 
+/**
+ * The sprites in the atlas. Sprites which use the same tiles share a rectangle.
+ */
+sprites: AtlasSprite[] }
+
+/**
+ * A rectangle in an atlas image, in pixels
+ */
+export type AtlasRect = { 
+/**
+ * The left edge
+ */
+x: number; 
+/**
+ * The top edge
+ */
+y: number; 
+/**
+ * The width
+ */
+width: number; 
+/**
+ * The height
+ */
+height: number }
+
+/**
+ * A sprite in an atlas, with what it shows
+ */
+export type AtlasSprite = { 
+/**
+ * The identifier of what the sprite shows (e.g. `DWARF` or `APPLE`)
+ */
+targetIdentifier: string; 
+/**
+ * The caste, for sprites from `CREATURE_CASTE_GRAPHICS`
+ */
+caste?: string | null; 
+/**
+ * The condition of the sprite (e.g. `DEFAULT` or `CHILD`). For layers this is `LAYER_SET:<name>`,
+ * and for plant growths `GROWTH:<growth>`.
+ */
+condition: string; 
+/**
+ * The secondary condition of the sprite (e.g. `ZOMBIE`), or the stage of a growth (e.g. `GROWTH_1`)
+ */
+secondaryCondition?: string | null; 
+/**
+ * The name of the layer, for layers of a layer set
+ */
+layer?: string | null; 
+/**
+ * Where the sprite is in the atlas image
+ */
+rect: AtlasRect }
+
 /**
  * An enum representing a biome.
  */
@@ -4552,7 +4624,15 @@ objectId: string;
 /**
  * Various `SELECT_CREATUR` modifications.
  */
-selectCreatureVariation: SelectCreature[] | null }
+selectCreatureVariation: SelectCreature[] | null; 
+/**
+ * A record of where each tag applied to this creature came from (its own definition, a copied
+ * creature, a creature variation or a `GO_TO_*` insertion).
+ * 
+ * This is only recorded when `trace_creature_resolution` is enabled in the parser options, and only
+ * for creatures which needed resolving (i.e. use `COPY_TAGS_FROM`, `APPLY_CREATURE_VARIATION`, etc.)
+ */
+resolutionTrace: TokenOrigin[] | null }
 
 /**
  * A creature effect.
@@ -5891,6 +5971,46 @@ export type CreatureVariationToken =
  */
 export type CustomGraphicExtension = { extensionType: GraphicTypeToken; tilePageId: string | null; value1: number | null; value2: number | null }
 
+/**
+ * A single problem found when evaluating module dependencies
+ */
+export type DependencyIssue = 
+/**
+ * A module requires another module which is not in the set
+ */
+{ kind: "missingRequirement"; module: string; required: string } | 
+/**
+ * A module declares a conflict with another module which is in the set
+ */
+{ kind: "conflict"; module: string; conflicts_with: string } | 
+/**
+ * A version of a module was requested which the available module is not compatible with
+ */
+{ kind: "incompatibleVersion"; module: string; requested_version: number; available_version: number; earliest_compatible_version: number } | 
+/**
+ * A module is loaded before a module it must be loaded after
+ */
+{ kind: "outOfOrder"; module: string; must_load_after: string } | 
+/**
+ * The ordering requirements between these modules form a cycle
+ */
+{ kind: "cycle"; modules: string[] }
+
+/**
+ * A report of the load order and any issues found for a set of modules
+ */
+export type DependencyReport = { 
+/**
+ * The module identifiers in the order they should be loaded.
+ * 
+ * Modules which are part of a cycle are placed at the end in their original order.
+ */
+loadOrder: string[]; 
+/**
+ * All issues found with the set of modules
+ */
+issues: DependencyIssue[] }
+
 /**
  * A struct representing a Dimensions object.
  */
@@ -7061,319 +7181,835 @@ export type EnvironmentClassToken =
 "None"
 
 /**
- * A material fuel type that can be set in a material definition.
+ * An artifact from a legends export (an `artifact` element), e.g. a named weapon or a book.
  */
-export type FuelTypeToken = 
+export type ExportedArtifact = { id: number; name: string; 
 /**
- * Charcoal or coal
+ * The name of the item of the artifact, e.g. the title of a book
  */
-"Charcoal" | 
+itemName: string | null; 
 /**
- * Coal coke
+ * The type of the item, e.g. `weapon` or `book` (only in legends-plus exports)
  */
-"Coke" | 
+itemType: string | null; 
 /**
- * No glass furnace fuel
+ * The subtype of the item, e.g. `battle axe` (only in legends-plus exports)
  */
-"NoMaterialGloss" | 
+itemSubtype: string | null; 
 /**
- * None is an invalid option, so its a hint that this is not set.
+ * The description of the item (only in legends-plus exports)
  */
-"None"
-
+itemDescription: string | null; 
 /**
- * A struct describing how a creature moves.
- * 
- * Gaits define the mechanics of movement modes like walking, swimming, or flying,
- * including speed, acceleration, and energy costs. They are defined in raw files
- * using the `[GAIT:type:name:full_speed:build_up:turning:start_speed:energy_use]` tag.
- * 
- * These optional flags go at the end:
- * 
- * * `LAYERS_SLOW` - fat/muscle layers slow the movement (muscle-slowing counter-acted by strength bonus)
- * * `STRENGTH` - strength attribute can speed/slow movement
- * * `AGILITY` - agility attribute can speed/slow movement
- * * `STEALTH_SLOWS:<n>` - n is percentage slowed
- * 
- * Instead of specifying a `build_up` you can use `NO_BUILD_UP` to instantly get to speed.
- * 
- * 
- * Examples:
- * 
- * `[CV_NEW_TAG:GAIT:WALK:Sprint:!ARG4:10:3:!ARG2:50:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:50]`
- * `[CV_NEW_TAG:GAIT:WALK:Run:!ARG3:5:3:!ARG2:10:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:20]`
- * `[CV_NEW_TAG:GAIT:WALK:Jog:!ARG2:NO_BUILD_UP:5:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:10]`
- * `[CV_NEW_TAG:GAIT:WALK:Walk:!ARG1:NO_BUILD_UP:0]`
- * `[CV_NEW_TAG:GAIT:WALK:Stroll:!ARG5:NO_BUILD_UP:0]`
- * `[CV_NEW_TAG:GAIT:WALK:Creep:!ARG6:NO_BUILD_UP:0]`
+ * The material of the item, e.g. `steel` (only in legends-plus exports)
  */
-export type Gait = { 
+material: string | null; 
 /**
- * The movement medium (e.g., [`GaitTypeTag::Walk`], [`GaitTypeTag::Swim`]).
+ * The site the artifact is at, if any
  */
-gaitType: GaitTypeToken; 
+siteId: number | null; 
 /**
- * The descriptive name of the movement (e.g., "Sprint", "Jog").
+ * The structure of the site the artifact is in, if any
  */
-name: string; 
+structureLocalId: number | null; 
 /**
- * The maximum speed achievable, where lower values are faster.
+ * The historical figure holding the artifact, if any
  */
-maxSpeed: number; 
+holderHistoricalFigureId: number | null; 
 /**
- * The time in game ticks required to reach full speed.
+ * How many pages the artifact has, if it is a book
  */
-buildUpTime: number; 
+pageCount: number | null; 
 /**
- * The maximum speed at which the creature can turn effectively.
+ * The written contents of the artifact, if it is a book or scroll
  */
-turningMax: number; 
+writtenContentIds: number[] }
+
 /**
- * The speed at which the creature begins moving from a standstill.
+ * A poetic, musical or dance form from a legends export (a `poetic_form`, `musical_form` or `dance_form`
+ * element), generated with the world.
  */
-startSpeed: number; 
+export type ExportedCulturalForm = { id: number; 
 /**
- * The fatigue or energy cost associated with this movement.
+ * The name of the form (only in legends-plus exports)
  */
-energyUse: number; 
+name: string | null; 
 /**
- * Optional modifiers affecting speed based on attributes or stealth.
+ * The description of the form (only in the export from Dwarf Fortress)
  */
-modifiers: GaitModifierToken[] }
+description: string | null }
 
 /**
- * An enum representing a gait modifier.
+ * The `ExportedEntity` struct is used to store information about an entity that has been exported
+ * 
+ * Most of the details (positions, occasions, links) are only in legends-plus exports, and the name is only
+ * in the export from Dwarf Fortress.
  */
-export type GaitModifierToken = 
+export type ExportedEntity = { entityId: number; name: string; race: string; entityType: string; childIds: number[]; links: ExportedEntityRelation[]; positions: ExportedEntityPosition[]; assignments: ExportedEntityPositionAssignment[]; occasions: ExportedEntityOccasion[]; 
 /**
- * Fat/muscle layers slow the movement (muscle-slowing counter-acted by strength bonus)
- * Makes `THICKENS_ON_ENERGY_STORAGE` and `THICKENS_ON_STRENGTH` tissue layers slow movement depending on how thick they are.
- * Adding the `STRENGTH` gait flag counteracts the impact of the latter layer.
+ * The historical figures which are members of the entity
  */
-"LayersSlow" | 
+historicalFigureIds: number[]; 
 /**
- * Speeds/slows movement depending on the creature's Strength stat.
+ * The historical figures (deities) the entity worships
  */
-"Strength" | 
+worshipIds: number[]; weapons: string[]; professions: string[] }
+
 /**
- * Speeds/slows movement depending on the creature's Agility stat.
+ * A link from a historical figure to an entity (an `entity_link` element), e.g. membership of a civilization.
  */
-"Agility" | 
+export type ExportedEntityLink = { 
 /**
- * Stealth slows movement by the specified percentage when the creature is sneaking.
+ * The type of the link, e.g. `member` or `enemy`
  */
-{ StealthSlows: { 
+linkType: string; 
 /**
- * The percentage slowed
+ * The id of the linked entity
  */
-percentage: number } } | 
+entityId: number; 
 /**
- * No build up time
+ * The strength of the link, if given
  */
-"NoBuildUp" | 
+linkStrength: number | null }
+
 /**
- * Build up time. Only used if the gait has a build up time.
+ * An occasion (festival) held by an entity (an `occasion` element).
  */
-{ BuildUp: { 
+export type ExportedEntityOccasion = { 
 /**
- * The build up time indicates how long it will take for a creature using this gait to go from `<start speed>` to `<max speed>`.
- * For example, a value of 10 means that it should be able to reach the maximum speed by moving 10 tiles in a straight line over even terrain.
+ * The id of the occasion within its entity
  */
-time: number; 
+id: number; 
 /**
- * The turning max indicates the maximum speed permissible when the creature suddenly changes its direction of motion.
- * The creature's speed will be reduced to `<max turning speed>` if traveling at a higher speed than this before turning.
+ * The name of the occasion
  */
-turning_max: number; 
+name: string; 
 /**
- * The creature's speed when it starts moving using this gait
+ * The historical event the occasion commemorates, if any
  */
-start_speed: number } }
+eventId: number | null; 
+/**
+ * The parts of the occasion
+ */
+schedules: ExportedOccasionSchedule[] }
 
 /**
- * An enum representing a gait type.
+ * An entity population from a legends export (an `entity_population` element), i.e. the members of a
+ * civilization which aren't historical figures.
  */
-export type GaitTypeToken = 
+export type ExportedEntityPopulation = { id: number; 
 /**
- * Travel on foot/the ground
- * Used for moving normally over ground tiles.
+ * The civilization the population belongs to (only in legends-plus exports)
  */
-"Walk" | 
+civId: number | null; 
 /**
- * Travel on foot/the ground
- * Used for moving over ground tiles whilst prone.
+ * The races of the population with how many of each there are (only in legends-plus exports)
  */
-"Crawl" | 
+races: ([string, number])[] }
+
 /**
- * Climbing on walls, etc.
- * Used for moving whilst climbing.
+ * A position of an entity (an `entity_position` element), e.g. a king.
  */
-"Climb" | 
+export type ExportedEntityPosition = { 
 /**
- * Swimming in water/liquid
- * Used for moving through tiles containing water or magma at a depth of at least 4/7.
+ * The id of the position within its entity
  */
-"Swim" | 
+id: number; 
 /**
- * Flying through the air
- * Used for moving through open space.
+ * The name of the position
  */
-"Fly" | 
+name: string; 
 /**
- * Other gait type which is unexpected, but we can still handle it
+ * The name of the position when held by a male
  */
-{ Other: string } | 
+nameMale: string | null; 
 /**
- * Unknown gait type (unset)
+ * The name of the position when held by a female
  */
-"Unknown"
-
+nameFemale: string | null; 
 /**
- * A struct representing a Graphic object.
- * 
- * Stores data about layers and sprites defined in the graphic raw.
+ * The name of the spouse of the holder of the position
  */
-export type Graphic = { 
+spouse: string | null; 
 /**
- * The `metadata` field is of type `RawMetadata` and is used to provide additional information
- * about the raws the `Graphic` is found in.
+ * The name of the spouse of the holder of the position when male
  */
-metadata: Metadata | null; 
+spouseMale: string | null; 
 /**
- * The `identifier` field is a string that represents the identifier of the graphic. It is used
- * to uniquely identify the graphic
+ * The name of the spouse of the holder of the position when female
  */
-identifier: string; 
+spouseFemale: string | null }
+
 /**
- * A generated id that is used to uniquely identify this object.
- * 
- * This is deterministic based on the following:
- * * The raw's `identifier`
- * * The raw's [`ObjectType`]
- * * [`RawModuleLocation`] where the raw was found
- * * The containing module's `numeric_version`
- * 
- * See [`crate::utilities::generate_object_id`]
+ * An assignment of a position of an entity (an `entity_position_assignment` element), i.e. who holds it.
  */
-objectId: string; 
+export type ExportedEntityPositionAssignment = { 
 /**
- * An optional identifier targeting a specific caste
+ * The id of the assignment within its entity
  */
-casteIdentifier: string | null; 
+id: number; 
 /**
- * The type of graphic
+ * The historical figure holding the position, or `None` if it is vacant
  */
-kind: GraphicTypeToken; 
+historicalFigureId: number | null; 
 /**
- * A vector of sprites defined in the raw
+ * The position assigned
  */
-sprites: SpriteGraphic[] | null; 
+positionId: number | null; 
 /**
- * A vector of layers defined in the raw
+ * The squad led by the holder of the position, if any
  */
-layers: ([string, SpriteLayer[]])[] | null; 
+squadId: number | null }
+
 /**
- * A vector of growths defined in the raw
+ * A link from an entity to another (an `entity_link` element of an entity), e.g. to its parent civilization.
  */
-growths: ([string, SpriteGraphic[]])[] | null; 
+export type ExportedEntityRelation = { 
 /**
- * A vector of custom extensions defined in the raw
+ * The type of the link, e.g. `PARENT` or `CHILD`
  */
-customExtensions: CustomGraphicExtension[] | null; 
+linkType: string; 
 /**
- * A vector of the defined tags in the raw
+ * The entity linked to
  */
-tags: string[] | null; 
+targetId: number; 
 /**
- * The palletes used or defined in the raw
+ * How strong the link is
  */
-palletes: GraphicPalette[] }
+strength: number }
 
 /**
- * A struct representing a Graphic object.
+ * A collection of historical events from a legends export (a `historical_event_collection` element), e.g.
+ * a war, battle or duel.
  */
-export type GraphicPalette = { 
+export type ExportedEventCollection = { id: number; name: string | null; collectionType: string; 
 /**
- * Name of the palette
+ * `None` if the start year wasn't exported
  */
-name: string; 
+startYear: number | null; startSeconds72: number | null; 
 /**
- * Relative file path to the palette file
+ * `None` while the collection is ongoing (exported as `-1`)
  */
-file: string; 
+endYear: number | null; endSeconds72: number | null; eventIds: number[]; childCollectionIds: number[]; 
 /**
- * Default row of the palette
+ * The fields specific to the type of the collection, as `(name, value)`
  */
-defaultRow: number }
+fields: ([string, string])[] }
 
 /**
- * The graphic type of the tile
+ * A historical event from a legends export (a `historical_event` element).
+ * 
+ * The fields every event has are kept as fields. The fields which depend on the type of the event are kept
+ * in the order they were exported, and the historical figures, entities and sites they refer to are
+ * collected to be able to build timelines.
  */
-export type GraphicTypeToken = 
+export type ExportedHistoricalEvent = { id: number; 
 /**
- * The tile is a creature
+ * `None` if the year wasn't exported
  */
-"creature" | 
+year: number | null; 
 /**
- * The tile is a creature caste
+ * The time within the year, if known (exported as `-1` when not)
  */
-"creatureCaste" | 
+seconds72: number | null; eventType: string; 
 /**
- * The tile is a statue of a creature
+ * The fields specific to the type of the event, as `(name, value)`
  */
-"statueCreature" | 
+fields: ([string, string])[]; historicalFigureIds: number[]; entityIds: number[]; siteIds: number[] }
+
 /**
- * The tile is a statue of a creature caste
+ * A historical figure from a legends export (a `historical_figure` element).
  */
-"statueCreatureCaste" | 
+export type ExportedHistoricalFigure = { id: number; name: string; race: string; caste: string; 
 /**
- * The tile is a statue of a creature caste with a giant size
+ * `None` if the birth year wasn't exported
  */
-"statuesSurfaceGiant" | 
+birthYear: number | null; 
 /**
- * A tile
+ * `None` while the figure is alive (exported as `-1`)
  */
-"tile" | 
+deathYear: number | null; associatedType: string; entityLinks: ExportedEntityLink[]; historicalFigureLinks: ExportedHistoricalFigureLink[]; skills: ExportedSkill[]; spheres: string[]; activeInteractions: string[]; 
 /**
- * An empty tile
+ * Empty elements given for the figure, e.g. `deity` or `force`
  */
-"empty" | 
+flags: string[] }
+
 /**
- * A plant
+ * A link between two historical figures (an `hf_link` element), e.g. `mother` or `spouse`.
  */
-"plant" | 
+export type ExportedHistoricalFigureLink = { 
 /**
- * An unknown type
+ * The type of the link, e.g. `mother`, `spouse` or `deity`
  */
-"unknown" | 
+linkType: string; 
 /**
- * A template
+ * The id of the linked historical figure
  */
-"template" | 
+historicalFigureId: number; 
 /**
- * The tile is soil background
+ * The strength of the link, if given
  */
-"soilBackground" | 
+linkStrength: number | null }
+
 /**
- * The tile is grass-1
+ * A part of an occasion (a `schedule` element), e.g. a procession or a competition.
  */
-"grass1" | 
+export type ExportedOccasionSchedule = { 
 /**
- * The tile is grass-2
+ * The id of the schedule within its occasion
  */
-"grass2" | 
+id: number; 
 /**
- * The tile is grass-3
+ * The type of the schedule, e.g. `procession` or `poetry_recital`
  */
-"grass3" | 
+scheduleType: string; 
 /**
- * The tile is grass-4
+ * The type of item involved, if any (e.g. the prize of a competition)
  */
-"grass4" | 
+itemType: string | null; 
 /**
- * The tile is custom edging
+ * The subtype of item involved, if any
  */
-"customEdging" | 
+itemSubtype: string | null; 
+/**
+ * What the schedule refers to, e.g. the poetic form recited
+ */
+reference: number | null; 
+/**
+ * What else the schedule refers to
+ */
+reference2: number | null }
+
+/**
+ * A region of the surface of the world from a legends export (a `region` element).
+ */
+export type ExportedRegion = { id: number; name: string; regionType: string; 
+/**
+ * The world map tiles of the region (only in legends-plus exports)
+ */
+coordinates: Dimensions[]; 
+/**
+ * How good or evil the region is, e.g. `neutral` (only in legends-plus exports)
+ */
+evilness: string | null }
+
+/**
+ * A site from a legends export (a `site` element), e.g. a fortress, hamlet or cave.
+ */
+export type ExportedSite = { id: number; name: string; siteType: string; 
+/**
+ * The world map tile the site is on
+ */
+coordinates: Dimensions | null; 
+/**
+ * The corners of the area the site covers, in region tiles
+ */
+rectangle: [Dimensions, Dimensions] | null; 
+/**
+ * The civilization the site belongs to (only in legends-plus exports)
+ */
+civId: number | null; 
+/**
+ * The entity currently holding the site (only in legends-plus exports)
+ */
+currentOwnerId: number | null; structures: ExportedStructure[]; properties: ExportedSiteProperty[] }
+
+/**
+ * A property of a site (a `site_property` element), e.g. a house and its owner.
+ */
+export type ExportedSiteProperty = { 
+/**
+ * The id of the property within its site
+ */
+id: number; 
+/**
+ * The type of the property, e.g. `house`
+ */
+propertyType: string | null; 
+/**
+ * The historical figure which owns the property, if any
+ */
+ownerHistoricalFigureId: number | null; 
+/**
+ * The structure of the site the property is, if any
+ */
+structureId: number | null }
+
+/**
+ * A skill of a historical figure (an `hf_skill` element).
+ */
+export type ExportedSkill = { 
+/**
+ * The skill, e.g. `MINING`
+ */
+skill: string; 
+/**
+ * The experience in the skill
+ */
+totalIp: number }
+
+/**
+ * A structure of a site (a `structure` element), e.g. a temple, tavern or library.
+ */
+export type ExportedStructure = { 
+/**
+ * The id of the structure within its site
+ */
+localId: number; 
+/**
+ * The type of the structure, e.g. `temple`
+ */
+structureType: string; 
+/**
+ * The name of the structure
+ */
+name: string; 
+/**
+ * The entity which owns the structure, if any
+ */
+entityId: number | null; 
+/**
+ * The historical figure (deity) worshipped at the structure, if any
+ */
+worshipHistoricalFigureId: number | null }
+
+/**
+ * A region below the surface of the world from a legends export (an `underground_region` element), e.g. a
+ * cavern layer.
+ */
+export type ExportedUndergroundRegion = { id: number; regionType: string; 
+/**
+ * `None` if the depth wasn't exported
+ */
+depth: number | null; 
+/**
+ * The world map tiles of the region (only in legends-plus exports)
+ */
+coordinates: Dimensions[] }
+
+/**
+ * A world construction from a legends export (a `world_construction` element).
+ */
+export type ExportedWorldConstruction = { id: number; name: string; constructionType: string; 
+/**
+ * The world map tiles the construction passes through
+ */
+coordinates: Dimensions[] }
+
+/**
+ * A written work from a legends export (a `written_content` element), e.g. a poem or a treatise.
+ */
+export type ExportedWrittenContent = { id: number; title: string; 
+/**
+ * The historical figure who wrote the work
+ */
+authorHistoricalFigureId: number | null; 
+/**
+ * The type of the work, e.g. `Poem` or `MusicalComposition`
+ */
+contentType: string | null; 
+/**
+ * The poetic, musical or dance form the work follows, if any
+ */
+formId: number | null; 
+/**
+ * The styles of the work, e.g. `meandering`
+ */
+styles: string[]; pageStart: number | null; pageEnd: number | null; 
+/**
+ * What the work is about
+ */
+references: ExportedWrittenContentReference[] }
+
+/**
+ * What a written work is about (a `reference` element), e.g. a historical figure or a site.
+ */
+export type ExportedWrittenContentReference = { 
+/**
+ * The type of object referred to, e.g. `HISTORICAL_FIGURE` or `SITE`
+ */
+referenceType: string; 
+/**
+ * The id of the object referred to
+ */
+id: number | null }
+
+/**
+ * A material fuel type that can be set in a material definition.
+ */
+export type FuelTypeToken = 
+/**
+ * Charcoal or coal
+ */
+"Charcoal" | 
+/**
+ * Coal coke
+ */
+"Coke" | 
+/**
+ * No glass furnace fuel
+ */
+"NoMaterialGloss" | 
+/**
+ * None is an invalid option, so its a hint that this is not set.
+ */
+"None"
+
+/**
+ * A struct describing how a creature moves.
+ * 
+ * Gaits define the mechanics of movement modes like walking, swimming, or flying,
+ * including speed, acceleration, and energy costs. They are defined in raw files
+ * using the `[GAIT:type:name:full_speed:build_up:turning:start_speed:energy_use]` tag.
+ * 
+ * These optional flags go at the end:
+ * 
+ * * `LAYERS_SLOW` - fat/muscle layers slow the movement (muscle-slowing counter-acted by strength bonus)
+ * * `STRENGTH` - strength attribute can speed/slow movement
+ * * `AGILITY` - agility attribute can speed/slow movement
+ * * `STEALTH_SLOWS:<n>` - n is percentage slowed
+ * 
+ * Instead of specifying a `build_up` you can use `NO_BUILD_UP` to instantly get to speed.
+ * 
+ * 
+ * Examples:
+ * 
+ * `[CV_NEW_TAG:GAIT:WALK:Sprint:!ARG4:10:3:!ARG2:50:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:50]`
+ * `[CV_NEW_TAG:GAIT:WALK:Run:!ARG3:5:3:!ARG2:10:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:20]`
+ * `[CV_NEW_TAG:GAIT:WALK:Jog:!ARG2:NO_BUILD_UP:5:LAYERS_SLOW:STRENGTH:AGILITY:STEALTH_SLOWS:10]`
+ * `[CV_NEW_TAG:GAIT:WALK:Walk:!ARG1:NO_BUILD_UP:0]`
+ * `[CV_NEW_TAG:GAIT:WALK:Stroll:!ARG5:NO_BUILD_UP:0]`
+ * `[CV_NEW_TAG:GAIT:WALK:Creep:!ARG6:NO_BUILD_UP:0]`
+ */
+export type Gait = { 
+/**
+ * The movement medium (e.g., [`GaitTypeTag::Walk`], [`GaitTypeTag::Swim`]).
+ */
+gaitType: GaitTypeToken; 
+/**
+ * The descriptive name of the movement (e.g., "Sprint", "Jog").
+ */
+name: string; 
+/**
+ * The maximum speed achievable, where lower values are faster.
+ */
+maxSpeed: number; 
+/**
+ * The time in game ticks required to reach full speed.
+ */
+buildUpTime: number; 
+/**
+ * The maximum speed at which the creature can turn effectively.
+ */
+turningMax: number; 
+/**
+ * The speed at which the creature begins moving from a standstill.
+ */
+startSpeed: number; 
+/**
+ * The fatigue or energy cost associated with this movement.
+ */
+energyUse: number; 
+/**
+ * Optional modifiers affecting speed based on attributes or stealth.
+ */
+modifiers: GaitModifierToken[] }
+
+/**
+ * An enum representing a gait modifier.
+ */
+export type GaitModifierToken = 
+/**
+ * Fat/muscle layers slow the movement (muscle-slowing counter-acted by strength bonus)
+ * Makes `THICKENS_ON_ENERGY_STORAGE` and `THICKENS_ON_STRENGTH` tissue layers slow movement depending on how thick they are.
+ * Adding the `STRENGTH` gait flag counteracts the impact of the latter layer.
+ */
+"LayersSlow" | 
+/**
+ * Speeds/slows movement depending on the creature's Strength stat.
+ */
+"Strength" | 
+/**
+ * Speeds/slows movement depending on the creature's Agility stat.
+ */
+"Agility" | 
+/**
+ * Stealth slows movement by the specified percentage when the creature is sneaking.
+ */
+{ StealthSlows: { 
+/**
+ * The percentage slowed
+ */
+percentage: number } } | 
+/**
+ * No build up time
+ */
+"NoBuildUp" | 
+/**
+ * Build up time. Only used if the gait has a build up time.
+ */
+{ BuildUp: { 
+/**
+ * The build up time indicates how long it will take for a creature using this gait to go from `<start speed>` to `<max speed>`.
+ * For example, a value of 10 means that it should be able to reach the maximum speed by moving 10 tiles in a straight line over even terrain.
+ */
+time: number; 
+/**
+ * The turning max indicates the maximum speed permissible when the creature suddenly changes its direction of motion.
+ * The creature's speed will be reduced to `<max turning speed>` if traveling at a higher speed than this before turning.
+ */
+turning_max: number; 
+/**
+ * The creature's speed when it starts moving using this gait
+ */
+start_speed: number } }
+
+/**
+ * An enum representing a gait type.
+ */
+export type GaitTypeToken = 
+/**
+ * Travel on foot/the ground
+ * Used for moving normally over ground tiles.
+ */
+"Walk" | 
+/**
+ * Travel on foot/the ground
+ * Used for moving over ground tiles whilst prone.
+ */
+"Crawl" | 
+/**
+ * Climbing on walls, etc.
+ * Used for moving whilst climbing.
+ */
+"Climb" | 
+/**
+ * Swimming in water/liquid
+ * Used for moving through tiles containing water or magma at a depth of at least 4/7.
+ */
+"Swim" | 
+/**
+ * Flying through the air
+ * Used for moving through open space.
+ */
+"Fly" | 
+/**
+ * Other gait type which is unexpected, but we can still handle it
+ */
+{ Other: string } | 
+/**
+ * Unknown gait type (unset)
+ */
+"Unknown"
+
+/**
+ * A struct representing a Graphic object.
+ * 
+ * Stores data about layers and sprites defined in the graphic raw.
+ */
+export type Graphic = { 
+/**
+ * The `metadata` field is of type `RawMetadata` and is used to provide additional information
+ * about the raws the `Graphic` is found in.
+ */
+metadata: Metadata | null; 
+/**
+ * The `identifier` field is a string that represents the identifier of the graphic. It is used
+ * to uniquely identify the graphic
+ */
+identifier: string; 
+/**
+ * A generated id that is used to uniquely identify this object.
+ * 
+ * This is deterministic based on the following:
+ * * The raw's `identifier`
+ * * The raw's [`ObjectType`]
+ * * [`RawModuleLocation`] where the raw was found
+ * * The containing module's `numeric_version`
+ * 
+ * See [`crate::utilities::generate_object_id`]
+ */
+objectId: string; 
+/**
+ * An optional identifier targeting a specific caste
+ */
+casteIdentifier: string | null; 
+/**
+ * The type of graphic
+ */
+kind: GraphicTypeToken; 
+/**
+ * A vector of sprites defined in the raw
+ */
+sprites: SpriteGraphic[] | null; 
+/**
+ * A vector of layers defined in the raw
+ */
+layers: ([string, SpriteLayer[]])[] | null; 
+/**
+ * A vector of growths defined in the raw
+ */
+growths: ([string, SpriteGraphic[]])[] | null; 
+/**
+ * A vector of custom extensions defined in the raw
+ */
+customExtensions: CustomGraphicExtension[] | null; 
+/**
+ * A vector of the defined tags in the raw
+ */
+tags: string[] | null; 
+/**
+ * The palletes used or defined in the raw
+ */
+palletes: GraphicPalette[] }
+
+/**
+ * The category of things drawn by a [`GraphicTypeToken`]
+ */
+export type GraphicCategory = 
+/**
+ * Creatures, their castes and their statues
+ */
+"creature" | 
+/**
+ * Plants (trees, shrubs and crops)
+ */
+"plant" | 
+/**
+ * Items, which are targeted by their item identifier (e.g. `ITEM_TOOL_CAULDRON`)
+ */
+"item" | 
+/**
+ * Buildings and items placed in buildings (e.g. weapon traps and workshops)
+ */
+"building" | 
+/**
+ * Terrain, like grass and the edges of custom grasses
+ */
+"terrain" | 
+/**
+ * Icons for the interface
+ */
+"interface" | 
+/**
+ * Anything else (tiles without a specific type and templates)
+ */
+"other"
+
+/**
+ * A struct representing a Graphic object.
+ */
+export type GraphicPalette = { 
+/**
+ * Name of the palette
+ */
+name: string; 
+/**
+ * Relative file path to the palette file
+ */
+file: string; 
+/**
+ * Default row of the palette
+ */
+defaultRow: number }
+
+/**
+ * A simplified struct for a palette (`LS_PALETTE`) defined in a graphics raw
+ */
+export type GraphicPaletteData = { 
+/**
+ * database id for this palette
+ */
+id: string; 
+/**
+ * linked raw id (of graphics raw) this belongs to
+ */
+rawId: string; 
+/**
+ * the identifier of the thing using this palette
+ */
+targetIdentifier: string; 
+/**
+ * the name of the palette
+ */
+name: string; 
+/**
+ * path to the palette image
+ */
+filePath: string; 
+/**
+ * the row of the palette image used by default
+ */
+defaultRow: number }
+
+/**
+ * The graphic type of the tile
+ */
+export type GraphicTypeToken = 
+/**
+ * The tile is a creature
+ */
+"creature" | 
+/**
+ * The tile is a creature caste
+ */
+"creatureCaste" | 
+/**
+ * The tile is a statue of a creature
+ */
+"statueCreature" | 
+/**
+ * The tile is a statue of a creature caste
+ */
+"statueCreatureCaste" | 
+/**
+ * The tile is a statue of a creature caste with a giant size
+ */
+"statuesSurfaceGiant" | 
+/**
+ * A tile
+ */
+"tile" | 
+/**
+ * An empty tile
+ */
+"empty" | 
+/**
+ * A plant
+ */
+"plant" | 
+/**
+ * An unknown type
+ */
+"unknown" | 
+/**
+ * A template
+ */
+"template" | 
+/**
+ * The tile is soil background
+ */
+"soilBackground" | 
+/**
+ * The tile is grass-1
+ */
+"grass1" | 
+/**
+ * The tile is grass-2
+ */
+"grass2" | 
+/**
+ * The tile is grass-3
+ */
+"grass3" | 
+/**
+ * The tile is grass-4
+ */
+"grass4" | 
+/**
+ * The tile is custom edging
+ */
+"customEdging" | 
 /**
  * The tile is custom ramp
  */
@@ -7787,9 +8423,121 @@ export type GraphicTypeToken =
  */
 "weaponUpright9B" | 
 /**
- * The graphic is of a weapon upright 10-B
+ * The graphic is of a weapon upright 10-B
+ */
+"weaponUpright10B"
+
+/**
+ * A report of the graphics coverage of a set of parsed raws, per module
+ */
+export type GraphicsCoverageReport = { 
+/**
+ * The coverage of each module, in the order the modules' raws were found
+ */
+modules: ModuleGraphicsCoverage[] }
+
+/**
+ * A single problem found when validating graphics and tile pages
+ */
+export type GraphicsIssue = 
+/**
+ * A sprite uses a tile page which wasn't parsed
+ */
+{ kind: "missingTilePage"; graphic: string; module: string; sprite: string; tile_page: string } | 
+/**
+ * A sprite uses tiles which are outside of its tile page
+ */
+{ kind: "tileOutOfRange"; graphic: string; module: string; sprite: string; tile_page: string; offset: Dimensions; offset2: Dimensions | null; page_dim: Dimensions } | 
+/**
+ * The image of a tile page doesn't exist
+ */
+{ kind: "missingImage"; tile_page: string; module: string; path: string } | 
+/**
+ * The image of a tile page exists but couldn't be read
+ */
+{ kind: "unreadableImage"; tile_page: string; module: string; path: string; error: string } | 
+/**
+ * The image of a tile page isn't the size declared by its `TILE_DIM` and `PAGE_DIM`
+ */
+{ kind: "imageSizeMismatch"; tile_page: string; module: string; path: string; expected: Dimensions; actual: Dimensions } | 
+/**
+ * The size of a tile page declared by its `TILE_DIM` and `PAGE_DIM` is negative or too large
+ */
+{ kind: "invalidPageSize"; tile_page: string; module: string; tile_dim: Dimensions; page_dim: Dimensions }
+
+/**
+ * How a plant growth is drawn for part of the year, defined with the `GROWTH_PRINT` key.
+ * 
+ * A growth can have several prints with different timings (e.g. unripe and ripe fruit), which are the
+ * stages of the growth. The first print is drawn by `GROWTH_1` in the graphics, the second by
+ * `GROWTH_2`, and so on.
+ */
+export type GrowthPrint = { 
+/**
+ * The tile drawn when the growth is on a tree
+ */
+treeTile: string; 
+/**
+ * The tile drawn when the growth is on a shrub
+ */
+shrubTile: string; 
+/**
+ * The color of the tiles
+ */
+color: Color; 
+/**
+ * The part of the year the print is used, in ticks. `None` is all year (`ALL`).
+ */
+timing: [number, number] | null; 
+/**
+ * Decides which growth is drawn when more than one is on a tile, the highest is drawn
+ */
+priority: number }
+
+/**
+ * A simplified struct for the sprite of a plant growth
+ */
+export type GrowthSpriteData = { 
+/**
+ * database id for this growth sprite
+ */
+id: string; 
+/**
+ * linked raw id (of graphics raw) this belongs to
+ */
+rawId: string; 
+/**
+ * the identifier of the plant this sprite displays
+ */
+targetIdentifier: string; 
+/**
+ * the identifier of the growth, e.g. `FRUIT`
+ */
+growthIdentifier: string; 
+/**
+ * the stage of the growth this sprite is for, e.g. `GROWTH_1`
+ */
+growthStage: string | null; 
+/**
+ * identifier of tile page sprite is on
+ */
+tilePageIdentifier: string; 
+/**
+ * sprite offset x1
+ */
+offsetX: number; 
+/**
+ * sprite offset y1
+ */
+offsetY: number; 
+/**
+ * for large sprites, offset x2
+ */
+offsetX2: number | null; 
+/**
+ * for large sprites, offset y2
  */
-"weaponUpright10B"
+offsetY2: number | null }
 
 /**
  * The growth tag of the tile
@@ -7980,6 +8728,93 @@ export type InorganicToken =
  */
 "Unknown"
 
+/**
+ * The entities, historical figures, sites, regions, world constructions, history and culture of a world
+ * from its legends exports.
+ */
+export type LegendsWorld = { 
+/**
+ * The entities of the world, e.g. civilizations and their site governments
+ */
+entities: ExportedEntity[]; 
+/**
+ * The members of the civilizations of the world which aren't historical figures
+ */
+entityPopulations: ExportedEntityPopulation[]; 
+/**
+ * The historical figures of the world
+ */
+historicalFigures: ExportedHistoricalFigure[]; 
+/**
+ * The sites of the world
+ */
+sites: ExportedSite[]; 
+/**
+ * The regions of the surface of the world
+ */
+regions: ExportedRegion[]; 
+/**
+ * The regions below the surface of the world
+ */
+undergroundRegions: ExportedUndergroundRegion[]; 
+/**
+ * The roads, bridges, tunnels and walls of the world
+ */
+worldConstructions: ExportedWorldConstruction[]; 
+/**
+ * The historical events of the world
+ */
+historicalEvents: ExportedHistoricalEvent[]; 
+/**
+ * The collections of historical events of the world, e.g. wars and battles
+ */
+historicalEventCollections: ExportedEventCollection[]; 
+/**
+ * The artifacts of the world, e.g. named weapons and books
+ */
+artifacts: ExportedArtifact[]; 
+/**
+ * The written works of the world, e.g. poems and treatises
+ */
+writtenContents: ExportedWrittenContent[]; 
+/**
+ * The poetic forms generated with the world
+ */
+poeticForms: ExportedCulturalForm[]; 
+/**
+ * The musical forms generated with the world
+ */
+musicalForms: ExportedCulturalForm[]; 
+/**
+ * The dance forms generated with the world
+ */
+danceForms: ExportedCulturalForm[] }
+
+/**
+ * A simplified struct for a world read from a legends export
+ */
+export type LegendsWorldData = { 
+/**
+ * database id for this world
+ */
+id: string; 
+/**
+ * the name the world was stored with
+ */
+name: string; 
+/**
+ * how many historical figures are stored for the world
+ */
+historicalFigureCount: number; 
+/**
+ * how many sites are stored for the world
+ */
+siteCount: number; 
+/**
+ * how many historical events are stored for the world
+ */
+historicalEventCount: number }
+
 /**
  * Helper struct for managing locations related to the game directory and user directory.
  */
@@ -8942,6 +9777,10 @@ export type ModificationToken =
 identifier: string } } | 
 /**
  * `APPLY_CREATURE_VARIATION` tag
+ * 
+ * When reading raw files, `APPLY_CREATURE_VARIATION` is kept in place within the raws so that it
+ * applies to the castes selected at that point. This variant applies the variation to the castes
+ * selected when it is reached during resolution.
  */
 { ApplyCreatureVariation: { 
 /**
@@ -9003,6 +9842,57 @@ raws: string[] } } |
  */
 raws: string[] } }
 
+/**
+ * The graphics coverage of the creatures, plants and graphics from one module
+ */
+export type ModuleGraphicsCoverage = { 
+/**
+ * The name of the module
+ */
+module: string; 
+/**
+ * The `object_id` of the module
+ */
+moduleObjectId: string; 
+/**
+ * The number of creatures defined in the module
+ */
+creatures: string; 
+/**
+ * The number of creatures defined in the module which have graphics (from any module)
+ */
+creaturesWithGraphics: string; 
+/**
+ * The number of plants defined in the module
+ */
+plants: string; 
+/**
+ * The number of plants defined in the module which have graphics (from any module)
+ */
+plantsWithGraphics: string; 
+/**
+ * Creatures with no `CREATURE_GRAPHICS` or `CREATURE_CASTE_GRAPHICS`
+ */
+creaturesWithoutGraphics: string[]; 
+/**
+ * Castes (as `CREATURE:CASTE`) with no graphics, for creatures which only have
+ * `CREATURE_CASTE_GRAPHICS` for some of their castes
+ */
+castesWithoutGraphics: string[]; 
+/**
+ * Plants with no `PLANT_GRAPHICS`
+ */
+plantsWithoutGraphics: string[]; 
+/**
+ * Growths (as `PLANT:GROWTH`) with no sprites in the `PLANT_GRAPHICS` of their plant
+ */
+growthsWithoutGraphics: string[]; 
+/**
+ * Graphics whose creature, caste, plant or growth wasn't parsed (as `CREATURE`, `CREATURE:CASTE`,
+ * `PLANT` or `PLANT:GROWTH`)
+ */
+graphicsWithoutTargets: string[] }
+
 /**
  * Represents the `info.txt` file for a raw module
  */
@@ -9307,6 +10197,14 @@ skipApplyCopyTagsFrom: boolean;
  * Default: false.
  */
 skipApplyCreatureVariations: boolean; 
+/**
+ * Whether to record where each tag on a resolved creature came from.
+ * If true, creatures which are resolved (e.g. use `COPY_TAGS_FROM` or `APPLY_CREATURE_VARIATION`)
+ * will have a `resolution_trace` field explaining how they were assembled.
+ * 
+ * Default: false.
+ */
+traceCreatureResolution?: boolean; 
 /**
  * What types of raws to parse. If this is left empty, all parsable raws will be parsed.
  * 
@@ -9484,8 +10382,12 @@ export type PlantGraphicTemplateToken =
  */
 export type PlantGrowth = { 
 /**
- * Plant growths are not given an identifier, since they are just supporting
- * data for the plant definition. They are defined instead by the type of growth.
+ * The identifier of the growth, as written in the `GROWTH` token (e.g. `FRUIT`). This is only unique
+ * within the plant, and is used by `PLANT_GRAPHICS` to give the growth its sprites.
+ */
+identifier: string; 
+/**
+ * The type of growth, which is determined by the identifier (unknown identifiers have no type).
  */
 growthType: PlantGrowthTypeToken; 
 /**
@@ -9517,10 +10419,10 @@ trunkHeightPercentage: [number, number] | null;
  */
 density: number | null; 
 /**
- * Specifies the appearance of the growth. This is defined with `GROWTH_PRINT` key.
- * This is a string until we make a proper print structure.
+ * Specifies the appearance of the growth. This is defined with `GROWTH_PRINT` key, which can be
+ * used more than once to change the appearance through the year (the stages of the growth).
  */
-print: string | null; 
+prints: GrowthPrint[] | null; 
 /**
  * Specifies at which part of the year the growth appears. Default is all year round.
  * Minimum: 0, Maximum: `402_200`. This is defined with `GROWTH_TIMING` key.
@@ -10249,7 +11151,16 @@ favoritesOnly: boolean;
  * Specify using the module's `object_id` which can be found from the raw:
  * `raw.metadata.module_object_it`
  */
-inModules: string[] }
+inModules: string[]; 
+/**
+ * Limit results to the effective definition of each raw under the stored load order, i.e. the
+ * definition from the last loaded module which defines that identifier.
+ * 
+ * Raws from modules which are not in the load order are not returned.
+ * 
+ * Default: false
+ */
+effectiveOnly: boolean }
 
 /**
  * A structured response for search operations, containing the requested page of data
@@ -10290,6 +11201,31 @@ export type SeasonToken =
  */
 "Unknown"
 
+/**
+ * A growth which is present on a plant on a day of the year
+ */
+export type SeasonalGrowth = { 
+/**
+ * The identifier of the growth, e.g. `FRUIT`
+ */
+growth: string; 
+/**
+ * The type of the growth
+ */
+growthType: PlantGrowthTypeToken; 
+/**
+ * The stage of the growth (the index of its `GROWTH_PRINT` used on the day), if it has a print
+ */
+stage: string | null; 
+/**
+ * The `GROWTH_PRINT` used on the day, which gives the tile and color in ASCII mode
+ */
+print: GrowthPrint | null; 
+/**
+ * The sprite for the stage from the plant's `PLANT_GRAPHICS`, if it has any for the growth
+ */
+sprite: SpriteGraphic | null }
+
 /**
  * A struct representing a seed material
  */
@@ -10538,7 +11474,19 @@ export type ShrubToken =
 /**
  * A struct representing a sprite graphic.
  */
-export type SpriteGraphic = { primaryCondition: ConditionToken; tilePageId: string; offset: Dimensions; color?: ColorModificationToken | null; largeImage: boolean | null; offset2: Dimensions | null; secondaryCondition?: ConditionToken | null; colorPalletSwap: number | null; targetIdentifier: string | null; extraDescriptor: string | null }
+export type SpriteGraphic = { primaryCondition: ConditionToken; tilePageId: string; offset: Dimensions; color?: ColorModificationToken | null; largeImage: boolean | null; offset2: Dimensions | null; secondaryCondition?: ConditionToken | null; colorPalletSwap: number | null; 
+/**
+ * The variant index given before the tile page of tool sprites, e.g. `3` for `TOOL_GRAPHICS_WOOD_VARIANT:3`
+ */
+variantIndex?: number | null; targetIdentifier: string | null; extraDescriptor: string | null; growth?: GrowthToken | null; 
+/**
+ * The specific graphic type (e.g. `WEAPON_GRAPHICS_UPRIGHT_1T`) of item, building and terrain sprites
+ */
+graphicType?: GraphicTypeToken | null; 
+/**
+ * The material the sprite is for, e.g. `WOOD` for `TOY_GRAPHICS` or `GLASS_GREEN` for `ROUGH_GEM_GRAPHICS`
+ */
+material: string | null }
 
 /**
  * A simplified struct for sprite graphic data
@@ -10612,7 +11560,157 @@ largeImage: boolean | null;
 /**
  * An array of required conditions for this sprite to be visible/used
  */
-conditions: ([ConditionToken, string])[] | null }
+conditions: ([ConditionToken, string])[] | null; 
+/**
+ * The `LAYER_GROUP` this layer belongs to (numbered in order within the graphic), if any.
+ * 
+ * Only the first matching layer in a group is drawn.
+ */
+layerGroup: number | null }
+
+/**
+ * A condition which must be met for a layer to be drawn
+ */
+export type SpriteLayerConditionData = { 
+/**
+ * the condition token, e.g. `CONDITION_BP`
+ */
+condition: string; 
+/**
+ * the value given to the condition (empty if it has none)
+ */
+value: string }
+
+/**
+ * A simplified struct for a layer of a layer set in a graphics raw
+ */
+export type SpriteLayerData = { 
+/**
+ * database id for this layer
+ */
+id: string; 
+/**
+ * linked raw id (of graphics raw) this belongs to
+ */
+rawId: string; 
+/**
+ * the identifier of the thing this layer displays
+ */
+targetIdentifier: string; 
+/**
+ * the layer set this layer belongs to, e.g. `DEFAULT` or `PORTRAIT`
+ */
+layerSet: string; 
+/**
+ * the position of the layer within its layer set (layers are drawn in this order)
+ */
+layerIndex: number; 
+/**
+ * the name of the layer
+ */
+layerName: string; 
+/**
+ * the `LAYER_GROUP` of the layer, if any (only the first matching layer in a group is drawn)
+ */
+layerGroup: number | null; 
+/**
+ * identifier of tile page sprite is on
+ */
+tilePageIdentifier: string; 
+/**
+ * sprite offset x1
+ */
+offsetX: number; 
+/**
+ * sprite offset y1
+ */
+offsetY: number; 
+/**
+ * for large sprites, offset x2
+ */
+offsetX2: number | null; 
+/**
+ * for large sprites, offset y2
+ */
+offsetY2: number | null; 
+/**
+ * the conditions which must be met for the layer to be drawn, in the order they were defined
+ */
+conditions: SpriteLayerConditionData[] }
+
+/**
+ * The state to show a creature in, used to choose between its sprites.
+ * 
+ * Sprites are defined as `[primary:TILE_PAGE:x:y:color:secondary]`, where the primary condition is the
+ * form of the creature (e.g. `DEFAULT`, `CHILD`, `CORPSE`) and the secondary condition is how it has
+ * been changed (e.g. `DEFAULT`, `SKELETON`, `ZOMBIE`). When the exact sprite for a state isn't defined,
+ * the game falls back to a more general one (ending with `DEFAULT:DEFAULT`), see [`Self::fallbacks`].
+ */
+export type SpriteState = 
+/**
+ * The adult creature (`DEFAULT`)
+ */
+"Default" | 
+/**
+ * A child (`CHILD`)
+ */
+"Child" | 
+/**
+ * An animated (risen) corpse (`ANIMATED`)
+ */
+"Animated" | 
+/**
+ * A corpse (`CORPSE`)
+ */
+"Corpse" | 
+/**
+ * The remains of a vermin (`REMAINS`)
+ */
+"Remains" | 
+/**
+ * The icon shown in lists (`LIST_ICON`)
+ */
+"ListIcon" | 
+/**
+ * Trained for hunting (`TRAINED_HUNTER`)
+ */
+"TrainedHunter" | 
+/**
+ * Trained for war (`TRAINED_WAR`)
+ */
+"TrainedWar" | 
+/**
+ * A skeleton (secondary `SKELETON`)
+ */
+"Skeleton" | 
+/**
+ * A skeleton with a skull (secondary `SKELETON_WITH_SKULL`)
+ */
+"SkeletonWithSkull" | 
+/**
+ * A zombie (secondary `ZOMBIE`)
+ */
+"Zombie" | 
+/**
+ * A necromancer (secondary `NECROMANCER`)
+ */
+"Necromancer" | 
+/**
+ * A vampire (secondary `VAMPCURSE`)
+ */
+"VampireCursed" | 
+/**
+ * A ghoul (secondary `GHOUL`)
+ */
+"Ghoul" | 
+/**
+ * Disturbed dead (secondary `DISTURBED_DEAD`)
+ */
+"DisturbedDead" | 
+/**
+ * The portrait (the `PORTRAIT` layer set)
+ */
+"Portrait"
 
 /**
  * Represents the name of a materials 3 states (solid, liquid, gas)
@@ -10884,6 +11982,85 @@ export type TilePageToken =
  */
 "unknown"
 
+/**
+ * What happened to a token during creature resolution
+ */
+export type TokenAction = 
+/**
+ * The token was added to the creature
+ */
+"Added" | 
+/**
+ * The token was removed from the creature
+ */
+"Removed" | 
+/**
+ * The token was converted (its value was replaced)
+ */
+"Converted" | 
+/**
+ * The token selected the castes which the tokens after it apply to (e.g. `SELECT_CASTE:MALE`)
+ */
+"Selected"
+
+/**
+ * A single step taken while resolving a creature, recording a token and where it came from.
+ * 
+ * These are only recorded when [`ParserOptions::trace_creature_resolution`] is enabled.
+ * 
+ * [`ParserOptions::trace_creature_resolution`]: crate::metadata::ParserOptions::trace_creature_resolution
+ */
+export type TokenOrigin = { 
+/**
+ * The token as it appears in the raws (without brackets), e.g. `BODY_SIZE:0:0:500`
+ */
+token: string; 
+/**
+ * The castes which were selected when the token was applied
+ */
+castes: string[]; 
+/**
+ * What happened to the token
+ */
+action: TokenAction; 
+/**
+ * Where the token came from
+ */
+source: TokenSource }
+
+/**
+ * Where a token applied during creature resolution came from
+ */
+export type TokenSource = 
+/**
+ * The creature's own definition
+ */
+{ kind: "body" } | 
+/**
+ * Inserted at the start of the creature with `GO_TO_START`
+ */
+{ kind: "goToStart" } | 
+/**
+ * Inserted at the end of the creature with `GO_TO_END`
+ */
+{ kind: "goToEnd" } | 
+/**
+ * Inserted before a tag with `GO_TO_TAG`
+ */
+{ kind: "goToTag"; tag: string } | 
+/**
+ * Copied from another creature with `COPY_TAGS_FROM`
+ */
+{ kind: "copiedFrom"; creature: string; module: string; module_object_id: string; original: TokenSource } | 
+/**
+ * Applied by a creature variation rule with `APPLY_CREATURE_VARIATION`
+ */
+{ kind: "variation"; variation: string; rule: CreatureVariationRuleToken; args: string[] } | 
+/**
+ * Applied by a `CV_*` tag in the creature itself with `APPLY_CURRENT_CREATURE_VARIATION`
+ */
+{ kind: "currentVariation"; rule: CreatureVariationRuleToken }
+
 /**
  * A struct representing a tree.
  */
@@ -11246,3 +12423,36 @@ export type TwigPlacementToken =
  */
 "Unknown"
 
+/**
+ * A reference from a raw object to an identifier which doesn't exist in the parsed raws
+ */
+export type UnresolvedReference = { 
+/**
+ * The type of the object with the reference
+ */
+sourceType: ObjectType; 
+/**
+ * The identifier of the object with the reference
+ */
+sourceIdentifier: string; 
+/**
+ * The `object_id` of the object with the reference
+ */
+sourceObjectId: string; 
+/**
+ * The name of the module the object with the reference is from
+ */
+module: string; 
+/**
+ * The field (or tag) which holds the reference, e.g. `COPY_TAGS_FROM`
+ */
+field: string; 
+/**
+ * The type of object which was expected
+ */
+targetType: ObjectType; 
+/**
+ * The identifier which was expected to exist
+ */
+expectedIdentifier: string }
+
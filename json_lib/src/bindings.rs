@@ -9,6 +9,7 @@ use specta_typescript::{ExportError, Typescript};
 /// # Errors
 ///
 /// Will error if the Type bindings export fails
+#[allow(clippy::too_many_lines)]
 pub fn generate_bindings(output_path: &Path) -> Result<(), ExportError> {
     let exporter = Typescript::default().bigint(specta_typescript::BigIntExportBehavior::String);
     let mut types = TypeCollection::default();
@@ -28,6 +29,7 @@ pub fn generate_bindings(output_path: &Path) -> Result<(), ExportError> {
         .register::<dfraw_parser::tokens::FuelTypeToken>()
         .register::<dfraw_parser::tokens::GaitModifierToken>()
         .register::<dfraw_parser::tokens::GaitTypeToken>()
+        .register::<dfraw_parser::tokens::GraphicCategory>()
         .register::<dfraw_parser::tokens::GraphicTypeToken>()
         .register::<dfraw_parser::tokens::GrowthToken>()
         .register::<dfraw_parser::tokens::InclusionTypeToken>()
@@ -62,6 +64,7 @@ pub fn generate_bindings(output_path: &Path) -> Result<(), ExportError> {
         .register::<dfraw_parser::Entity>()
         .register::<dfraw_parser::Gait>()
         .register::<dfraw_parser::Graphic>()
+        .register::<dfraw_parser::GrowthPrint>()
         .register::<dfraw_parser::ModuleInfo>()
         .register::<dfraw_parser::Inorganic>()
         .register::<dfraw_parser::Material>()
@@ -84,13 +87,56 @@ pub fn generate_bindings(output_path: &Path) -> Result<(), ExportError> {
         .register::<dfraw_parser::Temperatures>()
         .register::<dfraw_parser::Tile>()
         .register::<dfraw_parser::TilePage>()
+        .register::<dfraw_parser::TokenAction>()
+        .register::<dfraw_parser::TokenOrigin>()
+        .register::<dfraw_parser::TokenSource>()
         .register::<dfraw_parser::Tree>()
+        .register::<dfraw_parser::dependencies::DependencyIssue>()
+        .register::<dfraw_parser::dependencies::DependencyReport>()
+        .register::<dfraw_parser::graphics::AtlasManifest>()
+        .register::<dfraw_parser::graphics::AtlasRect>()
+        .register::<dfraw_parser::graphics::AtlasSprite>()
+        .register::<dfraw_parser::graphics::GraphicsCoverageReport>()
+        .register::<dfraw_parser::graphics::GraphicsIssue>()
+        .register::<dfraw_parser::graphics::ModuleGraphicsCoverage>()
+        .register::<dfraw_parser::graphics::SeasonalGrowth>()
+        .register::<dfraw_parser::graphics::SpriteState>()
+        .register::<dfraw_parser::legends_export::ExportedArtifact>()
+        .register::<dfraw_parser::legends_export::ExportedCulturalForm>()
+        .register::<dfraw_parser::legends_export::ExportedEntity>()
+        .register::<dfraw_parser::legends_export::ExportedEntityLink>()
+        .register::<dfraw_parser::legends_export::ExportedEntityOccasion>()
+        .register::<dfraw_parser::legends_export::ExportedEntityPopulation>()
+        .register::<dfraw_parser::legends_export::ExportedEntityPosition>()
+        .register::<dfraw_parser::legends_export::ExportedEntityPositionAssignment>()
+        .register::<dfraw_parser::legends_export::ExportedEntityRelation>()
+        .register::<dfraw_parser::legends_export::ExportedEventCollection>()
+        .register::<dfraw_parser::legends_export::ExportedHistoricalEvent>()
+        .register::<dfraw_parser::legends_export::ExportedHistoricalFigure>()
+        .register::<dfraw_parser::legends_export::ExportedHistoricalFigureLink>()
+        .register::<dfraw_parser::legends_export::ExportedOccasionSchedule>()
+        .register::<dfraw_parser::legends_export::ExportedRegion>()
+        .register::<dfraw_parser::legends_export::ExportedSite>()
+        .register::<dfraw_parser::legends_export::ExportedSiteProperty>()
+        .register::<dfraw_parser::legends_export::ExportedSkill>()
+        .register::<dfraw_parser::legends_export::ExportedStructure>()
+        .register::<dfraw_parser::legends_export::ExportedUndergroundRegion>()
+        .register::<dfraw_parser::legends_export::ExportedWorldConstruction>()
+        .register::<dfraw_parser::legends_export::ExportedWrittenContent>()
+        .register::<dfraw_parser::legends_export::ExportedWrittenContentReference>()
+        .register::<dfraw_parser::legends_export::LegendsWorld>()
+        .register::<dfraw_parser::references::UnresolvedReference>()
         .register::<dfraw_parser::metadata::ParserOptions>()
         .register::<dfraw_parser::metadata::RawModuleLocation>()
         .register::<dfraw_parser::metadata::RawMetadata>()
         .register::<dfraw_parser::metadata::RawObject>()
         .register::<dfraw_parser_sqlite_lib::models::TilePageData>()
         .register::<dfraw_parser_sqlite_lib::models::SpriteGraphicData>()
+        .register::<dfraw_parser_sqlite_lib::models::SpriteLayerData>()
+        .register::<dfraw_parser_sqlite_lib::models::SpriteLayerConditionData>()
+        .register::<dfraw_parser_sqlite_lib::models::GrowthSpriteData>()
+        .register::<dfraw_parser_sqlite_lib::models::GraphicPaletteData>()
+        .register::<dfraw_parser_sqlite_lib::models::LegendsWorldData>()
         .register::<dfraw_parser_sqlite_lib::ClientOptions>()
         .register::<dfraw_parser_sqlite_lib::SearchQuery>()
         .register::<dfraw_parser_sqlite_lib::NumericFilter>()
@@ -9,6 +9,15 @@ pub static DF_ENCODING: LazyLock<&'static encoding_rs::Encoding> = LazyLock::new
     encoding_rs::Encoding::for_label(b"latin1").expect("Failed to get encoding: latin1")
 });
 
+/// The number of ticks in a day (in fortress mode)
+pub const TICKS_PER_DAY: u32 = 1200;
+
+/// The number of days in a year (12 months of 28 days)
+pub const DAYS_PER_YEAR: u32 = 336;
+
+/// The number of ticks in a year, which is the scale used by `GROWTH_TIMING` and `GROWTH_PRINT`
+pub const TICKS_PER_YEAR: u32 = TICKS_PER_DAY * DAYS_PER_YEAR;
+
 /// The Steam AppID for Dwarf Fortress
 pub const DF_STEAM_APPID: u32 = 975370;
 
@@ -0,0 +1,380 @@
+//! Dependency graph built from a set of `ModuleInfo`s.
+
+use std::collections::{BTreeSet, HashMap};
+
+use tracing::debug;
+
+use crate::ModuleInfo;
+
+use super::{DependencyIssue, DependencyReport};
+
+/// A graph of the dependencies between a set of modules.
+///
+/// When more than one module shares an identifier (e.g. the same mod in both the workshop and installed
+/// mods directories), the module with the highest `numeric_version` is used. The other copies are checked
+/// for compatibility against it using `earliest_compatible_numeric_version`.
+#[derive(Debug, Clone)]
+pub struct ModuleDependencyGraph<'a> {
+    /// The modules in the graph (one per identifier), in the order they were provided
+    modules: Vec<&'a ModuleInfo>,
+    /// Modules which were replaced by a newer module with the same identifier
+    superseded: Vec<&'a ModuleInfo>,
+    /// Lookup of module identifier to its index in `modules`
+    index: HashMap<String, usize>,
+    /// Ordering edges, `edges[a]` contains `b` if `a` must be loaded before `b`
+    edges: Vec<BTreeSet<usize>>,
+}
+
+impl<'a> ModuleDependencyGraph<'a> {
+    /// Builds the dependency graph for the provided modules.
+    ///
+    /// The order of `modules` is preserved in the load order wherever the dependencies allow it.
+    ///
+    /// # Arguments
+    ///
+    /// * `modules` - The modules to build the graph from
+    #[must_use]
+    pub fn new(modules: &'a [ModuleInfo]) -> Self {
+        let mut graph = Self {
+            modules: Vec::new(),
+            superseded: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+        };
+
+        for module in modules {
+            let identifier = module.get_identifier();
+            if let Some(&existing) = graph.index.get(&identifier) {
+                if module.get_numeric_version() > graph.modules[existing].get_numeric_version() {
+                    graph.superseded.push(graph.modules[existing]);
+                    graph.modules[existing] = module;
+                } else {
+                    graph.superseded.push(module);
+                }
+            } else {
+                graph.index.insert(identifier, graph.modules.len());
+                graph.modules.push(module);
+            }
+        }
+
+        graph.edges = vec![BTreeSet::new(); graph.modules.len()];
+        for (i, module) in graph.modules.iter().enumerate() {
+            for before in module.get_requires_ids_before().unwrap_or_default() {
+                if let Some(&j) = graph.index.get(&before) {
+                    graph.edges[j].insert(i);
+                }
+            }
+            for after in module.get_requires_ids_after().unwrap_or_default() {
+                if let Some(&j) = graph.index.get(&after) {
+                    graph.edges[i].insert(j);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the modules in the graph (one per identifier), in the order they were provided
+    #[must_use]
+    pub fn modules(&self) -> &[&'a ModuleInfo] {
+        &self.modules
+    }
+
+    /// Returns the module in the graph with the given identifier
+    #[must_use]
+    pub fn get_module(&self, identifier: &str) -> Option<&'a ModuleInfo> {
+        self.index.get(identifier).map(|&i| self.modules[i])
+    }
+
+    /// Returns the modules sorted so that every module is loaded after the modules it depends on.
+    ///
+    /// Any modules which are part of (or depend on) a cycle cannot be sorted, and are added to the end
+    /// in the order they were provided. Use [`Self::find_cycles`] to check for them.
+    #[must_use]
+    pub fn load_order(&self) -> Vec<&'a ModuleInfo> {
+        let (mut sorted, remaining) = self.topological_sort();
+        sorted.extend(remaining);
+        sorted.into_iter().map(|i| self.modules[i]).collect()
+    }
+
+    /// Finds all cycles in the ordering requirements.
+    ///
+    /// Each cycle is returned as the identifiers of the modules in it, in the order they were provided.
+    #[must_use]
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let (_, remaining) = self.topological_sort();
+
+        // Find everything reachable from each unsorted module (only through other unsorted modules)
+        let remaining_set: BTreeSet<usize> = remaining.iter().copied().collect();
+        let reachable: HashMap<usize, BTreeSet<usize>> = remaining
+            .iter()
+            .map(|&start| {
+                let mut seen = BTreeSet::new();
+                let mut stack = vec![start];
+                while let Some(node) = stack.pop() {
+                    for &next in &self.edges[node] {
+                        if remaining_set.contains(&next) && seen.insert(next) {
+                            stack.push(next);
+                        }
+                    }
+                }
+                (start, seen)
+            })
+            .collect();
+
+        // Modules which can reach each other are part of the same cycle
+        let mut assigned: BTreeSet<usize> = BTreeSet::new();
+        let mut cycles = Vec::new();
+        for &i in &remaining {
+            if assigned.contains(&i) || !reachable[&i].contains(&i) {
+                continue;
+            }
+            let members: Vec<usize> = remaining
+                .iter()
+                .copied()
+                .filter(|j| reachable[&i].contains(j) && reachable[j].contains(&i))
+                .collect();
+            assigned.extend(members.iter().copied());
+            cycles.push(
+                members
+                    .into_iter()
+                    .map(|j| self.modules[j].get_identifier())
+                    .collect(),
+            );
+        }
+
+        cycles
+    }
+
+    /// Checks that the modules can satisfy the versions a save was created with.
+    ///
+    /// # Arguments
+    ///
+    /// * `saved_versions` - Pairs of module identifier and the `numeric_version` which was used
+    ///
+    /// # Returns
+    ///
+    /// An issue for each saved module which is missing or is not compatible with the available module.
+    #[must_use]
+    pub fn check_saved_versions(&self, saved_versions: &[(String, u32)]) -> Vec<DependencyIssue> {
+        saved_versions
+            .iter()
+            .filter_map(|(identifier, version)| match self.get_module(identifier) {
+                None => Some(DependencyIssue::MissingRequirement {
+                    module: String::from("save"),
+                    required: identifier.clone(),
+                }),
+                Some(module) if !module.is_compatible_with_version(*version) => {
+                    Some(Self::incompatible_version(module, *version))
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    /// Evaluates all the dependency information for the modules.
+    ///
+    /// This checks for missing requirements, conflicts, incompatible duplicate versions and cycles.
+    #[must_use]
+    pub fn validate(&self) -> DependencyReport {
+        let mut issues = Vec::new();
+
+        for module in &self.modules {
+            let identifier = module.get_identifier();
+            let required = module
+                .get_requires_ids()
+                .unwrap_or_default()
+                .into_iter()
+                .chain(module.get_requires_ids_before().unwrap_or_default())
+                .chain(module.get_requires_ids_after().unwrap_or_default());
+
+            let mut reported: BTreeSet<String> = BTreeSet::new();
+            for required in required {
+                if !self.index.contains_key(&required) && reported.insert(required.clone()) {
+                    issues.push(DependencyIssue::MissingRequirement {
+                        module: identifier.clone(),
+                        required,
+                    });
+                }
+            }
+
+            for conflict in module.get_conflicts_with_ids().unwrap_or_default() {
+                if conflict != identifier && self.index.contains_key(&conflict) {
+                    issues.push(DependencyIssue::Conflict {
+                        module: identifier.clone(),
+                        conflicts_with: conflict,
+                    });
+                }
+            }
+        }
+
+        for module in &self.superseded {
+            if let Some(newest) = self.get_module(&module.get_identifier())
+                && !newest.is_compatible_with_version(module.get_numeric_version())
+            {
+                issues.push(Self::incompatible_version(
+                    newest,
+                    module.get_numeric_version(),
+                ));
+            }
+        }
+
+        for modules in self.find_cycles() {
+            debug!("Found load order cycle: {:?}", modules);
+            issues.push(DependencyIssue::Cycle { modules });
+        }
+
+        DependencyReport {
+            load_order: self
+                .load_order()
+                .into_iter()
+                .map(ModuleInfo::get_identifier)
+                .collect(),
+            issues,
+        }
+    }
+
+    fn incompatible_version(module: &ModuleInfo, requested_version: u32) -> DependencyIssue {
+        DependencyIssue::IncompatibleVersion {
+            module: module.get_identifier(),
+            requested_version,
+            available_version: module.get_numeric_version(),
+            earliest_compatible_version: module.get_earliest_compatible_numeric_version(),
+        }
+    }
+
+    /// Kahn's algorithm, always picking the earliest provided module which is ready.
+    ///
+    /// Returns the sorted indices and the indices which could not be sorted.
+    fn topological_sort(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut in_degree = vec![0_usize; self.modules.len()];
+        for targets in &self.edges {
+            for &target in targets {
+                in_degree[target] += 1;
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..self.modules.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect();
+        let mut sorted = Vec::with_capacity(self.modules.len());
+
+        while let Some(node) = ready.pop_first() {
+            sorted.push(node);
+            for &target in &self.edges[node] {
+                in_degree[target] -= 1;
+                if in_degree[target] == 0 {
+                    ready.insert(target);
+                }
+            }
+        }
+
+        let remaining = (0..self.modules.len())
+            .filter(|&i| in_degree[i] > 0)
+            .collect();
+
+        (sorted, remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::RawModuleLocation;
+
+    fn module(identifier: &str) -> ModuleInfo {
+        ModuleInfo::new(identifier, RawModuleLocation::InstalledMods, identifier)
+    }
+
+    fn identifiers(modules: &[&ModuleInfo]) -> Vec<String> {
+        modules.iter().map(|m| m.get_identifier()).collect()
+    }
+
+    #[test]
+    fn test_load_order_respects_before_and_after() {
+        let mut a = module("a");
+        a.add_requires_id_before("c");
+        let b = module("b");
+        let mut c = module("c");
+        c.add_requires_id_after("b");
+        let modules = vec![a, b, c];
+
+        let graph = ModuleDependencyGraph::new(&modules);
+        assert_eq!(identifiers(&graph.load_order()), vec!["c", "a", "b"]);
+
+        let report = graph.validate();
+        assert!(report.can_load(), "Unexpected issues: {:?}", report.issues);
+    }
+
+    #[test]
+    fn test_missing_and_conflicting_modules() {
+        let mut a = module("a");
+        a.add_requires_id("missing");
+        a.add_conflicts_with_id("b");
+        let b = module("b");
+        let modules = vec![a, b];
+
+        let report = ModuleDependencyGraph::new(&modules).validate();
+        assert_eq!(
+            report.issues,
+            vec![
+                DependencyIssue::MissingRequirement {
+                    module: String::from("a"),
+                    required: String::from("missing"),
+                },
+                DependencyIssue::Conflict {
+                    module: String::from("a"),
+                    conflicts_with: String::from("b"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycles_are_detected() {
+        let mut a = module("a");
+        a.add_requires_id_before("b");
+        let mut b = module("b");
+        b.add_requires_id_before("a");
+        let mut c = module("c");
+        c.add_requires_id_before("a");
+        let d = module("d");
+        let modules = vec![a, b, c, d];
+
+        let graph = ModuleDependencyGraph::new(&modules);
+        assert_eq!(
+            graph.find_cycles(),
+            vec![vec![String::from("a"), String::from("b")]]
+        );
+        // Unsortable modules are appended in their original order
+        assert_eq!(identifiers(&graph.load_order()), vec!["d", "a", "b", "c"]);
+        assert!(!graph.validate().can_load());
+    }
+
+    #[test]
+    fn test_incompatible_versions() {
+        let mut old = module("a");
+        old.set_numeric_version(1);
+        let mut new = module("a");
+        new.set_numeric_version(3);
+        new.set_earliest_compatible_numeric_version(2);
+        let modules = vec![old, new];
+
+        let graph = ModuleDependencyGraph::new(&modules);
+        assert_eq!(graph.modules().len(), 1);
+        assert_eq!(
+            graph.validate().issues,
+            vec![DependencyIssue::IncompatibleVersion {
+                module: String::from("a"),
+                requested_version: 1,
+                available_version: 3,
+                earliest_compatible_version: 2,
+            }]
+        );
+        assert!(
+            graph
+                .check_saved_versions(&[(String::from("a"), 2)])
+                .is_empty()
+        );
+    }
+}
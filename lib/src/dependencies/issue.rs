@@ -0,0 +1,76 @@
+//! Problems which prevent a set of modules from loading.
+
+use serde::{Deserialize, Serialize};
+
+/// A single problem found when evaluating module dependencies
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum DependencyIssue {
+    /// A module requires another module which is not in the set
+    MissingRequirement {
+        /// The identifier of the module declaring the requirement
+        module: String,
+        /// The identifier of the module which is missing
+        required: String,
+    },
+    /// A module declares a conflict with another module which is in the set
+    Conflict {
+        /// The identifier of the module declaring the conflict
+        module: String,
+        /// The identifier of the conflicting module
+        conflicts_with: String,
+    },
+    /// A version of a module was requested which the available module is not compatible with
+    IncompatibleVersion {
+        /// The identifier of the module
+        module: String,
+        /// The numeric version which was requested
+        requested_version: u32,
+        /// The numeric version of the module which is available
+        available_version: u32,
+        /// The earliest numeric version the available module is compatible with
+        earliest_compatible_version: u32,
+    },
+    /// A module is loaded before a module it must be loaded after
+    OutOfOrder {
+        /// The identifier of the module which is loaded too early
+        module: String,
+        /// The identifier of the module it must be loaded after
+        must_load_after: String,
+    },
+    /// The ordering requirements between these modules form a cycle
+    Cycle {
+        /// The identifiers of the modules in the cycle
+        modules: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for DependencyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingRequirement { module, required } => {
+                write!(f, "{module} requires {required}, which is not present")
+            }
+            Self::Conflict {
+                module,
+                conflicts_with,
+            } => write!(f, "{module} conflicts with {conflicts_with}"),
+            Self::IncompatibleVersion {
+                module,
+                requested_version,
+                available_version,
+                earliest_compatible_version,
+            } => write!(
+                f,
+                "{module} version {requested_version} was requested but version {available_version} is only compatible back to {earliest_compatible_version}"
+            ),
+            Self::OutOfOrder {
+                module,
+                must_load_after,
+            } => write!(f, "{module} must be loaded after {must_load_after}"),
+            Self::Cycle { modules } => {
+                write!(f, "load order cycle between {}", modules.join(", "))
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Evaluates the dependency information declared in module `info.txt` files.
+//!
+//! Each [`ModuleInfo`](crate::ModuleInfo) can declare the following:
+//!
+//! * `REQUIRES_ID` - another module which must be present
+//! * `REQUIRES_ID_BEFORE_ME` - another module which must be present and loaded before this one
+//! * `REQUIRES_ID_AFTER_ME` - another module which must be present and loaded after this one
+//! * `CONFLICTS_WITH_ID` - another module which cannot be loaded alongside this one
+//!
+//! The [`ModuleDependencyGraph`] evaluates these over a set of modules, providing a load order and a
+//! [`DependencyReport`] which lists every reason the set of modules cannot be loaded.
+
+mod graph;
+mod issue;
+mod report;
+
+pub use graph::ModuleDependencyGraph;
+pub use issue::DependencyIssue;
+pub use report::DependencyReport;
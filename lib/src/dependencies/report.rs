@@ -0,0 +1,25 @@
+//! The result of evaluating a set of modules.
+
+use serde::{Deserialize, Serialize};
+
+use super::DependencyIssue;
+
+/// A report of the load order and any issues found for a set of modules
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    /// The module identifiers in the order they should be loaded.
+    ///
+    /// Modules which are part of a cycle are placed at the end in their original order.
+    pub load_order: Vec<String>,
+    /// All issues found with the set of modules
+    pub issues: Vec<DependencyIssue>,
+}
+
+impl DependencyReport {
+    /// Returns true if no issues were found, i.e. the set of modules can be loaded
+    #[must_use]
+    pub const fn can_load(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
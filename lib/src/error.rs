@@ -28,4 +28,10 @@ pub enum Parser {
     /// Cannot parse the target from the given string
     #[error("Target for parsing cannot be opened: {0}")]
     NothingToParse(String),
+    /// An image (e.g. a tile page) could not be read, cropped or written
+    #[error("Image error: {0}")]
+    Image(String),
+    /// A legends export could not be read (e.g. it isn't valid XML)
+    #[error("Invalid legends export: {0}")]
+    InvalidLegendsExport(String),
 }
@@ -0,0 +1,388 @@
+//! Packs the sprites of a set of graphics into one texture atlas, with a manifest of where each is.
+
+use std::{collections::HashMap, path::Path};
+
+use image::{RgbaImage, imageops};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    Dimensions, Graphic, ParserError,
+    tokens::{ConditionToken, ObjectType},
+    traits::{RawObject, RawToken as _},
+};
+
+use super::{SpriteExtractor, write_png};
+
+/// A rectangle in an atlas image, in pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasRect {
+    /// The left edge
+    pub x: u32,
+    /// The top edge
+    pub y: u32,
+    /// The width
+    pub width: u32,
+    /// The height
+    pub height: u32,
+}
+
+/// A sprite in an atlas, with what it shows
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasSprite {
+    /// The identifier of what the sprite shows (e.g. `DWARF` or `APPLE`)
+    pub target_identifier: String,
+    /// The caste, for sprites from `CREATURE_CASTE_GRAPHICS`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caste: Option<String>,
+    /// The condition of the sprite (e.g. `DEFAULT` or `CHILD`). For layers this is `LAYER_SET:<name>`,
+    /// and for plant growths `GROWTH:<growth>`.
+    pub condition: String,
+    /// The secondary condition of the sprite (e.g. `ZOMBIE`), or the stage of a growth (e.g. `GROWTH_1`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_condition: Option<String>,
+    /// The name of the layer, for layers of a layer set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    /// Where the sprite is in the atlas image
+    pub rect: AtlasRect,
+}
+
+/// The manifest of an atlas, which maps each sprite to its place in the atlas image
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasManifest {
+    /// The file name of the atlas image
+    pub image: String,
+    /// The width of the atlas image
+    pub width: u32,
+    /// The height of the atlas image
+    pub height: u32,
+    /// The sprites in the atlas. Sprites which use the same tiles share a rectangle.
+    pub sprites: Vec<AtlasSprite>,
+}
+
+/// A texture atlas of the sprites of a set of graphics, built with [`SpriteAtlas::build`].
+#[derive(Debug, Clone)]
+pub struct SpriteAtlas {
+    /// The packed sprites
+    image: RgbaImage,
+    /// Where each sprite is in the image
+    manifest: AtlasManifest,
+    /// Descriptions of the sprites which couldn't be cropped from their tile pages
+    skipped: Vec<String>,
+}
+
+/// The tiles of a sprite, which identify it in the atlas
+#[derive(Hash, PartialEq, Eq, Clone)]
+struct RegionKey {
+    tile_page_id: String,
+    offset: Dimensions,
+    offset2: Option<Dimensions>,
+}
+
+impl SpriteAtlas {
+    /// Builds an atlas of the sprites, layers and growth sprites of the graphics in a set of raws.
+    ///
+    /// Each region of a tile page is only packed once, however many sprites use it. Sprites which can't be
+    /// cropped (e.g. their tile page wasn't parsed or its image is missing) are left out, see
+    /// [`Self::get_skipped`].
+    ///
+    /// # Arguments
+    ///
+    /// * `extractor` - The extractor to crop the sprites with, made from the same raws (or raws with the
+    ///   same tile pages)
+    /// * `raws` - The parsed raws, any which are not graphics are ignored
+    /// * `module_object_id` - Only include the graphics of this module
+    /// * `image_name` - The file name of the atlas image, written to the manifest
+    #[must_use]
+    pub fn build(
+        extractor: &mut SpriteExtractor,
+        raws: &[Box<dyn RawObject>],
+        module_object_id: Option<Uuid>,
+        image_name: &str,
+    ) -> Self {
+        let mut regions: Vec<RgbaImage> = Vec::new();
+        let mut region_indices: HashMap<RegionKey, usize> = HashMap::new();
+        let mut sprites: Vec<(AtlasSprite, usize)> = Vec::new();
+        let mut skipped = Vec::new();
+
+        for graphic in raws
+            .iter()
+            .filter(|raw| {
+                raw.get_type() == ObjectType::Graphics
+                    && module_object_id.is_none_or(|id| raw.get_module_object_id() == id)
+            })
+            .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+        {
+            for (sprite, key) in atlas_sprites(graphic) {
+                let index = if let Some(index) = region_indices.get(&key) {
+                    *index
+                } else {
+                    match extractor.extract_region(&key.tile_page_id, key.offset, key.offset2) {
+                        Ok(image) => {
+                            regions.push(image);
+                            region_indices.insert(key, regions.len() - 1);
+                            regions.len() - 1
+                        }
+                        Err(e) => {
+                            warn!(
+                                "SpriteAtlas::build: Skipping {}:{}: {e}",
+                                graphic.get_identifier(),
+                                sprite.condition
+                            );
+                            skipped.push(format!(
+                                "{} {}: {e}",
+                                graphic.get_identifier(),
+                                sprite.condition
+                            ));
+                            continue;
+                        }
+                    }
+                };
+                sprites.push((sprite, index));
+            }
+        }
+
+        let (width, height, rects) = pack(&regions);
+        let mut image = RgbaImage::new(width, height);
+        for (region, rect) in regions.iter().zip(&rects) {
+            imageops::replace(&mut image, region, i64::from(rect.x), i64::from(rect.y));
+        }
+
+        Self {
+            image,
+            manifest: AtlasManifest {
+                image: String::from(image_name),
+                width,
+                height,
+                sprites: sprites
+                    .into_iter()
+                    .map(|(sprite, index)| AtlasSprite {
+                        rect: rects[index],
+                        ..sprite
+                    })
+                    .collect(),
+            },
+            skipped,
+        }
+    }
+
+    /// Returns the atlas image
+    #[must_use]
+    pub const fn get_image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// Returns the manifest of the atlas
+    #[must_use]
+    pub const fn get_manifest(&self) -> &AtlasManifest {
+        &self.manifest
+    }
+
+    /// Returns descriptions of the sprites which were left out because they couldn't be cropped
+    #[must_use]
+    pub fn get_skipped(&self) -> &[String] {
+        &self.skipped
+    }
+
+    /// Writes the atlas image and its manifest (as `<image name without extension>.json`) to a directory.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the image or manifest cannot be written
+    pub fn write(&self, directory: &Path) -> Result<(), ParserError> {
+        let image_path = directory.join(&self.manifest.image);
+        write_png(&self.image, &image_path)?;
+
+        let manifest_path = image_path.with_extension("json");
+        let manifest = serde_json::to_string_pretty(&self.manifest)
+            .map_err(|e| ParserError::Image(format!("Unable to serialize atlas manifest: {e}")))?;
+        std::fs::write(&manifest_path, manifest).map_err(|e| {
+            ParserError::Image(format!("Unable to write {}: {e}", manifest_path.display()))
+        })
+    }
+}
+
+/// Lists the sprites of a graphic to put in the atlas (without their rectangles yet)
+fn atlas_sprites(graphic: &Graphic) -> Vec<(AtlasSprite, RegionKey)> {
+    let base = AtlasSprite {
+        target_identifier: String::from(graphic.get_target_identifier()),
+        caste: graphic.get_caste_identifier().map(String::from),
+        ..AtlasSprite::default()
+    };
+    let condition_key = |condition: ConditionToken| condition.get_key().map(String::from);
+    let mut sprites = Vec::new();
+
+    for sprite in graphic.get_sprites() {
+        // Templates are stored with the template token in place of the tile page
+        if sprite.get_primary_condition() == ConditionToken::CopyOfTemplate {
+            continue;
+        }
+        sprites.push((
+            AtlasSprite {
+                condition: condition_key(sprite.get_primary_condition()).unwrap_or_default(),
+                secondary_condition: condition_key(sprite.get_secondary_condition()),
+                ..base.clone()
+            },
+            RegionKey {
+                tile_page_id: sprite.get_tile_page_id().to_uppercase(),
+                offset: sprite.get_offset(),
+                offset2: sprite.get_offset2(),
+            },
+        ));
+    }
+    for (layer_set, layers) in graphic.get_layers() {
+        for layer in layers {
+            sprites.push((
+                AtlasSprite {
+                    condition: format!("LAYER_SET:{layer_set}"),
+                    layer: Some(layer.get_name()),
+                    ..base.clone()
+                },
+                RegionKey {
+                    tile_page_id: layer.get_tile_page_id().to_uppercase(),
+                    offset: layer.get_offset(),
+                    offset2: layer.get_offset2(),
+                },
+            ));
+        }
+    }
+    for (growth, growth_sprites) in graphic.get_growths() {
+        for sprite in growth_sprites {
+            if sprite.get_primary_condition() == ConditionToken::CopyOfTemplate {
+                continue;
+            }
+            sprites.push((
+                AtlasSprite {
+                    condition: format!("GROWTH:{growth}"),
+                    secondary_condition: sprite
+                        .get_growth()
+                        .and_then(|growth| growth.get_key())
+                        .map(String::from),
+                    ..base.clone()
+                },
+                RegionKey {
+                    tile_page_id: sprite.get_tile_page_id().to_uppercase(),
+                    offset: sprite.get_offset(),
+                    offset2: sprite.get_offset2(),
+                },
+            ));
+        }
+    }
+
+    sprites
+}
+
+/// Packs images into rows (tallest first), in an image about as wide as it is tall.
+///
+/// # Returns
+///
+/// The width and height of the packed image, and the rectangle of each image (in the given order)
+fn pack(images: &[RgbaImage]) -> (u32, u32, Vec<AtlasRect>) {
+    let area: u64 = images
+        .iter()
+        .map(|image| u64::from(image.width()) * u64::from(image.height()))
+        .sum();
+    let widest = images
+        .iter()
+        .map(RgbaImage::width)
+        .max()
+        .unwrap_or_default();
+    let width = u32::try_from(area.isqrt())
+        .unwrap_or(u32::MAX)
+        .next_power_of_two()
+        .max(widest);
+
+    let mut order: Vec<usize> = (0..images.len()).collect();
+    order.sort_by_key(|index| std::cmp::Reverse(images[*index].height()));
+
+    let mut rects = vec![AtlasRect::default(); images.len()];
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+    for index in order {
+        let image = &images[index];
+        if x + image.width() > width {
+            x = 0;
+            y += row_height;
+            row_height = 0;
+        }
+        rects[index] = AtlasRect {
+            x,
+            y,
+            width: image.width(),
+            height: image.height(),
+        };
+        x += image.width();
+        row_height = row_height.max(image.height());
+    }
+
+    (width, y + row_height, rects)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+    use crate::{TilePage, metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    #[test]
+    fn test_build_sprite_atlas() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let graphics_directory = temp_dir.path();
+        std::fs::create_dir_all(graphics_directory.join("images"))
+            .expect("Failed to create test directory");
+        // A 4x1 page of 2x2 tiles, where each pixel is colored by the tile it's in
+        let page = RgbaImage::from_fn(8, 2, |x, _| {
+            Rgba([u8::try_from(x / 2).unwrap_or_default(), 0, 0, 255])
+        });
+        write_png(&page, &graphics_directory.join("images").join("page.png"))
+            .expect("Failed to write test image");
+
+        let metadata = RawMetadata::default().with_raw_file_path(
+            graphics_directory
+                .join("tile_page_test.txt")
+                .display()
+                .to_string(),
+        );
+        let mut tile_page = TilePage::new("PAGE", &metadata);
+        tile_page.parse_tag("FILE", "images/page.png");
+        tile_page.parse_tag("TILE_DIM", "2:2");
+        tile_page.parse_tag("PAGE_DIM", "4:1");
+
+        let mut dog = Graphic::new("DOG", &metadata, GraphicTypeToken::Creature);
+        for (key, value) in [
+            ("DEFAULT", "PAGE:1:0:AS_IS:DEFAULT"),
+            ("CHILD", "PAGE:1:0:AS_IS:DEFAULT"),
+            ("DEFAULT", "PAGE:LARGE_IMAGE:2:0:3:0:AS_IS:ZOMBIE"),
+            ("CORPSE", "MISSING:0:0:AS_IS:DEFAULT"),
+        ] {
+            dog.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+        }
+        let raws: Vec<Box<dyn RawObject>> = vec![Box::new(tile_page), Box::new(dog)];
+
+        let mut extractor = SpriteExtractor::new(&raws);
+        let atlas = SpriteAtlas::build(&mut extractor, &raws, None, "atlas.png");
+        atlas
+            .write(graphics_directory)
+            .expect("Failed to write atlas");
+
+        let manifest = atlas.get_manifest();
+        assert!(graphics_directory.join("atlas.json").is_file());
+        assert_eq!(atlas.get_skipped().len(), 1);
+        assert_eq!(manifest.sprites.len(), 3);
+        // The default and child sprites share a region
+        assert_eq!(manifest.sprites[0].rect, manifest.sprites[1].rect);
+        assert_eq!(manifest.sprites[1].condition, "CHILD");
+        let zombie = &manifest.sprites[2];
+        assert_eq!(zombie.secondary_condition.as_deref(), Some("ZOMBIE"));
+        assert_eq!((zombie.rect.width, zombie.rect.height), (4, 2));
+        // Each sprite's pixels come from its tiles
+        let pixel = |rect: AtlasRect| atlas.get_image().get_pixel(rect.x, rect.y)[0];
+        assert_eq!(pixel(manifest.sprites[0].rect), 1);
+        assert_eq!(pixel(zombie.rect), 2);
+    }
+}
@@ -0,0 +1,319 @@
+//! Reports which creatures and plants have graphics, and which graphics have nothing to show.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    Creature, Graphic, Plant,
+    tokens::{GraphicTypeToken, ObjectType},
+    traits::RawObject,
+};
+
+/// The graphics coverage of the creatures, plants and graphics from one module
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraphicsCoverage {
+    /// The name of the module
+    pub module: String,
+    /// The `object_id` of the module
+    pub module_object_id: Uuid,
+    /// The number of creatures defined in the module
+    pub creatures: usize,
+    /// The number of creatures defined in the module which have graphics (from any module)
+    pub creatures_with_graphics: usize,
+    /// The number of plants defined in the module
+    pub plants: usize,
+    /// The number of plants defined in the module which have graphics (from any module)
+    pub plants_with_graphics: usize,
+    /// Creatures with no `CREATURE_GRAPHICS` or `CREATURE_CASTE_GRAPHICS`
+    pub creatures_without_graphics: Vec<String>,
+    /// Castes (as `CREATURE:CASTE`) with no graphics, for creatures which only have
+    /// `CREATURE_CASTE_GRAPHICS` for some of their castes
+    pub castes_without_graphics: Vec<String>,
+    /// Plants with no `PLANT_GRAPHICS`
+    pub plants_without_graphics: Vec<String>,
+    /// Growths (as `PLANT:GROWTH`) with no sprites in the `PLANT_GRAPHICS` of their plant
+    pub growths_without_graphics: Vec<String>,
+    /// Graphics whose creature, caste, plant or growth wasn't parsed (as `CREATURE`, `CREATURE:CASTE`,
+    /// `PLANT` or `PLANT:GROWTH`)
+    pub graphics_without_targets: Vec<String>,
+}
+
+impl ModuleGraphicsCoverage {
+    /// Returns true if everything in the module has graphics and every graphic has something to show
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.creatures_without_graphics.is_empty()
+            && self.castes_without_graphics.is_empty()
+            && self.plants_without_graphics.is_empty()
+            && self.growths_without_graphics.is_empty()
+            && self.graphics_without_targets.is_empty()
+    }
+}
+
+/// A report of the graphics coverage of a set of parsed raws, per module
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphicsCoverageReport {
+    /// The coverage of each module, in the order the modules' raws were found
+    pub modules: Vec<ModuleGraphicsCoverage>,
+}
+
+impl GraphicsCoverageReport {
+    /// Returns true if every module is fully covered
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.modules.iter().all(ModuleGraphicsCoverage::is_complete)
+    }
+
+    /// Returns the coverage of the module with the given `object_id`
+    #[must_use]
+    pub fn get_module(&self, module_object_id: Uuid) -> Option<&ModuleGraphicsCoverage> {
+        self.modules
+            .iter()
+            .find(|module| module.module_object_id == module_object_id)
+    }
+}
+
+/// The graphics for each target, by uppercase identifier
+#[derive(Default)]
+struct GraphicTargets {
+    /// Creatures with `CREATURE_GRAPHICS`
+    creatures: HashSet<String>,
+    /// The castes of each creature with `CREATURE_CASTE_GRAPHICS`
+    castes: HashMap<String, HashSet<String>>,
+    /// The growths of each plant with `PLANT_GRAPHICS`
+    plants: HashMap<String, HashSet<String>>,
+}
+
+/// Cross-references the graphics in a set of parsed raws against the creatures and plants.
+///
+/// Graphics from any module count for a creature or plant, since a module can add graphics for another
+/// module's objects. Missing graphics are reported in the module of the creature or plant, and graphics
+/// without targets in the module of the graphic. Graphics without targets are only reported for the
+/// object types which were parsed.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws, any which are not creatures, plants or graphics are ignored
+#[must_use]
+pub fn graphics_coverage(raws: &[Box<dyn RawObject>]) -> GraphicsCoverageReport {
+    let graphics: Vec<&Graphic> = raws
+        .iter()
+        .filter(|raw| raw.get_type() == ObjectType::Graphics)
+        .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+        .collect();
+    let mut targets = GraphicTargets::default();
+    for graphic in &graphics {
+        let target = graphic.get_target_identifier().to_uppercase();
+        match (graphic.get_graphic_type(), graphic.get_caste_identifier()) {
+            (GraphicTypeToken::Creature, _) => {
+                targets.creatures.insert(target);
+            }
+            (GraphicTypeToken::CreatureCaste, Some(caste)) => {
+                targets
+                    .castes
+                    .entry(target)
+                    .or_default()
+                    .insert(caste.to_uppercase());
+            }
+            (GraphicTypeToken::Plant, _) => {
+                targets.plants.entry(target).or_default().extend(
+                    graphic
+                        .get_growths()
+                        .into_iter()
+                        .map(|(growth, _)| growth.to_uppercase()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let mut modules: Vec<ModuleGraphicsCoverage> = Vec::new();
+    // The castes and growths of every parsed creature and plant
+    let mut creature_castes: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut plant_growths: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for raw in raws {
+        let any = raw.as_any();
+        if let Some(creature) = any.downcast_ref::<Creature>() {
+            let index = module_index(&mut modules, creature);
+            let coverage = &mut modules[index];
+            let identifier = creature.get_identifier().to_uppercase();
+            let castes: Vec<String> = creature
+                .get_castes()
+                .iter()
+                .map(|caste| caste.get_identifier().to_uppercase())
+                .filter(|caste| caste != "ALL")
+                .collect();
+
+            coverage.creatures += 1;
+            let caste_graphics = targets.castes.get(&identifier);
+            if targets.creatures.contains(&identifier) {
+                coverage.creatures_with_graphics += 1;
+            } else if let Some(caste_graphics) = caste_graphics {
+                coverage.creatures_with_graphics += 1;
+                coverage.castes_without_graphics.extend(
+                    castes
+                        .iter()
+                        .filter(|caste| !caste_graphics.contains(*caste))
+                        .map(|caste| format!("{}:{caste}", creature.get_identifier())),
+                );
+            } else {
+                coverage
+                    .creatures_without_graphics
+                    .push(String::from(creature.get_identifier()));
+            }
+            creature_castes
+                .entry(identifier)
+                .or_default()
+                .extend(castes);
+        } else if let Some(plant) = any.downcast_ref::<Plant>() {
+            let index = module_index(&mut modules, plant);
+            let coverage = &mut modules[index];
+            let identifier = plant.get_identifier().to_uppercase();
+            let growths: Vec<&str> = plant
+                .get_growths()
+                .iter()
+                .map(crate::PlantGrowth::get_identifier)
+                .filter(|growth| !growth.is_empty())
+                .collect();
+
+            coverage.plants += 1;
+            if let Some(growth_graphics) = targets.plants.get(&identifier) {
+                coverage.plants_with_graphics += 1;
+                coverage.growths_without_graphics.extend(
+                    growths
+                        .iter()
+                        .filter(|growth| !growth_graphics.contains(&growth.to_uppercase()))
+                        .map(|growth| format!("{}:{growth}", plant.get_identifier())),
+                );
+            } else {
+                coverage
+                    .plants_without_graphics
+                    .push(String::from(plant.get_identifier()));
+            }
+            plant_growths
+                .entry(identifier)
+                .or_default()
+                .extend(growths.iter().map(|growth| growth.to_uppercase()));
+        }
+    }
+
+    let any_creatures = raws
+        .iter()
+        .any(|raw| raw.get_type() == ObjectType::Creature);
+    let any_plants = raws.iter().any(|raw| raw.get_type() == ObjectType::Plant);
+    for graphic in graphics {
+        let target = graphic.get_target_identifier();
+        let missing: Vec<String> = match graphic.get_graphic_type() {
+            GraphicTypeToken::Creature | GraphicTypeToken::CreatureCaste if any_creatures => {
+                match creature_castes.get(&target.to_uppercase()) {
+                    None => vec![String::from(graphic.get_identifier())],
+                    Some(castes) => graphic
+                        .get_caste_identifier()
+                        .filter(|caste| !castes.contains(&caste.to_uppercase()))
+                        .map(|_| String::from(graphic.get_identifier()))
+                        .into_iter()
+                        .collect(),
+                }
+            }
+            GraphicTypeToken::Plant if any_plants => {
+                match plant_growths.get(&target.to_uppercase()) {
+                    None => vec![String::from(graphic.get_identifier())],
+                    Some(growths) => graphic
+                        .get_growths()
+                        .into_iter()
+                        .filter(|(growth, _)| !growths.contains(&growth.to_uppercase()))
+                        .map(|(growth, _)| format!("{target}:{growth}"))
+                        .collect(),
+                }
+            }
+            _ => Vec::new(),
+        };
+        if !missing.is_empty() {
+            let index = module_index(&mut modules, graphic);
+            modules[index].graphics_without_targets.extend(missing);
+        }
+    }
+
+    GraphicsCoverageReport { modules }
+}
+
+/// Returns the index of the coverage for the module of a raw, adding it if it isn't there yet
+fn module_index(modules: &mut Vec<ModuleGraphicsCoverage>, raw: &dyn RawObject) -> usize {
+    let module_object_id = raw.get_module_object_id();
+    if let Some(index) = modules
+        .iter()
+        .position(|module| module.module_object_id == module_object_id)
+    {
+        return index;
+    }
+    modules.push(ModuleGraphicsCoverage {
+        module: raw.get_metadata().get_module_name().to_string(),
+        module_object_id,
+        ..ModuleGraphicsCoverage::default()
+    });
+    modules.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    #[test]
+    fn test_graphics_coverage() {
+        let metadata = RawMetadata::default();
+        let creature = |identifier: &str, castes: &[&str]| -> Box<dyn RawObject> {
+            let mut creature = Creature::new(identifier, &metadata);
+            for caste in castes {
+                creature.select_caste(caste);
+            }
+            Box::new(creature)
+        };
+        let graphic = |identifier: &str, graphic_type: GraphicTypeToken, growths: &[&str]| {
+            let mut graphic = Graphic::new(identifier, &metadata, graphic_type);
+            for growth in growths {
+                graphic.parse_sprite_from_tag("GROWTH", growth, graphic_type);
+            }
+            Box::new(graphic) as Box<dyn RawObject>
+        };
+        let mut apple = Plant::new("APPLE", &metadata);
+        apple.parse_tag("GROWTH", "LEAVES");
+        apple.parse_tag("GROWTH", "FRUIT");
+
+        let raws = vec![
+            creature("DOG", &["FEMALE", "MALE"]),
+            creature("DWARF", &["FEMALE", "MALE"]),
+            creature("CAT", &["FEMALE", "MALE"]),
+            Box::new(apple),
+            Box::new(Plant::new("POTATO", &metadata)),
+            graphic("DOG", GraphicTypeToken::Creature, &[]),
+            graphic("DWARF:FEMALE", GraphicTypeToken::CreatureCaste, &[]),
+            graphic("DOG:PUPPY", GraphicTypeToken::CreatureCaste, &[]),
+            graphic("UNICORN", GraphicTypeToken::Creature, &[]),
+            graphic("APPLE", GraphicTypeToken::Plant, &["LEAVES", "FLOWERS"]),
+        ];
+
+        let report = graphics_coverage(&raws);
+        assert_eq!(report.modules.len(), 1);
+        let coverage = &report.modules[0];
+        assert_eq!(
+            (coverage.creatures, coverage.creatures_with_graphics),
+            (3, 2)
+        );
+        assert_eq!((coverage.plants, coverage.plants_with_graphics), (2, 1));
+        assert_eq!(coverage.creatures_without_graphics, vec!["CAT"]);
+        assert_eq!(coverage.castes_without_graphics, vec!["DWARF:MALE"]);
+        assert_eq!(coverage.plants_without_graphics, vec!["POTATO"]);
+        assert_eq!(coverage.growths_without_graphics, vec!["APPLE:FRUIT"]);
+        assert_eq!(
+            coverage.graphics_without_targets,
+            vec!["DOG:PUPPY", "UNICORN", "APPLE:FLOWERS"]
+        );
+        assert!(!report.is_complete());
+    }
+}
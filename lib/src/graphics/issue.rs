@@ -0,0 +1,160 @@
+//! Problems found when validating graphics raws.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Dimensions;
+
+/// A single problem found when validating graphics and tile pages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum GraphicsIssue {
+    /// A sprite uses a tile page which wasn't parsed
+    MissingTilePage {
+        /// The identifier of the graphic with the sprite
+        graphic: String,
+        /// The name of the module the graphic is from
+        module: String,
+        /// The sprite, e.g. `CHILD:DEFAULT`, `LAYER:BODY` or `GROWTH:FRUIT`
+        sprite: String,
+        /// The identifier of the tile page which is missing
+        tile_page: String,
+    },
+    /// A sprite uses tiles which are outside of its tile page
+    TileOutOfRange {
+        /// The identifier of the graphic with the sprite
+        graphic: String,
+        /// The name of the module the graphic is from
+        module: String,
+        /// The sprite, e.g. `CHILD:DEFAULT`, `LAYER:BODY` or `GROWTH:FRUIT`
+        sprite: String,
+        /// The identifier of the tile page
+        tile_page: String,
+        /// The tile offset of the (top-left) tile of the sprite
+        offset: Dimensions,
+        /// The tile offset of the bottom-right tile of the sprite, for large images
+        offset2: Option<Dimensions>,
+        /// The size of the tile page, in tiles (`PAGE_DIM`)
+        page_dim: Dimensions,
+    },
+    /// The image of a tile page doesn't exist
+    MissingImage {
+        /// The identifier of the tile page
+        tile_page: String,
+        /// The name of the module the tile page is from
+        module: String,
+        /// The path of the image
+        path: String,
+    },
+    /// The image of a tile page exists but couldn't be read
+    UnreadableImage {
+        /// The identifier of the tile page
+        tile_page: String,
+        /// The name of the module the tile page is from
+        module: String,
+        /// The path of the image
+        path: String,
+        /// Why the image couldn't be read
+        error: String,
+    },
+    /// The image of a tile page isn't the size declared by its `TILE_DIM` and `PAGE_DIM`
+    ImageSizeMismatch {
+        /// The identifier of the tile page
+        tile_page: String,
+        /// The name of the module the tile page is from
+        module: String,
+        /// The path of the image
+        path: String,
+        /// The declared size, in pixels
+        expected: Dimensions,
+        /// The size of the image, in pixels
+        actual: Dimensions,
+    },
+    /// The size of a tile page declared by its `TILE_DIM` and `PAGE_DIM` is negative or too large
+    InvalidPageSize {
+        /// The identifier of the tile page
+        tile_page: String,
+        /// The name of the module the tile page is from
+        module: String,
+        /// The size of a tile, in pixels (`TILE_DIM`)
+        tile_dim: Dimensions,
+        /// The size of the tile page, in tiles (`PAGE_DIM`)
+        page_dim: Dimensions,
+    },
+}
+
+impl std::fmt::Display for GraphicsIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTilePage {
+                graphic,
+                module,
+                sprite,
+                tile_page,
+            } => write!(
+                f,
+                "{graphic} ({module}) sprite {sprite} uses tile page {tile_page}, which is not present"
+            ),
+            Self::TileOutOfRange {
+                graphic,
+                module,
+                sprite,
+                tile_page,
+                offset,
+                offset2,
+                page_dim,
+            } => {
+                write!(
+                    f,
+                    "{graphic} ({module}) sprite {sprite} uses tile {},{}",
+                    offset.x, offset.y
+                )?;
+                if let Some(offset2) = offset2 {
+                    write!(f, " to {},{}", offset2.x, offset2.y)?;
+                }
+                write!(
+                    f,
+                    " which is outside of tile page {tile_page} ({}x{} tiles)",
+                    page_dim.x, page_dim.y
+                )
+            }
+            Self::MissingImage {
+                tile_page,
+                module,
+                path,
+            } => write!(
+                f,
+                "tile page {tile_page} ({module}) image {path} does not exist"
+            ),
+            Self::UnreadableImage {
+                tile_page,
+                module,
+                path,
+                error,
+            } => write!(
+                f,
+                "tile page {tile_page} ({module}) image {path} could not be read: {error}"
+            ),
+            Self::ImageSizeMismatch {
+                tile_page,
+                module,
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "tile page {tile_page} ({module}) image {path} is {}x{} pixels but should be {}x{}",
+                actual.x, actual.y, expected.x, expected.y
+            ),
+            Self::InvalidPageSize {
+                tile_page,
+                module,
+                tile_dim,
+                page_dim,
+            } => write!(
+                f,
+                "tile page {tile_page} ({module}) has {}x{} tiles of {}x{} pixels, which is not a valid image size",
+                page_dim.x, page_dim.y, tile_dim.x, tile_dim.y
+            ),
+        }
+    }
+}
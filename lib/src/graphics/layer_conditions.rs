@@ -0,0 +1,405 @@
+//! Evaluates the conditions on the layers of a `LAYER_SET`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{Graphic, SpriteLayer, tokens::ConditionToken};
+
+/// The appearance of a tissue layer (e.g. `HAIR`), as chosen by its `TL_COLOR_MODIFIER` and
+/// `TISSUE_LAYER_APPEARANCE_MODIFIER`s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TissueAppearance {
+    /// The descriptor color of the tissue, e.g. `BLACK`
+    pub color: Option<String>,
+    /// The shaping (style) of the tissue, e.g. `BRAIDED`. `None` if it isn't shaped
+    pub shaping: Option<String>,
+    /// The `LENGTH` appearance modifier
+    pub length: i32,
+    /// The `DENSITY` appearance modifier
+    pub density: i32,
+    /// The `CURLY` appearance modifier
+    pub curly: i32,
+}
+
+/// The state of a creature to draw, used to choose which layers of a `LAYER_SET` are drawn.
+///
+/// Conditions are matched as follows:
+///
+/// * Multiple `CONDITION_CASTE` on a layer match if any of them is the caste
+/// * `CONDITION_CHILD` and `CONDITION_NOT_CHILD` check the [`ConditionToken::Child`] (or
+///   [`ConditionToken::Baby`]) flag
+/// * `CONDITION_ITEM_WORN` and `SHUT_OFF_IF_ITEM_PRESENT` check the worn items added with
+///   [`Self::with_item_worn`], which must be written the same way as in the condition (e.g.
+///   `BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM`)
+/// * `CONDITION_RANDOM_PART_INDEX` checks the index chosen with [`Self::with_random_part`] (the first
+///   option if none was chosen)
+/// * `TISSUE_*` conditions check the [`TissueAppearance`] of the tissue selected by the
+///   `CONDITION_TISSUE_LAYER` before them (or of any tissue if none was selected). Unset measures are 0.
+/// * Conditions which select a body part or change how a layer is drawn (`CONDITION_BP`, `BP_PRESENT`,
+///   `USE_PALETTE`, `TISSUE_SWAP`, etc.) always match
+/// * Any other condition matches if it was added as a flag, or with a value which has a part (split on
+///   `:`) in common with the condition's value (e.g. a profession for `CONDITION_PROFESSION_CATEGORY`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerConditions {
+    /// The layer set to draw
+    layer_set: String,
+    /// The identifier of the caste
+    caste: Option<String>,
+    /// Conditions which are active without a value
+    flags: HashSet<ConditionToken>,
+    /// Conditions which are active with a value
+    values: Vec<(ConditionToken, String)>,
+    /// The chosen index of each random part
+    random_parts: HashMap<String, u32>,
+    /// The appearance of each tissue layer
+    tissues: HashMap<String, TissueAppearance>,
+    /// The number of items being hauled
+    haul_count: i32,
+    /// The size of the creature, if known
+    body_size: Option<i32>,
+}
+
+impl Default for LayerConditions {
+    fn default() -> Self {
+        Self {
+            layer_set: String::from("DEFAULT"),
+            caste: None,
+            flags: HashSet::new(),
+            values: Vec::new(),
+            random_parts: HashMap::new(),
+            tissues: HashMap::new(),
+            haul_count: 0,
+            body_size: None,
+        }
+    }
+}
+
+impl LayerConditions {
+    /// Creates the conditions for a caste, drawing the `DEFAULT` layer set.
+    #[must_use]
+    pub fn new(caste: &str) -> Self {
+        Self::default().with_caste(caste)
+    }
+
+    /// Sets the layer set to draw (e.g. `PORTRAIT`)
+    #[must_use]
+    pub fn with_layer_set(mut self, layer_set: &str) -> Self {
+        self.layer_set = String::from(layer_set);
+        self
+    }
+
+    /// Sets the caste (e.g. `MALE`)
+    #[must_use]
+    pub fn with_caste(mut self, caste: &str) -> Self {
+        self.caste = Some(String::from(caste));
+        self
+    }
+
+    /// Adds a condition which is active without a value (e.g. [`ConditionToken::Child`] or
+    /// [`ConditionToken::Ghost`])
+    #[must_use]
+    pub fn with_flag(mut self, condition: ConditionToken) -> Self {
+        self.flags.insert(condition);
+        self
+    }
+
+    /// Adds a condition which is active with a value (e.g. [`ConditionToken::SyndromeClass`] with `ZOMBIE`)
+    #[must_use]
+    pub fn with_value(mut self, condition: ConditionToken, value: &str) -> Self {
+        self.values.push((condition, String::from(value)));
+        self
+    }
+
+    /// Adds a worn item, as written in `CONDITION_ITEM_WORN` (e.g. `BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM`)
+    #[must_use]
+    pub fn with_item_worn(self, item: &str) -> Self {
+        self.with_value(ConditionToken::ItemWorn, item)
+    }
+
+    /// Adds a profession (or profession category), e.g. `MINER`
+    #[must_use]
+    pub fn with_profession(self, profession: &str) -> Self {
+        self.with_value(ConditionToken::ProfessionCategory, profession)
+    }
+
+    /// Chooses the index (starting at 1) of a random part, e.g. `HEAD`
+    #[must_use]
+    pub fn with_random_part(mut self, part: &str, index: u32) -> Self {
+        self.random_parts.insert(part.to_uppercase(), index);
+        self
+    }
+
+    /// Sets the appearance of a tissue layer, e.g. `HAIR`
+    #[must_use]
+    pub fn with_tissue(mut self, tissue: &str, appearance: TissueAppearance) -> Self {
+        self.tissues.insert(tissue.to_uppercase(), appearance);
+        self
+    }
+
+    /// Sets the color of a tissue layer (its `TL_COLOR_MODIFIER` choice), e.g. `HAIR` and `BLACK`
+    #[must_use]
+    pub fn with_tissue_color(mut self, tissue: &str, color: &str) -> Self {
+        self.tissues.entry(tissue.to_uppercase()).or_default().color = Some(String::from(color));
+        self
+    }
+
+    /// Sets the number of items being hauled
+    #[must_use]
+    pub const fn with_haul_count(mut self, haul_count: i32) -> Self {
+        self.haul_count = haul_count;
+        self
+    }
+
+    /// Sets the size of the creature
+    #[must_use]
+    pub const fn with_body_size(mut self, body_size: i32) -> Self {
+        self.body_size = Some(body_size);
+        self
+    }
+
+    /// Returns the layer set to draw
+    #[must_use]
+    pub fn get_layer_set(&self) -> &str {
+        &self.layer_set
+    }
+
+    /// Returns the layers of the graphic's layer set which should be drawn, in the order to draw them.
+    ///
+    /// Within a `LAYER_GROUP`, only the first matching layer is drawn.
+    #[must_use]
+    pub fn select_layers<'a>(&self, graphic: &'a Graphic) -> Vec<&'a SpriteLayer> {
+        let mut drawn_groups = HashSet::new();
+        let mut layers = Vec::new();
+        for layer in graphic.get_layer_set(&self.layer_set).unwrap_or_default() {
+            let group = layer.get_layer_group();
+            if group.is_some_and(|group| drawn_groups.contains(&group)) || !self.matches(layer) {
+                continue;
+            }
+            if let Some(group) = group {
+                drawn_groups.insert(group);
+            }
+            layers.push(layer);
+        }
+        layers
+    }
+
+    /// Returns true if all the conditions of the layer are met
+    #[must_use]
+    pub fn matches(&self, layer: &SpriteLayer) -> bool {
+        let mut castes = layer
+            .get_conditions()
+            .iter()
+            .filter(|(condition, _)| *condition == ConditionToken::Caste)
+            .peekable();
+        if castes.peek().is_some()
+            && !castes.any(|(_, caste)| {
+                self.caste
+                    .as_ref()
+                    .is_some_and(|selected| selected.eq_ignore_ascii_case(caste))
+            })
+        {
+            return false;
+        }
+
+        let mut tissue: Option<&str> = None;
+        layer
+            .get_conditions()
+            .iter()
+            .all(|(condition, value)| match condition {
+                ConditionToken::Caste => true,
+                ConditionToken::TissueLayer => {
+                    // e.g. BY_CATEGORY:HEAD:HAIR or BY_CATEGORY:ALL:SKIN
+                    tissue = value.rsplit(':').next().filter(|t| *t != "ALL");
+                    true
+                }
+                _ => self.condition_is_met(*condition, value, tissue),
+            })
+    }
+
+    fn condition_is_met(
+        &self,
+        condition: ConditionToken,
+        value: &str,
+        tissue: Option<&str>,
+    ) -> bool {
+        let number = || {
+            value
+                .split(':')
+                .next_back()
+                .and_then(|n| n.parse::<i32>().ok())
+        };
+        match condition {
+            ConditionToken::BodyPart
+            | ConditionToken::LayerGroupBodyPart
+            | ConditionToken::BodyPartPresent
+            | ConditionToken::BodyPartAppearanceModifierRange
+            | ConditionToken::UsePalette
+            | ConditionToken::UseStandardPaletteFromItem
+            | ConditionToken::TissueSwap => true,
+            ConditionToken::Child => self.is_child(),
+            ConditionToken::NotChild => !self.is_child(),
+            ConditionToken::NotDyed => !self.has_value(ConditionToken::Dye),
+            ConditionToken::ItemWorn => self.is_wearing(value),
+            ConditionToken::ShutOffIfItemPresent => !self.is_wearing(value),
+            ConditionToken::RandomPartIndex => {
+                let mut parts = value.split(':');
+                let part = parts.next().unwrap_or_default().to_uppercase();
+                let index = parts.next().and_then(|index| index.parse::<u32>().ok());
+                index == Some(self.random_parts.get(&part).copied().unwrap_or(1))
+            }
+            ConditionToken::HaulCountMin => number().is_some_and(|n| self.haul_count >= n),
+            ConditionToken::HaulCountMax => number().is_some_and(|n| self.haul_count <= n),
+            ConditionToken::BodySizeMin => {
+                number().is_some_and(|n| self.body_size.is_none_or(|size| size >= n))
+            }
+            ConditionToken::BodySizeMax => {
+                number().is_some_and(|n| self.body_size.is_none_or(|size| size <= n))
+            }
+            ConditionToken::TissueMayHaveColor => self.any_tissue(tissue, |appearance| {
+                appearance
+                    .color
+                    .as_ref()
+                    .is_some_and(|color| value.split(':').any(|c| c.eq_ignore_ascii_case(color)))
+            }),
+            ConditionToken::TissueMayHaveShaping => self.any_tissue(tissue, |appearance| {
+                appearance.shaping.as_ref().is_some_and(|shaping| {
+                    value.split(':').any(|s| s.eq_ignore_ascii_case(shaping))
+                })
+            }),
+            ConditionToken::TissueNotShaped => {
+                self.any_tissue(tissue, |appearance| appearance.shaping.is_none())
+            }
+            ConditionToken::TissueMinLength => {
+                self.tissue_measure(tissue, number(), |a, n| a.length >= n)
+            }
+            ConditionToken::TissueMaxLength => {
+                self.tissue_measure(tissue, number(), |a, n| a.length <= n)
+            }
+            ConditionToken::TissueMinDensity => {
+                self.tissue_measure(tissue, number(), |a, n| a.density >= n)
+            }
+            ConditionToken::TissueMaxDensity => {
+                self.tissue_measure(tissue, number(), |a, n| a.density <= n)
+            }
+            ConditionToken::TissueMinCurly => {
+                self.tissue_measure(tissue, number(), |a, n| a.curly >= n)
+            }
+            ConditionToken::TissueMaxCurly => {
+                self.tissue_measure(tissue, number(), |a, n| a.curly <= n)
+            }
+            _ => {
+                self.flags.contains(&condition)
+                    || self.values.iter().any(|(active, active_value)| {
+                        *active == condition
+                            && active_value.split(':').any(|part| {
+                                value
+                                    .split(':')
+                                    .any(|other| other.eq_ignore_ascii_case(part))
+                            })
+                    })
+            }
+        }
+    }
+
+    fn is_child(&self) -> bool {
+        self.flags.contains(&ConditionToken::Child) || self.flags.contains(&ConditionToken::Baby)
+    }
+
+    fn has_value(&self, condition: ConditionToken) -> bool {
+        self.values.iter().any(|(active, _)| *active == condition)
+    }
+
+    fn is_wearing(&self, item: &str) -> bool {
+        self.values.iter().any(|(active, worn)| {
+            *active == ConditionToken::ItemWorn && worn.eq_ignore_ascii_case(item)
+        })
+    }
+
+    /// Checks the selected tissue, or any tissue if none is selected. A tissue which wasn't set up has the
+    /// default appearance.
+    fn any_tissue(&self, tissue: Option<&str>, check: impl Fn(&TissueAppearance) -> bool) -> bool {
+        match tissue {
+            Some(tissue) => self
+                .tissues
+                .get(&tissue.to_uppercase())
+                .map_or_else(|| check(&TissueAppearance::default()), &check),
+            None if self.tissues.is_empty() => check(&TissueAppearance::default()),
+            None => self.tissues.values().any(check),
+        }
+    }
+
+    fn tissue_measure(
+        &self,
+        tissue: Option<&str>,
+        number: Option<i32>,
+        check: impl Fn(&TissueAppearance, i32) -> bool,
+    ) -> bool {
+        number.is_some_and(|n| self.any_tissue(tissue, |appearance| check(appearance, n)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    fn dwarf_graphic() -> Graphic {
+        let mut graphic =
+            Graphic::new("DWARF", &RawMetadata::default(), GraphicTypeToken::Creature);
+        for (key, value) in [
+            ("LAYER_SET", "DEFAULT"),
+            ("LAYER", "BODY:PAGE:0:0"),
+            ("LAYER", "BEARD:PAGE:1:0"),
+            ("CONDITION_CASTE", "MALE"),
+            ("CONDITION_NOT_CHILD", ""),
+            ("LAYER_GROUP", ""),
+            ("LAYER", "HAIR_BLACK:PAGE:2:0"),
+            ("CONDITION_TISSUE_LAYER", "BY_CATEGORY:HEAD:HAIR"),
+            ("TISSUE_MAY_HAVE_COLOR", "BLACK:CHARCOAL"),
+            ("LAYER", "HAIR:PAGE:3:0"),
+            ("CONDITION_TISSUE_LAYER", "BY_CATEGORY:HEAD:HAIR"),
+            ("END_LAYER_GROUP", ""),
+            ("LAYER", "HELM:PAGE:4:0"),
+            (
+                "CONDITION_ITEM_WORN",
+                "BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM",
+            ),
+            ("LAYER", "PICK:PAGE:5:0"),
+            ("CONDITION_PROFESSION_CATEGORY", "MINER:WOODCUTTER"),
+        ] {
+            graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+        }
+        graphic
+    }
+
+    fn layer_names(graphic: &Graphic, conditions: &LayerConditions) -> Vec<String> {
+        conditions
+            .select_layers(graphic)
+            .iter()
+            .map(|layer| layer.get_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_select_layers() {
+        let graphic = dwarf_graphic();
+
+        // Only the first matching layer of a group is drawn
+        let adult_male = LayerConditions::new("MALE").with_tissue_color("HAIR", "BLACK");
+        assert_eq!(
+            layer_names(&graphic, &adult_male),
+            vec!["BODY", "BEARD", "HAIR_BLACK"]
+        );
+
+        let child = LayerConditions::new("MALE")
+            .with_flag(ConditionToken::Child)
+            .with_tissue_color("HAIR", "RED")
+            .with_item_worn("BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM")
+            .with_profession("MINER");
+        assert_eq!(
+            layer_names(&graphic, &child),
+            vec!["BODY", "HAIR", "HELM", "PICK"]
+        );
+
+        assert!(layer_names(&graphic, &adult_male.with_layer_set("PORTRAIT")).is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+//! Works with the images referenced by the graphics raws.
+//!
+//! A [`TilePage`](crate::TilePage) points to a PNG image which is split into a grid of tiles, and each
+//! [`SpriteGraphic`](crate::SpriteGraphic) or [`SpriteLayer`](crate::SpriteLayer) names a tile page and the
+//! tile offset (and for large images, the bottom-right tile offset) of its sprite.
+//!
+//! * [`TilePageImage`] - a loaded tile page image, which sprites can be cropped from
+//! * [`SpriteExtractor`] - crops sprites out of the tile pages in a set of parsed raws, loading each
+//!   tile page image once, and composites the layers of a `LAYER_SET`
+//! * [`SpriteAtlas`] - packs the sprites of a set of graphics into one image, with an [`AtlasManifest`]
+//!   mapping each sprite (by target, caste and condition) to its rectangle, for web front ends
+//! * [`LayerConditions`] - the state of a creature (caste, age, worn items, hair color, etc.) which chooses
+//!   the layers of a `LAYER_SET` to draw
+//! * [`SpriteState`] - the state of a creature (e.g. child, corpse, skeleton), used with
+//!   [`find_best_sprite`] to choose one of its sprites the way the game does
+//! * [`growths_on_day`] - the growths a plant has on a day of the year, with the sprite for the stage of
+//!   each growth, for seasonal previews
+//! * [`graphics_coverage`] - cross-references graphics against creatures and plants, reporting what is
+//!   missing graphics (and which graphics have nothing to show) per module
+//! * [`validate_graphics`] - checks sprites against the tile pages they use, and the tile page images
+//!   against their declared size, reporting each [`GraphicsIssue`]
+//!
+//! Images are returned as [`RgbaImage`] pixel buffers, which can be written out with [`write_png`] or
+//! encoded in memory with [`encode_png`].
+
+mod atlas;
+mod coverage;
+mod issue;
+mod layer_conditions;
+mod seasonal_growths;
+mod sprite_extractor;
+mod sprite_state;
+mod tile_page_image;
+mod validator;
+
+pub use atlas::{AtlasManifest, AtlasRect, AtlasSprite, SpriteAtlas};
+pub use coverage::{GraphicsCoverageReport, ModuleGraphicsCoverage, graphics_coverage};
+pub use image::RgbaImage;
+pub use issue::GraphicsIssue;
+pub use layer_conditions::{LayerConditions, TissueAppearance};
+pub use seasonal_growths::{SeasonalGrowth, growths_on_day};
+pub use sprite_extractor::SpriteExtractor;
+pub use sprite_state::{SpriteState, find_best_sprite};
+pub use tile_page_image::{TilePageImage, encode_png, write_png};
+pub use validator::validate_graphics;
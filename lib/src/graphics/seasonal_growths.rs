@@ -0,0 +1,153 @@
+//! Resolves which growths a plant has on a day of the year, and the sprites to draw them with.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Graphic, GrowthPrint, Plant, SpriteGraphic,
+    tokens::{GraphicTypeToken, GrowthToken, ObjectType, PlantGrowthTypeToken},
+    traits::RawObject,
+};
+
+/// A growth which is present on a plant on a day of the year
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonalGrowth {
+    /// The identifier of the growth, e.g. `FRUIT`
+    pub growth: String,
+    /// The type of the growth
+    pub growth_type: PlantGrowthTypeToken,
+    /// The stage of the growth (the index of its `GROWTH_PRINT` used on the day), if it has a print
+    pub stage: Option<usize>,
+    /// The `GROWTH_PRINT` used on the day, which gives the tile and color in ASCII mode
+    pub print: Option<GrowthPrint>,
+    /// The sprite for the stage from the plant's `PLANT_GRAPHICS`, if it has any for the growth
+    pub sprite: Option<SpriteGraphic>,
+}
+
+/// Finds the growths present on a plant on a day of the year, and the sprite to draw each of them with.
+///
+/// A growth is present when the day is inside its `GROWTH_TIMING`, and its stage is the first of its
+/// `GROWTH_PRINT`s used on that day. The sprite is the `GROWTH_1` to `GROWTH_4` sprite matching the
+/// stage, or the first sprite given for the growth when there is no sprite for the stage.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws, which are searched for the `PLANT_GRAPHICS` of the plant
+/// * `plant` - The plant
+/// * `day_of_year` - The day of the year, from 0 (the 1st of Granite) to 335
+///
+/// # Returns
+///
+/// The present growths, in the order they are defined in the plant
+#[must_use]
+pub fn growths_on_day(
+    raws: &[Box<dyn RawObject>],
+    plant: &Plant,
+    day_of_year: u32,
+) -> Vec<SeasonalGrowth> {
+    let growth_graphics: Vec<(String, Vec<SpriteGraphic>)> = raws
+        .iter()
+        .filter(|raw| {
+            raw.get_type() == ObjectType::Graphics
+                && raw
+                    .get_identifier()
+                    .eq_ignore_ascii_case(plant.get_identifier())
+        })
+        .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+        .filter(|graphic| graphic.get_graphic_type() == GraphicTypeToken::Plant)
+        .flat_map(Graphic::get_growths)
+        .collect();
+
+    plant
+        .get_growths()
+        .iter()
+        .filter(|growth| growth.is_present_on_day(day_of_year))
+        .map(|growth| {
+            let stage = growth.get_stage_on_day(day_of_year);
+            let sprites = growth_graphics
+                .iter()
+                .filter(|(identifier, _)| identifier.eq_ignore_ascii_case(growth.get_identifier()))
+                .flat_map(|(_, sprites)| sprites);
+            SeasonalGrowth {
+                growth: String::from(growth.get_identifier()),
+                growth_type: *growth.get_growth_type(),
+                stage: stage.map(|(stage, _)| stage),
+                print: stage.map(|(_, print)| print.clone()),
+                sprite: select_growth_sprite(sprites, stage.map_or(0, |(stage, _)| stage)),
+            }
+        })
+        .collect()
+}
+
+/// Chooses the sprite for a stage of a growth, falling back to the first sprite
+fn select_growth_sprite<'a>(
+    sprites: impl Iterator<Item = &'a SpriteGraphic> + Clone,
+    stage: usize,
+) -> Option<SpriteGraphic> {
+    let stage_token = match stage {
+        0 => GrowthToken::Growth1,
+        1 => GrowthToken::Growth2,
+        2 => GrowthToken::Growth3,
+        _ => GrowthToken::Growth4,
+    };
+    sprites
+        .clone()
+        .find(|sprite| sprite.get_growth() == Some(stage_token))
+        .or_else(|| sprites.clone().next())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::RawMetadata;
+
+    #[test]
+    fn test_growths_on_day() {
+        let metadata = RawMetadata::default();
+        let mut plant = Plant::new("APPLE", &metadata);
+        for (key, value) in [
+            ("GROWTH", "LEAVES"),
+            ("GROWTH_PRINT", "0:6:2:0:0:ALL:1"),
+            ("GROWTH", "FRUIT"),
+            ("GROWTH_TIMING", "120000:300000"),
+            ("GROWTH_PRINT", "'%':'%':2:0:0:120000:199999:3"),
+            ("GROWTH_PRINT", "'%':'%':4:0:1:200000:300000:3"),
+        ] {
+            plant.parse_tag(key, value);
+        }
+        let mut graphic = Graphic::new("APPLE", &metadata, GraphicTypeToken::Plant);
+        for (key, value) in [
+            ("GROWTH", "LEAVES"),
+            ("GROWTH_1", "TREE_GROWTHS:0:0"),
+            ("GROWTH", "FRUIT"),
+            ("GROWTH_1", "TREE_GROWTHS:0:1"),
+            ("GROWTH_2", "TREE_GROWTHS:1:1"),
+        ] {
+            graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Plant);
+        }
+        let raws: Vec<Box<dyn RawObject>> = vec![Box::new(graphic)];
+        let growths = |day| -> Vec<(String, Option<usize>, Option<i32>)> {
+            growths_on_day(&raws, &plant, day)
+                .into_iter()
+                .map(|growth| {
+                    (
+                        growth.growth,
+                        growth.stage,
+                        growth.sprite.map(|sprite| sprite.get_offset().x),
+                    )
+                })
+                .collect()
+        };
+
+        // Early spring has only leaves
+        assert_eq!(
+            growths(10),
+            vec![(String::from("LEAVES"), Some(0), Some(0))]
+        );
+        // Summer has unripe fruit, autumn has ripe fruit
+        assert_eq!(growths(110)[1], (String::from("FRUIT"), Some(0), Some(0)));
+        assert_eq!(growths(200)[1], (String::from("FRUIT"), Some(1), Some(1)));
+        assert_eq!(growths(300).len(), 1);
+    }
+}
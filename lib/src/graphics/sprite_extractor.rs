@@ -0,0 +1,312 @@
+//! Crops sprites out of the tile pages in a set of parsed raws.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use image::{RgbaImage, imageops};
+use uuid::Uuid;
+
+use crate::{
+    Dimensions, Graphic, ParserError, SpriteGraphic, SpriteLayer, TilePage, tokens::ObjectType,
+    traits::RawObject,
+};
+
+use super::{LayerConditions, TilePageImage, write_png};
+
+/// Crops sprites out of the tile pages in a set of parsed raws.
+///
+/// Each tile page image is loaded the first time a sprite is cropped from it, and kept for later sprites.
+///
+/// By default the images are loaded from the paths the tile pages were parsed with. Use
+/// [`Self::set_module_directory`] when the module lives somewhere else (e.g. the raws were loaded from a
+/// saved parse result).
+#[derive(Debug)]
+pub struct SpriteExtractor<'a> {
+    /// Tile pages by (uppercase) identifier
+    tile_pages: HashMap<String, &'a TilePage>,
+    /// Directories to load the images of a module from, by module `object_id`
+    module_directories: HashMap<Uuid, PathBuf>,
+    /// Loaded images by (uppercase) tile page identifier
+    images: HashMap<String, TilePageImage>,
+}
+
+impl<'a> SpriteExtractor<'a> {
+    /// Creates an extractor for the tile pages in the raws.
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws, any which are not tile pages are ignored
+    #[must_use]
+    pub fn new(raws: &'a [Box<dyn RawObject>]) -> Self {
+        let tile_pages = raws
+            .iter()
+            .filter(|raw| raw.get_type() == ObjectType::TilePage)
+            .filter_map(|raw| raw.as_any().downcast_ref::<TilePage>())
+            .map(|tile_page| (tile_page.get_identifier().to_uppercase(), tile_page))
+            .collect();
+
+        Self {
+            tile_pages,
+            module_directories: HashMap::new(),
+            images: HashMap::new(),
+        }
+    }
+
+    /// Loads the images of a module from a different directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `module_object_id` - The `object_id` of the module
+    /// * `directory` - The directory of the module (which contains its `graphics` directory)
+    pub fn set_module_directory(&mut self, module_object_id: Uuid, directory: &Path) {
+        self.module_directories
+            .insert(module_object_id, directory.to_path_buf());
+        // Anything already loaded for the module may have come from the old location
+        self.images.retain(|identifier, _| {
+            self.tile_pages.get(identifier).is_none_or(|tile_page| {
+                tile_page.get_metadata().get_module_object_id() != module_object_id
+            })
+        });
+    }
+
+    /// Returns the tile page with the given identifier
+    #[must_use]
+    pub fn get_tile_page(&self, identifier: &str) -> Option<&'a TilePage> {
+        self.tile_pages.get(&identifier.to_uppercase()).copied()
+    }
+
+    /// Returns the image of a tile page, loading it if it hasn't been loaded yet.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If there is no tile page with the identifier or its image cannot be read
+    pub fn get_image(&mut self, tile_page_id: &str) -> Result<&TilePageImage, ParserError> {
+        let key = tile_page_id.to_uppercase();
+        if !self.images.contains_key(&key) {
+            let Some(tile_page) = self.tile_pages.get(&key) else {
+                return Err(ParserError::Image(format!(
+                    "No tile page {tile_page_id} was parsed"
+                )));
+            };
+            let image = match self
+                .module_directories
+                .get(&tile_page.get_metadata().get_module_object_id())
+            {
+                Some(directory) => TilePageImage::load_from_module_directory(tile_page, directory)?,
+                None => TilePageImage::load(tile_page)?,
+            };
+            self.images.insert(key.clone(), image);
+        }
+        Ok(&self.images[&key])
+    }
+
+    /// Crops a region of tiles from a tile page.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page_id` - The identifier of the tile page
+    /// * `offset` - The tile offset of the top-left tile
+    /// * `offset2` - The tile offset of the bottom-right tile, if the region is larger than one tile
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If there is no tile page with the identifier, its image cannot be read or
+    ///   the region is outside of it
+    pub fn extract_region(
+        &mut self,
+        tile_page_id: &str,
+        offset: Dimensions,
+        offset2: Option<Dimensions>,
+    ) -> Result<RgbaImage, ParserError> {
+        self.get_image(tile_page_id)?.crop(offset, offset2)
+    }
+
+    /// Crops the image of a sprite from its tile page.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the tile page wasn't parsed, its image cannot be read or the sprite is
+    ///   outside of it
+    pub fn extract_sprite(&mut self, sprite: &SpriteGraphic) -> Result<RgbaImage, ParserError> {
+        self.extract_region(
+            sprite.get_tile_page_id(),
+            sprite.get_offset(),
+            sprite.get_offset2(),
+        )
+    }
+
+    /// Crops the image of a layer from its tile page.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the tile page wasn't parsed, its image cannot be read or the layer is
+    ///   outside of it
+    pub fn extract_layer(&mut self, layer: &SpriteLayer) -> Result<RgbaImage, ParserError> {
+        self.extract_region(
+            layer.get_tile_page_id(),
+            layer.get_offset(),
+            layer.get_offset2(),
+        )
+    }
+
+    /// Draws the layers of a graphic's layer set which match the conditions, one on top of the other.
+    ///
+    /// The layers are drawn from the top-left corner in the order they are defined, and the image is as
+    /// large as the largest layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphic` - The graphic with the layer set (e.g. the `CREATURE_GRAPHICS` of a dwarf)
+    /// * `conditions` - The state of the creature, which chooses the layers to draw
+    ///
+    /// # Returns
+    ///
+    /// The composited image, or `None` if no layers match
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If a layer's tile page wasn't parsed, its image cannot be read or the layer
+    ///   is outside of it
+    pub fn compose_layers(
+        &mut self,
+        graphic: &Graphic,
+        conditions: &LayerConditions,
+    ) -> Result<Option<RgbaImage>, ParserError> {
+        let layers = conditions
+            .select_layers(graphic)
+            .into_iter()
+            .map(|layer| self.extract_layer(layer))
+            .collect::<Result<Vec<RgbaImage>, ParserError>>()?;
+
+        let Some(width) = layers.iter().map(RgbaImage::width).max() else {
+            return Ok(None);
+        };
+        let height = layers
+            .iter()
+            .map(RgbaImage::height)
+            .max()
+            .unwrap_or_default();
+
+        let mut image = RgbaImage::new(width, height);
+        for layer in &layers {
+            imageops::overlay(&mut image, layer, 0, 0);
+        }
+        Ok(Some(image))
+    }
+
+    /// Crops the image of a sprite and writes it to a PNG file.
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the tile page wasn't parsed, its image cannot be read, the sprite is
+    ///   outside of it or the file cannot be written
+    pub fn save_sprite_png(
+        &mut self,
+        sprite: &SpriteGraphic,
+        path: &Path,
+    ) -> Result<(), ParserError> {
+        write_png(&self.extract_sprite(sprite)?, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgba;
+
+    use super::*;
+    use crate::{Graphic, metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    #[test]
+    fn test_extract_sprites_from_module_directory() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let module_directory = temp_dir.path();
+        let images_directory = module_directory.join("graphics").join("images");
+        std::fs::create_dir_all(&images_directory).expect("Failed to create test directory");
+
+        // A 3x2 page of 2x2 tiles, where each pixel is colored by the tile it's in
+        let page = RgbaImage::from_fn(6, 4, |x, y| {
+            Rgba([
+                u8::try_from(x / 2).unwrap_or_default(),
+                u8::try_from(y / 2).unwrap_or_default(),
+                0,
+                255,
+            ])
+        });
+        write_png(&page, &images_directory.join("page.png")).expect("Failed to write test image");
+
+        let metadata = RawMetadata::default();
+        let mut tile_page = TilePage::new("PAGE", &metadata);
+        tile_page.parse_tag("FILE", "images/page.png");
+        tile_page.parse_tag("TILE_DIM", "2:2");
+        tile_page.parse_tag("PAGE_DIM", "3:2");
+
+        let mut graphic = Graphic::new("CREATURE", &metadata, GraphicTypeToken::Creature);
+        graphic.parse_sprite_from_tag(
+            "DEFAULT",
+            "PAGE:1:0:AS_IS:DEFAULT",
+            GraphicTypeToken::Creature,
+        );
+        graphic.parse_sprite_from_tag(
+            "DEFAULT",
+            "PAGE:LARGE_IMAGE:1:0:2:1:AS_IS",
+            GraphicTypeToken::Creature,
+        );
+        graphic.parse_sprite_from_tag(
+            "DEFAULT",
+            "PAGE:3:0:AS_IS:CHILD",
+            GraphicTypeToken::Creature,
+        );
+        let sprites = graphic.get_sprites();
+
+        let raws: Vec<Box<dyn RawObject>> = vec![Box::new(tile_page)];
+        let mut extractor = SpriteExtractor::new(&raws);
+        extractor.set_module_directory(metadata.get_module_object_id(), module_directory);
+
+        let sprite = extractor
+            .extract_sprite(&sprites[0])
+            .expect("Failed to extract sprite");
+        assert_eq!(sprite.dimensions(), (2, 2));
+        assert!(sprite.pixels().all(|pixel| *pixel == Rgba([1, 0, 0, 255])));
+
+        let large = extractor
+            .extract_sprite(&sprites[1])
+            .expect("Failed to extract large sprite");
+        assert_eq!(large.dimensions(), (4, 4));
+        assert_eq!(*large.get_pixel(3, 3), Rgba([2, 1, 0, 255]));
+
+        assert!(matches!(
+            extractor.extract_sprite(&sprites[2]),
+            Err(ParserError::Image(_))
+        ));
+
+        // Later layers are drawn on top of earlier ones
+        let mut layered = Graphic::new("LAYERED", &metadata, GraphicTypeToken::Creature);
+        for (key, value) in [
+            ("LAYER_SET", "DEFAULT"),
+            ("LAYER", "BODY:PAGE:0:1"),
+            ("LAYER", "HAT:PAGE:2:1"),
+            ("CONDITION_ITEM_WORN", "BY_CATEGORY:HEAD:HAT"),
+        ] {
+            layered.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+        }
+        let bare = extractor
+            .compose_layers(&layered, &LayerConditions::default())
+            .expect("Failed to compose layers");
+        assert_eq!(
+            bare.map(|image| *image.get_pixel(0, 0)),
+            Some(Rgba([0, 1, 0, 255]))
+        );
+        let hat = extractor
+            .compose_layers(
+                &layered,
+                &LayerConditions::default().with_item_worn("BY_CATEGORY:HEAD:HAT"),
+            )
+            .expect("Failed to compose layers");
+        assert_eq!(
+            hat.map(|image| *image.get_pixel(0, 0)),
+            Some(Rgba([2, 1, 0, 255]))
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Chooses the best sprite for a creature in a given state.
+
+use crate::{
+    Graphic, SpriteGraphic,
+    raw_definitions::CONDITION_TOKENS,
+    tokens::{ConditionToken, ObjectType},
+    traits::RawObject,
+};
+
+/// The state to show a creature in, used to choose between its sprites.
+///
+/// Sprites are defined as `[primary:TILE_PAGE:x:y:color:secondary]`, where the primary condition is the
+/// form of the creature (e.g. `DEFAULT`, `CHILD`, `CORPSE`) and the secondary condition is how it has
+/// been changed (e.g. `DEFAULT`, `SKELETON`, `ZOMBIE`). When the exact sprite for a state isn't defined,
+/// the game falls back to a more general one (ending with `DEFAULT:DEFAULT`), see [`Self::fallbacks`].
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, specta::Type,
+)]
+pub enum SpriteState {
+    /// The adult creature (`DEFAULT`)
+    #[default]
+    Default,
+    /// A child (`CHILD`)
+    Child,
+    /// An animated (risen) corpse (`ANIMATED`)
+    Animated,
+    /// A corpse (`CORPSE`)
+    Corpse,
+    /// The remains of a vermin (`REMAINS`)
+    Remains,
+    /// The icon shown in lists (`LIST_ICON`)
+    ListIcon,
+    /// Trained for hunting (`TRAINED_HUNTER`)
+    TrainedHunter,
+    /// Trained for war (`TRAINED_WAR`)
+    TrainedWar,
+    /// A skeleton (secondary `SKELETON`)
+    Skeleton,
+    /// A skeleton with a skull (secondary `SKELETON_WITH_SKULL`)
+    SkeletonWithSkull,
+    /// A zombie (secondary `ZOMBIE`)
+    Zombie,
+    /// A necromancer (secondary `NECROMANCER`)
+    Necromancer,
+    /// A vampire (secondary `VAMPCURSE`)
+    VampireCursed,
+    /// A ghoul (secondary `GHOUL`)
+    Ghoul,
+    /// Disturbed dead (secondary `DISTURBED_DEAD`)
+    DisturbedDead,
+    /// The portrait (the `PORTRAIT` layer set)
+    Portrait,
+}
+
+impl SpriteState {
+    /// Returns the (primary, secondary) conditions to look for, best first.
+    ///
+    /// A secondary of `None` matches any secondary condition, and a secondary of
+    /// [`ConditionToken::Default`] also matches sprites without one.
+    #[must_use]
+    pub const fn fallbacks(self) -> &'static [(ConditionToken, Option<ConditionToken>)] {
+        const DEFAULT: (ConditionToken, Option<ConditionToken>) =
+            (ConditionToken::Default, Some(ConditionToken::Default));
+        match self {
+            Self::Default => &[DEFAULT],
+            Self::Child => &[
+                (ConditionToken::ChildPrime, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::Animated => &[
+                (ConditionToken::Animated, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::Corpse => &[
+                (ConditionToken::Corpse, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::Remains => &[
+                (ConditionToken::Remains, Some(ConditionToken::Default)),
+                (ConditionToken::Corpse, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::ListIcon => &[
+                (ConditionToken::ListIcon, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::TrainedHunter => &[
+                (ConditionToken::TrainedHunter, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::TrainedWar => &[
+                (ConditionToken::TrainedWar, Some(ConditionToken::Default)),
+                DEFAULT,
+            ],
+            Self::Skeleton => &[
+                (ConditionToken::Default, Some(ConditionToken::Skeleton)),
+                (
+                    ConditionToken::Default,
+                    Some(ConditionToken::SkeletonWithSkull),
+                ),
+                DEFAULT,
+            ],
+            Self::SkeletonWithSkull => &[
+                (
+                    ConditionToken::Default,
+                    Some(ConditionToken::SkeletonWithSkull),
+                ),
+                (ConditionToken::Default, Some(ConditionToken::Skeleton)),
+                DEFAULT,
+            ],
+            Self::Zombie => &[
+                (ConditionToken::Default, Some(ConditionToken::Zombie)),
+                DEFAULT,
+            ],
+            Self::Necromancer => &[
+                (ConditionToken::Default, Some(ConditionToken::Necromancer)),
+                DEFAULT,
+            ],
+            Self::VampireCursed => &[
+                (ConditionToken::Default, Some(ConditionToken::VampireCursed)),
+                DEFAULT,
+            ],
+            Self::Ghoul => &[
+                (ConditionToken::Default, Some(ConditionToken::Ghoul)),
+                DEFAULT,
+            ],
+            Self::DisturbedDead => &[
+                (ConditionToken::Default, Some(ConditionToken::DisturbedDead)),
+                DEFAULT,
+            ],
+            Self::Portrait => &[(ConditionToken::Portrait, None), DEFAULT],
+        }
+    }
+
+    /// Ranks a sprite by its conditions, where a lower rank is a better match.
+    ///
+    /// # Returns
+    ///
+    /// The index in [`Self::fallbacks`] of the first match, or `None` if the sprite isn't used for this
+    /// state
+    #[must_use]
+    pub fn rank(self, primary: ConditionToken, secondary: ConditionToken) -> Option<usize> {
+        self.fallbacks()
+            .iter()
+            .position(|(wanted_primary, wanted_secondary)| {
+                *wanted_primary == primary
+                    && wanted_secondary.is_none_or(|wanted| {
+                        wanted == secondary
+                            || (wanted == ConditionToken::Default
+                                && secondary == ConditionToken::None)
+                    })
+            })
+    }
+
+    /// Ranks a sprite by its conditions written as tokens (e.g. `CHILD` and `DEFAULT`), as they are stored
+    /// in a database. A missing or empty secondary condition counts as no secondary condition.
+    /// See [`Self::rank`].
+    ///
+    /// # Returns
+    ///
+    /// The rank of the sprite, or `None` if the sprite isn't used for this state or a condition isn't a
+    /// known token
+    #[must_use]
+    pub fn rank_keys(self, primary: &str, secondary: Option<&str>) -> Option<usize> {
+        let primary = *CONDITION_TOKENS.get(primary)?;
+        let secondary = match secondary.filter(|secondary| !secondary.is_empty()) {
+            Some(secondary) => *CONDITION_TOKENS.get(secondary)?,
+            None => ConditionToken::None,
+        };
+        self.rank(primary, secondary)
+    }
+
+    /// Chooses the best sprite for this state from a list of sprites.
+    ///
+    /// When more than one sprite matches equally well, the first one is chosen.
+    #[must_use]
+    pub fn select<'a>(
+        self,
+        sprites: impl IntoIterator<Item = &'a SpriteGraphic>,
+    ) -> Option<&'a SpriteGraphic> {
+        sprites
+            .into_iter()
+            .filter_map(|sprite| {
+                self.rank(
+                    sprite.get_primary_condition(),
+                    sprite.get_secondary_condition(),
+                )
+                .map(|rank| (rank, sprite))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, sprite)| sprite)
+    }
+}
+
+/// Finds the best sprite to show a creature (or other target) in a state.
+///
+/// When a caste is given, the sprites from its `CREATURE_CASTE_GRAPHICS` are used if any of them match,
+/// otherwise the sprites from the `CREATURE_GRAPHICS` are used.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws, any which are not graphics are ignored
+/// * `target_identifier` - The identifier of the creature, e.g. `DWARF`
+/// * `caste` - The identifier of the caste, e.g. `FEMALE`
+/// * `state` - The state to show the creature in
+#[must_use]
+pub fn find_best_sprite(
+    raws: &[Box<dyn RawObject>],
+    target_identifier: &str,
+    caste: Option<&str>,
+    state: SpriteState,
+) -> Option<SpriteGraphic> {
+    let sprites_for = |identifier: &str| -> Vec<SpriteGraphic> {
+        raws.iter()
+            .filter(|raw| {
+                raw.get_type() == ObjectType::Graphics
+                    && raw.get_identifier().eq_ignore_ascii_case(identifier)
+            })
+            .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+            .flat_map(Graphic::get_sprites)
+            .collect()
+    };
+
+    caste
+        .and_then(|caste| {
+            state
+                .select(&sprites_for(&format!("{target_identifier}:{caste}")))
+                .cloned()
+        })
+        .or_else(|| state.select(&sprites_for(target_identifier)).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    fn graphic(identifier: &str, sprites: &[&str]) -> Box<dyn RawObject> {
+        let mut graphic = Graphic::new(
+            identifier,
+            &RawMetadata::default(),
+            GraphicTypeToken::Creature,
+        );
+        for sprite in sprites {
+            let (key, value) = sprite.split_once(':').unwrap_or_default();
+            graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+        }
+        Box::new(graphic)
+    }
+
+    #[test]
+    fn test_find_best_sprite_falls_back() {
+        let raws = vec![
+            graphic(
+                "DOG",
+                &[
+                    "DEFAULT:PAGE:0:0:AS_IS:DEFAULT",
+                    "CHILD:PAGE:1:0:AS_IS:DEFAULT",
+                    "DEFAULT:PAGE:2:0:AS_IS:SKELETON",
+                    "CORPSE:PAGE:3:0:AS_IS",
+                ],
+            ),
+            graphic("DOG:FEMALE", &["CHILD:PAGE:4:0:AS_IS:DEFAULT"]),
+        ];
+        let offset_x = |caste: Option<&str>, state: SpriteState| {
+            find_best_sprite(&raws, "DOG", caste, state).map(|sprite| sprite.get_offset().x)
+        };
+
+        assert_eq!(offset_x(None, SpriteState::Default), Some(0));
+        assert_eq!(offset_x(None, SpriteState::Child), Some(1));
+        assert_eq!(offset_x(None, SpriteState::Skeleton), Some(2));
+        // Corpse sprites without a secondary condition count as DEFAULT
+        assert_eq!(offset_x(None, SpriteState::Corpse), Some(3));
+        assert_eq!(offset_x(None, SpriteState::Remains), Some(3));
+        assert_eq!(offset_x(None, SpriteState::Zombie), Some(0));
+        // Caste graphics are used when they have a matching sprite
+        assert_eq!(offset_x(Some("FEMALE"), SpriteState::Child), Some(4));
+        assert_eq!(offset_x(Some("FEMALE"), SpriteState::Default), Some(0));
+        assert_eq!(offset_x(None, SpriteState::Portrait), Some(0));
+        assert_eq!(
+            find_best_sprite(&raws, "CAT", None, SpriteState::Default),
+            None
+        );
+
+        assert_eq!(SpriteState::Child.rank_keys("CHILD", None), Some(0));
+        assert_eq!(
+            SpriteState::Child.rank_keys("DEFAULT", Some("DEFAULT")),
+            Some(1)
+        );
+        assert_eq!(
+            SpriteState::Child.rank_keys("DEFAULT", Some("ZOMBIE")),
+            None
+        );
+        assert_eq!(SpriteState::Child.rank_keys("ChildPrime", None), None);
+        assert_eq!(SpriteState::Zombie.rank_keys("DEFAULT", Some("")), Some(1));
+    }
+}
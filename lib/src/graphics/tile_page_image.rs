@@ -0,0 +1,241 @@
+//! A loaded tile page image.
+
+use std::{
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use image::{ImageFormat, RgbaImage, imageops};
+use tracing::debug;
+
+use crate::{Dimensions, ParserError, TilePage, traits::RawObject as _};
+
+/// The image for a [`TilePage`], which sprites can be cropped from.
+#[derive(Debug, Clone)]
+pub struct TilePageImage {
+    /// The identifier of the tile page
+    identifier: String,
+    /// The size of each tile, in pixels
+    tile_dim: Dimensions,
+    /// The pixels of the page
+    image: RgbaImage,
+}
+
+impl TilePageImage {
+    /// Loads the image for a tile page from the path it was parsed with.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page` - The tile page to load the image for
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the image cannot be read
+    pub fn load(tile_page: &TilePage) -> Result<Self, ParserError> {
+        Self::load_from_path(tile_page, &tile_page.get_file_path())
+    }
+
+    /// Loads the image for a tile page from a module directory.
+    ///
+    /// The image is expected at `graphics/<FILE>` inside the module directory, which is where Dwarf
+    /// Fortress looks for it. This is useful when the raws were parsed somewhere else (e.g. a parse result
+    /// saved as JSON).
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page` - The tile page to load the image for
+    /// * `module_directory` - The directory of the module which defines the tile page
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the image cannot be read
+    pub fn load_from_module_directory(
+        tile_page: &TilePage,
+        module_directory: &Path,
+    ) -> Result<Self, ParserError> {
+        let path = module_directory
+            .join("graphics")
+            .join(tile_page.get_relative_file_path());
+        Self::load_from_path(tile_page, &path)
+    }
+
+    fn load_from_path(tile_page: &TilePage, path: &PathBuf) -> Result<Self, ParserError> {
+        debug!(
+            "Loading tile page {} from {}",
+            tile_page.get_identifier(),
+            path.display()
+        );
+        let image = image::open(path)
+            .map_err(|e| {
+                ParserError::Image(format!(
+                    "Unable to load tile page {} from {}: {e}",
+                    tile_page.get_identifier(),
+                    path.display()
+                ))
+            })?
+            .to_rgba8();
+
+        Ok(Self::from_image(tile_page, image))
+    }
+
+    /// Creates a tile page image from pixels which are already loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_page` - The tile page the image is for
+    /// * `image` - The pixels of the page
+    #[must_use]
+    pub fn from_image(tile_page: &TilePage, image: RgbaImage) -> Self {
+        Self {
+            identifier: String::from(tile_page.get_identifier()),
+            tile_dim: tile_page.get_tile_dimensions(),
+            image,
+        }
+    }
+
+    /// Returns the identifier of the tile page
+    #[must_use]
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Returns the pixels of the whole page
+    #[must_use]
+    pub const fn get_image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// Crops a sprite from the page.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The tile offset of the (top-left) tile of the sprite
+    /// * `offset2` - For large images, the tile offset of the bottom-right tile of the sprite
+    ///
+    /// # Returns
+    ///
+    /// The pixels of the sprite
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::Image` - If the sprite is not inside the page
+    pub fn crop(
+        &self,
+        offset: Dimensions,
+        offset2: Option<Dimensions>,
+    ) -> Result<RgbaImage, ParserError> {
+        let bottom_right = offset2.unwrap_or(offset);
+        let out_of_bounds = || {
+            ParserError::Image(format!(
+                "Sprite at {},{} to {},{} is outside of tile page {} ({}x{} pixels, {}x{} tiles)",
+                offset.x,
+                offset.y,
+                bottom_right.x,
+                bottom_right.y,
+                self.identifier,
+                self.image.width(),
+                self.image.height(),
+                self.tile_dim.x,
+                self.tile_dim.y
+            ))
+        };
+
+        // Offsets and tile sizes come from the raws, so anything which overflows is out of bounds
+        let to_pixels = |tiles: Option<i32>, tile_size: i32| {
+            let tiles = u32::try_from(tiles?).ok()?;
+            tiles.checked_mul(u32::try_from(tile_size).ok()?)
+        };
+        let (Some(x), Some(y), Some(right), Some(bottom)) = (
+            to_pixels(Some(offset.x), self.tile_dim.x),
+            to_pixels(Some(offset.y), self.tile_dim.y),
+            to_pixels(bottom_right.x.checked_add(1), self.tile_dim.x),
+            to_pixels(bottom_right.y.checked_add(1), self.tile_dim.y),
+        ) else {
+            return Err(out_of_bounds());
+        };
+
+        if right <= x || bottom <= y || right > self.image.width() || bottom > self.image.height() {
+            return Err(out_of_bounds());
+        }
+
+        Ok(imageops::crop_imm(&self.image, x, y, right - x, bottom - y).to_image())
+    }
+}
+
+/// Writes an image to a PNG file.
+///
+/// # Errors
+///
+/// * `ParserError::Image` - If the image cannot be written
+pub fn write_png(image: &RgbaImage, path: &Path) -> Result<(), ParserError> {
+    image
+        .save_with_format(path, ImageFormat::Png)
+        .map_err(|e| ParserError::Image(format!("Unable to write {}: {e}", path.display())))
+}
+
+/// Encodes an image as PNG bytes (e.g. to send to a web front end).
+///
+/// # Errors
+///
+/// * `ParserError::Image` - If the image cannot be encoded
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, ParserError> {
+    let mut bytes = Cursor::new(Vec::new());
+    image
+        .write_to(&mut bytes, ImageFormat::Png)
+        .map_err(|e| ParserError::Image(format!("Unable to encode image as PNG: {e}")))?;
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::RawMetadata;
+
+    fn page_image(tile_dim: &str) -> TilePageImage {
+        let mut tile_page = TilePage::new("PAGE", &RawMetadata::default());
+        tile_page.parse_tag("TILE_DIM", tile_dim);
+        TilePageImage::from_image(&tile_page, RgbaImage::new(4, 4))
+    }
+
+    #[test]
+    fn test_crop_out_of_bounds() {
+        let image = page_image("2:2");
+        assert_eq!(
+            image
+                .crop(Dimensions { x: 1, y: 1 }, None)
+                .map(|sprite| sprite.dimensions())
+                .ok(),
+            Some((2, 2))
+        );
+        assert!(matches!(
+            image.crop(Dimensions { x: 2, y: 0 }, None),
+            Err(ParserError::Image(_))
+        ));
+        assert!(matches!(
+            image.crop(Dimensions { x: -1, y: 0 }, None),
+            Err(ParserError::Image(_))
+        ));
+    }
+
+    #[test]
+    fn test_crop_overflow() {
+        // The bottom-right tile can't be converted to pixels without overflowing
+        let image = page_image("2:2");
+        assert!(matches!(
+            image.crop(
+                Dimensions { x: 0, y: 0 },
+                Some(Dimensions {
+                    x: i32::MAX,
+                    y: i32::MAX
+                })
+            ),
+            Err(ParserError::Image(_))
+        ));
+        // Neither can an offset on a page with huge tiles
+        let image = page_image("65536:65536");
+        assert!(matches!(
+            image.crop(Dimensions { x: 65536, y: 0 }, None),
+            Err(ParserError::Image(_))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! Checks the sprites in graphics raws against the tile pages they use.
+
+use std::collections::HashMap;
+
+use crate::{
+    Dimensions, Graphic, TilePage,
+    tokens::{ConditionToken, ObjectType},
+    traits::{RawObject, RawToken as _},
+};
+
+use super::GraphicsIssue;
+
+/// A sprite (or layer) to check, with a description of where it is in its graphic
+struct SpriteTiles {
+    description: String,
+    tile_page_id: String,
+    offset: Dimensions,
+    offset2: Option<Dimensions>,
+}
+
+/// Validates the graphics and tile pages in a set of parsed raws.
+///
+/// The following are reported:
+///
+/// * Sprites, layers and growths which use a tile page that wasn't parsed (only if any tile pages were
+///   parsed)
+/// * Sprites, layers and growths which use tiles outside of their tile page's `PAGE_DIM`
+/// * Tile page images which don't exist or can't be read
+/// * Tile page images which aren't the size declared by `TILE_DIM` and `PAGE_DIM`
+/// * Tile pages whose `TILE_DIM` and `PAGE_DIM` don't make a valid image size (negative or too large)
+///
+/// Only the header of each image is read.
+///
+/// # Arguments
+///
+/// * `raws` - The parsed raws, any which are not graphics or tile pages are ignored
+///
+/// # Returns
+///
+/// Each problem found, with the tile page problems first
+#[must_use]
+pub fn validate_graphics(raws: &[Box<dyn RawObject>]) -> Vec<GraphicsIssue> {
+    let tile_pages: Vec<&TilePage> = raws
+        .iter()
+        .filter(|raw| raw.get_type() == ObjectType::TilePage)
+        .filter_map(|raw| raw.as_any().downcast_ref::<TilePage>())
+        .collect();
+    let pages_by_identifier: HashMap<String, &TilePage> = tile_pages
+        .iter()
+        .map(|tile_page| (tile_page.get_identifier().to_uppercase(), *tile_page))
+        .collect();
+
+    let mut issues: Vec<GraphicsIssue> = tile_pages
+        .iter()
+        .filter_map(|tile_page| check_image(tile_page))
+        .collect();
+
+    for graphic in raws
+        .iter()
+        .filter(|raw| raw.get_type() == ObjectType::Graphics)
+        .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+    {
+        let module = graphic.get_metadata().get_module_name().to_string();
+        for sprite in sprite_tiles(graphic) {
+            match pages_by_identifier.get(&sprite.tile_page_id.to_uppercase()) {
+                None if !tile_pages.is_empty() => issues.push(GraphicsIssue::MissingTilePage {
+                    graphic: String::from(graphic.get_identifier()),
+                    module: module.clone(),
+                    sprite: sprite.description,
+                    tile_page: sprite.tile_page_id,
+                }),
+                Some(tile_page) if !is_in_range(&sprite, tile_page.get_page_dimensions()) => {
+                    issues.push(GraphicsIssue::TileOutOfRange {
+                        graphic: String::from(graphic.get_identifier()),
+                        module: module.clone(),
+                        sprite: sprite.description,
+                        tile_page: sprite.tile_page_id,
+                        offset: sprite.offset,
+                        offset2: sprite.offset2,
+                        page_dim: tile_page.get_page_dimensions(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    issues
+}
+
+/// Collects the tiles used by the sprites, layers and growths of a graphic
+fn sprite_tiles(graphic: &Graphic) -> Vec<SpriteTiles> {
+    let condition_key = |condition: ConditionToken| condition.get_key().unwrap_or_default();
+    let mut tiles = Vec::new();
+
+    for sprite in graphic.get_sprites() {
+        // Templates are stored with the template token in place of the tile page
+        if sprite.get_primary_condition() == ConditionToken::CopyOfTemplate {
+            continue;
+        }
+        tiles.push(SpriteTiles {
+            description: format!(
+                "{}:{}",
+                condition_key(sprite.get_primary_condition()),
+                condition_key(sprite.get_secondary_condition())
+            ),
+            tile_page_id: String::from(sprite.get_tile_page_id()),
+            offset: sprite.get_offset(),
+            offset2: sprite.get_offset2(),
+        });
+    }
+    for (layer_set, layers) in graphic.get_layers() {
+        for layer in layers {
+            tiles.push(SpriteTiles {
+                description: format!("LAYER_SET:{layer_set}:{}", layer.get_name()),
+                tile_page_id: String::from(layer.get_tile_page_id()),
+                offset: layer.get_offset(),
+                offset2: layer.get_offset2(),
+            });
+        }
+    }
+    for (growth, sprites) in graphic.get_growths() {
+        for sprite in sprites {
+            if sprite.get_primary_condition() == ConditionToken::CopyOfTemplate {
+                continue;
+            }
+            tiles.push(SpriteTiles {
+                description: format!("GROWTH:{growth}"),
+                tile_page_id: String::from(sprite.get_tile_page_id()),
+                offset: sprite.get_offset(),
+                offset2: sprite.get_offset2(),
+            });
+        }
+    }
+
+    tiles
+}
+
+/// Returns true if the sprite's tiles are inside the page. Pages without a `PAGE_DIM` aren't checked.
+const fn is_in_range(sprite: &SpriteTiles, page_dim: Dimensions) -> bool {
+    if page_dim.x <= 0 || page_dim.y <= 0 {
+        return true;
+    }
+    is_in_page(sprite.offset, page_dim)
+        && match sprite.offset2 {
+            Some(offset2) => {
+                is_in_page(offset2, page_dim)
+                    && offset2.x >= sprite.offset.x
+                    && offset2.y >= sprite.offset.y
+            }
+            None => true,
+        }
+}
+
+const fn is_in_page(tile: Dimensions, page_dim: Dimensions) -> bool {
+    tile.x >= 0 && tile.y >= 0 && tile.x < page_dim.x && tile.y < page_dim.y
+}
+
+/// Checks that the image of a tile page exists and is the declared size
+fn check_image(tile_page: &TilePage) -> Option<GraphicsIssue> {
+    let path = tile_page.get_file_path();
+    let tile_page_id = String::from(tile_page.get_identifier());
+    let module = tile_page.get_metadata().get_module_name().to_string();
+
+    if !path.is_file() {
+        return Some(GraphicsIssue::MissingImage {
+            tile_page: tile_page_id,
+            module,
+            path: path.display().to_string(),
+        });
+    }
+
+    let (width, height) = match image::image_dimensions(&path) {
+        Ok(dimensions) => dimensions,
+        Err(e) => {
+            return Some(GraphicsIssue::UnreadableImage {
+                tile_page: tile_page_id,
+                module,
+                path: path.display().to_string(),
+                error: e.to_string(),
+            });
+        }
+    };
+
+    let tile_dim = tile_page.get_tile_dimensions();
+    let page_dim = tile_page.get_page_dimensions();
+    let pixels = |tiles: i32, tile_size: i32| {
+        tile_size
+            .checked_mul(tiles)
+            .and_then(|size| u32::try_from(size).ok())
+    };
+    let (Some(expected_x), Some(expected_y)) = (
+        pixels(page_dim.x, tile_dim.x),
+        pixels(page_dim.y, tile_dim.y),
+    ) else {
+        return Some(GraphicsIssue::InvalidPageSize {
+            tile_page: tile_page_id,
+            module,
+            tile_dim,
+            page_dim,
+        });
+    };
+    let expected = Dimensions::from_xy(expected_x, expected_y);
+    let actual = Dimensions::from_xy(width, height);
+
+    // Without both TILE_DIM and PAGE_DIM there is no declared size to compare against
+    if expected.x == 0 || expected.y == 0 || expected == actual {
+        return None;
+    }
+    Some(GraphicsIssue::ImageSizeMismatch {
+        tile_page: tile_page_id,
+        module,
+        path: path.display().to_string(),
+        expected,
+        actual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use image::RgbaImage;
+
+    use super::*;
+    use crate::{graphics::write_png, metadata::RawMetadata, tokens::GraphicTypeToken};
+
+    #[test]
+    fn test_validate_graphics() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let graphics_directory = temp_dir.path();
+        std::fs::create_dir_all(graphics_directory.join("images"))
+            .expect("Failed to create test directory");
+        // Declared as 2x2 tiles of 2x2 pixels, but only 4x2 pixels
+        write_png(
+            &RgbaImage::new(4, 2),
+            &graphics_directory.join("images").join("page.png"),
+        )
+        .expect("Failed to write test image");
+
+        let metadata = RawMetadata::default().with_raw_file_path(
+            graphics_directory
+                .join("tile_page_test.txt")
+                .display()
+                .to_string(),
+        );
+        let mut tile_page = TilePage::new("PAGE", &metadata);
+        tile_page.parse_tag("FILE", "images/page.png");
+        tile_page.parse_tag("TILE_DIM", "2:2");
+        tile_page.parse_tag("PAGE_DIM", "2:2");
+        let mut missing_image = TilePage::new("MISSING", &metadata);
+        missing_image.parse_tag("FILE", "images/missing.png");
+        // 65536 tiles of 65536 pixels is wider than an `i32` can hold
+        let mut too_large = TilePage::new("TOO_LARGE", &metadata);
+        too_large.parse_tag("FILE", "images/page.png");
+        too_large.parse_tag("TILE_DIM", "65536:2");
+        too_large.parse_tag("PAGE_DIM", "65536:1");
+
+        let mut graphic = Graphic::new("DOG", &metadata, GraphicTypeToken::Creature);
+        for (key, value) in [
+            ("DEFAULT", "PAGE:1:1:AS_IS:DEFAULT"),
+            ("CHILD", "PAGE:2:0:AS_IS:DEFAULT"),
+            ("CORPSE", "OTHER:0:0:AS_IS:DEFAULT"),
+            ("DEFAULT", "PAGE:LARGE_IMAGE:0:0:1:2:AS_IS:ZOMBIE"),
+            ("LAYER_SET", "PORTRAIT"),
+            ("LAYER", "BODY:PAGE:0:1"),
+        ] {
+            graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+        }
+
+        let raws: Vec<Box<dyn RawObject>> = vec![
+            Box::new(tile_page),
+            Box::new(missing_image),
+            Box::new(too_large),
+            Box::new(graphic),
+        ];
+        let issues = validate_graphics(&raws);
+
+        let kinds: Vec<String> = issues
+            .iter()
+            .map(|issue| match issue {
+                GraphicsIssue::MissingTilePage { sprite, .. } => format!("missing page {sprite}"),
+                GraphicsIssue::TileOutOfRange { sprite, .. } => format!("out of range {sprite}"),
+                GraphicsIssue::MissingImage { tile_page, .. } => {
+                    format!("missing image {tile_page}")
+                }
+                GraphicsIssue::UnreadableImage { tile_page, .. } => {
+                    format!("unreadable image {tile_page}")
+                }
+                GraphicsIssue::ImageSizeMismatch {
+                    tile_page, actual, ..
+                } => format!("size mismatch {tile_page} {}x{}", actual.x, actual.y),
+                GraphicsIssue::InvalidPageSize { tile_page, .. } => {
+                    format!("invalid size {tile_page}")
+                }
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "size mismatch PAGE 4x2",
+                "missing image MISSING",
+                "invalid size TOO_LARGE",
+                "out of range CHILD:DEFAULT",
+                "missing page CORPSE:DEFAULT",
+                "out of range DEFAULT:ZOMBIE",
+            ]
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! The `ExportedArtifact` struct is used to store information about an artifact that has been exported
+//! from the Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Inorganic,
+    legends_export::{
+        merge::{Merge, fill_option, fill_string, union},
+        util::parse_id,
+    },
+    tokens::ObjectType,
+    traits::RawObject,
+};
+
+use super::ExportSection;
+
+/// An artifact from a legends export (an `artifact` element), e.g. a named weapon or a book.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedArtifact {
+    id: i32,
+    name: String,
+    /// The name of the item of the artifact, e.g. the title of a book
+    item_name: Option<String>,
+    /// The type of the item, e.g. `weapon` or `book` (only in legends-plus exports)
+    item_type: Option<String>,
+    /// The subtype of the item, e.g. `battle axe` (only in legends-plus exports)
+    item_subtype: Option<String>,
+    /// The description of the item (only in legends-plus exports)
+    item_description: Option<String>,
+    /// The material of the item, e.g. `steel` (only in legends-plus exports)
+    material: Option<String>,
+    /// The site the artifact is at, if any
+    site_id: Option<i32>,
+    /// The structure of the site the artifact is in, if any
+    structure_local_id: Option<i32>,
+    /// The historical figure holding the artifact, if any
+    holder_historical_figure_id: Option<i32>,
+    /// How many pages the artifact has, if it is a book
+    page_count: Option<i32>,
+    /// The written contents of the artifact, if it is a book or scroll
+    written_content_ids: Vec<i32>,
+}
+
+impl ExportedArtifact {
+    /// The id of the artifact
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the artifact
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The name of the item of the artifact, e.g. the title of a book
+    #[must_use]
+    pub fn get_item_name(&self) -> Option<&str> {
+        self.item_name.as_deref()
+    }
+    /// The type of the item, e.g. `weapon` or `book` (only in legends-plus exports)
+    #[must_use]
+    pub fn get_item_type(&self) -> Option<&str> {
+        self.item_type.as_deref()
+    }
+    /// The subtype of the item, e.g. `battle axe` (only in legends-plus exports)
+    #[must_use]
+    pub fn get_item_subtype(&self) -> Option<&str> {
+        self.item_subtype.as_deref()
+    }
+    /// The description of the item (only in legends-plus exports)
+    #[must_use]
+    pub fn get_item_description(&self) -> Option<&str> {
+        self.item_description.as_deref()
+    }
+    /// The material of the item, e.g. `steel` (only in legends-plus exports)
+    #[must_use]
+    pub fn get_material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+    /// The material of the item as a raw identifier, e.g. `PIG_IRON` for `pig iron`
+    #[must_use]
+    pub fn get_material_identifier(&self) -> Option<String> {
+        self.material
+            .as_ref()
+            .map(|material| material.to_uppercase().replace(' ', "_"))
+    }
+    /// The site the artifact is at, if any
+    #[must_use]
+    pub const fn get_site_id(&self) -> Option<i32> {
+        self.site_id
+    }
+    /// The structure of the site the artifact is in, if any
+    #[must_use]
+    pub const fn get_structure_local_id(&self) -> Option<i32> {
+        self.structure_local_id
+    }
+    /// The historical figure holding the artifact, if any
+    #[must_use]
+    pub const fn get_holder_historical_figure_id(&self) -> Option<i32> {
+        self.holder_historical_figure_id
+    }
+    /// How many pages the artifact has, if it is a book
+    #[must_use]
+    pub const fn get_page_count(&self) -> Option<i32> {
+        self.page_count
+    }
+    /// The written contents of the artifact, if it is a book or scroll
+    #[must_use]
+    pub fn get_written_content_ids(&self) -> &[i32] {
+        &self.written_content_ids
+    }
+    /// Find the inorganic the artifact is made of.
+    ///
+    /// Only artifacts made of inorganic materials (e.g. metals and stones) have one, since the other
+    /// materials are exported by name (e.g. `dwarf bone`).
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws to search
+    ///
+    /// # Returns
+    ///
+    /// The inorganic, or `None` if it wasn't parsed
+    #[must_use]
+    pub fn find_inorganic<'a>(&self, raws: &'a [Box<dyn RawObject>]) -> Option<&'a Inorganic> {
+        let identifier = self.get_material_identifier()?;
+        raws.iter()
+            .filter(|raw| {
+                raw.get_type() == ObjectType::Inorganic
+                    && raw.get_identifier().eq_ignore_ascii_case(&identifier)
+            })
+            .find_map(|raw| raw.as_any().downcast_ref::<Inorganic>())
+    }
+    /// Read the value of an element of the artifact
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element the value is inside of
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, section: ExportSection, field: &str, value: &str) {
+        match (section, field) {
+            (ExportSection::None, "id") => self.id = value.parse().unwrap_or_default(),
+            (ExportSection::None, "name") => self.name = value.to_string(),
+            (ExportSection::None, "item_type") => self.item_type = Some(value.to_string()),
+            (ExportSection::None, "item_subtype") => self.item_subtype = Some(value.to_string()),
+            (ExportSection::None, "item_description") => {
+                self.item_description = Some(value.to_string());
+            }
+            (ExportSection::None, "mat") => self.material = Some(value.to_string()),
+            (ExportSection::None, "site_id") => self.site_id = parse_id(value),
+            (ExportSection::None, "structure_local_id") => {
+                self.structure_local_id = parse_id(value);
+            }
+            (ExportSection::None, "holder_hfid") => {
+                self.holder_historical_figure_id = parse_id(value);
+            }
+            (ExportSection::None, "page_count") | (ExportSection::ArtifactItem, "page_number") => {
+                self.page_count = parse_id(value);
+            }
+            (ExportSection::None, "writing")
+            | (
+                ExportSection::ArtifactItem,
+                "page_written_content_id" | "writing_written_content_id",
+            ) => {
+                if let Some(id) = parse_id(value)
+                    && !self.written_content_ids.contains(&id)
+                {
+                    self.written_content_ids.push(id);
+                }
+            }
+            (ExportSection::ArtifactItem, "name_string") => {
+                self.item_name = Some(value.to_string());
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Merge for ExportedArtifact {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_option(&mut self.item_name, other.item_name);
+        fill_option(&mut self.item_type, other.item_type);
+        fill_option(&mut self.item_subtype, other.item_subtype);
+        fill_option(&mut self.item_description, other.item_description);
+        fill_option(&mut self.material, other.material);
+        fill_option(&mut self.site_id, other.site_id);
+        fill_option(&mut self.structure_local_id, other.structure_local_id);
+        fill_option(
+            &mut self.holder_historical_figure_id,
+            other.holder_historical_figure_id,
+        );
+        fill_option(&mut self.page_count, other.page_count);
+        union(&mut self.written_content_ids, other.written_content_ids);
+    }
+}
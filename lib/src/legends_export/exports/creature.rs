@@ -3,10 +3,14 @@
 //! The `ExportedCreature` struct is used to store information about a creature that has been exported
 //! from the Legends Viewer.
 
-use crate::{Creature, Name, metadata::RawMetadata};
+use crate::{
+    Creature, Name,
+    legends_export::merge::{Merge, fill_string, union},
+    metadata::RawMetadata,
+};
 
 /// The `ExportedCreature` struct is used to store information about a creature that has been exported
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ExportedCreature {
     creature_id: String,
     name_singular: String,
@@ -74,3 +78,16 @@ impl ExportedCreature {
         creature
     }
 }
+
+impl Merge for ExportedCreature {
+    type Key = String;
+
+    fn merge_key(&self) -> String {
+        self.creature_id.to_uppercase()
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name_singular, other.name_singular);
+        fill_string(&mut self.name_plural, other.name_plural);
+        union(&mut self.tags, other.tags);
+    }
+}
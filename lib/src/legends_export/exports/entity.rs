@@ -1,30 +1,134 @@
 //! The `ExportedEntity` struct is used to store information about an entity that has been exported
 //! from the Legends Viewer.
 
-use crate::{Entity, metadata::RawMetadata};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Entity,
+    legends_export::{
+        merge::{Merge, fill_option, fill_string, merge_by_key, union},
+        util::{creature_identifier, parse_id},
+    },
+    metadata::RawMetadata,
+    tokens::ObjectType,
+    traits::RawObject,
+};
+
+use super::ExportSection;
 
 /// The `ExportedEntity` struct is used to store information about an entity that has been exported
-#[derive(Debug, Default)]
+///
+/// Most of the details (positions, occasions, links) are only in legends-plus exports, and the name is only
+/// in the export from Dwarf Fortress.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct ExportedEntity {
     entity_id: u32,
+    name: String,
     race: String,
     entity_type: String,
-    child_id: u32,
-    // population: u32,
-    // civ_id: u32,
-    // positions: Vec<ExportedEntityPosition>,
-}
-
-// #[derive(Debug, Default)]
-// pub(crate) struct ExportedEntityPosition {
-//     id: u32,
-//     name: String,
-//     male_name: String,
-//     female_name: String,
-//     spouse_name: String,
-//     male_spouse_name: String,
-//     female_spouse_name: String,
-// }
+    child_ids: Vec<u32>,
+    links: Vec<ExportedEntityRelation>,
+    positions: Vec<ExportedEntityPosition>,
+    assignments: Vec<ExportedEntityPositionAssignment>,
+    occasions: Vec<ExportedEntityOccasion>,
+    /// The historical figures which are members of the entity
+    historical_figure_ids: Vec<i32>,
+    /// The historical figures (deities) the entity worships
+    worship_ids: Vec<i32>,
+    weapons: Vec<String>,
+    professions: Vec<String>,
+}
+
+/// A link from an entity to another (an `entity_link` element of an entity), e.g. to its parent civilization.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityRelation {
+    /// The type of the link, e.g. `PARENT` or `CHILD`
+    pub link_type: String,
+    /// The entity linked to
+    pub target_id: u32,
+    /// How strong the link is
+    pub strength: i32,
+}
+
+/// A position of an entity (an `entity_position` element), e.g. a king.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPosition {
+    /// The id of the position within its entity
+    pub id: i32,
+    /// The name of the position
+    pub name: String,
+    /// The name of the position when held by a male
+    pub name_male: Option<String>,
+    /// The name of the position when held by a female
+    pub name_female: Option<String>,
+    /// The name of the spouse of the holder of the position
+    pub spouse: Option<String>,
+    /// The name of the spouse of the holder of the position when male
+    pub spouse_male: Option<String>,
+    /// The name of the spouse of the holder of the position when female
+    pub spouse_female: Option<String>,
+}
+
+/// An assignment of a position of an entity (an `entity_position_assignment` element), i.e. who holds it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPositionAssignment {
+    /// The id of the assignment within its entity
+    pub id: i32,
+    /// The historical figure holding the position, or `None` if it is vacant
+    pub historical_figure_id: Option<i32>,
+    /// The position assigned
+    pub position_id: Option<i32>,
+    /// The squad led by the holder of the position, if any
+    pub squad_id: Option<i32>,
+}
+
+/// An occasion (festival) held by an entity (an `occasion` element).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityOccasion {
+    /// The id of the occasion within its entity
+    pub id: i32,
+    /// The name of the occasion
+    pub name: String,
+    /// The historical event the occasion commemorates, if any
+    pub event_id: Option<i32>,
+    /// The parts of the occasion
+    pub schedules: Vec<ExportedOccasionSchedule>,
+}
+
+/// A part of an occasion (a `schedule` element), e.g. a procession or a competition.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedOccasionSchedule {
+    /// The id of the schedule within its occasion
+    pub id: i32,
+    /// The type of the schedule, e.g. `procession` or `poetry_recital`
+    pub schedule_type: String,
+    /// The type of item involved, if any (e.g. the prize of a competition)
+    pub item_type: Option<String>,
+    /// The subtype of item involved, if any
+    pub item_subtype: Option<String>,
+    /// What the schedule refers to, e.g. the poetic form recited
+    pub reference: Option<i32>,
+    /// What else the schedule refers to
+    pub reference2: Option<i32>,
+}
+
+/// An entity population from a legends export (an `entity_population` element), i.e. the members of a
+/// civilization which aren't historical figures.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityPopulation {
+    id: i32,
+    /// The civilization the population belongs to (only in legends-plus exports)
+    civ_id: Option<i32>,
+    /// The races of the population with how many of each there are (only in legends-plus exports)
+    races: Vec<(String, u32)>,
+}
 
 impl ExportedEntity {
     /// Set the entity ID
@@ -51,13 +155,151 @@ impl ExportedEntity {
     pub fn set_entity_type(&mut self, entity_type: &str) {
         self.entity_type = entity_type.into();
     }
-    /// Set the child ID
+    /// Add a child entity ID
     ///
     /// # Arguments
     ///
     /// * `child_id` - A `u32` that represents the child ID
-    pub fn set_child_id(&mut self, child_id: u32) {
-        self.child_id = child_id;
+    pub fn add_child_id(&mut self, child_id: u32) {
+        if !self.child_ids.contains(&child_id) {
+            self.child_ids.push(child_id);
+        }
+    }
+    /// The id of the entity
+    #[must_use]
+    pub const fn get_id(&self) -> u32 {
+        self.entity_id
+    }
+    /// The name of the entity (only in the export from Dwarf Fortress)
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The race of the entity, e.g. `dwarf`
+    #[must_use]
+    pub fn get_race(&self) -> &str {
+        &self.race
+    }
+    /// The race of the entity as a creature identifier, e.g. `DWARF`
+    #[must_use]
+    pub fn get_creature_identifier(&self) -> String {
+        creature_identifier(&self.race)
+    }
+    /// The type of the entity, e.g. `civilization`, `sitegovernment` or `religion`
+    #[must_use]
+    pub fn get_entity_type(&self) -> &str {
+        &self.entity_type
+    }
+    /// Whether the entity is a civilization
+    #[must_use]
+    pub fn is_civilization(&self) -> bool {
+        self.entity_type.eq_ignore_ascii_case("civilization")
+    }
+    /// The entities below this one, e.g. the site governments of a civilization
+    #[must_use]
+    pub fn get_child_ids(&self) -> Vec<u32> {
+        let mut child_ids = self.child_ids.clone();
+        for id in self.get_linked_entity_ids("CHILD") {
+            if !child_ids.contains(&id) {
+                child_ids.push(id);
+            }
+        }
+        child_ids
+    }
+    /// The entities above this one, e.g. the civilization of a site government
+    #[must_use]
+    pub fn get_parent_ids(&self) -> Vec<u32> {
+        self.get_linked_entity_ids("PARENT")
+    }
+    /// The ids of the entities the entity is linked to with a link type, e.g. `PARENT`
+    #[must_use]
+    pub fn get_linked_entity_ids(&self, link_type: &str) -> Vec<u32> {
+        self.links
+            .iter()
+            .filter(|link| link.link_type.eq_ignore_ascii_case(link_type))
+            .map(|link| link.target_id)
+            .collect()
+    }
+    /// The links to other entities
+    #[must_use]
+    pub fn get_links(&self) -> &[ExportedEntityRelation] {
+        &self.links
+    }
+    /// The positions of the entity
+    #[must_use]
+    pub fn get_positions(&self) -> &[ExportedEntityPosition] {
+        &self.positions
+    }
+    /// The assignments of the positions of the entity
+    #[must_use]
+    pub fn get_assignments(&self) -> &[ExportedEntityPositionAssignment] {
+        &self.assignments
+    }
+    /// The historical figure holding a position of the entity, if it is held
+    #[must_use]
+    pub fn get_position_holder(&self, position_id: i32) -> Option<i32> {
+        self.assignments
+            .iter()
+            .filter(|assignment| assignment.position_id == Some(position_id))
+            .find_map(|assignment| assignment.historical_figure_id)
+    }
+    /// The occasions (festivals) held by the entity
+    #[must_use]
+    pub fn get_occasions(&self) -> &[ExportedEntityOccasion] {
+        &self.occasions
+    }
+    /// The historical figures which are members of the entity
+    #[must_use]
+    pub fn get_historical_figure_ids(&self) -> &[i32] {
+        &self.historical_figure_ids
+    }
+    /// The historical figures (deities) the entity worships
+    #[must_use]
+    pub fn get_worship_ids(&self) -> &[i32] {
+        &self.worship_ids
+    }
+    /// The weapons the entity uses, e.g. `spear`
+    #[must_use]
+    pub fn get_weapons(&self) -> &[String] {
+        &self.weapons
+    }
+    /// The professions of the entity, e.g. `hunter`
+    #[must_use]
+    pub fn get_professions(&self) -> &[String] {
+        &self.professions
+    }
+    /// Find the entity raw the entity was created from.
+    ///
+    /// Every type of entity (e.g. the site governments and religions of a civilization as well as the
+    /// civilization itself) is created from the entity raw of its race, so the raw is the one whose
+    /// `CREATURE` is the race of the entity. Entities without a race (e.g. some `outcast` groups) have none.
+    ///
+    /// The type of the entity isn't used: the export doesn't name the raw, and entity raws don't say which
+    /// types they make. When several entity raws have the same `CREATURE` (e.g. a mod adding a second
+    /// dwarven civilization), the first one in `raws` is returned, which is the first in load order when
+    /// the raws come from [`crate::parse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws to search
+    ///
+    /// # Returns
+    ///
+    /// The entity raw, or `None` if it wasn't parsed
+    #[must_use]
+    pub fn find_entity<'a>(&self, raws: &'a [Box<dyn RawObject>]) -> Option<&'a Entity> {
+        if self.race.is_empty() {
+            return None;
+        }
+        let identifier = self.get_creature_identifier();
+        raws.iter()
+            .filter(|raw| raw.get_type() == ObjectType::Entity)
+            .filter_map(|raw| raw.as_any().downcast_ref::<Entity>())
+            .find(|entity| {
+                entity
+                    .get_creature()
+                    .is_some_and(|creature| creature.eq_ignore_ascii_case(&identifier))
+            })
     }
     /// Convert the `ExportedEntity` struct into an `Entity` struct
     ///
@@ -69,17 +311,323 @@ impl ExportedEntity {
     ///
     /// An `Entity` object
     #[must_use]
-    #[allow(dead_code)]
     pub fn into_entity(self, legend_metadata: &RawMetadata) -> Entity {
-        Entity::new(
+        let mut entity = Entity::new(
             format!("{}-{}{}", self.race, self.entity_type, self.entity_id).as_str(),
             legend_metadata,
-        )
-    }
-    // pub fn set_population(&mut self, population: u32) {
-    //     self.population = population;
-    // }
-    // pub fn set_civ_id(&mut self, civ_id: u32) {
-    //     self.civ_id = civ_id;
-    // }
+        );
+        if !self.race.is_empty() {
+            entity.parse_tag("CREATURE", &self.get_creature_identifier());
+        }
+        entity
+    }
+    /// Read the value of an element of the entity
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element the value is inside of
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, section: ExportSection, field: &str, value: &str) {
+        match section {
+            ExportSection::None => match field {
+                "id" => self.entity_id = value.parse().unwrap_or_default(),
+                "name" => self.name = value.to_string(),
+                "race" => self.race = value.to_string(),
+                "type" => self.entity_type = value.to_string(),
+                "child" => {
+                    if let Ok(id) = value.parse() {
+                        self.add_child_id(id);
+                    }
+                }
+                "histfig_id" => push_id(&mut self.historical_figure_ids, value),
+                "worship_id" => push_id(&mut self.worship_ids, value),
+                "weapon" => self.weapons.push(value.to_string()),
+                "profession" => self.professions.push(value.to_string()),
+                _ => (),
+            },
+            ExportSection::EntityLink => {
+                let Some(link) = self.links.last_mut() else {
+                    return;
+                };
+                match field {
+                    "type" => link.link_type = value.to_string(),
+                    "target" => link.target_id = value.parse().unwrap_or_default(),
+                    "strength" => link.strength = value.parse().unwrap_or_default(),
+                    _ => (),
+                }
+            }
+            ExportSection::EntityPosition => {
+                let Some(position) = self.positions.last_mut() else {
+                    return;
+                };
+                match field {
+                    "id" => position.id = value.parse().unwrap_or_default(),
+                    "name" => position.name = value.to_string(),
+                    "name_male" => position.name_male = Some(value.to_string()),
+                    "name_female" => position.name_female = Some(value.to_string()),
+                    "spouse" => position.spouse = Some(value.to_string()),
+                    "spouse_male" => position.spouse_male = Some(value.to_string()),
+                    "spouse_female" => position.spouse_female = Some(value.to_string()),
+                    _ => (),
+                }
+            }
+            ExportSection::EntityPositionAssignment => {
+                let Some(assignment) = self.assignments.last_mut() else {
+                    return;
+                };
+                match field {
+                    "id" => assignment.id = value.parse().unwrap_or_default(),
+                    "histfig" => assignment.historical_figure_id = parse_id(value),
+                    "position_id" => assignment.position_id = parse_id(value),
+                    "squad_id" => assignment.squad_id = parse_id(value),
+                    _ => (),
+                }
+            }
+            ExportSection::Occasion => {
+                let Some(occasion) = self.occasions.last_mut() else {
+                    return;
+                };
+                match field {
+                    "id" => occasion.id = value.parse().unwrap_or_default(),
+                    "name" => occasion.name = value.to_string(),
+                    "event" => occasion.event_id = parse_id(value),
+                    _ => (),
+                }
+            }
+            ExportSection::OccasionSchedule => {
+                let Some(schedule) = self
+                    .occasions
+                    .last_mut()
+                    .and_then(|occasion| occasion.schedules.last_mut())
+                else {
+                    return;
+                };
+                match field {
+                    "id" => schedule.id = value.parse().unwrap_or_default(),
+                    "type" => schedule.schedule_type = value.to_string(),
+                    "item_type" => schedule.item_type = Some(value.to_string()),
+                    "item_subtype" => schedule.item_subtype = Some(value.to_string()),
+                    "reference" => schedule.reference = parse_id(value),
+                    "reference2" => schedule.reference2 = parse_id(value),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+    /// Start reading a nested element of the entity
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element which was started
+    pub(crate) fn start_section(&mut self, section: ExportSection) {
+        match section {
+            ExportSection::EntityLink => self.links.push(ExportedEntityRelation::default()),
+            ExportSection::EntityPosition => {
+                self.positions.push(ExportedEntityPosition::default());
+            }
+            ExportSection::EntityPositionAssignment => {
+                self.assignments
+                    .push(ExportedEntityPositionAssignment::default());
+            }
+            ExportSection::Occasion => self.occasions.push(ExportedEntityOccasion::default()),
+            ExportSection::OccasionSchedule => {
+                if let Some(occasion) = self.occasions.last_mut() {
+                    occasion.schedules.push(ExportedOccasionSchedule::default());
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl ExportedEntityPopulation {
+    /// The id of the entity population
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The civilization the population belongs to (only in legends-plus exports)
+    #[must_use]
+    pub const fn get_civ_id(&self) -> Option<i32> {
+        self.civ_id
+    }
+    /// The races of the population with how many of each there are, e.g. `("goblin", 18)`
+    #[must_use]
+    pub fn get_races(&self) -> &[(String, u32)] {
+        &self.races
+    }
+    /// How many members the population has
+    #[must_use]
+    pub fn get_population(&self) -> u32 {
+        self.races.iter().map(|(_, count)| count).sum()
+    }
+    /// Read the value of an element of the entity population
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "civ_id" => self.civ_id = parse_id(value),
+            // The race is exported with its count, e.g. `goblin:18`
+            "race" => {
+                let (race, count) = value.rsplit_once(':').unwrap_or((value, ""));
+                self.races
+                    .push((race.to_string(), count.parse().unwrap_or_default()));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Add the id of an object to a list, ignoring the `-1` used for none and duplicates
+fn push_id(ids: &mut Vec<i32>, value: &str) {
+    if let Some(id) = parse_id(value)
+        && !ids.contains(&id)
+    {
+        ids.push(id);
+    }
+}
+
+impl Merge for ExportedEntity {
+    type Key = u32;
+
+    fn merge_key(&self) -> u32 {
+        self.entity_id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_string(&mut self.race, other.race);
+        fill_string(&mut self.entity_type, other.entity_type);
+        union(&mut self.child_ids, other.child_ids);
+        union(&mut self.links, other.links);
+        merge_by_key(&mut self.positions, other.positions);
+        merge_by_key(&mut self.assignments, other.assignments);
+        merge_by_key(&mut self.occasions, other.occasions);
+        union(&mut self.historical_figure_ids, other.historical_figure_ids);
+        union(&mut self.worship_ids, other.worship_ids);
+        union(&mut self.weapons, other.weapons);
+        union(&mut self.professions, other.professions);
+    }
+}
+
+impl Merge for ExportedEntityPosition {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_option(&mut self.name_male, other.name_male);
+        fill_option(&mut self.name_female, other.name_female);
+        fill_option(&mut self.spouse, other.spouse);
+        fill_option(&mut self.spouse_male, other.spouse_male);
+        fill_option(&mut self.spouse_female, other.spouse_female);
+    }
+}
+
+impl Merge for ExportedEntityPositionAssignment {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.historical_figure_id, other.historical_figure_id);
+        fill_option(&mut self.position_id, other.position_id);
+        fill_option(&mut self.squad_id, other.squad_id);
+    }
+}
+
+impl Merge for ExportedEntityOccasion {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_option(&mut self.event_id, other.event_id);
+        merge_by_key(&mut self.schedules, other.schedules);
+    }
+}
+
+impl Merge for ExportedOccasionSchedule {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.schedule_type, other.schedule_type);
+        fill_option(&mut self.item_type, other.item_type);
+        fill_option(&mut self.item_subtype, other.item_subtype);
+        fill_option(&mut self.reference, other.reference);
+        fill_option(&mut self.reference2, other.reference2);
+    }
+}
+
+impl Merge for ExportedEntityPopulation {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.civ_id, other.civ_id);
+        union(&mut self.races, other.races);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity_raw(identifier: &str, creature: &str) -> Box<dyn RawObject> {
+        let mut entity = Entity::new(identifier, &RawMetadata::default());
+        entity.parse_tag("CREATURE", creature);
+        Box::new(entity)
+    }
+
+    fn exported_entity(race: &str, entity_type: &str) -> ExportedEntity {
+        let mut entity = ExportedEntity::default();
+        entity.read_value(ExportSection::None, "id", "12");
+        entity.read_value(ExportSection::None, "race", race);
+        entity.read_value(ExportSection::None, "type", entity_type);
+        entity
+    }
+
+    #[test]
+    fn test_find_entity_uses_first_raw_of_race() {
+        let raws = vec![
+            entity_raw("PLAINS", "HUMAN"),
+            entity_raw("MOUNTAIN", "DWARF"),
+            entity_raw("DEEP_MOUNTAIN", "DWARF"),
+        ];
+
+        // Every type of entity of a race is made from the first entity raw of the race
+        for entity_type in ["civilization", "sitegovernment", "religion"] {
+            let found = exported_entity("dwarf", entity_type)
+                .find_entity(&raws)
+                .map(Entity::get_identifier);
+            assert_eq!(found, Some("MOUNTAIN"), "{entity_type}");
+        }
+        assert_eq!(
+            exported_entity("human", "civilization")
+                .find_entity(&raws)
+                .map(Entity::get_identifier),
+            Some("PLAINS")
+        );
+        assert!(exported_entity("", "outcast").find_entity(&raws).is_none());
+        assert!(
+            exported_entity("elf", "civilization")
+                .find_entity(&raws)
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,311 @@
+//! The `ExportedHistoricalEvent` and `ExportedEventCollection` structs are used to store information about
+//! the historical events (and the collections grouping them, e.g. wars) that have been exported from the
+//! Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::legends_export::merge::{Merge, fill_option, fill_string, union};
+
+/// A historical event from a legends export (a `historical_event` element).
+///
+/// The fields every event has are kept as fields. The fields which depend on the type of the event are kept
+/// in the order they were exported, and the historical figures, entities and sites they refer to are
+/// collected to be able to build timelines.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedHistoricalEvent {
+    id: i32,
+    /// `None` if the year wasn't exported
+    year: Option<i32>,
+    /// The time within the year, if known (exported as `-1` when not)
+    seconds72: Option<i32>,
+    event_type: String,
+    /// The fields specific to the type of the event, as `(name, value)`
+    fields: Vec<(String, String)>,
+    historical_figure_ids: Vec<i32>,
+    entity_ids: Vec<i32>,
+    site_ids: Vec<i32>,
+}
+
+/// A collection of historical events from a legends export (a `historical_event_collection` element), e.g.
+/// a war, battle or duel.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEventCollection {
+    id: i32,
+    name: Option<String>,
+    collection_type: String,
+    /// `None` if the start year wasn't exported
+    start_year: Option<i32>,
+    start_seconds72: Option<i32>,
+    /// `None` while the collection is ongoing (exported as `-1`)
+    end_year: Option<i32>,
+    end_seconds72: Option<i32>,
+    event_ids: Vec<i32>,
+    child_collection_ids: Vec<i32>,
+    /// The fields specific to the type of the collection, as `(name, value)`
+    fields: Vec<(String, String)>,
+}
+
+/// The kind of object a field of an event refers to, judged by the name of the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventReference {
+    HistoricalFigure,
+    Entity,
+    Site,
+}
+
+impl EventReference {
+    /// Judge what a field of an event refers to by its name, e.g. `slayer_hfid` or `attacker_civ_id`
+    fn from_field(field: &str) -> Option<Self> {
+        if field.contains("hfid")
+            || field.contains("hf_id")
+            || field.contains("hist_fig")
+            || field.contains("histfig")
+        {
+            Some(Self::HistoricalFigure)
+        } else if field.contains("site_id") || field == "site" {
+            Some(Self::Site)
+        } else if field.ends_with("civ_id")
+            || field.contains("entity_id")
+            || field.ends_with("enid")
+            || field == "civ"
+            || field == "entity"
+        {
+            Some(Self::Entity)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a time (year or `seconds72`) from a legends export, which uses `-1` for unknown
+fn parse_time(value: &str) -> Option<i32> {
+    value.parse().ok().filter(|time: &i32| *time >= 0)
+}
+
+impl ExportedHistoricalEvent {
+    /// The id of the event
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The year the event happened in (0 if it wasn't exported)
+    #[must_use]
+    pub fn get_year(&self) -> i32 {
+        self.year.unwrap_or_default()
+    }
+    /// The time within the year the event happened at (in 72nds of a second), if known
+    #[must_use]
+    pub const fn get_seconds72(&self) -> Option<i32> {
+        self.seconds72
+    }
+    /// The type of the event, e.g. `hf died` or `change hf state`
+    #[must_use]
+    pub fn get_event_type(&self) -> &str {
+        &self.event_type
+    }
+    /// The fields specific to the type of the event, as `(name, value)` in the order they were exported
+    #[must_use]
+    pub fn get_fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+    /// The value of the first field of the event with a name, e.g. `cause`
+    #[must_use]
+    pub fn get_field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// The historical figures involved in the event
+    #[must_use]
+    pub fn get_historical_figure_ids(&self) -> &[i32] {
+        &self.historical_figure_ids
+    }
+    /// The entities involved in the event
+    #[must_use]
+    pub fn get_entity_ids(&self) -> &[i32] {
+        &self.entity_ids
+    }
+    /// The sites the event happened at (or involved)
+    #[must_use]
+    pub fn get_site_ids(&self) -> &[i32] {
+        &self.site_ids
+    }
+    /// The position of the event in time, to sort events chronologically
+    #[must_use]
+    pub fn chronological_key(&self) -> (i32, i32, i32) {
+        (self.get_year(), self.seconds72.unwrap_or(-1), self.id)
+    }
+    /// Read the value of an element of the event
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "year" => self.year = value.parse().ok(),
+            "seconds72" => self.seconds72 = parse_time(value),
+            "type" => self.event_type = value.to_string(),
+            _ => {
+                if let Some(reference) = EventReference::from_field(field) {
+                    self.add_reference(reference, value);
+                }
+                self.fields.push((field.to_string(), value.to_string()));
+            }
+        }
+    }
+    /// Add an empty element given for the event
+    ///
+    /// # Arguments
+    ///
+    /// * `flag` - The name of the element
+    pub(crate) fn add_flag(&mut self, flag: &str) {
+        self.fields.push((flag.to_string(), String::new()));
+    }
+    /// Add an object the event refers to, ignoring the `-1` used for none
+    fn add_reference(&mut self, reference: EventReference, value: &str) {
+        let Ok(id) = value.parse::<i32>() else {
+            return;
+        };
+        if id < 0 {
+            return;
+        }
+        let ids = match reference {
+            EventReference::HistoricalFigure => &mut self.historical_figure_ids,
+            EventReference::Entity => &mut self.entity_ids,
+            EventReference::Site => &mut self.site_ids,
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+}
+
+impl ExportedEventCollection {
+    /// The id of the collection
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the collection (e.g. for wars and battles)
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// The type of the collection, e.g. `war`, `battle` or `duel`
+    #[must_use]
+    pub fn get_collection_type(&self) -> &str {
+        &self.collection_type
+    }
+    /// The year the collection started in (0 if it wasn't exported)
+    #[must_use]
+    pub fn get_start_year(&self) -> i32 {
+        self.start_year.unwrap_or_default()
+    }
+    /// The time within the year the collection started at (in 72nds of a second), if known
+    #[must_use]
+    pub const fn get_start_seconds72(&self) -> Option<i32> {
+        self.start_seconds72
+    }
+    /// The year the collection ended in, or `None` if it is ongoing
+    #[must_use]
+    pub const fn get_end_year(&self) -> Option<i32> {
+        self.end_year
+    }
+    /// The time within the year the collection ended at (in 72nds of a second), if known
+    #[must_use]
+    pub const fn get_end_seconds72(&self) -> Option<i32> {
+        self.end_seconds72
+    }
+    /// The events in the collection
+    #[must_use]
+    pub fn get_event_ids(&self) -> &[i32] {
+        &self.event_ids
+    }
+    /// The collections inside this collection, e.g. the battles of a war
+    #[must_use]
+    pub fn get_child_collection_ids(&self) -> &[i32] {
+        &self.child_collection_ids
+    }
+    /// The fields specific to the type of the collection, as `(name, value)` in the order they were exported
+    #[must_use]
+    pub fn get_fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+    /// The value of the first field of the collection with a name, e.g. `aggressor_ent_id`
+    #[must_use]
+    pub fn get_field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+    /// Read the value of an element of the collection
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "name" => self.name = Some(value.to_string()),
+            "type" => self.collection_type = value.to_string(),
+            "start_year" => self.start_year = value.parse().ok(),
+            "start_seconds72" => self.start_seconds72 = parse_time(value),
+            "end_year" => self.end_year = parse_time(value),
+            "end_seconds72" => self.end_seconds72 = parse_time(value),
+            "event" => {
+                if let Ok(id) = value.parse() {
+                    self.event_ids.push(id);
+                }
+            }
+            "eventcol" => {
+                if let Ok(id) = value.parse() {
+                    self.child_collection_ids.push(id);
+                }
+            }
+            _ => self.fields.push((field.to_string(), value.to_string())),
+        }
+    }
+}
+
+impl Merge for ExportedHistoricalEvent {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.year, other.year);
+        fill_option(&mut self.seconds72, other.seconds72);
+        fill_string(&mut self.event_type, other.event_type);
+        union(&mut self.fields, other.fields);
+        union(&mut self.historical_figure_ids, other.historical_figure_ids);
+        union(&mut self.entity_ids, other.entity_ids);
+        union(&mut self.site_ids, other.site_ids);
+    }
+}
+
+impl Merge for ExportedEventCollection {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.name, other.name);
+        fill_string(&mut self.collection_type, other.collection_type);
+        fill_option(&mut self.start_year, other.start_year);
+        fill_option(&mut self.start_seconds72, other.start_seconds72);
+        fill_option(&mut self.end_year, other.end_year);
+        fill_option(&mut self.end_seconds72, other.end_seconds72);
+        union(&mut self.event_ids, other.event_ids);
+        union(&mut self.child_collection_ids, other.child_collection_ids);
+        union(&mut self.fields, other.fields);
+    }
+}
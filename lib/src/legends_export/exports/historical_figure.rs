@@ -0,0 +1,310 @@
+//! The `ExportedHistoricalFigure` struct is used to store information about a historical figure that has
+//! been exported from the Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Creature,
+    legends_export::{
+        merge::{Merge, fill_option, fill_string, union},
+        util::creature_identifier,
+    },
+    tokens::ObjectType,
+    traits::RawObject,
+};
+
+use super::ExportSection;
+
+/// A historical figure from a legends export (a `historical_figure` element).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedHistoricalFigure {
+    id: i32,
+    name: String,
+    race: String,
+    caste: String,
+    /// `None` if the birth year wasn't exported
+    birth_year: Option<i32>,
+    /// `None` while the figure is alive (exported as `-1`)
+    death_year: Option<i32>,
+    associated_type: String,
+    entity_links: Vec<ExportedEntityLink>,
+    historical_figure_links: Vec<ExportedHistoricalFigureLink>,
+    skills: Vec<ExportedSkill>,
+    spheres: Vec<String>,
+    active_interactions: Vec<String>,
+    /// Empty elements given for the figure, e.g. `deity` or `force`
+    flags: Vec<String>,
+}
+
+/// A link from a historical figure to an entity (an `entity_link` element), e.g. membership of a civilization.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntityLink {
+    /// The type of the link, e.g. `member` or `enemy`
+    pub link_type: String,
+    /// The id of the linked entity
+    pub entity_id: i32,
+    /// The strength of the link, if given
+    pub link_strength: Option<u32>,
+}
+
+/// A link between two historical figures (an `hf_link` element), e.g. `mother` or `spouse`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedHistoricalFigureLink {
+    /// The type of the link, e.g. `mother`, `spouse` or `deity`
+    pub link_type: String,
+    /// The id of the linked historical figure
+    pub historical_figure_id: i32,
+    /// The strength of the link, if given
+    pub link_strength: Option<u32>,
+}
+
+/// A skill of a historical figure (an `hf_skill` element).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSkill {
+    /// The skill, e.g. `MINING`
+    pub skill: String,
+    /// The experience in the skill
+    pub total_ip: u32,
+}
+
+impl ExportedHistoricalFigure {
+    /// The id of the historical figure
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the historical figure
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The race of the historical figure, as it was exported
+    #[must_use]
+    pub fn get_race(&self) -> &str {
+        &self.race
+    }
+    /// The identifier of the creature the race of the historical figure refers to, e.g. `DWARF`
+    #[must_use]
+    pub fn get_creature_identifier(&self) -> String {
+        creature_identifier(&self.race)
+    }
+    /// The caste of the historical figure, e.g. `FEMALE`
+    #[must_use]
+    pub fn get_caste(&self) -> &str {
+        &self.caste
+    }
+    /// The year the historical figure was born, or `None` if it wasn't exported
+    #[must_use]
+    pub const fn get_birth_year(&self) -> Option<i32> {
+        self.birth_year
+    }
+    /// The year the historical figure died, or `None` if it is still alive
+    #[must_use]
+    pub const fn get_death_year(&self) -> Option<i32> {
+        self.death_year
+    }
+    /// Whether the historical figure is still alive
+    #[must_use]
+    pub const fn is_alive(&self) -> bool {
+        self.death_year.is_none()
+    }
+    /// The associated type of the historical figure, e.g. `STANDARD` or `MINER`
+    #[must_use]
+    pub fn get_associated_type(&self) -> &str {
+        &self.associated_type
+    }
+    /// The links to entities of the historical figure
+    #[must_use]
+    pub fn get_entity_links(&self) -> &[ExportedEntityLink] {
+        &self.entity_links
+    }
+    /// The ids of the entities the historical figure is linked to with a link type, e.g. `member`
+    #[must_use]
+    pub fn get_linked_entity_ids(&self, link_type: &str) -> Vec<i32> {
+        self.entity_links
+            .iter()
+            .filter(|link| link.link_type.eq_ignore_ascii_case(link_type))
+            .map(|link| link.entity_id)
+            .collect()
+    }
+    /// The links to other historical figures of the historical figure
+    #[must_use]
+    pub fn get_historical_figure_links(&self) -> &[ExportedHistoricalFigureLink] {
+        &self.historical_figure_links
+    }
+    /// The skills of the historical figure
+    #[must_use]
+    pub fn get_skills(&self) -> &[ExportedSkill] {
+        &self.skills
+    }
+    /// The spheres of the historical figure (for deities), e.g. `caverns`
+    #[must_use]
+    pub fn get_spheres(&self) -> &[String] {
+        &self.spheres
+    }
+    /// The interactions active on the historical figure, e.g. `DEITY_CURSE_VAMPIRE_1`
+    #[must_use]
+    pub fn get_active_interactions(&self) -> &[String] {
+        &self.active_interactions
+    }
+    /// Whether an interaction containing `name` is active on the historical figure
+    #[must_use]
+    pub fn has_active_interaction(&self, name: &str) -> bool {
+        let name = name.to_uppercase();
+        self.active_interactions
+            .iter()
+            .any(|interaction| interaction.to_uppercase().contains(&name))
+    }
+    /// Whether the historical figure has been cursed to be a vampire
+    #[must_use]
+    pub fn is_vampire(&self) -> bool {
+        self.has_active_interaction("VAMPIRE")
+    }
+    /// Whether the historical figure has been cursed to be a werebeast
+    #[must_use]
+    pub fn is_werebeast(&self) -> bool {
+        self.has_active_interaction("WEREBEAST")
+    }
+    /// The empty elements given for the historical figure, e.g. `deity` or `force`
+    #[must_use]
+    pub fn get_flags(&self) -> &[String] {
+        &self.flags
+    }
+    /// Whether the historical figure is a deity
+    #[must_use]
+    pub fn is_deity(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "deity")
+    }
+    /// Find the creature the race of the historical figure refers to in the parsed raws
+    ///
+    /// # Arguments
+    ///
+    /// * `raws` - The parsed raws
+    ///
+    /// # Returns
+    ///
+    /// The creature, or `None` if it wasn't parsed
+    #[must_use]
+    pub fn find_creature<'a>(&self, raws: &'a [Box<dyn RawObject>]) -> Option<&'a Creature> {
+        let identifier = self.get_creature_identifier();
+        raws.iter()
+            .filter(|raw| {
+                raw.get_type() == ObjectType::Creature
+                    && raw.get_identifier().eq_ignore_ascii_case(&identifier)
+            })
+            .find_map(|raw| raw.as_any().downcast_ref::<Creature>())
+    }
+    /// Read the value of an element of the historical figure
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element the value is inside of
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, section: ExportSection, field: &str, value: &str) {
+        match section {
+            ExportSection::None => match field {
+                "id" => self.id = value.parse().unwrap_or_default(),
+                "name" => self.name = value.to_string(),
+                "race" => self.race = value.to_string(),
+                "caste" => self.caste = value.to_string(),
+                "birth_year" => self.birth_year = value.parse().ok(),
+                "death_year" => {
+                    self.death_year = value.parse().ok().filter(|year: &i32| *year >= 0);
+                }
+                "associated_type" => self.associated_type = value.to_string(),
+                "sphere" => self.spheres.push(value.to_string()),
+                "active_interaction" => self.active_interactions.push(value.to_string()),
+                _ => (),
+            },
+            ExportSection::EntityLink => {
+                let Some(link) = self.entity_links.last_mut() else {
+                    return;
+                };
+                match field {
+                    "link_type" => link.link_type = value.to_string(),
+                    "entity_id" => link.entity_id = value.parse().unwrap_or_default(),
+                    "link_strength" => link.link_strength = value.parse().ok(),
+                    _ => (),
+                }
+            }
+            ExportSection::HistoricalFigureLink => {
+                let Some(link) = self.historical_figure_links.last_mut() else {
+                    return;
+                };
+                match field {
+                    "link_type" => link.link_type = value.to_string(),
+                    "hfid" => link.historical_figure_id = value.parse().unwrap_or_default(),
+                    "link_strength" => link.link_strength = value.parse().ok(),
+                    _ => (),
+                }
+            }
+            ExportSection::Skill => {
+                let Some(skill) = self.skills.last_mut() else {
+                    return;
+                };
+                match field {
+                    "skill" => skill.skill = value.to_string(),
+                    "total_ip" => skill.total_ip = value.parse().unwrap_or_default(),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+    /// Start reading a nested element of the historical figure
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element which was started
+    pub(crate) fn start_section(&mut self, section: ExportSection) {
+        match section {
+            ExportSection::EntityLink => {
+                self.entity_links.push(ExportedEntityLink::default());
+            }
+            ExportSection::HistoricalFigureLink => {
+                self.historical_figure_links
+                    .push(ExportedHistoricalFigureLink::default());
+            }
+            ExportSection::Skill => self.skills.push(ExportedSkill::default()),
+            _ => (),
+        }
+    }
+    /// Add an empty element given for the historical figure, e.g. `deity`
+    ///
+    /// # Arguments
+    ///
+    /// * `flag` - The name of the element
+    pub(crate) fn add_flag(&mut self, flag: &str) {
+        self.flags.push(flag.to_string());
+    }
+}
+
+impl Merge for ExportedHistoricalFigure {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_string(&mut self.race, other.race);
+        fill_string(&mut self.caste, other.caste);
+        fill_option(&mut self.birth_year, other.birth_year);
+        fill_option(&mut self.death_year, other.death_year);
+        fill_string(&mut self.associated_type, other.associated_type);
+        union(&mut self.entity_links, other.entity_links);
+        union(
+            &mut self.historical_figure_links,
+            other.historical_figure_links,
+        );
+        union(&mut self.skills, other.skills);
+        union(&mut self.spheres, other.spheres);
+        union(&mut self.active_interactions, other.active_interactions);
+        union(&mut self.flags, other.flags);
+    }
+}
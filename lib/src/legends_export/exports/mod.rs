@@ -1,7 +1,45 @@
 //! Representations of the various exports from the Legends Viewer.
 
+pub mod artifact;
 pub mod creature;
 pub mod entity;
+pub mod historical_event;
+pub mod historical_figure;
+pub mod region;
+pub mod site;
+pub mod world_construction;
+pub mod written_content;
 
+pub use artifact::ExportedArtifact;
 pub use creature::ExportedCreature;
-pub use entity::ExportedEntity;
+pub use entity::{
+    ExportedEntity, ExportedEntityOccasion, ExportedEntityPopulation, ExportedEntityPosition,
+    ExportedEntityPositionAssignment, ExportedEntityRelation, ExportedOccasionSchedule,
+};
+pub use historical_event::{ExportedEventCollection, ExportedHistoricalEvent};
+pub use historical_figure::{
+    ExportedEntityLink, ExportedHistoricalFigure, ExportedHistoricalFigureLink, ExportedSkill,
+};
+pub use region::{ExportedRegion, ExportedUndergroundRegion};
+pub use site::{ExportedSite, ExportedSiteProperty, ExportedStructure};
+pub use world_construction::ExportedWorldConstruction;
+pub use written_content::{
+    ExportedCulturalForm, ExportedWrittenContent, ExportedWrittenContentReference,
+};
+
+/// The nested element of an exported object which is being read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportSection {
+    None,
+    EntityLink,
+    HistoricalFigureLink,
+    Skill,
+    Structure,
+    SiteProperty,
+    EntityPosition,
+    EntityPositionAssignment,
+    Occasion,
+    OccasionSchedule,
+    ArtifactItem,
+    WrittenContentReference,
+}
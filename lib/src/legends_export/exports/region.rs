@@ -0,0 +1,151 @@
+//! The `ExportedRegion` and `ExportedUndergroundRegion` structs are used to store information about the
+//! regions of the world that have been exported from the Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Dimensions;
+use crate::legends_export::{
+    merge::{Merge, fill_list, fill_option, fill_string},
+    util::parse_coordinate_list,
+};
+
+/// A region of the surface of the world from a legends export (a `region` element).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedRegion {
+    id: i32,
+    name: String,
+    region_type: String,
+    /// The world map tiles of the region (only in legends-plus exports)
+    coordinates: Vec<Dimensions>,
+    /// How good or evil the region is, e.g. `neutral` (only in legends-plus exports)
+    evilness: Option<String>,
+}
+
+/// A region below the surface of the world from a legends export (an `underground_region` element), e.g. a
+/// cavern layer.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedUndergroundRegion {
+    id: i32,
+    region_type: String,
+    /// `None` if the depth wasn't exported
+    depth: Option<i32>,
+    /// The world map tiles of the region (only in legends-plus exports)
+    coordinates: Vec<Dimensions>,
+}
+
+impl ExportedRegion {
+    /// The id of the region
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the region
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The type of the region, e.g. `Mountains` or `Ocean`
+    #[must_use]
+    pub fn get_region_type(&self) -> &str {
+        &self.region_type
+    }
+    /// The world map tiles of the region (only in legends-plus exports)
+    #[must_use]
+    pub fn get_coordinates(&self) -> &[Dimensions] {
+        &self.coordinates
+    }
+    /// How good or evil the region is, e.g. `neutral` (only in legends-plus exports)
+    #[must_use]
+    pub fn get_evilness(&self) -> Option<&str> {
+        self.evilness.as_deref()
+    }
+    /// Whether the region covers a world map tile
+    #[must_use]
+    pub fn contains(&self, coordinates: Dimensions) -> bool {
+        self.coordinates.contains(&coordinates)
+    }
+    /// Read the value of an element of the region
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "name" => self.name = value.to_string(),
+            "type" => self.region_type = value.to_string(),
+            "coords" => self.coordinates = parse_coordinate_list(value),
+            "evilness" => self.evilness = Some(value.to_string()),
+            _ => (),
+        }
+    }
+}
+
+impl ExportedUndergroundRegion {
+    /// The id of the underground region
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The type of the underground region, e.g. `cavern` or `magma`
+    #[must_use]
+    pub fn get_region_type(&self) -> &str {
+        &self.region_type
+    }
+    /// How deep the underground region is, starting at 1 for the first cavern layer (0 if it wasn't
+    /// exported)
+    #[must_use]
+    pub fn get_depth(&self) -> i32 {
+        self.depth.unwrap_or_default()
+    }
+    /// The world map tiles of the underground region (only in legends-plus exports)
+    #[must_use]
+    pub fn get_coordinates(&self) -> &[Dimensions] {
+        &self.coordinates
+    }
+    /// Read the value of an element of the underground region
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "type" => self.region_type = value.to_string(),
+            "depth" => self.depth = value.parse().ok(),
+            "coords" => self.coordinates = parse_coordinate_list(value),
+            _ => (),
+        }
+    }
+}
+
+impl Merge for ExportedRegion {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_string(&mut self.region_type, other.region_type);
+        fill_list(&mut self.coordinates, other.coordinates);
+        fill_option(&mut self.evilness, other.evilness);
+    }
+}
+
+impl Merge for ExportedUndergroundRegion {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.region_type, other.region_type);
+        fill_option(&mut self.depth, other.depth);
+        fill_list(&mut self.coordinates, other.coordinates);
+    }
+}
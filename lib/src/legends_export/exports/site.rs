@@ -0,0 +1,228 @@
+//! The `ExportedSite` struct is used to store information about a site (with its structures and properties)
+//! that has been exported from the Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Dimensions;
+use crate::legends_export::{
+    merge::{Merge, fill_option, fill_string, merge_by_key},
+    util::{parse_coordinates, parse_rectangle},
+};
+
+use super::ExportSection;
+
+/// A site from a legends export (a `site` element), e.g. a fortress, hamlet or cave.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSite {
+    id: i32,
+    name: String,
+    site_type: String,
+    /// The world map tile the site is on
+    coordinates: Option<Dimensions>,
+    /// The corners of the area the site covers, in region tiles
+    rectangle: Option<(Dimensions, Dimensions)>,
+    /// The civilization the site belongs to (only in legends-plus exports)
+    civ_id: Option<i32>,
+    /// The entity currently holding the site (only in legends-plus exports)
+    current_owner_id: Option<i32>,
+    structures: Vec<ExportedStructure>,
+    properties: Vec<ExportedSiteProperty>,
+}
+
+/// A structure of a site (a `structure` element), e.g. a temple, tavern or library.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedStructure {
+    /// The id of the structure within its site
+    pub local_id: i32,
+    /// The type of the structure, e.g. `temple`
+    pub structure_type: String,
+    /// The name of the structure
+    pub name: String,
+    /// The entity which owns the structure, if any
+    pub entity_id: Option<i32>,
+    /// The historical figure (deity) worshipped at the structure, if any
+    pub worship_historical_figure_id: Option<i32>,
+}
+
+/// A property of a site (a `site_property` element), e.g. a house and its owner.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSiteProperty {
+    /// The id of the property within its site
+    pub id: i32,
+    /// The type of the property, e.g. `house`
+    pub property_type: Option<String>,
+    /// The historical figure which owns the property, if any
+    pub owner_historical_figure_id: Option<i32>,
+    /// The structure of the site the property is, if any
+    pub structure_id: Option<i32>,
+}
+
+impl ExportedSite {
+    /// The id of the site
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the site
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The type of the site, e.g. `fortress` or `cave`
+    #[must_use]
+    pub fn get_site_type(&self) -> &str {
+        &self.site_type
+    }
+    /// The world map tile the site is on
+    #[must_use]
+    pub const fn get_coordinates(&self) -> Option<Dimensions> {
+        self.coordinates
+    }
+    /// The corners of the area the site covers, in region tiles
+    #[must_use]
+    pub const fn get_rectangle(&self) -> Option<(Dimensions, Dimensions)> {
+        self.rectangle
+    }
+    /// The civilization the site belongs to (only in legends-plus exports)
+    #[must_use]
+    pub const fn get_civ_id(&self) -> Option<i32> {
+        self.civ_id
+    }
+    /// The entity currently holding the site (only in legends-plus exports)
+    #[must_use]
+    pub const fn get_current_owner_id(&self) -> Option<i32> {
+        self.current_owner_id
+    }
+    /// Whether the site belongs to, or is held by, an entity
+    #[must_use]
+    pub fn is_owned_by(&self, entity_id: i32) -> bool {
+        self.civ_id == Some(entity_id) || self.current_owner_id == Some(entity_id)
+    }
+    /// The structures of the site
+    #[must_use]
+    pub fn get_structures(&self) -> &[ExportedStructure] {
+        &self.structures
+    }
+    /// The properties of the site
+    #[must_use]
+    pub fn get_properties(&self) -> &[ExportedSiteProperty] {
+        &self.properties
+    }
+    /// Read the value of an element of the site
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element the value is inside of
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, section: ExportSection, field: &str, value: &str) {
+        match section {
+            ExportSection::None => match field {
+                "id" => self.id = value.parse().unwrap_or_default(),
+                "name" => self.name = value.to_string(),
+                "type" => self.site_type = value.to_string(),
+                "coords" => self.coordinates = parse_coordinates(value),
+                "rectangle" => self.rectangle = parse_rectangle(value),
+                "civ_id" => self.civ_id = value.parse().ok(),
+                "cur_owner_id" => self.current_owner_id = value.parse().ok(),
+                _ => (),
+            },
+            ExportSection::Structure => {
+                let Some(structure) = self.structures.last_mut() else {
+                    return;
+                };
+                match field {
+                    "local_id" | "id" => structure.local_id = value.parse().unwrap_or_default(),
+                    "type" => structure.structure_type = value.to_string(),
+                    "name" => structure.name = value.to_string(),
+                    "entity_id" => structure.entity_id = value.parse().ok(),
+                    "worship_hfid" | "deity" => {
+                        structure.worship_historical_figure_id = value.parse().ok();
+                    }
+                    _ => (),
+                }
+            }
+            ExportSection::SiteProperty => {
+                let Some(property) = self.properties.last_mut() else {
+                    return;
+                };
+                match field {
+                    "id" => property.id = value.parse().unwrap_or_default(),
+                    "type" => property.property_type = Some(value.to_string()),
+                    "owner_hfid" => property.owner_historical_figure_id = value.parse().ok(),
+                    "structure_id" => property.structure_id = value.parse().ok(),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+    /// Start reading a nested element of the site
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element which was started
+    pub(crate) fn start_section(&mut self, section: ExportSection) {
+        match section {
+            ExportSection::Structure => self.structures.push(ExportedStructure::default()),
+            ExportSection::SiteProperty => {
+                self.properties.push(ExportedSiteProperty::default());
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Merge for ExportedSite {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_string(&mut self.site_type, other.site_type);
+        fill_option(&mut self.coordinates, other.coordinates);
+        fill_option(&mut self.rectangle, other.rectangle);
+        fill_option(&mut self.civ_id, other.civ_id);
+        fill_option(&mut self.current_owner_id, other.current_owner_id);
+        merge_by_key(&mut self.structures, other.structures);
+        merge_by_key(&mut self.properties, other.properties);
+    }
+}
+
+impl Merge for ExportedStructure {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.local_id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.structure_type, other.structure_type);
+        fill_string(&mut self.name, other.name);
+        fill_option(&mut self.entity_id, other.entity_id);
+        fill_option(
+            &mut self.worship_historical_figure_id,
+            other.worship_historical_figure_id,
+        );
+    }
+}
+
+impl Merge for ExportedSiteProperty {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.property_type, other.property_type);
+        fill_option(
+            &mut self.owner_historical_figure_id,
+            other.owner_historical_figure_id,
+        );
+        fill_option(&mut self.structure_id, other.structure_id);
+    }
+}
@@ -0,0 +1,74 @@
+//! The `ExportedWorldConstruction` struct is used to store information about a world construction (a road,
+//! bridge, tunnel or wall between sites) that has been exported from the Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Dimensions;
+use crate::legends_export::{
+    merge::{Merge, fill_list, fill_string},
+    util::parse_coordinate_list,
+};
+
+/// A world construction from a legends export (a `world_construction` element).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedWorldConstruction {
+    id: i32,
+    name: String,
+    construction_type: String,
+    /// The world map tiles the construction passes through
+    coordinates: Vec<Dimensions>,
+}
+
+impl ExportedWorldConstruction {
+    /// The id of the world construction
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the world construction
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    /// The type of the world construction, e.g. `road`
+    ///
+    /// This matches (ignoring case) the `WORLD_CONSTRUCTION` tokens of the entities which can build it.
+    #[must_use]
+    pub fn get_construction_type(&self) -> &str {
+        &self.construction_type
+    }
+    /// The world map tiles the world construction passes through
+    #[must_use]
+    pub fn get_coordinates(&self) -> &[Dimensions] {
+        &self.coordinates
+    }
+    /// Read the value of an element of the world construction
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "name" => self.name = value.to_string(),
+            "type" => self.construction_type = value.to_string(),
+            "coords" => self.coordinates = parse_coordinate_list(value),
+            _ => (),
+        }
+    }
+}
+
+impl Merge for ExportedWorldConstruction {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.name, other.name);
+        fill_string(&mut self.construction_type, other.construction_type);
+        fill_list(&mut self.coordinates, other.coordinates);
+    }
+}
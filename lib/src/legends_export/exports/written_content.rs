@@ -0,0 +1,224 @@
+//! The `ExportedWrittenContent` and `ExportedCulturalForm` structs are used to store information about the
+//! written works, and the poetic, musical and dance forms they follow, that have been exported from the
+//! Legends Viewer.
+
+use serde::{Deserialize, Serialize};
+
+use crate::legends_export::{
+    merge::{Merge, fill_option, fill_string, union},
+    util::parse_id,
+};
+
+use super::ExportSection;
+
+/// A written work from a legends export (a `written_content` element), e.g. a poem or a treatise.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedWrittenContent {
+    id: i32,
+    title: String,
+    /// The historical figure who wrote the work
+    author_historical_figure_id: Option<i32>,
+    /// The type of the work, e.g. `Poem` or `MusicalComposition`
+    content_type: Option<String>,
+    /// The poetic, musical or dance form the work follows, if any
+    form_id: Option<i32>,
+    /// The styles of the work, e.g. `meandering`
+    styles: Vec<String>,
+    page_start: Option<i32>,
+    page_end: Option<i32>,
+    /// What the work is about
+    references: Vec<ExportedWrittenContentReference>,
+}
+
+/// What a written work is about (a `reference` element), e.g. a historical figure or a site.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedWrittenContentReference {
+    /// The type of object referred to, e.g. `HISTORICAL_FIGURE` or `SITE`
+    pub reference_type: String,
+    /// The id of the object referred to
+    pub id: Option<i32>,
+}
+
+/// A poetic, musical or dance form from a legends export (a `poetic_form`, `musical_form` or `dance_form`
+/// element), generated with the world.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedCulturalForm {
+    id: i32,
+    /// The name of the form (only in legends-plus exports)
+    name: Option<String>,
+    /// The description of the form (only in the export from Dwarf Fortress)
+    description: Option<String>,
+}
+
+impl ExportedWrittenContent {
+    /// The id of the written work
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The title of the written work
+    #[must_use]
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+    /// The historical figure who wrote the work
+    #[must_use]
+    pub const fn get_author_historical_figure_id(&self) -> Option<i32> {
+        self.author_historical_figure_id
+    }
+    /// The type of the work, e.g. `Poem`, `MusicalComposition` or `Choreography`
+    #[must_use]
+    pub fn get_content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+    /// The poetic, musical or dance form the work follows, if any
+    #[must_use]
+    pub const fn get_form_id(&self) -> Option<i32> {
+        self.form_id
+    }
+    /// The styles of the work, e.g. `meandering`
+    #[must_use]
+    pub fn get_styles(&self) -> &[String] {
+        &self.styles
+    }
+    /// Whether the work is written in a style (ignoring case)
+    #[must_use]
+    pub fn has_style(&self, style: &str) -> bool {
+        self.styles
+            .iter()
+            .any(|written_style| written_style.eq_ignore_ascii_case(style))
+    }
+    /// The first page of the work in its artifact, if known
+    #[must_use]
+    pub const fn get_page_start(&self) -> Option<i32> {
+        self.page_start
+    }
+    /// The last page of the work in its artifact, if known
+    #[must_use]
+    pub const fn get_page_end(&self) -> Option<i32> {
+        self.page_end
+    }
+    /// What the work is about
+    #[must_use]
+    pub fn get_references(&self) -> &[ExportedWrittenContentReference] {
+        &self.references
+    }
+    /// Read the value of an element of the written work
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element the value is inside of
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, section: ExportSection, field: &str, value: &str) {
+        match section {
+            ExportSection::None => match field {
+                "id" => self.id = value.parse().unwrap_or_default(),
+                "title" => self.title = value.to_string(),
+                "author_hfid" | "author" => self.author_historical_figure_id = parse_id(value),
+                "type" | "form" => self.content_type = Some(value.to_string()),
+                "form_id" => self.form_id = parse_id(value),
+                "page_start" => self.page_start = parse_id(value),
+                "page_end" => self.page_end = parse_id(value),
+                // The export from Dwarf Fortress adds the strength of the style, e.g. `meandering:1`
+                "style" => {
+                    let style = value.split_once(':').map_or(value, |(style, _)| style);
+                    if !self.has_style(style) {
+                        self.styles.push(style.to_lowercase());
+                    }
+                }
+                _ => (),
+            },
+            ExportSection::WrittenContentReference => {
+                let Some(reference) = self.references.last_mut() else {
+                    return;
+                };
+                match field {
+                    "type" => reference.reference_type = value.to_string(),
+                    "id" => reference.id = parse_id(value),
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+    /// Start reading a nested element of the written work
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The nested element which was started
+    pub(crate) fn start_section(&mut self, section: ExportSection) {
+        if section == ExportSection::WrittenContentReference {
+            self.references
+                .push(ExportedWrittenContentReference::default());
+        }
+    }
+}
+
+impl ExportedCulturalForm {
+    /// The id of the form
+    #[must_use]
+    pub const fn get_id(&self) -> i32 {
+        self.id
+    }
+    /// The name of the form (only in legends-plus exports)
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// The description of the form (only in the export from Dwarf Fortress)
+    #[must_use]
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Read the value of an element of the form
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the element
+    /// * `value` - The text of the element
+    pub(crate) fn read_value(&mut self, field: &str, value: &str) {
+        match field {
+            "id" => self.id = value.parse().unwrap_or_default(),
+            "name" => self.name = Some(value.to_string()),
+            "description" => self.description = Some(value.to_string()),
+            _ => (),
+        }
+    }
+}
+
+impl Merge for ExportedWrittenContent {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_string(&mut self.title, other.title);
+        fill_option(
+            &mut self.author_historical_figure_id,
+            other.author_historical_figure_id,
+        );
+        fill_option(&mut self.content_type, other.content_type);
+        fill_option(&mut self.form_id, other.form_id);
+        union(&mut self.styles, other.styles);
+        fill_option(&mut self.page_start, other.page_start);
+        fill_option(&mut self.page_end, other.page_end);
+        union(&mut self.references, other.references);
+    }
+}
+
+impl Merge for ExportedCulturalForm {
+    type Key = i32;
+
+    fn merge_key(&self) -> i32 {
+        self.id
+    }
+    fn merge(&mut self, other: Self) {
+        fill_option(&mut self.name, other.name);
+        fill_option(&mut self.description, other.description);
+    }
+}
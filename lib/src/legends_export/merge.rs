@@ -0,0 +1,187 @@
+//! Merging of the records of the legends exports of a world.
+//!
+//! Dwarf Fortress exports `<world>-legends.xml`, and `DFHack` exports `<world>-legends_plus.xml` with more
+//! details about the same records (e.g. the races of entities, and the coordinates of regions). The records
+//! are unified by their id, with the empty fields of a record filled in from the other file.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+/// A record of a legends export which can be unified with the same record from another export.
+pub(crate) trait Merge {
+    /// The type of the id the records are unified by
+    type Key: Eq + Hash;
+
+    /// The id the records are unified by
+    fn merge_key(&self) -> Self::Key;
+    /// Enrich this record with the same record from another export
+    fn merge(&mut self, other: Self);
+}
+
+/// Unify two lists of records by their id, in the order of the first list.
+///
+/// Records which are only in the second list are added at the end.
+pub(crate) fn merge_by_key<T: Merge>(records: &mut Vec<T>, others: Vec<T>) {
+    let mut positions: HashMap<T::Key, usize> = records
+        .iter()
+        .enumerate()
+        .map(|(position, record)| (record.merge_key(), position))
+        .collect();
+
+    for other in others {
+        let key = other.merge_key();
+        if let Some(&position) = positions.get(&key) {
+            records[position].merge(other);
+        } else {
+            positions.insert(key, records.len());
+            records.push(other);
+        }
+    }
+}
+
+/// Fill a text field which is empty
+pub(crate) fn fill_string(value: &mut String, other: String) {
+    if value.is_empty() {
+        *value = other;
+    }
+}
+
+/// Fill an optional field which is unset
+pub(crate) fn fill_option<T>(value: &mut Option<T>, other: Option<T>) {
+    if value.is_none() {
+        *value = other;
+    }
+}
+
+/// Add the values of another list which aren't already in a list
+pub(crate) fn union<T: PartialEq>(values: &mut Vec<T>, others: Vec<T>) {
+    for other in others {
+        if !values.contains(&other) {
+            values.push(other);
+        }
+    }
+}
+
+/// Fill a list which is empty
+pub(crate) fn fill_list<T>(values: &mut Vec<T>, others: Vec<T>) {
+    if values.is_empty() {
+        *values = others;
+    }
+}
+
+/// The path which identifies the world a legends export is from, i.e. the path without the `-legends.xml` or
+/// `-legends_plus.xml` suffix.
+///
+/// Exports which aren't named like the ones from Dwarf Fortress and `DFHack` are their own world.
+#[must_use]
+pub fn legends_world_path(input_path: &Path) -> PathBuf {
+    let file_name = input_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    file_name
+        .strip_suffix("-legends_plus.xml")
+        .or_else(|| file_name.strip_suffix("-legends.xml"))
+        .map_or_else(
+            || input_path.to_path_buf(),
+            |world| input_path.with_file_name(world),
+        )
+}
+
+/// Whether a legends export is a `DFHack` legends-plus export, judged by its name.
+#[must_use]
+pub fn is_legends_plus_export(input_path: &Path) -> bool {
+    input_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .ends_with("-legends_plus.xml")
+}
+
+/// Group the legends exports by the world they are from.
+///
+/// Within each group, the exports from Dwarf Fortress come before the `DFHack` legends-plus exports, so the
+/// records of the plus exports enrich the base ones. The groups are in the order their first export was given.
+#[must_use]
+pub fn group_legends_exports_by_world<P: AsRef<Path>>(input_paths: &[P]) -> Vec<Vec<PathBuf>> {
+    let mut groups: Vec<(PathBuf, Vec<PathBuf>)> = Vec::new();
+
+    for input_path in input_paths {
+        let input_path = input_path.as_ref();
+        let world = legends_world_path(input_path);
+        if let Some((_, group)) = groups.iter_mut().find(|(key, _)| *key == world) {
+            group.push(input_path.to_path_buf());
+        } else {
+            groups.push((world, vec![input_path.to_path_buf()]));
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, mut group)| {
+            group.sort_by_key(|path| is_legends_plus_export(path));
+            group
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legends_export::exports::ExportSection;
+    use crate::legends_export::{
+        ExportedEntity, ExportedHistoricalEvent, ExportedHistoricalFigure,
+    };
+
+    fn figure(values: &[(&str, &str)]) -> ExportedHistoricalFigure {
+        let mut figure = ExportedHistoricalFigure::default();
+        for (field, value) in values {
+            figure.read_value(ExportSection::None, field, value);
+        }
+        figure
+    }
+
+    #[test]
+    fn test_merge_keeps_zero_values() {
+        let mut figures = vec![figure(&[("id", "0"), ("birth_year", "0")])];
+        merge_by_key(
+            &mut figures,
+            vec![
+                figure(&[("id", "0"), ("birth_year", "12")]),
+                figure(&[("id", "1")]),
+            ],
+        );
+        assert_eq!(figures.len(), 2);
+        assert_eq!(figures[0].get_id(), 0);
+        assert_eq!(figures[0].get_birth_year(), Some(0));
+
+        // Fields which weren't exported are filled in
+        let mut unknown = vec![figure(&[("id", "1")])];
+        merge_by_key(
+            &mut unknown,
+            vec![figure(&[("id", "1"), ("birth_year", "12")])],
+        );
+        assert_eq!(unknown[0].get_birth_year(), Some(12));
+
+        let mut events = vec![ExportedHistoricalEvent::default()];
+        events[0].read_value("year", "0");
+        let mut plus_event = ExportedHistoricalEvent::default();
+        plus_event.read_value("year", "20");
+        merge_by_key(&mut events, vec![plus_event]);
+        assert_eq!(events[0].get_year(), 0);
+    }
+
+    #[test]
+    fn test_merge_keeps_every_child_entity() {
+        let mut entities = vec![ExportedEntity::default()];
+        entities[0].read_value(ExportSection::None, "child", "14");
+        entities[0].read_value(ExportSection::None, "child", "15");
+        let mut plus_entity = ExportedEntity::default();
+        plus_entity.read_value(ExportSection::None, "child", "15");
+        plus_entity.read_value(ExportSection::None, "child", "16");
+        merge_by_key(&mut entities, vec![plus_entity]);
+        assert_eq!(entities[0].get_child_ids(), [14, 15, 16]);
+    }
+}
@@ -1,12 +1,44 @@
 //! This module supports parsing Dwarf Fortress legends export files.
 //!
-//! So far this only supports parsing the creature and entity sections of the legends export.
+//! The creatures and entities of a legends export are parsed into raw objects. The entities, historical
+//! figures, sites, regions, world constructions, historical events, artifacts, written works and cultural
+//! forms are parsed into a [`LegendsWorld`], whose events can be indexed into a [`Timeline`].
+//!
+//! The `-legends.xml` export from Dwarf Fortress and the `-legends_plus.xml` export from `DFHack` hold
+//! different details of the same records, and can be merged with [`parse_merged_legends_exports`].
+//!
+//! Exports are read as a stream, so the records of exports too large to hold in memory can be handled one at a
+//! time with a [`LegendsReader`] or [`for_each_legends_record`].
 
+mod merge;
 mod reader;
+mod stream;
+mod timeline;
 mod util;
+mod world;
 
 pub mod exports;
 
+pub use exports::ExportedArtifact;
 pub use exports::ExportedCreature;
-pub use exports::ExportedEntity;
+pub use exports::{
+    ExportedEntity, ExportedEntityOccasion, ExportedEntityPopulation, ExportedEntityPosition,
+    ExportedEntityPositionAssignment, ExportedEntityRelation, ExportedOccasionSchedule,
+};
+pub use exports::{ExportedEventCollection, ExportedHistoricalEvent};
+pub use exports::{
+    ExportedEntityLink, ExportedHistoricalFigure, ExportedHistoricalFigureLink, ExportedSkill,
+};
+pub use exports::{ExportedRegion, ExportedUndergroundRegion};
+pub use exports::{ExportedSite, ExportedSiteProperty, ExportedStructure};
+pub use exports::ExportedWorldConstruction;
+pub use exports::{ExportedCulturalForm, ExportedWrittenContent, ExportedWrittenContentReference};
+pub use merge::{group_legends_exports_by_world, is_legends_plus_export, legends_world_path};
+pub(crate) use reader::read_merged_legends_exports;
+pub use reader::parse_historical_figures;
 pub use reader::parse_legends_export as parse;
+pub use reader::parse_legends_world;
+pub use reader::{parse_merged_legends_exports, parse_merged_legends_world};
+pub use stream::{LegendsFileReader, LegendsReader, LegendsRecord, for_each_legends_record};
+pub use timeline::Timeline;
+pub use world::LegendsWorld;
@@ -1,42 +1,120 @@
-use std::io::Read;
-use std::path::Path;
+use std::{io::BufRead, path::Path};
 
-use quick_xml::{Reader, events::Event};
-use tracing::{error, info};
+use tracing::info;
 
 use crate::{
     ParserError,
-    legends_export::{ExportedCreature, ExportedEntity},
+    legends_export::{ExportedCreature, ExportedHistoricalFigure, LegendsWorld},
     metadata::ParserOptions,
     tokens::ObjectType,
     traits::RawObject,
-    utilities::try_get_file,
 };
 
+use super::merge::merge_by_key;
+use super::stream::{LegendsFileReader, LegendsReader, LegendsRecord};
 use super::util::legends_metadata;
 
-#[derive(Eq, PartialEq)]
-enum Current {
-    None,
-    NameSingular,
-    NamePlural,
-    Identifier,
-    CivId,
-    Child,
-    Type,
-    Race,
+/// Everything read from a legends export
+#[derive(Default)]
+pub(crate) struct ExportedLegends {
+    pub creatures: Vec<ExportedCreature>,
+    pub world: LegendsWorld,
 }
 
-#[derive(Eq, PartialEq)]
-enum Parent {
-    None,
-    Creature,
-    Entity,
+impl ExportedLegends {
+    /// Unify the records of another export of the same world with these, by their ids
+    ///
+    /// The empty fields of the records are filled in from the other export, and the records only in the
+    /// other export are added.
+    pub fn merge(&mut self, other: Self) {
+        merge_by_key(&mut self.creatures, other.creatures);
+        merge_by_key(&mut self.world.entities, other.world.entities);
+        merge_by_key(
+            &mut self.world.entity_populations,
+            other.world.entity_populations,
+        );
+        merge_by_key(
+            &mut self.world.historical_figures,
+            other.world.historical_figures,
+        );
+        merge_by_key(&mut self.world.sites, other.world.sites);
+        merge_by_key(&mut self.world.regions, other.world.regions);
+        merge_by_key(
+            &mut self.world.underground_regions,
+            other.world.underground_regions,
+        );
+        merge_by_key(
+            &mut self.world.world_constructions,
+            other.world.world_constructions,
+        );
+        merge_by_key(
+            &mut self.world.historical_events,
+            other.world.historical_events,
+        );
+        merge_by_key(
+            &mut self.world.historical_event_collections,
+            other.world.historical_event_collections,
+        );
+        merge_by_key(&mut self.world.artifacts, other.world.artifacts);
+        merge_by_key(
+            &mut self.world.written_contents,
+            other.world.written_contents,
+        );
+        merge_by_key(&mut self.world.poetic_forms, other.world.poetic_forms);
+        merge_by_key(&mut self.world.musical_forms, other.world.musical_forms);
+        merge_by_key(&mut self.world.dance_forms, other.world.dance_forms);
+    }
+    /// Add a record read from a legends export
+    pub fn add(&mut self, record: LegendsRecord) {
+        match record {
+            LegendsRecord::Creature(creature) => self.creatures.push(creature),
+            LegendsRecord::Entity(entity) => self.world.entities.push(entity),
+            LegendsRecord::EntityPopulation(population) => {
+                self.world.entity_populations.push(population);
+            }
+            LegendsRecord::HistoricalFigure(figure) => self.world.historical_figures.push(figure),
+            LegendsRecord::Site(site) => self.world.sites.push(site),
+            LegendsRecord::Region(region) => self.world.regions.push(region),
+            LegendsRecord::UndergroundRegion(region) => {
+                self.world.underground_regions.push(region);
+            }
+            LegendsRecord::WorldConstruction(construction) => {
+                self.world.world_constructions.push(construction);
+            }
+            LegendsRecord::HistoricalEvent(event) => self.world.historical_events.push(event),
+            LegendsRecord::EventCollection(collection) => {
+                self.world.historical_event_collections.push(collection);
+            }
+            LegendsRecord::Artifact(artifact) => self.world.artifacts.push(artifact),
+            LegendsRecord::WrittenContent(content) => self.world.written_contents.push(content),
+            LegendsRecord::PoeticForm(form) => self.world.poetic_forms.push(form),
+            LegendsRecord::MusicalForm(form) => self.world.musical_forms.push(form),
+            LegendsRecord::DanceForm(form) => self.world.dance_forms.push(form),
+        }
+    }
+    /// Convert the creatures and entities into raw objects
+    pub fn to_raws(&self, input_path: &Path, options: &ParserOptions) -> Vec<Box<dyn RawObject>> {
+        let mut results: Vec<Box<dyn RawObject>> = Vec::new();
+        let legend_metadata = legends_metadata(input_path, ObjectType::Creature, options);
+
+        for creature in &self.creatures {
+            results.push(Box::new(creature.clone().into_creature(&legend_metadata)));
+        }
+
+        let legend_metadata = legends_metadata(input_path, ObjectType::Entity, options);
+
+        for entity in &self.world.entities {
+            results.push(Box::new(entity.clone().into_entity(&legend_metadata)));
+        }
+
+        results
+    }
 }
 
-#[allow(clippy::too_many_lines)]
 /// Parses the legends export file at the specified input path and returns a vector of raw objects.
 ///
+/// Only the creatures and entities are kept while the export is read, the rest of the world is dropped.
+///
 /// # Arguments
 ///
 /// * `input_path` - The path to the legends export file.
@@ -52,13 +130,133 @@ pub fn parse_legends_export<P: AsRef<Path>>(
     input_path: &P,
     options: &ParserOptions,
 ) -> Result<Vec<Box<dyn RawObject>>, ParserError> {
-    let mut results: Vec<Box<dyn RawObject>> = Vec::new();
-    let mut file = try_get_file(&input_path.as_ref())?;
+    Ok(read_legends_raws(open_legends_export(input_path)?)?.to_raws(input_path.as_ref(), options))
+}
+
+/// Parses the historical figures from the legends export file at the specified input path.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+///
+/// # Returns
+///
+/// The historical figures, in the order they are exported.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file or parsing the file.
+pub fn parse_historical_figures<P: AsRef<Path>>(
+    input_path: &P,
+) -> Result<Vec<ExportedHistoricalFigure>, ParserError> {
+    Ok(read_legends_export(input_path)?.world.historical_figures)
+}
+
+/// Parses the world (historical figures, sites, regions and world constructions) from the legends export
+/// file at the specified input path.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+///
+/// # Returns
+///
+/// The world described by the legends export.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file or parsing the file.
+pub fn parse_legends_world<P: AsRef<Path>>(input_path: &P) -> Result<LegendsWorld, ParserError> {
+    Ok(read_legends_export(input_path)?.world)
+}
+
+/// Parses the legends exports of one world (e.g. its `-legends.xml` and `-legends_plus.xml`), unifying
+/// their records by id, and returns a vector of raw objects.
+///
+/// The records of the later exports enrich the records of the earlier ones, so the export from Dwarf Fortress
+/// should come first. See [`super::group_legends_exports_by_world`] to put the exports in that order.
+///
+/// # Arguments
+///
+/// * `input_paths` - The paths to the legends exports of the world.
+/// * `options` - The parser options.
+///
+/// # Returns
+///
+/// A vector of boxed dynamic `RawObject` trait objects.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the files or parsing the files.
+pub fn parse_merged_legends_exports<P: AsRef<Path>>(
+    input_paths: &[P],
+    options: &ParserOptions,
+) -> Result<Vec<Box<dyn RawObject>>, ParserError> {
+    let Some(first_path) = input_paths.first() else {
+        return Ok(Vec::new());
+    };
+    Ok(read_merged_legends_exports(input_paths)?.to_raws(first_path.as_ref(), options))
+}
+
+/// Parses the world from the legends exports of one world (e.g. its `-legends.xml` and
+/// `-legends_plus.xml`), unifying their records by id.
+///
+/// The records of the later exports enrich the records of the earlier ones, so the export from Dwarf Fortress
+/// should come first.
+///
+/// # Arguments
+///
+/// * `input_paths` - The paths to the legends exports of the world.
+///
+/// # Returns
+///
+/// The world described by the legends exports.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the files or parsing the files.
+pub fn parse_merged_legends_world<P: AsRef<Path>>(
+    input_paths: &[P],
+) -> Result<LegendsWorld, ParserError> {
+    Ok(read_merged_legends_exports(input_paths)?.world)
+}
+
+/// Reads the legends exports of one world and unifies their records by id, in the order the exports are given.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the files or parsing the files.
+pub(crate) fn read_merged_legends_exports<P: AsRef<Path>>(
+    input_paths: &[P],
+) -> Result<ExportedLegends, ParserError> {
+    let mut legends = ExportedLegends::default();
+    for input_path in input_paths {
+        legends.merge(read_legends_export(input_path)?);
+    }
+    Ok(legends)
+}
+
+/// Reads the creatures, entities and the world from the legends export file at the specified path.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if there is an issue reading the file, or `ParserError::InvalidLegendsExport` if
+/// it isn't valid XML.
+pub(crate) fn read_legends_export<P: AsRef<Path>>(
+    input_path: &P,
+) -> Result<ExportedLegends, ParserError> {
+    read_legends(open_legends_export(input_path)?)
+}
 
-    // Read the file into a str for parsing
-    let mut file_str = String::new();
-    file.read_to_string(&mut file_str)
-        .map_err(|e| ParserError::Io { source: e })?;
+/// Opens a legends export for reading
+///
+/// # Errors
+///
+/// Returns a `ParserError` if the file can't be opened or read.
+fn open_legends_export<P: AsRef<Path>>(
+    input_path: &P,
+) -> Result<LegendsReader<LegendsFileReader>, ParserError> {
+    let reader = LegendsReader::open(input_path)?;
 
     info!(
         "parse_legends_export: Parsing file {}",
@@ -69,119 +267,866 @@ pub fn parse_legends_export<P: AsRef<Path>>(
             .to_string_lossy()
     );
 
-    let mut reader = Reader::from_str(&file_str);
-    reader.config_mut().trim_text(true);
-
-    let mut current_tag = Current::None;
-    let mut parent_tag = Parent::None;
-    let mut creatures: Vec<ExportedCreature> = Vec::new();
-    let mut temp_creature = ExportedCreature::default();
-    let mut entities: Vec<ExportedEntity> = Vec::new();
-    let mut temp_entity = ExportedEntity::default();
-
-    let mut tag_txt = String::new();
-
-    let mut buf = Vec::new();
-
-    // The `Reader` does not implement `Iterator` because it outputs borrowed data (`Cow`s)
-    loop {
-        // NOTE: this is the generic case when we don't know about the input BufRead.
-        // when the input is a &str or a &[u8], we don't actually need to use another
-        // buffer, we could directly call `reader.read_event()`
-        match reader.read_event_into(&mut buf) {
-            Err(e) => {
-                error!(
-                    "parse_legends_export: Error at position {}: {:?}",
-                    reader.buffer_position(),
-                    e
-                );
-                break;
-            }
-            // exits the loop when reaching end of file
-            Ok(Event::Eof) => break,
-
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"creature" => parent_tag = Parent::Creature,
-                b"name_singular" => current_tag = Current::NameSingular,
-                b"name_plural" => current_tag = Current::NamePlural,
-                b"creature_id" | b"id" => current_tag = Current::Identifier,
-                b"race" => current_tag = Current::Race,
-                b"civ_id" => current_tag = Current::CivId,
-                b"type" => current_tag = Current::Type,
-                b"child" => current_tag = Current::Child,
-                b"entity" => parent_tag = Parent::Entity,
-                _ => (),
-            },
-            Ok(Event::Text(e)) => {
-                if parent_tag != Parent::None {
-                    tag_txt = e
-                        .decode()
-                        .map_or_else(|_| String::new(), std::borrow::Cow::into_owned);
-                }
-                match parent_tag {
-                    Parent::Creature => match current_tag {
-                        Current::NameSingular => temp_creature.set_name_singular(&tag_txt),
-                        Current::NamePlural => temp_creature.set_name_plural(&tag_txt),
-                        Current::Identifier => temp_creature.set_creature_id(&tag_txt),
-                        _ => (),
-                    },
-                    Parent::Entity => match current_tag {
-                        Current::Identifier => {
-                            temp_entity.set_id(tag_txt.parse().unwrap_or_default());
-                        }
-                        Current::Child => {
-                            temp_entity.set_child_id(tag_txt.parse().unwrap_or_default());
-                        }
-                        Current::Race => temp_entity.set_race(&tag_txt),
-                        Current::Type => temp_entity.set_entity_type(&tag_txt),
-                        _ => (),
-                    },
-                    Parent::None => (),
-                }
-            }
+    Ok(reader)
+}
+
+/// Reads the creatures, entities and the world from a legends export
+///
+/// # Errors
+///
+/// Returns `ParserError::InvalidLegendsExport` if the export isn't valid XML.
+fn read_legends<R: BufRead>(reader: LegendsReader<R>) -> Result<ExportedLegends, ParserError> {
+    let mut legends = ExportedLegends::default();
+    for record in reader {
+        legends.add(record?);
+    }
+    Ok(legends)
+}
+
+/// Reads only the creatures and entities from a legends export, dropping every other record as it is read
+///
+/// # Errors
+///
+/// Returns a `ParserError` if the export isn't valid XML.
+fn read_legends_raws<R: BufRead>(reader: LegendsReader<R>) -> Result<ExportedLegends, ParserError> {
+    let mut legends = ExportedLegends::default();
+    for record in reader {
+        let record = record?;
+        if matches!(
+            record,
+            LegendsRecord::Creature(_) | LegendsRecord::Entity(_)
+        ) {
+            legends.add(record);
+        }
+    }
+    Ok(legends)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::legends_export::{ExportedEventCollection, ExportedHistoricalEvent, ExportedRegion};
+    use crate::metadata::RawMetadata;
+    use crate::{Dimensions, Entity, Inorganic};
 
-            Ok(Event::Empty(e)) => {
-                let tag_name = reader
-                    .decoder()
-                    .decode(e.name().as_ref())
-                    .map_or_else(|_| String::new(), std::borrow::Cow::into_owned);
-                if parent_tag == Parent::Creature {
-                    temp_creature.add_tag(&tag_name);
-                }
+    /// Read a legends export from memory
+    fn read_test_export(contents: &str) -> Result<ExportedLegends, ParserError> {
+        read_legends(LegendsReader::from_reader(contents.as_bytes()))
+    }
+
+    /// A directory to write legends exports to, which is removed when dropped (even when an assert fails)
+    struct TestExports {
+        dir: tempfile::TempDir,
+    }
+
+    impl TestExports {
+        fn new() -> Self {
+            Self {
+                dir: tempfile::tempdir().expect("Failed to create test directory"),
             }
-            Ok(Event::End(e)) => match e.name().as_ref() {
-                b"creature" => {
-                    parent_tag = Parent::None;
-                    if !temp_creature.is_empty() {
-                        creatures.push(temp_creature);
-                        temp_creature = ExportedCreature::default();
-                    }
-                }
-                b"entity" => {
-                    parent_tag = Parent::None;
-                    entities.push(temp_entity);
-                    temp_entity = ExportedEntity::default();
-                }
-                _ => (),
-            },
-            // There are several other `Event`s we do not consider here
-            _ => (),
         }
-        // if we don't keep a borrow elsewhere, we can clear the buffer to keep memory usage low
-        buf.clear();
+
+        fn write(&self, file_name: &str, contents: &str) -> PathBuf {
+            let path = self.dir.path().join(file_name);
+            std::fs::write(&path, contents).expect("Failed to write test legends export");
+            path
+        }
     }
 
-    let legend_metadata = legends_metadata(input_path.as_ref(), ObjectType::Creature, options);
+    const LEGENDS: &str = r#"<?xml version="1.0" encoding='CP437'?>
+<df_world>
+<historical_figures>
+<historical_figure>
+	<id>0</id>
+	<name>ngitha the lashes of dread</name>
+	<race>DWARF</race>
+	<caste>FEMALE</caste>
+	<appeared>1</appeared>
+	<birth_year>-112</birth_year>
+	<birth_seconds72>-1</birth_seconds72>
+	<death_year>-1</death_year>
+	<death_seconds72>-1</death_seconds72>
+	<associated_type>STANDARD</associated_type>
+	<entity_link>
+		<link_type>member</link_type>
+		<entity_id>12</entity_id>
+	</entity_link>
+	<entity_link>
+		<link_type>enemy</link_type>
+		<entity_id>3</entity_id>
+		<link_strength>40</link_strength>
+	</entity_link>
+	<hf_link>
+		<link_type>deity</link_type>
+		<hfid>1</hfid>
+		<link_strength>20</link_strength>
+	</hf_link>
+	<active_interaction>DEITY_CURSE_VAMPIRE_7</active_interaction>
+	<hf_skill>
+		<skill>MINING</skill>
+		<total_ip>700</total_ip>
+	</hf_skill>
+</historical_figure>
+<historical_figure>
+	<id>1</id>
+	<name>ama</name>
+	<race>HUMAN</race>
+	<caste>MALE</caste>
+	<birth_year>-250</birth_year>
+	<death_year>-1</death_year>
+	<associated_type>STANDARD</associated_type>
+	<deity/>
+	<sphere>caverns</sphere>
+	<sphere>mountains</sphere>
+</historical_figure>
+<historical_figure>
+	<id>2</id>
+	<race>DWARF</race>
+	<caste>MALE</caste>
+	<birth_year>5</birth_year>
+	<death_year>102</death_year>
+</historical_figure>
+</historical_figures>
+<historical_events>
+<historical_event>
+	<id>1</id>
+	<year>20</year>
+	<seconds72>-1</seconds72>
+	<type>hf died</type>
+	<hfid>2</hfid>
+	<slayer_hfid>0</slayer_hfid>
+	<slayer_race>DWARF</slayer_race>
+	<slayer_caste>FEMALE</slayer_caste>
+	<slayer_item_id>-1</slayer_item_id>
+	<site_id>7</site_id>
+	<subregion_id>-1</subregion_id>
+	<cause>vampire</cause>
+</historical_event>
+<historical_event>
+	<id>0</id>
+	<year>20</year>
+	<seconds72>1000</seconds72>
+	<type>add hf entity link</type>
+	<civ_id>12</civ_id>
+	<hfid>0</hfid>
+	<link>member</link>
+</historical_event>
+<historical_event>
+	<id>2</id>
+	<year>3</year>
+	<seconds72>500</seconds72>
+	<type>change hf state</type>
+	<hfid>0</hfid>
+	<state>settled</state>
+	<site_id>7</site_id>
+</historical_event>
+</historical_events>
+<historical_event_collections>
+<historical_event_collection>
+	<id>0</id>
+	<start_year>1</start_year>
+	<start_seconds72>-1</start_seconds72>
+	<end_year>-1</end_year>
+	<end_seconds72>-1</end_seconds72>
+	<eventcol>1</eventcol>
+	<event>2</event>
+	<type>war</type>
+	<name>the war of blood</name>
+	<aggressor_ent_id>12</aggressor_ent_id>
+</historical_event_collection>
+<historical_event_collection>
+	<id>1</id>
+	<start_year>20</start_year>
+	<end_year>20</end_year>
+	<event>1</event>
+	<type>battle</type>
+</historical_event_collection>
+</historical_event_collections>
+<entities>
+<entity>
+	<id>12</id>
+	<name>the fair axes</name>
+</entity>
+</entities>
+</df_world>
+"#;
+
+    #[test]
+    fn test_parse_historical_figures() {
+        let legends = read_test_export(LEGENDS).expect("Failed to parse legends export");
+        let raws = legends.to_raws(Path::new("region1-legends.xml"), &ParserOptions::default());
+        let figures = &legends.world.historical_figures;
+
+        assert_eq!(figures.len(), 3);
+        // The historical figures don't end up as (or inside of) raws
+        assert_eq!(raws.len(), 1);
+
+        let vampire = &figures[0];
+        assert_eq!(vampire.get_name(), "ngitha the lashes of dread");
+        assert_eq!(vampire.get_creature_identifier(), "DWARF");
+        assert_eq!(vampire.get_caste(), "FEMALE");
+        assert_eq!(vampire.get_birth_year(), Some(-112));
+        assert!(vampire.is_alive());
+        assert!(vampire.is_vampire());
+        assert!(!vampire.is_werebeast());
+        assert_eq!(vampire.get_linked_entity_ids("member"), vec![12]);
+        assert_eq!(vampire.get_entity_links()[1].link_strength, Some(40));
+        assert_eq!(
+            vampire.get_historical_figure_links()[0].historical_figure_id,
+            1
+        );
+        assert_eq!(vampire.get_skills()[0].skill, "MINING");
+        assert_eq!(vampire.get_skills()[0].total_ip, 700);
+
+        let deity = &figures[1];
+        assert!(deity.is_deity());
+        assert_eq!(deity.get_spheres(), ["caverns", "mountains"]);
+        assert!(deity.get_entity_links().is_empty());
 
-    for creature in creatures {
-        results.push(Box::new(creature.into_creature(&legend_metadata)));
+        assert_eq!(figures[2].get_death_year(), Some(102));
+        assert_eq!(
+            figures
+                .iter()
+                .filter(|figure| figure.get_creature_identifier() == "DWARF" && figure.is_vampire())
+                .count(),
+            1
+        );
     }
 
-    let legend_metadata = legends_metadata(input_path.as_ref(), ObjectType::Entity, options);
+    const LEGENDS_PLUS: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<regions>
+<region>
+	<id>0</id>
+	<name>the sea of whispers</name>
+	<type>Ocean</type>
+	<coords>0,0|0,1|1,0|</coords>
+	<evilness>neutral</evilness>
+</region>
+<region>
+	<id>1</id>
+	<name>the hills of gold</name>
+	<type>Hills</type>
+	<coords>4,5|4,6|</coords>
+</region>
+</regions>
+<underground_regions>
+<underground_region>
+	<id>0</id>
+	<type>cavern</type>
+	<depth>1</depth>
+	<coords>4,5|</coords>
+</underground_region>
+</underground_regions>
+<sites>
+<site>
+	<id>1</id>
+	<type>fortress</type>
+	<name>boltstone</name>
+	<coords>4,6</coords>
+	<rectangle>64,96:69,101</rectangle>
+	<civ_id>12</civ_id>
+	<cur_owner_id>14</cur_owner_id>
+	<structures>
+		<structure>
+			<local_id>0</local_id>
+			<type>temple</type>
+			<name>the temple of rings</name>
+			<entity_id>14</entity_id>
+			<worship_hfid>1</worship_hfid>
+		</structure>
+		<structure>
+			<local_id>1</local_id>
+			<type>tavern</type>
+			<name>the cozy mug</name>
+		</structure>
+	</structures>
+	<site_properties>
+		<site_property>
+			<id>0</id>
+			<type>house</type>
+			<owner_hfid>0</owner_hfid>
+		</site_property>
+	</site_properties>
+</site>
+<site>
+	<id>2</id>
+	<type>cave</type>
+	<name>the dark hole</name>
+	<coords>0,1</coords>
+</site>
+</sites>
+<world_constructions>
+<world_construction>
+	<id>0</id>
+	<name>the dusty road</name>
+	<type>road</type>
+	<coords>4,6|3,6|2,6</coords>
+</world_construction>
+</world_constructions>
+<historical_events>
+<historical_event>
+	<id>0</id>
+	<type>created_site</type>
+	<site>1</site>
+	<region>1</region>
+</historical_event>
+</historical_events>
+</df_world>
+"#;
+
+    #[test]
+    fn test_parse_legends_world() {
+        let world = read_test_export(LEGENDS_PLUS)
+            .expect("Failed to parse legends export")
+            .world;
+
+        // The `site` and `region` fields of the historical event aren't read as sites or regions
+        assert_eq!(world.sites.len(), 2);
+        assert_eq!(world.regions.len(), 2);
+        assert!(world.historical_figures.is_empty());
+
+        let ocean = &world.regions[0];
+        assert_eq!(ocean.get_region_type(), "Ocean");
+        assert_eq!(ocean.get_coordinates().len(), 3);
+        assert_eq!(ocean.get_evilness(), Some("neutral"));
+
+        assert_eq!(world.underground_regions[0].get_depth(), 1);
+        assert_eq!(world.underground_regions[0].get_region_type(), "cavern");
+
+        let fortress = world.get_site(1).expect("Site 1 should be parsed");
+        assert_eq!(fortress.get_name(), "boltstone");
+        assert_eq!(fortress.get_coordinates(), Some(Dimensions { x: 4, y: 6 }));
+        assert_eq!(
+            fortress.get_rectangle(),
+            Some((Dimensions { x: 64, y: 96 }, Dimensions { x: 69, y: 101 }))
+        );
+        assert_eq!(fortress.get_structures().len(), 2);
+        assert_eq!(fortress.get_structures()[0].structure_type, "temple");
+        assert_eq!(fortress.get_structures()[0].entity_id, Some(14));
+        assert_eq!(
+            fortress.get_structures()[0].worship_historical_figure_id,
+            Some(1)
+        );
+        assert_eq!(fortress.get_structures()[1].name, "the cozy mug");
+        assert_eq!(fortress.get_structures()[1].entity_id, None);
+        assert_eq!(
+            fortress.get_properties()[0].owner_historical_figure_id,
+            Some(0)
+        );
+        assert_eq!(world.get_sites_owned_by(12).len(), 1);
+        assert_eq!(world.get_sites_owned_by(14).len(), 1);
+        assert_eq!(
+            world
+                .get_region_of_site(fortress)
+                .map(ExportedRegion::get_name),
+            Some("the hills of gold")
+        );
 
-    for entity in entities {
-        results.push(Box::new(entity.into_entity(&legend_metadata)));
+        let roads = world.get_world_constructions_of_type("ROAD");
+        assert_eq!(roads.len(), 1);
+        assert_eq!(roads[0].get_coordinates().len(), 3);
     }
 
-    Ok(results)
+    #[test]
+    fn test_invalid_legends_export() {
+        let (valid, _) = LEGENDS.split_at(LEGENDS.find("</historical_figure>").unwrap_or_default());
+        let result = read_test_export(&format!("{valid}</historical_event>"));
+        assert!(matches!(result, Err(ParserError::InvalidLegendsExport(_))));
+    }
+
+    #[test]
+    fn test_timeline() {
+        let world = read_test_export(LEGENDS)
+            .expect("Failed to parse legends export")
+            .world;
+
+        assert_eq!(world.historical_events.len(), 3);
+        assert_eq!(world.historical_event_collections.len(), 2);
+
+        let death = world
+            .get_historical_event(1)
+            .expect("Event 1 should be parsed");
+        assert_eq!(death.get_event_type(), "hf died");
+        assert_eq!(death.get_year(), 20);
+        assert_eq!(death.get_seconds72(), None);
+        assert_eq!(death.get_historical_figure_ids(), [2, 0]);
+        assert_eq!(death.get_site_ids(), [7]);
+        assert_eq!(death.get_field("cause"), Some("vampire"));
+        assert_eq!(death.get_field("slayer_race"), Some("DWARF"));
+
+        let war = world
+            .get_event_collection(0)
+            .expect("Collection 0 should be parsed");
+        assert_eq!(war.get_name(), Some("the war of blood"));
+        assert_eq!(war.get_collection_type(), "war");
+        assert_eq!(war.get_end_year(), None);
+        assert_eq!(war.get_child_collection_ids(), [1]);
+        assert_eq!(war.get_field("aggressor_ent_id"), Some("12"));
+        // The events aren't exported in the order of their ids, so they aren't all where their id is
+        for id in 0..3 {
+            assert_eq!(
+                world
+                    .get_historical_event(id)
+                    .map(ExportedHistoricalEvent::get_id),
+                Some(id)
+            );
+        }
+        assert!(world.get_historical_event(3).is_none());
+        assert!(world.get_historical_event(-1).is_none());
+
+        let timeline = world.timeline();
+        let event_ids = |events: Vec<&ExportedHistoricalEvent>| {
+            events
+                .iter()
+                .map(|event| event.get_id())
+                .collect::<Vec<_>>()
+        };
+        // Chronological, where an unknown time within a year comes first
+        assert_eq!(event_ids(timeline.get_events()), [2, 1, 0]);
+        assert_eq!(
+            event_ids(timeline.get_events_for_historical_figure(0)),
+            [2, 1, 0]
+        );
+        assert_eq!(event_ids(timeline.get_events_for_historical_figure(2)), [1]);
+        assert_eq!(event_ids(timeline.get_events_for_site(7)), [2, 1]);
+        assert_eq!(event_ids(timeline.get_events_for_entity(12)), [0]);
+        assert_eq!(event_ids(timeline.get_events_between(10, 30)), [1, 0]);
+        assert_eq!(event_ids(timeline.get_events_for_collection(0)), [2, 1]);
+        assert_eq!(
+            timeline
+                .get_collections_for_event(1)
+                .iter()
+                .map(|collection| collection.get_collection_type())
+                .collect::<Vec<_>>(),
+            ["battle"]
+        );
+        assert_eq!(timeline.get_event(1), world.get_historical_event(1));
+        assert!(timeline.get_event(3).is_none());
+        assert_eq!(
+            timeline
+                .get_collection(1)
+                .map(ExportedEventCollection::get_collection_type),
+            Some("battle")
+        );
+        assert!(timeline.get_collection(2).is_none());
+    }
+
+    const MERGE_BASE: &str = r#"<?xml version="1.0" encoding='CP437'?>
+<df_world>
+<regions>
+<region>
+	<id>0</id>
+	<name>the hills of gold</name>
+	<type>Hills</type>
+</region>
+</regions>
+<sites>
+<site>
+	<id>1</id>
+	<type>fortress</type>
+	<name>boltstone</name>
+	<coords>4,6</coords>
+	<structures>
+		<structure>
+			<local_id>0</local_id>
+			<type>temple</type>
+			<name>the temple of rings</name>
+		</structure>
+	</structures>
+</site>
+</sites>
+<historical_figures>
+<historical_figure>
+	<id>0</id>
+	<name>ngitha</name>
+	<race>DWARF</race>
+	<caste>FEMALE</caste>
+	<birth_year>-112</birth_year>
+	<death_year>-1</death_year>
+</historical_figure>
+</historical_figures>
+<entities>
+<entity>
+	<id>12</id>
+	<name>the fair axes</name>
+</entity>
+</entities>
+</df_world>
+"#;
+
+    const MERGE_PLUS: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<creature_raw>
+<creature>
+	<creature_id>DWARF</creature_id>
+	<name_singular>dwarf</name_singular>
+	<name_plural>dwarves</name_plural>
+	<has_any_intelligent_learns/>
+</creature>
+</creature_raw>
+<regions>
+<region>
+	<id>0</id>
+	<coords>4,6|4,7|</coords>
+	<evilness>neutral</evilness>
+</region>
+</regions>
+<sites>
+<site>
+	<id>1</id>
+	<civ_id>12</civ_id>
+	<structures>
+		<structure>
+			<id>0</id>
+			<entity_id>14</entity_id>
+			<deity>0</deity>
+		</structure>
+	</structures>
+</site>
+</sites>
+<historical_figures>
+<historical_figure>
+	<id>0</id>
+	<sphere>caverns</sphere>
+</historical_figure>
+<historical_figure>
+	<id>5</id>
+	<race>HUMAN</race>
+</historical_figure>
+</historical_figures>
+<entities>
+<entity>
+	<id>12</id>
+	<race>dwarf</race>
+	<type>civilization</type>
+	<child>14</child>
+	<entity_position>
+		<id>0</id>
+		<name>king</name>
+	</entity_position>
+</entity>
+</entities>
+</df_world>
+"#;
+
+    #[test]
+    fn test_merge_legends_plus() {
+        let exports = TestExports::new();
+        let base_path = exports.write("region1-00250-01-01-legends.xml", MERGE_BASE);
+        let plus_path = exports.write("region1-00250-01-01-legends_plus.xml", MERGE_PLUS);
+        let other_path = exports.dir.path().join("region2-00100-01-01-legends.xml");
+
+        // The plus export is put after the base export of its world
+        let groups =
+            super::super::group_legends_exports_by_world(&[&plus_path, &other_path, &base_path]);
+        assert_eq!(
+            groups,
+            vec![
+                vec![base_path.clone(), plus_path.clone()],
+                vec![other_path.clone()]
+            ]
+        );
+
+        let legends =
+            read_merged_legends_exports(&groups[0]).expect("Failed to parse legends exports");
+
+        assert_eq!(legends.world.entities.len(), 1);
+        assert_eq!(legends.creatures.len(), 1);
+        let raws = legends.to_raws(&base_path, &ParserOptions::default());
+        let identifiers: Vec<&str> = raws.iter().map(|raw| raw.get_identifier()).collect();
+        assert_eq!(identifiers, ["DWARF", "dwarf-civilization12"]);
+    }
+
+    #[test]
+    fn test_merge_legends_plus_world() {
+        let exports = TestExports::new();
+        let base_path = exports.write("region1-legends.xml", MERGE_BASE);
+        let plus_path = exports.write("region1-legends_plus.xml", MERGE_PLUS);
+        let world = parse_merged_legends_world(&[&base_path, &plus_path])
+            .expect("Failed to parse legends exports");
+
+        assert_eq!(world.regions.len(), 1);
+        let region = &world.regions[0];
+        assert_eq!(region.get_name(), "the hills of gold");
+        assert_eq!(region.get_coordinates().len(), 2);
+        assert_eq!(region.get_evilness(), Some("neutral"));
+
+        assert_eq!(world.sites.len(), 1);
+        let site = &world.sites[0];
+        assert_eq!(site.get_name(), "boltstone");
+        assert_eq!(site.get_civ_id(), Some(12));
+        assert_eq!(site.get_structures().len(), 1);
+        assert_eq!(site.get_structures()[0].name, "the temple of rings");
+        assert_eq!(site.get_structures()[0].entity_id, Some(14));
+        assert_eq!(
+            site.get_structures()[0].worship_historical_figure_id,
+            Some(0)
+        );
+        assert_eq!(
+            world.get_region_of_site(site).map(ExportedRegion::get_name),
+            Some("the hills of gold")
+        );
+
+        assert_eq!(world.historical_figures.len(), 2);
+        let figure = &world.historical_figures[0];
+        assert_eq!(figure.get_name(), "ngitha");
+        assert_eq!(figure.get_birth_year(), Some(-112));
+        assert_eq!(figure.get_spheres(), ["caverns"]);
+        assert_eq!(world.historical_figures[1].get_race(), "HUMAN");
+    }
+
+    const ENTITIES_PLUS: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<entities>
+<entity>
+	<id>12</id>
+	<race>dwarf</race>
+	<type>civilization</type>
+	<worship_id>0</worship_id>
+	<weapon>axe</weapon>
+	<weapon>crossbow</weapon>
+	<profession>miner</profession>
+	<entity_link>
+		<type>CHILD</type>
+		<target>14</target>
+		<strength>100</strength>
+	</entity_link>
+	<entity_position>
+		<id>0</id>
+		<name_male>king</name_male>
+		<name_female>queen</name_female>
+		<spouse_male>king consort</spouse_male>
+	</entity_position>
+	<entity_position_assignment>
+		<id>0</id>
+		<histfig>3</histfig>
+		<position_id>0</position_id>
+		<squad_id>-1</squad_id>
+	</entity_position_assignment>
+	<histfig_id>3</histfig_id>
+	<occasion>
+		<id>0</id>
+		<name>the festival of rings</name>
+		<event>7</event>
+		<schedule>
+			<id>0</id>
+			<type>procession</type>
+			<reference>-1</reference>
+			<feature>
+				<type>banners</type>
+				<reference>-1</reference>
+			</feature>
+		</schedule>
+		<schedule>
+			<id>1</id>
+			<type>poetry_recital</type>
+			<reference>2</reference>
+		</schedule>
+	</occasion>
+</entity>
+<entity>
+	<id>14</id>
+	<race>dwarf</race>
+	<type>sitegovernment</type>
+	<entity_link>
+		<type>PARENT</type>
+		<target>12</target>
+		<strength>100</strength>
+	</entity_link>
+</entity>
+</entities>
+<entity_populations>
+<entity_population>
+	<id>0</id>
+	<race>dwarf:40</race>
+	<race>goblin:2</race>
+	<civ_id>12</civ_id>
+</entity_population>
+</entity_populations>
+</df_world>
+"#;
+
+    #[test]
+    fn test_parse_legends_entities() {
+        let legends = read_test_export(ENTITIES_PLUS).expect("Failed to parse legends export");
+        let world = &legends.world;
+
+        // Only the entities are kept when reading the raws
+        let raws = read_legends_raws(LegendsReader::from_reader(ENTITIES_PLUS.as_bytes()))
+            .expect("Failed to parse legends export");
+        assert_eq!(raws.world.entities.len(), 2);
+        assert!(raws.world.entity_populations.is_empty());
+
+        assert_eq!(world.entities.len(), 2);
+        let civ = world.get_entity(12).expect("Expected the civilization");
+        assert!(civ.is_civilization());
+        assert_eq!(civ.get_worship_ids(), [0]);
+        assert_eq!(civ.get_weapons(), ["axe", "crossbow"]);
+        assert_eq!(civ.get_professions(), ["miner"]);
+        assert_eq!(civ.get_historical_figure_ids(), [3]);
+
+        assert_eq!(civ.get_positions().len(), 1);
+        let position = &civ.get_positions()[0];
+        assert_eq!(position.name_female.as_deref(), Some("queen"));
+        assert_eq!(position.spouse_male.as_deref(), Some("king consort"));
+        assert_eq!(civ.get_assignments()[0].squad_id, None);
+        assert_eq!(civ.get_position_holder(0), Some(3));
+
+        // The features of the schedules are nested deeper, and don't overwrite the schedules
+        assert_eq!(civ.get_occasions().len(), 1);
+        let occasion = &civ.get_occasions()[0];
+        assert_eq!(occasion.name, "the festival of rings");
+        assert_eq!(occasion.event_id, Some(7));
+        assert_eq!(occasion.schedules.len(), 2);
+        assert_eq!(occasion.schedules[0].schedule_type, "procession");
+        assert_eq!(occasion.schedules[0].reference, None);
+        assert_eq!(occasion.schedules[1].reference, Some(2));
+
+        let government = world.get_entity(14).expect("Expected the site government");
+        assert_eq!(world.get_child_entities(civ), [government]);
+        assert_eq!(world.get_parent_entities(government), [civ]);
+
+        let populations = world.get_populations_of_entity(12);
+        assert_eq!(populations.len(), 1);
+        assert_eq!(populations[0].get_population(), 42);
+        assert_eq!(populations[0].get_races()[1], ("goblin".to_string(), 2));
+
+        // Every entity of the dwarves resolves to the entity raw of the dwarves
+        let metadata = RawMetadata::default();
+        let mut mountain = Entity::new("MOUNTAIN", &metadata);
+        mountain.parse_tag("CREATURE", "DWARF");
+        let raws: Vec<Box<dyn RawObject>> =
+            vec![Box::new(Entity::new("EVIL", &metadata)), Box::new(mountain)];
+        for entity in &world.entities {
+            assert_eq!(
+                entity.find_entity(&raws).map(RawObject::get_identifier),
+                Some("MOUNTAIN")
+            );
+        }
+    }
+
+    const CULTURE_BASE: &str = r#"<?xml version="1.0" encoding='CP437'?>
+<df_world>
+<artifacts>
+<artifact>
+	<id>0</id>
+	<name>the axe of ages</name>
+	<site_id>1</site_id>
+	<holder_hfid>3</holder_hfid>
+	<item>
+		<name_string>axe of ages</name_string>
+	</item>
+</artifact>
+<artifact>
+	<id>1</id>
+	<name>the songs of stone</name>
+	<item>
+		<name_string>the songs of stone</name_string>
+		<page_number>12</page_number>
+		<page_written_content_id>5</page_written_content_id>
+	</item>
+</artifact>
+</artifacts>
+<poetic_forms>
+<poetic_form>
+	<id>2</id>
+	<description>The stanza is made of eight lines</description>
+</poetic_form>
+</poetic_forms>
+<written_contents>
+<written_content>
+	<id>5</id>
+	<title>the songs of stone</title>
+	<author_hfid>3</author_hfid>
+	<form>poem</form>
+	<form_id>2</form_id>
+	<style>meandering:1</style>
+</written_content>
+</written_contents>
+</df_world>
+"#;
+
+    const CULTURE_PLUS: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<artifacts>
+<artifact>
+	<id>0</id>
+	<item_type>weapon</item_type>
+	<item_subtype>battle axe</item_subtype>
+	<mat>pig iron</mat>
+</artifact>
+</artifacts>
+<poetic_forms>
+<poetic_form>
+	<id>2</id>
+	<name>the verses of rock</name>
+</poetic_form>
+</poetic_forms>
+<musical_forms>
+<musical_form>
+	<id>0</id>
+	<name>the anthem of hammers</name>
+</musical_form>
+</musical_forms>
+<written_contents>
+<written_content>
+	<id>5</id>
+	<type>Poem</type>
+	<style>Meandering</style>
+	<style>Tender</style>
+	<reference>
+		<type>HISTORICAL_FIGURE</type>
+		<id>3</id>
+	</reference>
+</written_content>
+</written_contents>
+</df_world>
+"#;
+
+    #[test]
+    fn test_parse_legends_culture() {
+        let exports = TestExports::new();
+        let base_path = exports.write("region1-legends.xml", CULTURE_BASE);
+        let plus_path = exports.write("region1-legends_plus.xml", CULTURE_PLUS);
+        let world = parse_merged_legends_world(&[&base_path, &plus_path])
+            .expect("Failed to parse legends exports");
+
+        assert_eq!(world.artifacts.len(), 2);
+        let axe = world.get_artifact(0).expect("Expected the axe");
+        assert_eq!(axe.get_item_name(), Some("axe of ages"));
+        assert_eq!(axe.get_item_subtype(), Some("battle axe"));
+        assert_eq!(axe.get_material_identifier().as_deref(), Some("PIG_IRON"));
+        assert_eq!(world.get_artifacts_held_by(3), [axe]);
+        assert_eq!(world.get_artifacts_at_site(1), [axe]);
+
+        let metadata = RawMetadata::default();
+        let raws: Vec<Box<dyn RawObject>> = vec![Box::new(Inorganic::new("PIG_IRON", &metadata))];
+        assert_eq!(
+            axe.find_inorganic(&raws).map(RawObject::get_identifier),
+            Some("PIG_IRON")
+        );
+
+        let book = world.get_artifact(1).expect("Expected the book");
+        assert_eq!(book.get_page_count(), Some(12));
+        let contents = world.get_written_contents_of_artifact(book);
+        assert_eq!(contents.len(), 1);
+        let poem = contents[0];
+        assert_eq!(world.get_written_contents_by(3), [poem]);
+        assert_eq!(poem.get_styles(), ["meandering", "tender"]);
+        assert_eq!(poem.get_references().len(), 1);
+        assert_eq!(poem.get_references()[0].reference_type, "HISTORICAL_FIGURE");
+
+        let form = world
+            .get_form_of_written_content(poem)
+            .expect("Expected the poetic form");
+        assert_eq!(form.get_name(), Some("the verses of rock"));
+        assert_eq!(
+            form.get_description(),
+            Some("The stanza is made of eight lines")
+        );
+        assert_eq!(world.musical_forms.len(), 1);
+    }
 }
@@ -0,0 +1,480 @@
+//! A streaming reader for legends exports, which yields the records of an export as they are read.
+//!
+//! Legends exports of large worlds can be several gigabytes, so the file is read through a buffer instead of
+//! into memory, and only the record being read is kept.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use quick_xml::{Reader, escape::resolve_predefined_entity, events::Event};
+
+use crate::{
+    ParserError,
+    constants::DF_ENCODING,
+    legends_export::{
+        ExportedArtifact, ExportedCreature, ExportedCulturalForm, ExportedEntity,
+        ExportedEntityPopulation, ExportedEventCollection, ExportedHistoricalEvent,
+        ExportedHistoricalFigure, ExportedRegion, ExportedSite, ExportedUndergroundRegion,
+        ExportedWorldConstruction, ExportedWrittenContent, exports::ExportSection,
+    },
+    utilities::try_get_file,
+};
+
+/// A record read from a legends export
+#[derive(Debug)]
+pub enum LegendsRecord {
+    /// A creature (from the `creature_raw` list of a legends-plus export)
+    Creature(ExportedCreature),
+    /// An entity, e.g. a civilization
+    Entity(ExportedEntity),
+    /// The members of a civilization which aren't historical figures
+    EntityPopulation(ExportedEntityPopulation),
+    /// A historical figure
+    HistoricalFigure(ExportedHistoricalFigure),
+    /// A site, e.g. a fortress
+    Site(ExportedSite),
+    /// A region of the surface of the world
+    Region(ExportedRegion),
+    /// A region below the surface of the world
+    UndergroundRegion(ExportedUndergroundRegion),
+    /// A road, bridge, tunnel or wall
+    WorldConstruction(ExportedWorldConstruction),
+    /// A historical event
+    HistoricalEvent(ExportedHistoricalEvent),
+    /// A collection of historical events, e.g. a war
+    EventCollection(ExportedEventCollection),
+    /// An artifact, e.g. a named weapon or a book
+    Artifact(ExportedArtifact),
+    /// A written work, e.g. a poem
+    WrittenContent(ExportedWrittenContent),
+    /// A poetic form
+    PoeticForm(ExportedCulturalForm),
+    /// A musical form
+    MusicalForm(ExportedCulturalForm),
+    /// A dance form
+    DanceForm(ExportedCulturalForm),
+}
+
+/// The lists of records in a legends export, which the record elements are read from.
+///
+/// Some of the record names are also used for fields elsewhere (e.g. `site` in the legends-plus historical
+/// events), so records are only read from inside their list.
+const RECORD_LISTS: [(&[u8], &[u8]); 15] = [
+    (b"creature_raw", b"creature"),
+    (b"entities", b"entity"),
+    (b"entity_populations", b"entity_population"),
+    (b"historical_figures", b"historical_figure"),
+    (b"sites", b"site"),
+    (b"regions", b"region"),
+    (b"underground_regions", b"underground_region"),
+    (b"world_constructions", b"world_construction"),
+    (b"historical_events", b"historical_event"),
+    (
+        b"historical_event_collections",
+        b"historical_event_collection",
+    ),
+    (b"artifacts", b"artifact"),
+    (b"written_contents", b"written_content"),
+    (b"poetic_forms", b"poetic_form"),
+    (b"musical_forms", b"musical_form"),
+    (b"dance_forms", b"dance_form"),
+];
+
+/// The reader used for legends export files, decoding them into UTF-8 as they are read
+pub type LegendsFileReader = BufReader<DecodeReaderBytes<BufReader<File>, Vec<u8>>>;
+
+/// A streaming reader of the records of a legends export.
+///
+/// Iterating yields each record as soon as its element is closed. A malformed export yields an error, after
+/// which the iteration ends.
+pub struct LegendsReader<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// The text of the element being read
+    text: String,
+    /// The list (e.g. `sites`) being read
+    list_tag: Vec<u8>,
+    /// The record being read
+    current: Option<LegendsRecord>,
+    /// How many elements are open
+    depth: usize,
+    /// The depth of the element of the record being read
+    record_depth: usize,
+    /// The nested elements of the record being read (e.g. an `hf_link`), with their depths
+    sections: Vec<(ExportSection, usize)>,
+    finished: bool,
+}
+
+impl LegendsReader<LegendsFileReader> {
+    /// Open a legends export file for reading.
+    ///
+    /// Exports which declare themselves as UTF-8 (the legends-plus exports from `DFHack`) are read as UTF-8,
+    /// and others (e.g. the `CP437` exports from Dwarf Fortress) with [`DF_ENCODING`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParserError` if the file can't be opened or read.
+    pub fn open<P: AsRef<Path>>(input_path: &P) -> Result<Self, ParserError> {
+        let mut file_reader = BufReader::new(try_get_file(input_path)?);
+        let encoding = if declares_utf8(file_reader.fill_buf()?) {
+            encoding_rs::UTF_8
+        } else {
+            *DF_ENCODING
+        };
+        let decoding_reader = DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .build(file_reader);
+
+        Ok(Self::from_reader(BufReader::new(decoding_reader)))
+    }
+}
+
+impl<R: BufRead> LegendsReader<R> {
+    /// Read a legends export from a reader which is already decoded into UTF-8.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            text: String::new(),
+            list_tag: Vec::new(),
+            current: None,
+            depth: 0,
+            record_depth: 0,
+            sections: Vec::new(),
+            finished: false,
+        }
+    }
+    /// Start reading an element
+    fn start(&mut self, name: &[u8]) {
+        self.depth += 1;
+        self.text.clear();
+
+        let parent_section = self.section();
+        let Some(current) = &mut self.current else {
+            if RECORD_LISTS.iter().any(|(list, _)| *list == name) {
+                self.list_tag = name.to_vec();
+            } else if RECORD_LISTS
+                .iter()
+                .any(|(list, record)| *list == self.list_tag && *record == name)
+            {
+                self.current = Some(new_record(name));
+                self.record_depth = self.depth;
+            }
+            return;
+        };
+
+        let section = match name {
+            b"entity_link" => ExportSection::EntityLink,
+            b"hf_link" => ExportSection::HistoricalFigureLink,
+            b"hf_skill" => ExportSection::Skill,
+            b"structure" => ExportSection::Structure,
+            b"site_property" => ExportSection::SiteProperty,
+            b"entity_position" => ExportSection::EntityPosition,
+            b"entity_position_assignment" => ExportSection::EntityPositionAssignment,
+            b"occasion" => ExportSection::Occasion,
+            b"item" if matches!(current, LegendsRecord::Artifact(_)) => ExportSection::ArtifactItem,
+            b"reference" if matches!(current, LegendsRecord::WrittenContent(_)) => {
+                ExportSection::WrittenContentReference
+            }
+            // Only the schedules of an occasion are read
+            b"schedule" if parent_section == ExportSection::Occasion => {
+                ExportSection::OccasionSchedule
+            }
+            _ => return,
+        };
+        match current {
+            LegendsRecord::Entity(entity) => entity.start_section(section),
+            LegendsRecord::HistoricalFigure(figure) => figure.start_section(section),
+            LegendsRecord::Site(site) => site.start_section(section),
+            LegendsRecord::WrittenContent(content) => content.start_section(section),
+            // The values of the item of an artifact are kept with the artifact
+            LegendsRecord::Artifact(_) => (),
+            _ => return,
+        }
+        self.sections.push((section, self.depth));
+    }
+    /// The innermost nested element of the record being read
+    fn section(&self) -> ExportSection {
+        self.sections
+            .last()
+            .map_or(ExportSection::None, |(section, _)| *section)
+    }
+    /// Finish reading an element, returning the record if it was the element of one
+    fn end(&mut self, name: &[u8]) -> Option<LegendsRecord> {
+        let depth = self.depth;
+        self.depth = self.depth.saturating_sub(1);
+        let text = std::mem::take(&mut self.text);
+
+        if self.current.is_none() {
+            if *self.list_tag == *name {
+                self.list_tag.clear();
+            }
+            return None;
+        }
+        if depth == self.record_depth {
+            self.sections.clear();
+            return match self.current.take() {
+                Some(LegendsRecord::Creature(creature)) if creature.is_empty() => None,
+                record => record,
+            };
+        }
+        if self
+            .sections
+            .last()
+            .is_some_and(|(_, section_depth)| *section_depth == depth)
+        {
+            self.sections.pop();
+            return None;
+        }
+
+        // Only the direct children of the record (or of the section being read) describe it, the other
+        // elements are details which aren't kept (e.g. the features of the schedule of an occasion)
+        let field_depth = self
+            .sections
+            .last()
+            .map_or(self.record_depth, |(_, section_depth)| *section_depth)
+            + 1;
+        let value = text.trim();
+        if depth != field_depth || value.is_empty() {
+            return None;
+        }
+        let field = String::from_utf8_lossy(name);
+        let section = self.section();
+        match &mut self.current {
+            Some(LegendsRecord::Creature(creature)) => match name {
+                b"creature_id" => creature.set_creature_id(value),
+                b"name_singular" => creature.set_name_singular(value),
+                b"name_plural" => creature.set_name_plural(value),
+                _ => (),
+            },
+            Some(LegendsRecord::Entity(entity)) => entity.read_value(section, &field, value),
+            Some(LegendsRecord::EntityPopulation(population)) => {
+                population.read_value(&field, value);
+            }
+            Some(LegendsRecord::HistoricalFigure(figure)) => {
+                figure.read_value(section, &field, value);
+            }
+            Some(LegendsRecord::Site(site)) => site.read_value(section, &field, value),
+            Some(LegendsRecord::Region(region)) => region.read_value(&field, value),
+            Some(LegendsRecord::UndergroundRegion(region)) => region.read_value(&field, value),
+            Some(LegendsRecord::WorldConstruction(construction)) => {
+                construction.read_value(&field, value);
+            }
+            Some(LegendsRecord::HistoricalEvent(event)) => event.read_value(&field, value),
+            Some(LegendsRecord::EventCollection(collection)) => {
+                collection.read_value(&field, value);
+            }
+            Some(LegendsRecord::Artifact(artifact)) => artifact.read_value(section, &field, value),
+            Some(LegendsRecord::WrittenContent(content)) => {
+                content.read_value(section, &field, value);
+            }
+            Some(
+                LegendsRecord::PoeticForm(form)
+                | LegendsRecord::MusicalForm(form)
+                | LegendsRecord::DanceForm(form),
+            ) => form.read_value(&field, value),
+            None => (),
+        }
+        None
+    }
+    /// Read an empty element, which is a flag of the record being read
+    fn empty(&mut self, name: &[u8]) {
+        if self.depth != self.record_depth {
+            return;
+        }
+        let flag = String::from_utf8_lossy(name);
+        match &mut self.current {
+            Some(LegendsRecord::Creature(creature)) => creature.add_tag(&flag),
+            Some(LegendsRecord::HistoricalFigure(figure)) => figure.add_flag(&flag),
+            Some(LegendsRecord::HistoricalEvent(event)) => event.add_flag(&flag),
+            _ => (),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for LegendsReader<R> {
+    type Item = Result<LegendsRecord, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut buf = std::mem::take(&mut self.buf);
+
+        let result = loop {
+            buf.clear();
+            match self.reader.read_event_into(&mut buf) {
+                Err(e) => {
+                    break Some(Err(ParserError::InvalidLegendsExport(format!(
+                        "Error at position {}: {e:?}",
+                        self.reader.error_position()
+                    ))));
+                }
+                // An export cut off while it was written ends with elements still open
+                Ok(Event::Eof) if self.depth > 0 => {
+                    break Some(Err(ParserError::InvalidLegendsExport(format!(
+                        "Unexpected end of file with {} elements open",
+                        self.depth
+                    ))));
+                }
+                Ok(Event::Eof) => break None,
+                Ok(Event::Start(e)) => self.start(e.name().as_ref()),
+                Ok(Event::Empty(e)) => self.empty(e.name().as_ref()),
+                Ok(Event::Text(e)) => {
+                    if self.current.is_some() {
+                        self.text.push_str(&e.decode().unwrap_or_default());
+                    }
+                }
+                Ok(Event::GeneralRef(e)) => {
+                    if self.current.is_some() {
+                        let name = e.decode().unwrap_or_default();
+                        if let Ok(Some(character)) = e.resolve_char_ref() {
+                            self.text.push(character);
+                        } else if let Some(entity) = resolve_predefined_entity(&name) {
+                            self.text.push_str(entity);
+                        }
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    if let Some(record) = self.end(e.name().as_ref()) {
+                        break Some(Ok(record));
+                    }
+                }
+                // There are several other `Event`s we do not consider here
+                Ok(_) => (),
+            }
+        };
+
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        // Keep the buffer (without what was read into it) to reuse its allocation
+        buf.clear();
+        self.buf = buf;
+        result
+    }
+}
+
+/// Create an empty record for the element of a record
+fn new_record(name: &[u8]) -> LegendsRecord {
+    match name {
+        b"creature" => LegendsRecord::Creature(ExportedCreature::default()),
+        b"entity" => LegendsRecord::Entity(ExportedEntity::default()),
+        b"entity_population" => {
+            LegendsRecord::EntityPopulation(ExportedEntityPopulation::default())
+        }
+        b"historical_figure" => {
+            LegendsRecord::HistoricalFigure(ExportedHistoricalFigure::default())
+        }
+        b"site" => LegendsRecord::Site(ExportedSite::default()),
+        b"region" => LegendsRecord::Region(ExportedRegion::default()),
+        b"underground_region" => {
+            LegendsRecord::UndergroundRegion(ExportedUndergroundRegion::default())
+        }
+        b"world_construction" => {
+            LegendsRecord::WorldConstruction(ExportedWorldConstruction::default())
+        }
+        b"historical_event" => LegendsRecord::HistoricalEvent(ExportedHistoricalEvent::default()),
+        b"historical_event_collection" => {
+            LegendsRecord::EventCollection(ExportedEventCollection::default())
+        }
+        b"artifact" => LegendsRecord::Artifact(ExportedArtifact::default()),
+        b"written_content" => LegendsRecord::WrittenContent(ExportedWrittenContent::default()),
+        b"poetic_form" => LegendsRecord::PoeticForm(ExportedCulturalForm::default()),
+        b"musical_form" => LegendsRecord::MusicalForm(ExportedCulturalForm::default()),
+        _ => LegendsRecord::DanceForm(ExportedCulturalForm::default()),
+    }
+}
+
+/// Whether the XML declaration at the start of a file declares it as UTF-8
+fn declares_utf8(start: &[u8]) -> bool {
+    let declaration_end = start
+        .iter()
+        .position(|byte| *byte == b'>')
+        .unwrap_or(start.len());
+    let declaration = String::from_utf8_lossy(&start[..declaration_end]).to_lowercase();
+    declaration.contains("encoding=\"utf-8\"") || declaration.contains("encoding='utf-8'")
+}
+
+/// Read the records of a legends export file one at a time, calling a function with each as it is read.
+///
+/// Only the record being read is kept in memory, so this works for exports of any size.
+///
+/// # Arguments
+///
+/// * `input_path` - The path to the legends export file.
+/// * `callback` - The function to call with each record.
+///
+/// # Errors
+///
+/// Returns a `ParserError` if the file can't be read or isn't valid XML. The records before the error have
+/// already been passed to the callback.
+pub fn for_each_legends_record<P: AsRef<Path>, F: FnMut(LegendsRecord)>(
+    input_path: &P,
+    mut callback: F,
+) -> Result<(), ParserError> {
+    for record in LegendsReader::open(input_path)? {
+        callback(record?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A legends export as Dwarf Fortress writes it: declared as `CP437` with a name which isn't valid
+    /// UTF-8, and escaped characters. It is decoded with [`DF_ENCODING`] (Latin-1, like the raw files), so
+    /// the `0xE9` in the name is read as `é`
+    const LEGENDS: &[u8] = b"<?xml version=\"1.0\" encoding='CP437'?>
+<df_world>
+<regions>
+<region><id>0</id><name>the jungle of caf\xe9s</name><type>Jungle</type></region>
+</regions>
+<sites>
+<site>
+\t<id>1</id>
+\t<type>fortress</type>
+\t<name>tom &amp; jerry&apos;s &#65;bbey</name>
+\t<structures><structure><local_id>0</local_id><type>temple</type></structure></structures>
+</site>
+</sites>
+<historical_figures>
+<historical_figure><id>2</id><name>urist</name><deity/>
+";
+
+    #[test]
+    fn test_stream_legends_records() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let path = temp_dir.path().join("legends.xml");
+        std::fs::write(&path, LEGENDS).expect("Failed to write test legends export");
+        let mut reader = LegendsReader::open(&path).expect("Failed to open legends export");
+
+        let Some(Ok(LegendsRecord::Region(region))) = reader.next() else {
+            panic!("Expected a region");
+        };
+        assert_eq!(region.get_name(), "the jungle of caf\u{e9}s");
+        assert_eq!(region.get_region_type(), "Jungle");
+
+        let Some(Ok(LegendsRecord::Site(site))) = reader.next() else {
+            panic!("Expected a site");
+        };
+        assert_eq!(site.get_name(), "tom & jerry's Abbey");
+        assert_eq!(site.get_structures().len(), 1);
+        assert_eq!(site.get_structures()[0].structure_type, "temple");
+
+        // The export is cut off inside the historical figure
+        assert!(matches!(
+            reader.next(),
+            Some(Err(ParserError::InvalidLegendsExport(_)))
+        ));
+        assert!(reader.next().is_none());
+
+        let mut records = 0;
+        let result = for_each_legends_record(&path, |_| records += 1);
+        assert!(result.is_err());
+        assert_eq!(records, 2);
+    }
+}
@@ -0,0 +1,187 @@
+//! An index over the historical events of a [`LegendsWorld`], to query the timeline of a historical figure,
+//! entity or site.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::legends_export::{ExportedEventCollection, ExportedHistoricalEvent, LegendsWorld};
+
+/// An index of the historical events of a world by the historical figures, entities and sites involved.
+///
+/// Every query returns the events in chronological order.
+pub struct Timeline<'a> {
+    world: &'a LegendsWorld,
+    /// The indices of the events of the world, in chronological order
+    chronological: Vec<usize>,
+    /// The index of each event by its id
+    events_by_id: HashMap<i32, usize>,
+    /// The index of each collection by its id
+    collections_by_id: HashMap<i32, usize>,
+    by_historical_figure: HashMap<i32, Vec<usize>>,
+    by_entity: HashMap<i32, Vec<usize>>,
+    by_site: HashMap<i32, Vec<usize>>,
+    /// The indices of the collections each event id is directly in
+    collections_by_event: HashMap<i32, Vec<usize>>,
+}
+
+impl<'a> Timeline<'a> {
+    /// Index the historical events and event collections of a world
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to index
+    #[must_use]
+    pub fn new(world: &'a LegendsWorld) -> Self {
+        let mut chronological: Vec<usize> = (0..world.historical_events.len()).collect();
+        chronological.sort_by_key(|&index| world.historical_events[index].chronological_key());
+
+        let events_by_id: HashMap<i32, usize> = world
+            .historical_events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| (event.get_id(), index))
+            .collect();
+        let collections_by_id: HashMap<i32, usize> = world
+            .historical_event_collections
+            .iter()
+            .enumerate()
+            .map(|(index, collection)| (collection.get_id(), index))
+            .collect();
+
+        let mut by_historical_figure: HashMap<i32, Vec<usize>> = HashMap::new();
+        let mut by_entity: HashMap<i32, Vec<usize>> = HashMap::new();
+        let mut by_site: HashMap<i32, Vec<usize>> = HashMap::new();
+        for &index in &chronological {
+            let event = &world.historical_events[index];
+            for id in event.get_historical_figure_ids() {
+                by_historical_figure.entry(*id).or_default().push(index);
+            }
+            for id in event.get_entity_ids() {
+                by_entity.entry(*id).or_default().push(index);
+            }
+            for id in event.get_site_ids() {
+                by_site.entry(*id).or_default().push(index);
+            }
+        }
+
+        let mut collections_by_event: HashMap<i32, Vec<usize>> = HashMap::new();
+        for (index, collection) in world.historical_event_collections.iter().enumerate() {
+            for id in collection.get_event_ids() {
+                collections_by_event.entry(*id).or_default().push(index);
+            }
+        }
+
+        Self {
+            world,
+            chronological,
+            events_by_id,
+            collections_by_id,
+            by_historical_figure,
+            by_entity,
+            by_site,
+            collections_by_event,
+        }
+    }
+    /// The indexed world
+    #[must_use]
+    pub const fn get_world(&self) -> &'a LegendsWorld {
+        self.world
+    }
+    /// Get a historical event by its id
+    #[must_use]
+    pub fn get_event(&self, id: i32) -> Option<&'a ExportedHistoricalEvent> {
+        self.events_by_id
+            .get(&id)
+            .map(|&index| &self.world.historical_events[index])
+    }
+    /// Get a collection of historical events by its id
+    #[must_use]
+    pub fn get_collection(&self, id: i32) -> Option<&'a ExportedEventCollection> {
+        self.collections_by_id
+            .get(&id)
+            .map(|&index| &self.world.historical_event_collections[index])
+    }
+    /// All the events of the world
+    #[must_use]
+    pub fn get_events(&self) -> Vec<&'a ExportedHistoricalEvent> {
+        self.resolve(&self.chronological)
+    }
+    /// The events a historical figure was involved in
+    #[must_use]
+    pub fn get_events_for_historical_figure(&self, id: i32) -> Vec<&'a ExportedHistoricalEvent> {
+        self.by_historical_figure
+            .get(&id)
+            .map_or_else(Vec::new, |indices| self.resolve(indices))
+    }
+    /// The events an entity was involved in
+    #[must_use]
+    pub fn get_events_for_entity(&self, id: i32) -> Vec<&'a ExportedHistoricalEvent> {
+        self.by_entity
+            .get(&id)
+            .map_or_else(Vec::new, |indices| self.resolve(indices))
+    }
+    /// The events which happened at (or involved) a site
+    #[must_use]
+    pub fn get_events_for_site(&self, id: i32) -> Vec<&'a ExportedHistoricalEvent> {
+        self.by_site
+            .get(&id)
+            .map_or_else(Vec::new, |indices| self.resolve(indices))
+    }
+    /// The events which happened between two years (inclusive)
+    #[must_use]
+    pub fn get_events_between(
+        &self,
+        from_year: i32,
+        to_year: i32,
+    ) -> Vec<&'a ExportedHistoricalEvent> {
+        self.get_events()
+            .into_iter()
+            .filter(|event| (from_year..=to_year).contains(&event.get_year()))
+            .collect()
+    }
+    /// The collections an event is directly in, e.g. the battle (but not the war) it was part of
+    #[must_use]
+    pub fn get_collections_for_event(&self, event_id: i32) -> Vec<&'a ExportedEventCollection> {
+        self.collections_by_event
+            .get(&event_id)
+            .map_or_else(Vec::new, |indices| {
+                indices
+                    .iter()
+                    .map(|&index| &self.world.historical_event_collections[index])
+                    .collect()
+            })
+    }
+    /// The events of a collection, including those of the collections inside it, e.g. every event of
+    /// every battle of a war
+    #[must_use]
+    pub fn get_events_for_collection(
+        &self,
+        collection_id: i32,
+    ) -> Vec<&'a ExportedHistoricalEvent> {
+        let mut event_ids: HashSet<i32> = HashSet::new();
+        let mut pending = vec![collection_id];
+        let mut visited: HashSet<i32> = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(collection) = self.get_collection(id) {
+                event_ids.extend(collection.get_event_ids());
+                pending.extend(collection.get_child_collection_ids());
+            }
+        }
+
+        let mut indices: Vec<usize> = event_ids
+            .iter()
+            .filter_map(|id| self.events_by_id.get(id).copied())
+            .collect();
+        indices.sort_by_key(|&index| self.world.historical_events[index].chronological_key());
+        self.resolve(&indices)
+    }
+    /// Get the events at the indices
+    fn resolve(&self, indices: &[usize]) -> Vec<&'a ExportedHistoricalEvent> {
+        indices
+            .iter()
+            .map(|&index| &self.world.historical_events[index])
+            .collect()
+    }
+}
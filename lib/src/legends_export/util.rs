@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use crate::{
-    ModuleInfo,
+    Dimensions, ModuleInfo,
     metadata::{ParserOptions, RawMetadata, RawModuleLocation},
     tokens::ObjectType,
 };
@@ -27,3 +27,34 @@ pub fn legends_metadata(
         options.attach_metadata_to_raws,
     )
 }
+
+/// The identifier of the creature a race from a legends export refers to, e.g. `DWARF` for `dwarf` or
+/// `GIANT_TOAD` for `giant toad`
+pub fn creature_identifier(race: &str) -> String {
+    race.to_uppercase().replace(' ', "_")
+}
+
+/// Parse the id of an object from a legends export, which is exported as `-1` for none
+pub fn parse_id(value: &str) -> Option<i32> {
+    value.parse().ok().filter(|id: &i32| *id >= 0)
+}
+
+/// Parse a coordinate from a legends export, e.g. `12,34`
+pub fn parse_coordinates(value: &str) -> Option<Dimensions> {
+    let (x, y) = value.trim().split_once(',')?;
+    Some(Dimensions {
+        x: x.trim().parse().ok()?,
+        y: y.trim().parse().ok()?,
+    })
+}
+
+/// Parse a list of coordinates from a legends export, e.g. `12,34|12,35|`
+pub fn parse_coordinate_list(value: &str) -> Vec<Dimensions> {
+    value.split('|').filter_map(parse_coordinates).collect()
+}
+
+/// Parse a rectangle from a legends export, e.g. `196,555:198,557`
+pub fn parse_rectangle(value: &str) -> Option<(Dimensions, Dimensions)> {
+    let (start, end) = value.split_once(':')?;
+    Some((parse_coordinates(start)?, parse_coordinates(end)?))
+}
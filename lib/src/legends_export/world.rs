@@ -0,0 +1,294 @@
+//! The `LegendsWorld` struct holds the parts of a legends export which describe the world itself, rather
+//! than the raws it was generated from.
+
+use serde::{Deserialize, Serialize};
+
+use crate::legends_export::{
+    ExportedArtifact, ExportedCulturalForm, ExportedEntity, ExportedEntityPopulation,
+    ExportedEventCollection, ExportedHistoricalEvent, ExportedHistoricalFigure, ExportedRegion,
+    ExportedSite, ExportedUndergroundRegion, ExportedWorldConstruction, ExportedWrittenContent,
+    Timeline,
+};
+
+/// The entities, historical figures, sites, regions, world constructions, history and culture of a world
+/// from its legends exports.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendsWorld {
+    /// The entities of the world, e.g. civilizations and their site governments
+    pub entities: Vec<ExportedEntity>,
+    /// The members of the civilizations of the world which aren't historical figures
+    pub entity_populations: Vec<ExportedEntityPopulation>,
+    /// The historical figures of the world
+    pub historical_figures: Vec<ExportedHistoricalFigure>,
+    /// The sites of the world
+    pub sites: Vec<ExportedSite>,
+    /// The regions of the surface of the world
+    pub regions: Vec<ExportedRegion>,
+    /// The regions below the surface of the world
+    pub underground_regions: Vec<ExportedUndergroundRegion>,
+    /// The roads, bridges, tunnels and walls of the world
+    pub world_constructions: Vec<ExportedWorldConstruction>,
+    /// The historical events of the world
+    pub historical_events: Vec<ExportedHistoricalEvent>,
+    /// The collections of historical events of the world, e.g. wars and battles
+    pub historical_event_collections: Vec<ExportedEventCollection>,
+    /// The artifacts of the world, e.g. named weapons and books
+    pub artifacts: Vec<ExportedArtifact>,
+    /// The written works of the world, e.g. poems and treatises
+    pub written_contents: Vec<ExportedWrittenContent>,
+    /// The poetic forms generated with the world
+    pub poetic_forms: Vec<ExportedCulturalForm>,
+    /// The musical forms generated with the world
+    pub musical_forms: Vec<ExportedCulturalForm>,
+    /// The dance forms generated with the world
+    pub dance_forms: Vec<ExportedCulturalForm>,
+}
+
+impl LegendsWorld {
+    /// Whether nothing has been read into the world
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+            && self.entity_populations.is_empty()
+            && self.historical_figures.is_empty()
+            && self.sites.is_empty()
+            && self.regions.is_empty()
+            && self.underground_regions.is_empty()
+            && self.world_constructions.is_empty()
+            && self.historical_events.is_empty()
+            && self.historical_event_collections.is_empty()
+            && self.artifacts.is_empty()
+            && self.written_contents.is_empty()
+            && self.poetic_forms.is_empty()
+            && self.musical_forms.is_empty()
+            && self.dance_forms.is_empty()
+    }
+    /// Add everything from another world into this one
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The world to add
+    pub fn extend(&mut self, other: Self) {
+        self.entities.extend(other.entities);
+        self.entity_populations.extend(other.entity_populations);
+        self.historical_figures.extend(other.historical_figures);
+        self.sites.extend(other.sites);
+        self.regions.extend(other.regions);
+        self.underground_regions.extend(other.underground_regions);
+        self.world_constructions.extend(other.world_constructions);
+        self.historical_events.extend(other.historical_events);
+        self.historical_event_collections
+            .extend(other.historical_event_collections);
+        self.artifacts.extend(other.artifacts);
+        self.written_contents.extend(other.written_contents);
+        self.poetic_forms.extend(other.poetic_forms);
+        self.musical_forms.extend(other.musical_forms);
+        self.dance_forms.extend(other.dance_forms);
+    }
+    /// Get an entity by its id
+    #[must_use]
+    pub fn get_entity(&self, id: u32) -> Option<&ExportedEntity> {
+        self.entities.iter().find(|entity| entity.get_id() == id)
+    }
+    /// Get the entities below an entity, e.g. the site governments and religions of a civilization
+    #[must_use]
+    pub fn get_child_entities(&self, entity: &ExportedEntity) -> Vec<&ExportedEntity> {
+        entity
+            .get_child_ids()
+            .into_iter()
+            .filter_map(|id| self.get_entity(id))
+            .collect()
+    }
+    /// Get the entities above an entity, e.g. the civilization of a site government
+    ///
+    /// Entities which list the entity as a child count as its parents too, since the export from Dwarf
+    /// Fortress only has the children.
+    #[must_use]
+    pub fn get_parent_entities(&self, entity: &ExportedEntity) -> Vec<&ExportedEntity> {
+        let parent_ids = entity.get_parent_ids();
+        self.entities
+            .iter()
+            .filter(|other| {
+                parent_ids.contains(&other.get_id())
+                    || other.get_child_ids().contains(&entity.get_id())
+            })
+            .collect()
+    }
+    /// Get the populations of a civilization (only in legends-plus exports)
+    #[must_use]
+    pub fn get_populations_of_entity(&self, entity_id: u32) -> Vec<&ExportedEntityPopulation> {
+        self.entity_populations
+            .iter()
+            .filter(|population| {
+                population
+                    .get_civ_id()
+                    .is_some_and(|civ_id| u32::try_from(civ_id) == Ok(entity_id))
+            })
+            .collect()
+    }
+    /// Get the historical figure holding a position of an entity, if it is held
+    #[must_use]
+    pub fn get_position_holder(
+        &self,
+        entity: &ExportedEntity,
+        position_id: i32,
+    ) -> Option<&ExportedHistoricalFigure> {
+        self.get_historical_figure(entity.get_position_holder(position_id)?)
+    }
+    /// Get a historical figure by its id
+    #[must_use]
+    pub fn get_historical_figure(&self, id: i32) -> Option<&ExportedHistoricalFigure> {
+        self.historical_figures
+            .iter()
+            .find(|figure| figure.get_id() == id)
+    }
+    /// Get a site by its id
+    #[must_use]
+    pub fn get_site(&self, id: i32) -> Option<&ExportedSite> {
+        self.sites.iter().find(|site| site.get_id() == id)
+    }
+    /// Get the sites which belong to, or are held by, an entity
+    #[must_use]
+    pub fn get_sites_owned_by(&self, entity_id: i32) -> Vec<&ExportedSite> {
+        self.sites
+            .iter()
+            .filter(|site| site.is_owned_by(entity_id))
+            .collect()
+    }
+    /// Get a region by its id
+    #[must_use]
+    pub fn get_region(&self, id: i32) -> Option<&ExportedRegion> {
+        self.regions.iter().find(|region| region.get_id() == id)
+    }
+    /// Get the region a site is in
+    ///
+    /// This needs the coordinates of the regions, which are only in legends-plus exports.
+    #[must_use]
+    pub fn get_region_of_site(&self, site: &ExportedSite) -> Option<&ExportedRegion> {
+        let coordinates = site.get_coordinates()?;
+        self.regions
+            .iter()
+            .find(|region| region.contains(coordinates))
+    }
+    /// Get the world constructions of a type, e.g. `road`
+    #[must_use]
+    pub fn get_world_constructions_of_type(
+        &self,
+        construction_type: &str,
+    ) -> Vec<&ExportedWorldConstruction> {
+        self.world_constructions
+            .iter()
+            .filter(|construction| {
+                construction
+                    .get_construction_type()
+                    .eq_ignore_ascii_case(construction_type)
+            })
+            .collect()
+    }
+    /// Get a historical event by its id
+    ///
+    /// To look up many events, use the index of a [`Timeline`] (see [`Timeline::get_event`]).
+    #[must_use]
+    pub fn get_historical_event(&self, id: i32) -> Option<&ExportedHistoricalEvent> {
+        find_by_id(&self.historical_events, id, ExportedHistoricalEvent::get_id)
+    }
+    /// Get a collection of historical events by its id
+    ///
+    /// To look up many collections, use the index of a [`Timeline`] (see [`Timeline::get_collection`]).
+    #[must_use]
+    pub fn get_event_collection(&self, id: i32) -> Option<&ExportedEventCollection> {
+        find_by_id(
+            &self.historical_event_collections,
+            id,
+            ExportedEventCollection::get_id,
+        )
+    }
+    /// Get an artifact by its id
+    #[must_use]
+    pub fn get_artifact(&self, id: i32) -> Option<&ExportedArtifact> {
+        self.artifacts
+            .iter()
+            .find(|artifact| artifact.get_id() == id)
+    }
+    /// Get the artifacts held by a historical figure
+    #[must_use]
+    pub fn get_artifacts_held_by(&self, historical_figure_id: i32) -> Vec<&ExportedArtifact> {
+        self.artifacts
+            .iter()
+            .filter(|artifact| {
+                artifact.get_holder_historical_figure_id() == Some(historical_figure_id)
+            })
+            .collect()
+    }
+    /// Get the artifacts at a site
+    #[must_use]
+    pub fn get_artifacts_at_site(&self, site_id: i32) -> Vec<&ExportedArtifact> {
+        self.artifacts
+            .iter()
+            .filter(|artifact| artifact.get_site_id() == Some(site_id))
+            .collect()
+    }
+    /// Get a written work by its id
+    #[must_use]
+    pub fn get_written_content(&self, id: i32) -> Option<&ExportedWrittenContent> {
+        self.written_contents
+            .iter()
+            .find(|content| content.get_id() == id)
+    }
+    /// Get the written works by a historical figure
+    #[must_use]
+    pub fn get_written_contents_by(&self, author_id: i32) -> Vec<&ExportedWrittenContent> {
+        self.written_contents
+            .iter()
+            .filter(|content| content.get_author_historical_figure_id() == Some(author_id))
+            .collect()
+    }
+    /// Get the written works of an artifact (e.g. the contents of a book)
+    #[must_use]
+    pub fn get_written_contents_of_artifact(
+        &self,
+        artifact: &ExportedArtifact,
+    ) -> Vec<&ExportedWrittenContent> {
+        artifact
+            .get_written_content_ids()
+            .iter()
+            .filter_map(|id| self.get_written_content(*id))
+            .collect()
+    }
+    /// Get the poetic, musical or dance form a written work follows, judged by the type of the work
+    #[must_use]
+    pub fn get_form_of_written_content(
+        &self,
+        content: &ExportedWrittenContent,
+    ) -> Option<&ExportedCulturalForm> {
+        let form_id = content.get_form_id()?;
+        let content_type = content.get_content_type()?.to_lowercase();
+        let forms = if content_type.contains("poem") {
+            &self.poetic_forms
+        } else if content_type.contains("musical") {
+            &self.musical_forms
+        } else if content_type.contains("choreography") {
+            &self.dance_forms
+        } else {
+            return None;
+        };
+        forms.iter().find(|form| form.get_id() == form_id)
+    }
+    /// Index the historical events of the world, to query the timelines of its historical figures, entities
+    /// and sites
+    #[must_use]
+    pub fn timeline(&self) -> Timeline<'_> {
+        Timeline::new(self)
+    }
+}
+
+/// Find an object by its id. The ids of the objects of a legends export count up from 0 in the order
+/// they are exported, so the object is looked for at the position of its id before searching the rest.
+fn find_by_id<T>(objects: &[T], id: i32, get_id: impl Fn(&T) -> i32) -> Option<&T> {
+    usize::try_from(id)
+        .ok()
+        .and_then(|index| objects.get(index))
+        .filter(|object| get_id(object) == id)
+        .or_else(|| objects.iter().find(|object| get_id(object) == id))
+}
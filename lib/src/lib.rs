@@ -6,9 +6,12 @@ mod parser;
 mod reader;
 
 pub mod constants;
+pub mod dependencies;
+pub mod graphics;
 pub mod legends_export;
 pub mod metadata;
 pub mod raw_definitions;
+pub mod references;
 pub mod regex;
 pub mod traits;
 pub mod utilities;
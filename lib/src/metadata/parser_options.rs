@@ -68,6 +68,13 @@ pub struct ParserOptions {
     ///
     /// Default: false.
     pub skip_apply_creature_variations: bool,
+    /// Whether to record where each tag on a resolved creature came from.
+    /// If true, creatures which are resolved (e.g. use `COPY_TAGS_FROM` or `APPLY_CREATURE_VARIATION`)
+    /// will have a `resolution_trace` field explaining how they were assembled.
+    ///
+    /// Default: false.
+    #[serde(default)]
+    pub trace_creature_resolution: bool,
     /// What types of raws to parse. If this is left empty, all parsable raws will be parsed.
     ///
     /// Default: `[Creature, CreatureVariation, Entity, Plant, Inorganic, MaterialTemplate, Graphics, TilePage]`
@@ -144,6 +151,7 @@ impl Default for ParserOptions {
             attach_metadata_to_raws: false,
             skip_apply_copy_tags_from: false,
             skip_apply_creature_variations: false,
+            trace_creature_resolution: false,
             include_warnings_for_info_file_format: false,
             log_summary: false,
             object_types_to_parse: all_object_types,
@@ -204,6 +212,13 @@ impl ParserOptions {
         self.skip_apply_creature_variations = true;
     }
 
+    /// Record where each tag on a resolved creature came from.
+    ///
+    /// See [`crate::Creature::get_resolution_trace`] for the result.
+    pub fn trace_creature_resolution(&mut self) {
+        self.trace_creature_resolution = true;
+    }
+
     /// Sets what kind of raws to parse.
     /// The default value will parse all the raws that are currently supported:
     ///
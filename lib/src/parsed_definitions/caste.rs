@@ -1,5 +1,7 @@
 //! A module for the Caste struct and its implementations.
 
+use std::mem::discriminant;
+
 use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
 use tracing::warn;
 
@@ -7,7 +9,7 @@ use crate::{
     BodySize, Gait, Milkable, Name, Tile,
     raw_definitions::CASTE_TOKENS,
     tokens::CasteToken,
-    traits::{IsEmpty, TagOperations},
+    traits::{IsEmpty, RawToken as _, TagOperations},
 };
 
 /// A struct representing a creature caste.
@@ -462,10 +464,10 @@ impl Caste {
                 "GRASS_TRAMPLE" => self.grass_trample = None,
                 "LOW_LIGHT_VISION" => self.low_light_vision = None,
                 "POP_RATIO" => self.pop_ratio = None,
-                "PET_VALUE" => self.pet_value = None,
+                "PETVALUE" => self.pet_value = None,
                 "CLUTCH_SIZE" => self.clutch_size = None,
                 "LITTER_SIZE" => self.litter_size = None,
-                "MAX_AGE" => self.max_age = None,
+                "MAXAGE" => self.max_age = None,
                 "CREATURE_CLASS" => {
                     if let Some(creature_classes) = self.creature_class.as_mut() {
                         creature_classes.retain(|class| class != value);
@@ -477,15 +479,15 @@ impl Caste {
                     }
                 }
                 "MILKABLE" => self.milkable = None,
-                "BABY_NAME" => self.baby_name = None,
-                "NAME" => self.caste_name = None,
-                "CHILD_NAME" => self.child_name = None,
-                "TILE" | //=> self.tile = Tile::default(),
-                "ALTTILE" | //=> self.tile = Tile::default(),
-                "COLOR" | //=> self.tile = Tile::default(),
-                "GLOWTILE" | //=> self.tile = Tile::default(),
-                "GLOWCOLOR" => self.tile = None,
-                "CHANGE_BODY_SIZE_PERCENT" => {
+                "BABYNAME" => self.baby_name = None,
+                "CASTE_NAME" => self.caste_name = None,
+                "CHILDNAME" => self.child_name = None,
+                "CASTE_TILE" | //=> self.tile = Tile::default(),
+                "CASTE_ALTTILE" | //=> self.tile = Tile::default(),
+                "CASTE_COLOR" | //=> self.tile = Tile::default(),
+                "CASTE_GLOWTILE" | //=> self.tile = Tile::default(),
+                "CASTE_GLOWCOLOR" => self.tile = None,
+                "CHANGE_BODY_SIZE_PERC" => {
                     self.change_body_size_percentage = None;
                 }
                 "GAIT" => {
@@ -498,8 +500,22 @@ impl Caste {
                 }
             }
 
+        // Without a value every instance of the tag is removed, otherwise only the ones whose
+        // values start with the given value.
         if let Some(tags) = self.tags.as_mut() {
-            tags.retain(|t| t != tag);
+            let prefix = format!("[{key}:{value}");
+            tags.retain(|t| {
+                if discriminant(t) != discriminant(tag) {
+                    return true;
+                }
+                if value.is_empty() {
+                    return false;
+                }
+                let raw_token = t.to_raw_token();
+                !raw_token
+                    .strip_prefix(&prefix)
+                    .is_some_and(|rest| rest.starts_with(':') || rest.starts_with(']'))
+            });
         }
     }
 
@@ -2,6 +2,29 @@
 
 use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
 
+/// The RGB values of the 16 colors, as set in the game's default `colors.txt`.
+///
+/// The first 8 are the colors used for the foreground and background (black, blue, green, cyan, red,
+/// magenta, brown, light gray), and the last 8 are their bright versions for the foreground.
+pub const DEFAULT_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (0, 0, 128),
+    (0, 128, 0),
+    (0, 128, 128),
+    (128, 0, 0),
+    (128, 0, 128),
+    (128, 128, 0),
+    (192, 192, 192),
+    (128, 128, 128),
+    (0, 0, 255),
+    (0, 255, 0),
+    (0, 255, 255),
+    (255, 0, 0),
+    (255, 0, 255),
+    (255, 255, 0),
+    (255, 255, 255),
+];
+
 /// Represents a Dwarf Fortress color triplet.
 ///
 /// This format is used throughout the game raws to define the foreground,
@@ -89,6 +112,39 @@ impl Color {
     pub fn get_brightness(&self) -> u8 {
         self.brightness
     }
+
+    /// Returns the RGB value of the foreground color from the [`DEFAULT_PALETTE`], using the bright
+    /// version of the color when the brightness is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dfraw_parser::Color;
+    /// assert_eq!(Color::from_value("6:0:1").get_foreground_rgb(), (255, 255, 0));
+    /// ```
+    #[must_use]
+    pub fn get_foreground_rgb(&self) -> (u8, u8, u8) {
+        let bright = if self.brightness > 0 { 8 } else { 0 };
+        DEFAULT_PALETTE[usize::from(self.foreground % 8 + bright)]
+    }
+
+    /// Returns the RGB value of the background color from the [`DEFAULT_PALETTE`].
+    ///
+    /// The brightness only applies to the foreground, so the background is always one of the first 8
+    /// colors.
+    #[must_use]
+    pub fn get_background_rgb(&self) -> (u8, u8, u8) {
+        DEFAULT_PALETTE[usize::from(self.background % 8)]
+    }
+
+    /// Returns the ANSI escape sequence which sets the terminal's foreground and background to this
+    /// color (using 24-bit color). Reset with `\x1b[0m`.
+    #[must_use]
+    pub fn to_ansi_escape(&self) -> String {
+        let (fr, fg, fb) = self.get_foreground_rgb();
+        let (br, bg, bb) = self.get_background_rgb();
+        format!("\x1b[38;2;{fr};{fg};{fb};48;2;{br};{bg};{bb}m")
+    }
 }
 
 impl std::convert::From<(u8, u8, u8)> for Color {
@@ -106,3 +162,39 @@ impl PartialEq<(u8, u8, u8)> for Color {
         self.foreground == other.0 && self.background == other.1 && self.brightness == other.2
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_from_palette() {
+        let color = Color::from_value("4:1:0");
+        assert_eq!(color.get_foreground_rgb(), (128, 0, 0));
+        assert_eq!(color.get_background_rgb(), (0, 0, 128));
+        // The brightness only changes the foreground
+        let color = Color::from_value("4:1:1");
+        assert_eq!(color.get_foreground_rgb(), (255, 0, 0));
+        assert_eq!(color.get_background_rgb(), (0, 0, 128));
+        assert_eq!(color.to_ansi_escape(), "\x1b[38;2;255;0;0;48;2;0;0;128m");
+    }
+
+    #[test]
+    fn test_rgb_out_of_range() {
+        // Indices outside of 0-7 wrap around instead of reading past the palette
+        let color = Color::from((15, 9, 0));
+        assert_eq!(color.get_foreground_rgb(), DEFAULT_PALETTE[7]);
+        assert_eq!(color.get_background_rgb(), DEFAULT_PALETTE[1]);
+        let color = Color::from((255, 255, 255));
+        assert_eq!(color.get_foreground_rgb(), DEFAULT_PALETTE[15]);
+        assert_eq!(color.get_background_rgb(), DEFAULT_PALETTE[7]);
+        // Any brightness above 0 is bright
+        assert_eq!(Color::from((1, 0, 2)).get_foreground_rgb(), (0, 0, 255));
+        // The parts of a color which can't be parsed are 0
+        assert_eq!(
+            Color::from_value("red:0:1").get_foreground_rgb(),
+            DEFAULT_PALETTE[8]
+        );
+        assert_eq!(Color::from_value("7:0").get_foreground_rgb(), (0, 0, 0));
+    }
+}
@@ -9,13 +9,13 @@ use tracing::{debug, trace, warn};
 use uuid::Uuid;
 
 use crate::{
-    Caste, Name, SelectCreature, Tile,
+    Caste, CreatureVariation, Name, SelectCreature, Tile, TokenOrigin, UnprocessedRaw,
     metadata::{NumericToken, RawMetadata},
     raw_definitions::{BIOME_TOKENS, CASTE_TOKENS, CREATURE_TOKENS},
     tokens::{BiomeToken, CasteToken, CreatureToken, ObjectType},
     traits::{
         Cleanable, CreatureVariationRequirements, NumericTokenTransform as _, RawObject, RawToken,
-        TagOperations,
+        Resolvable, TagOperations,
     },
     utilities::generate_object_id_using_raw_metadata,
 };
@@ -143,6 +143,13 @@ pub struct Creature {
     /// Various `SELECT_CREATUR` modifications.
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     select_creature_variation: Option<Vec<SelectCreature>>,
+    /// A record of where each tag applied to this creature came from (its own definition, a copied
+    /// creature, a creature variation or a `GO_TO_*` insertion).
+    ///
+    /// This is only recorded when `trace_creature_resolution` is enabled in the parser options, and only
+    /// for creatures which needed resolving (i.e. use `COPY_TAGS_FROM`, `APPLY_CREATURE_VARIATION`, etc.)
+    #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
+    resolution_trace: Option<Vec<TokenOrigin>>,
 }
 
 impl Creature {
@@ -223,6 +230,22 @@ impl Creature {
             })
     }
 
+    /// Set the references this creature was resolved with, replacing any copied from another creature.
+    ///
+    /// # Arguments
+    ///
+    /// * `copy_tags_from` - The identifier of the creature given to `COPY_TAGS_FROM`, if any
+    /// * `variations` - The creature variations given to `APPLY_CREATURE_VARIATION` (with their arguments),
+    ///   in order
+    pub(crate) fn set_resolved_references(
+        &mut self,
+        copy_tags_from: Option<String>,
+        variations: Vec<String>,
+    ) {
+        self.copy_tags_from = copy_tags_from;
+        self.apply_creature_variation = (!variations.is_empty()).then_some(variations);
+    }
+
     /// Adds a `SelectCreature` object to the internal `SelectCreature` vector.
     ///
     /// # Arguments
@@ -373,6 +396,10 @@ impl Creature {
             .clone_from(&creature.identifier);
         // our `object_id` is preserved
         combined_creature.object_id.clone_from(&creature.object_id);
+        // our resolution trace is preserved (the copied creature's trace is handled while resolving)
+        combined_creature
+            .resolution_trace
+            .clone_from(&creature.resolution_trace);
 
         // Clean the "creature" to remove any empty lists or strings for comparison
         let creature = creature.cleaned();
@@ -468,6 +495,36 @@ impl Creature {
         self.castes.as_slice()
     }
 
+    /// Get the record of where each tag applied to this creature came from, if it was traced.
+    ///
+    /// See [`TokenOrigin`] for what is recorded.
+    #[must_use]
+    pub fn get_resolution_trace(&self) -> Option<&[TokenOrigin]> {
+        self.resolution_trace.as_deref()
+    }
+    /// Set the record of where each tag applied to this creature came from.
+    pub fn set_resolution_trace(&mut self, resolution_trace: Option<Vec<TokenOrigin>>) {
+        self.resolution_trace = resolution_trace;
+    }
+    /// Explain where a tag on this creature came from.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the tag to explain, e.g. `BODY_SIZE`
+    ///
+    /// # Returns
+    ///
+    /// Every recorded step which added, removed or converted the tag, in the order they happened.
+    /// This is empty if the creature was not traced.
+    #[must_use]
+    pub fn explain_token(&self, key: &str) -> Vec<&TokenOrigin> {
+        self.resolution_trace
+            .iter()
+            .flatten()
+            .filter(|origin| origin.get_key() == key)
+            .collect()
+    }
+
     /// Get a list of tags that belong to this creature.
     #[must_use]
     pub fn get_tags(&self) -> Vec<CreatureToken> {
@@ -1059,3 +1116,22 @@ impl CreatureVariationRequirements for Creature {
         self.parse_tag(key, value);
     }
 }
+
+impl Resolvable for Creature {
+    const OBJECT_TYPE: ObjectType = ObjectType::Creature;
+
+    fn new_unresolved(identifier: &str, metadata: &RawMetadata) -> Self {
+        Self::new(identifier, metadata)
+    }
+    fn copy_tags_from(&self, source: &Self) -> Self {
+        Self::copy_tags_from(self, source)
+    }
+    fn resolve(
+        unprocessed: &UnprocessedRaw,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+        trace: bool,
+    ) -> Self {
+        unprocessed.resolve_creature(creature_variations, all_raws, trace)
+    }
+}
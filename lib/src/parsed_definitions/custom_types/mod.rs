@@ -2,4 +2,4 @@ mod habit_count;
 mod tile_char;
 
 pub use habit_count::HabitCount;
-pub use tile_char::TileCharacter;
+pub use tile_char::{CP437_TO_UNICODE, TileCharacter};
@@ -5,6 +5,27 @@ use std::{
     str::FromStr,
 };
 
+use crate::constants::DF_ENCODING;
+
+/// The unicode character drawn for each code page 437 index, as the game draws them with the default
+/// tileset (so the control characters are drawn as symbols).
+pub const CP437_TO_UNICODE: [char; 256] = [
+    ' ', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄', '↕',
+    '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼', ' ', '!', '"', '#', '$', '%',
+    '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', '0', '1', '2', '3', '4', '5', '6', '7', '8',
+    '9', ':', ';', '<', '=', '>', '?', '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K',
+    'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '\\', ']', '^',
+    '_', '`', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q',
+    'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '{', '|', '}', '~', '⌂', 'Ç', 'ü', 'é', 'â', 'ä',
+    'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù',
+    'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬',
+    '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜',
+    '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', '╨',
+    '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', 'α', 'ß', 'Γ', 'π',
+    'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±', '≥', '≤', '⌠', '⌡', '÷',
+    '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
 /// Custom wrapper for the Tile character used in tags
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, specta::Type, Eq)]
 pub struct TileCharacter {
@@ -26,6 +47,85 @@ impl TileCharacter {
     pub const fn new() -> Self {
         Self { value: '?' }
     }
+
+    /// Creates a `TileCharacter` for a code page 437 index, the same as parsing the index as a number
+    /// (e.g. `[TREE_TILE:24]`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dfraw_parser::custom_types::TileCharacter;
+    ///
+    /// let tile_char = TileCharacter::from_cp437(231);
+    /// assert_eq!(tile_char.get_cp437_index(), Some(231));
+    /// assert_eq!(tile_char.to_unicode(), 'τ');
+    /// ```
+    #[must_use]
+    pub const fn from_cp437(index: u8) -> Self {
+        Self {
+            value: index as char,
+        }
+    }
+
+    /// Returns the code page 437 index of the character.
+    ///
+    /// The character can come from a number (`65`), a character read from a raw file (`'A'` or `A`) or
+    /// a unicode character drawn by the game (`'↑'`), and all of them are normalized to the index of the
+    /// tile in the game's tileset.
+    ///
+    /// # Returns
+    ///
+    /// The index, or `None` if the character isn't in code page 437
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use dfraw_parser::custom_types::TileCharacter;
+    ///
+    /// for value in ["65", "'A'", "A"] {
+    ///     let tile_char = TileCharacter::from_str(value).unwrap();
+    ///     assert_eq!(tile_char.get_cp437_index(), Some(65));
+    /// }
+    /// let arrow = TileCharacter::from_str("'↑'").unwrap();
+    /// assert_eq!(arrow.get_cp437_index(), Some(24));
+    /// // The byte 0x80 in a raw file is read as '€'
+    /// let byte = TileCharacter::from_str("'€'").unwrap();
+    /// assert_eq!(byte.to_unicode(), 'Ç');
+    /// ```
+    #[must_use]
+    pub fn get_cp437_index(&self) -> Option<u8> {
+        // Numbers and latin1 characters map directly onto the index
+        if let Ok(index) = u8::try_from(u32::from(self.value)) {
+            return Some(index);
+        }
+        // Raw files are decoded as windows-1252, which puts some bytes (0x80-0x9F) elsewhere in unicode
+        let mut buffer = [0; 4];
+        let (bytes, _, had_errors) = DF_ENCODING.encode(self.value.encode_utf8(&mut buffer));
+        if !had_errors && let [index] = *bytes {
+            return Some(index);
+        }
+        CP437_TO_UNICODE
+            .iter()
+            .position(|chr| *chr == self.value)
+            .and_then(|index| u8::try_from(index).ok())
+    }
+
+    /// Returns the unicode character the game draws for this tile, e.g. `τ` for `231`.
+    ///
+    /// Characters which aren't in code page 437 are returned as they are.
+    #[must_use]
+    pub fn to_unicode(&self) -> char {
+        self.get_cp437_index()
+            .map_or(self.value, |index| CP437_TO_UNICODE[usize::from(index)])
+    }
+}
+
+impl From<u8> for TileCharacter {
+    fn from(index: u8) -> Self {
+        Self::from_cp437(index)
+    }
 }
 
 impl Default for TileCharacter {
@@ -42,16 +142,17 @@ impl FromStr for TileCharacter {
             return Err("Cannot create a tile without a char to use.".into());
         }
 
-        if s.len() > 1 {
-            // Sometimes an integer is provided for the ASCII character code.
-            if let Ok(ascii_code) = s.parse::<u32>()
-                && let Some(ascii_char) = char::from_u32(ascii_code)
-            {
-                return Ok(TileCharacter { value: ascii_char });
-            };
+        // Sometimes an integer is provided for the ASCII character code (even a single digit one).
+        if let Ok(ascii_code) = s.parse::<u32>()
+            && let Some(ascii_char) = char::from_u32(ascii_code)
+        {
+            return Ok(TileCharacter { value: ascii_char });
+        }
+
+        if s.chars().count() > 1 {
             // Othertimes it's a literal quoted character with `'`: `'t'`
             let stripped = s.trim_matches('\'');
-            if stripped.len() != 1 {
+            if stripped.chars().count() != 1 {
                 return Err(format!("Unable to find single character to use: {s}"));
             }
             return TileCharacter::from_str(stripped);
@@ -69,3 +170,59 @@ impl Display for TileCharacter {
         f.write_char(self.value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cp437_round_trip() {
+        for index in 0..=u8::MAX {
+            let tile_char = TileCharacter::from_cp437(index);
+            assert_eq!(tile_char.get_cp437_index(), Some(index));
+            assert_eq!(tile_char.to_unicode(), CP437_TO_UNICODE[usize::from(index)]);
+            assert_eq!(
+                TileCharacter::from_str(&index.to_string()),
+                Ok(tile_char),
+                "{index}"
+            );
+        }
+        // The characters drawn by the game map back onto their index, except the latin1 ones which are
+        // read as the byte of the raw file they came from
+        for (index, chr) in CP437_TO_UNICODE.iter().enumerate() {
+            if u32::from(*chr) > 0xFF && DF_ENCODING.encode(&chr.to_string()).2 {
+                let tile_char = TileCharacter { value: *chr };
+                assert_eq!(tile_char.get_cp437_index().map(usize::from), Some(index));
+                assert_eq!(tile_char.to_unicode(), *chr);
+            }
+        }
+    }
+
+    #[test]
+    fn test_windows_1252_bytes() {
+        // Raw files are decoded as windows-1252, so the bytes 0x80-0x9F don't come out as the same
+        // unicode code points, and have to be encoded back to find the index
+        for byte in 0x80..=0x9F_u8 {
+            let bytes = [byte];
+            let (decoded, _) = DF_ENCODING.decode_without_bom_handling(&bytes);
+            let tile_char = TileCharacter::from_str(&decoded).expect("Expected one character");
+            assert_eq!(tile_char.get_cp437_index(), Some(byte), "{decoded}");
+            assert_eq!(tile_char.to_unicode(), CP437_TO_UNICODE[usize::from(byte)]);
+        }
+        let euro = TileCharacter { value: '€' };
+        assert_eq!(euro.get_cp437_index(), Some(0x80));
+        assert_eq!(euro.to_unicode(), 'Ç');
+    }
+
+    #[test]
+    fn test_not_in_cp437() {
+        let snowman = TileCharacter { value: '☃' };
+        assert_eq!(snowman.get_cp437_index(), None);
+        assert_eq!(snowman.to_unicode(), '☃');
+        // Numbers past the end of the table aren't an index
+        let tile_char = TileCharacter::from_str("256").expect("Expected a character");
+        assert_eq!(tile_char.get_cp437_index(), None);
+        assert!(TileCharacter::from_str("").is_err());
+        assert!(TileCharacter::from_str("'ab'").is_err());
+    }
+}
@@ -14,6 +14,7 @@ use tracing::{error, warn};
     specta::Type,
     PartialEq,
     Eq,
+    Hash,
     IsEmpty,
 )]
 pub struct Dimensions {
@@ -9,7 +9,7 @@ use crate::{
     metadata::RawMetadata,
     raw_definitions::{ENTITY_TOKENS, POSITION_TOKENS},
     tokens::{EntityToken, ObjectType},
-    traits::RawObject,
+    traits::{RawObject, Resolvable},
     utilities::generate_object_id_using_raw_metadata,
 };
 
@@ -42,6 +42,10 @@ pub struct Entity {
     object_id: Uuid,
 
     tags: Vec<(EntityToken, String)>,
+    /// Internal count of the leading `tags` which were copied from another entity with `COPY_TAGS_FROM`
+    #[serde(skip)]
+    #[cleanable(ignore)]
+    copied_tag_count: usize,
 
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     creature: Option<String>,
@@ -228,6 +232,11 @@ impl Entity {
     pub fn get_tags(&self) -> Vec<(EntityToken, String)> {
         self.tags.clone()
     }
+    /// The creature the entity is made of (its `CREATURE` token), if any
+    #[must_use]
+    pub fn get_creature(&self) -> Option<&str> {
+        self.creature.as_deref()
+    }
 }
 
 #[typetag::serde]
@@ -284,7 +293,17 @@ impl RawObject for Entity {
             return;
         };
 
-        self.tags.push((*token, value.to_string()));
+        // Tokens with a single value (and flags) replace what was copied with `COPY_TAGS_FROM`. Repeats in
+        // the entity itself are kept, e.g. several `CREATURE`s to weight which one is picked.
+        if let Some(existing) = self.tags[..self.copied_tag_count]
+            .iter_mut()
+            .find(|(existing, _)| existing == token)
+            .filter(|_| value.is_empty() || is_single_valued(*token))
+        {
+            existing.1 = value.to_string();
+        } else {
+            self.tags.push((*token, value.to_string()));
+        }
 
         match token {
             EntityToken::ActiveSeason => {
@@ -704,3 +723,44 @@ impl RawObject for Entity {
         }
     }
 }
+
+/// Returns true if an entity has at most one value for the token, so a later value replaces the earlier one
+const fn is_single_valued(token: EntityToken) -> bool {
+    matches!(
+        token,
+        EntityToken::ActiveSeason
+            | EntityToken::Banditry
+            | EntityToken::ProgressTriggerPopulation
+            | EntityToken::ProgressTriggerProduction
+            | EntityToken::ProgressTriggerTrade
+            | EntityToken::ProgressTriggerPopulationSiege
+            | EntityToken::ProgressTriggerProductionSiege
+            | EntityToken::ProgressTriggerTradeSiege
+            | EntityToken::Religion
+            | EntityToken::LandHolderTrigger
+            | EntityToken::ExclusiveStartBiome
+            | EntityToken::MaxPopNumber
+            | EntityToken::MaxSitePopNumber
+            | EntityToken::MaxStartingCivNumber
+            | EntityToken::SourceHfid
+            | EntityToken::Translation
+    )
+}
+
+impl Resolvable for Entity {
+    const OBJECT_TYPE: ObjectType = ObjectType::Entity;
+
+    fn new_unresolved(identifier: &str, metadata: &RawMetadata) -> Self {
+        Self::new(identifier, metadata)
+    }
+    fn copy_tags_from(&self, source: &Self) -> Self {
+        // Everything comes from the source (our own tags are applied after), except what identifies us
+        Self {
+            metadata: self.metadata.clone(),
+            identifier: self.identifier.clone(),
+            object_id: self.object_id,
+            copied_tag_count: source.tags.len(),
+            ..source.clone()
+        }
+    }
+}
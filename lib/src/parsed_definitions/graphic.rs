@@ -8,7 +8,8 @@ use crate::{
     CustomGraphicExtension, GraphicPalette, SpriteGraphic, SpriteLayer,
     metadata::RawMetadata,
     raw_definitions::{
-        CONDITION_TOKENS, CUSTOM_GRAPHIC_TOKENS, GROWTH_TOKENS, PLANT_GRAPHIC_TEMPLATE_TOKENS,
+        CONDITION_TOKENS, CUSTOM_GRAPHIC_TOKENS, GRAPHIC_TYPE_TOKENS, GROWTH_TOKENS,
+        PLANT_GRAPHIC_TEMPLATE_TOKENS,
     },
     tokens::{ConditionToken, GraphicTypeToken, ObjectType},
     traits::RawObject,
@@ -78,6 +79,19 @@ pub struct Graphic {
     #[serde(skip)]
     #[cleanable(ignore)]
     group_conditions: Vec<(String, String)>,
+    /// Internal counter for the `LAYER_GROUP`s defined so far
+    #[serde(skip)]
+    #[cleanable(ignore)]
+    layer_group_count: u32,
+    /// Internal switch for the `LAYER_GROUP` new layers belong to
+    #[serde(skip)]
+    #[cleanable(ignore)]
+    current_layer_group: Option<u32>,
+    /// Internal cache of the item (or workshop) the following `TILE_GRAPHICS` sprites are for, named by
+    /// the last sprite with a target or by a header like `WEAPON_GRAPHICS`
+    #[serde(skip)]
+    #[cleanable(ignore)]
+    current_tile_target: Option<String>,
     /// The palletes used or defined in the raw
     palletes: Vec<GraphicPalette>,
 }
@@ -104,6 +118,15 @@ impl Graphic {
                 ObjectType::Graphics,
                 metadata,
             ),
+            // Caste graphics are identified like `CREATURE:CASTE`
+            caste_identifier: match graphic_type {
+                GraphicTypeToken::CreatureCaste | GraphicTypeToken::StatueCreatureCaste => {
+                    identifier
+                        .split_once(':')
+                        .map(|(_, caste)| String::from(caste))
+                }
+                _ => None,
+            },
             kind: graphic_type,
             ..Self::default()
         }
@@ -126,6 +149,18 @@ impl Graphic {
         }
     }
 
+    /// Get the identifier of the object this graphic is for, e.g. `DWARF` for `CREATURE_CASTE_GRAPHICS:DWARF:FEMALE`
+    #[must_use]
+    pub fn get_target_identifier(&self) -> &str {
+        self.identifier.split(':').next().unwrap_or_default()
+    }
+
+    /// Get the identifier of the caste this graphic is for, if it is `CREATURE_CASTE_GRAPHICS`
+    #[must_use]
+    pub fn get_caste_identifier(&self) -> Option<&str> {
+        self.caste_identifier.as_deref()
+    }
+
     /// Get the sprites defined in this graphic
     #[must_use]
     pub fn get_sprites(&self) -> Vec<SpriteGraphic> {
@@ -144,6 +179,33 @@ impl Graphic {
         }
     }
 
+    /// Get the sprites defined in this graphic for a target, e.g. every sprite of `ITEM_WEAPON_WHIP` in a
+    /// `TILE_GRAPHICS`
+    #[must_use]
+    pub fn get_sprites_for_target(&self, target_identifier: &str) -> Vec<SpriteGraphic> {
+        self.sprites
+            .iter()
+            .flatten()
+            .filter(|sprite| {
+                sprite
+                    .get_target_identifier()
+                    .eq_ignore_ascii_case(target_identifier)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get the layers of a layer set (e.g. `DEFAULT` or `PORTRAIT`) defined in this graphic
+    #[must_use]
+    pub fn get_layer_set(&self, name: &str) -> Option<&[SpriteLayer]> {
+        self.layers.as_ref().and_then(|layers| {
+            layers
+                .iter()
+                .find(|(layer_set, _)| layer_set.eq_ignore_ascii_case(name))
+                .map(|(_, layers)| layers.as_slice())
+        })
+    }
+
     /// Get the growths defined in this graphic
     #[must_use]
     pub fn get_growths(&self) -> Vec<(String, Vec<SpriteGraphic>)> {
@@ -153,6 +215,12 @@ impl Graphic {
         }
     }
 
+    /// Get the palettes (`LS_PALETTE`) defined in this graphic
+    #[must_use]
+    pub fn get_palettes(&self) -> &[GraphicPalette] {
+        self.palletes.as_slice()
+    }
+
     /// Get the type of the Graphic.
     ///
     /// # Returns
@@ -263,6 +331,7 @@ impl Graphic {
         // We clear the group conditions since this is a new layer group
         if key == "LAYER_SET" {
             self.group_conditions.clear();
+            self.current_layer_group = None;
             // Parse the value into a SpriteLayer
             self.parse_layer_set_from_value(value);
             self.layer_mode = true;
@@ -277,9 +346,18 @@ impl Graphic {
             return;
         }
 
+        // Start of a group, so any following layers belong to it (until the next group)
+        if key == "LAYER_GROUP" {
+            self.group_conditions.clear();
+            self.layer_group_count += 1;
+            self.current_layer_group = Some(self.layer_group_count);
+            return;
+        }
+
         // End of a group, so clear any groups and end layer mode
         if key == "END_LAYER_GROUP" {
             self.group_conditions.clear();
+            self.current_layer_group = None;
             self.layer_mode = false;
             return;
         }
@@ -305,8 +383,9 @@ impl Graphic {
             return;
         }
 
-        // Check if the value is empty, which means we have a tag
-        if value.is_empty() {
+        // Check if the value is empty, which means we have a tag (unless it's a layer condition like
+        // CONDITION_NOT_CHILD or BP_PRESENT)
+        if value.is_empty() && !(self.layer_mode && CONDITION_TOKENS.contains_key(key)) {
             if let Some(tags) = self.tags.as_mut() {
                 tags.push(String::from(key));
             } else {
@@ -335,8 +414,9 @@ impl Graphic {
         }
 
         // If the key is a growth token, parse it into a SpriteGraphic and add it to the current growth
-        if let Some(_growth_type) = GROWTH_TOKENS.get(key) {
-            if let Some(sprite_graphic) = SpriteGraphic::from_token(key, value, graphic_type) {
+        if let Some(growth_type) = GROWTH_TOKENS.get(key) {
+            if let Some(mut sprite_graphic) = SpriteGraphic::from_token(key, value, graphic_type) {
+                sprite_graphic.set_growth(*growth_type);
                 if let Some(growths) = self.growths.as_mut()
                     && let Some(growth) = growths.last_mut()
                 {
@@ -376,8 +456,31 @@ impl Graphic {
             return;
         }
 
+        // Headers like [WEAPON_GRAPHICS:ITEM_WEAPON_WHIP] name the item for the sprites which follow them
+        if self.kind == GraphicTypeToken::Tile
+            && GRAPHIC_TYPE_TOKENS
+                .get(key)
+                .is_some_and(GraphicTypeToken::is_target_header)
+        {
+            self.current_tile_target = Some(String::from(value));
+            return;
+        }
+
         // Otherwise we can parse it for a sprite and report an error if that fails.
-        if let Some(sprite_graphic) = SpriteGraphic::from_token(key, value, graphic_type) {
+        if let Some(mut sprite_graphic) = SpriteGraphic::from_token(key, value, graphic_type) {
+            // Item variants without a target (e.g. TOOL_GRAPHICS_WOOD) are for the last named item
+            if self.kind == GraphicTypeToken::Tile {
+                if !sprite_graphic.get_target_identifier().is_empty() {
+                    self.current_tile_target =
+                        Some(String::from(sprite_graphic.get_target_identifier()));
+                } else if sprite_graphic
+                    .get_graphic_type()
+                    .is_some_and(|graphic_type| graphic_type.is_target_variant())
+                    && let Some(target) = self.current_tile_target.as_deref()
+                {
+                    sprite_graphic.set_target_identifier(target);
+                }
+            }
             if let Some(sprites) = self.sprites.as_mut() {
                 sprites.push(sprite_graphic);
             } else {
@@ -410,6 +513,7 @@ impl Graphic {
             for (key, val) in &self.group_conditions {
                 layer.parse_condition_token(key, val);
             }
+            layer.set_layer_group(self.current_layer_group);
 
             if self.layers.is_none() {
                 self.add_layer_if_not_exists(String::from("default"));
@@ -0,0 +1,202 @@
+//! Contains the struct for the appearance of a plant growth (`GROWTH_PRINT`).
+
+use std::str::FromStr as _;
+
+use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
+use tracing::warn;
+
+use crate::{
+    Color,
+    constants::{DAYS_PER_YEAR, TICKS_PER_DAY},
+    custom_types::TileCharacter,
+};
+
+/// How a plant growth is drawn for part of the year, defined with the `GROWTH_PRINT` key.
+///
+/// A growth can have several prints with different timings (e.g. unripe and ripe fruit), which are the
+/// stages of the growth. The first print is drawn by `GROWTH_1` in the graphics, the second by
+/// `GROWTH_2`, and so on.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Default,
+    specta::Type,
+    PartialEq,
+    Eq,
+    IsEmpty,
+    Cleanable,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GrowthPrint {
+    /// The tile drawn when the growth is on a tree
+    tree_tile: String,
+    /// The tile drawn when the growth is on a shrub
+    shrub_tile: String,
+    /// The color of the tiles
+    color: Color,
+    /// The part of the year the print is used, in ticks. `None` is all year (`ALL`).
+    #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
+    timing: Option<[u32; 2]>,
+    /// Decides which growth is drawn when more than one is on a tile, the highest is drawn
+    priority: u32,
+}
+
+impl GrowthPrint {
+    /// Parses the value of a `GROWTH_PRINT` token, e.g. `'%':'%':4:0:1:200000:300000:3` or
+    /// `0:6:2:0:0:ALL:1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the token (everything after `GROWTH_PRINT:`)
+    ///
+    /// # Returns
+    ///
+    /// The print, or `None` if the value can't be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dfraw_parser::GrowthPrint;
+    ///
+    /// let print = GrowthPrint::from_value("'%':'%':4:0:1:200000:300000:3").unwrap();
+    /// assert_eq!(print.get_timing(), Some([200_000, 300_000]));
+    /// assert_eq!(print.get_priority(), 3);
+    /// ```
+    #[must_use]
+    pub fn from_value(value: &str) -> Option<Self> {
+        let parts = split_print_value(value);
+        let (tree_tile, shrub_tile, color, rest) = match parts.as_slice() {
+            [tree_tile, shrub_tile, fg, bg, br, rest @ ..] => (
+                tree_tile,
+                shrub_tile,
+                Color::from_value(&format!("{fg}:{bg}:{br}")),
+                rest,
+            ),
+            _ => {
+                warn!("GrowthPrint::from_value: Too few values in {}", value);
+                return None;
+            }
+        };
+        let (timing, priority) = match rest {
+            ["ALL", priority] => (None, priority),
+            [start, end, priority] => match (start.parse(), end.parse()) {
+                (Ok(start), Ok(end)) => (Some([start, end]), priority),
+                _ => {
+                    warn!(
+                        "GrowthPrint::from_value: Unable to parse timing of {}",
+                        value
+                    );
+                    return None;
+                }
+            },
+            _ => {
+                warn!("GrowthPrint::from_value: Unexpected timing in {}", value);
+                return None;
+            }
+        };
+
+        Some(Self {
+            tree_tile: String::from(*tree_tile),
+            shrub_tile: String::from(*shrub_tile),
+            color,
+            timing,
+            priority: priority.parse().unwrap_or_default(),
+        })
+    }
+    /// Returns the tile drawn when the growth is on a tree
+    #[must_use]
+    pub fn get_tree_tile(&self) -> Option<TileCharacter> {
+        TileCharacter::from_str(&self.tree_tile).ok()
+    }
+    /// Returns the tile drawn when the growth is on a shrub
+    #[must_use]
+    pub fn get_shrub_tile(&self) -> Option<TileCharacter> {
+        TileCharacter::from_str(&self.shrub_tile).ok()
+    }
+    /// Returns the color of the tiles
+    #[must_use]
+    pub const fn get_color(&self) -> &Color {
+        &self.color
+    }
+    /// Returns the part of the year the print is used, in ticks, or `None` if it is used all year
+    #[must_use]
+    pub const fn get_timing(&self) -> Option<[u32; 2]> {
+        self.timing
+    }
+    /// Returns the priority of the print
+    #[must_use]
+    pub const fn get_priority(&self) -> u32 {
+        self.priority
+    }
+    /// Returns true if the print is used on the given day of the year (0 to 335)
+    #[must_use]
+    pub fn is_used_on_day(&self, day_of_year: u32) -> bool {
+        self.timing
+            .is_none_or(|timing| is_in_timing(timing, day_of_year))
+    }
+}
+
+/// Returns true if the given day of the year (0 to 335) is inside of a `GROWTH_TIMING` or
+/// `GROWTH_PRINT` timing, which are in ticks. Timings which end before they start wrap around the
+/// new year.
+#[must_use]
+pub fn is_in_timing(timing: [u32; 2], day_of_year: u32) -> bool {
+    let tick = (day_of_year % DAYS_PER_YEAR) * TICKS_PER_DAY;
+    let [start, end] = timing;
+    if start <= end {
+        start <= tick && tick <= end
+    } else {
+        tick >= start || tick <= end
+    }
+}
+
+/// Splits a `GROWTH_PRINT` value on `:`, keeping a quoted colon (`':'`) as one tile
+fn split_print_value(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = value;
+    while !rest.is_empty() {
+        if rest.starts_with("':'") {
+            parts.push(&rest[..3]);
+            rest = rest[3..].strip_prefix(':').unwrap_or(&rest[3..]);
+            continue;
+        }
+        let (part, remainder) = rest.split_once(':').unwrap_or((rest, ""));
+        parts.push(part);
+        rest = remainder;
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_print_value() {
+        assert_eq!(
+            split_print_value("':':'%':4:0:1:ALL:1"),
+            vec!["':'", "'%'", "4", "0", "1", "ALL", "1"]
+        );
+        assert_eq!(split_print_value("0:':'"), vec!["0", "':'"]);
+        // Anything after a quoted colon which isn't a separator is kept
+        assert_eq!(split_print_value("':'4:0"), vec!["':'", "4", "0"]);
+    }
+
+    #[test]
+    fn test_is_in_timing() {
+        let spring = [0, 100_800];
+        assert!(is_in_timing(spring, 0));
+        assert!(is_in_timing(spring, 84));
+        assert!(!is_in_timing(spring, 85));
+        // Days after the first year wrap around, however far they are
+        assert!(is_in_timing(spring, DAYS_PER_YEAR));
+        assert!(is_in_timing(spring, u32::MAX - u32::MAX % DAYS_PER_YEAR));
+        // Timings which end before they start wrap around the new year
+        let winter = [302_400, 50_000];
+        assert!(is_in_timing(winter, 300));
+        assert!(is_in_timing(winter, 10));
+        assert!(!is_in_timing(winter, 100));
+    }
+}
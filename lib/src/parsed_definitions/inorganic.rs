@@ -103,6 +103,11 @@ impl Inorganic {
             ..Self::default()
         }
     }
+    /// Get the material defined by the inorganic
+    #[must_use]
+    pub const fn get_material(&self) -> &Material {
+        &self.material
+    }
     #[must_use]
     pub fn get_tags(&self) -> Vec<InorganicToken> {
         if self.tags.is_none() {
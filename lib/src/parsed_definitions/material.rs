@@ -123,6 +123,18 @@ impl Material {
             ..Self::default()
         }
     }
+    /// Get the name of the material (e.g. `STRUCTURAL`), if it has one
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Get the identifier of what this material was based on, if anything.
+    ///
+    /// This is the material template for `USE_MATERIAL_TEMPLATE`, or the local material for `USE_MATERIAL`.
+    #[must_use]
+    pub fn get_template_identifier(&self) -> Option<&str> {
+        self.template_identifier.as_deref()
+    }
     /// This may not be correct. This should be for `[USE_MATERIAL:XX:XX]` but couldn't find an example for Plant.
     ///
     /// # Arguments
@@ -17,6 +17,7 @@ mod entity;
 mod gait;
 mod graphic;
 mod graphic_palette;
+mod growth_print;
 mod inorganic;
 mod material;
 mod material_mechanics;
@@ -39,11 +40,12 @@ mod syndrome;
 mod temperatures;
 mod tile;
 mod tile_page;
+mod token_origin;
 mod tree;
 
 pub use body_size::BodySize;
 pub use caste::Caste;
-pub use color::Color;
+pub use color::{Color, DEFAULT_PALETTE};
 pub use creature::Creature;
 pub use creature_effect::CreatureEffect;
 pub use creature_variation::CreatureVariation;
@@ -53,6 +55,7 @@ pub use entity::Entity;
 pub use gait::Gait;
 pub use graphic::Graphic;
 pub use graphic_palette::GraphicPalette;
+pub use growth_print::{GrowthPrint, is_in_timing};
 pub use inorganic::Inorganic;
 pub use material::Material;
 pub use material_mechanics::MaterialMechanics;
@@ -75,4 +78,5 @@ pub use syndrome::Syndrome;
 pub use temperatures::Temperatures;
 pub use tile::Tile;
 pub use tile_page::TilePage;
+pub use token_origin::{TokenAction, TokenOrigin, TokenSource};
 pub use tree::Tree;
@@ -307,44 +307,16 @@ impl ModuleInfo {
                         info_file_data.description = String::from(captured_value);
                     }
                     "REQUIRES_ID" => {
-                        if info_file_data.requires_ids.is_none() {
-                            info_file_data.requires_ids = Some(Vec::new());
-                        }
-
-                        if let Some(requires_ids) = info_file_data.requires_ids.as_mut() {
-                            requires_ids.push(String::from(captured_value));
-                        }
+                        info_file_data.add_requires_id(captured_value);
                     }
                     "CONFLICTS_WITH_ID" => {
-                        if info_file_data.conflicts_with_ids.is_none() {
-                            info_file_data.conflicts_with_ids = Some(Vec::new());
-                        }
-
-                        if let Some(conflicts_with_ids) = info_file_data.conflicts_with_ids.as_mut()
-                        {
-                            conflicts_with_ids.push(String::from(captured_value));
-                        }
+                        info_file_data.add_conflicts_with_id(captured_value);
                     }
                     "REQUIRES_ID_BEFORE_ME" => {
-                        if info_file_data.requires_ids_before.is_none() {
-                            info_file_data.requires_ids_before = Some(Vec::new());
-                        }
-
-                        if let Some(requires_ids_before) =
-                            info_file_data.requires_ids_before.as_mut()
-                        {
-                            requires_ids_before.push(String::from(captured_value));
-                        }
+                        info_file_data.add_requires_id_before(captured_value);
                     }
                     "REQUIRES_ID_AFTER_ME" => {
-                        if info_file_data.requires_ids_after.is_none() {
-                            info_file_data.requires_ids_after = Some(Vec::new());
-                        }
-
-                        if let Some(requires_ids_after) = info_file_data.requires_ids_after.as_mut()
-                        {
-                            requires_ids_after.push(String::from(captured_value));
-                        }
+                        info_file_data.add_requires_id_after(captured_value);
                     }
                     "STEAM_TITLE" => {
                         if info_file_data.steam_data.is_none() {
@@ -532,6 +504,48 @@ impl ModuleInfo {
     pub fn get_conflicts_with_ids(&self) -> Option<Vec<String>> {
         self.conflicts_with_ids.clone()
     }
+    /// Adds a module identifier which must be present for this module to load (`REQUIRES_ID`)
+    pub fn add_requires_id(&mut self, identifier: &str) {
+        self.requires_ids
+            .get_or_insert_with(Vec::new)
+            .push(String::from(identifier));
+    }
+    /// Adds a module identifier which cannot be loaded alongside this module (`CONFLICTS_WITH_ID`)
+    pub fn add_conflicts_with_id(&mut self, identifier: &str) {
+        self.conflicts_with_ids
+            .get_or_insert_with(Vec::new)
+            .push(String::from(identifier));
+    }
+    /// Adds a module identifier which must be loaded before this module (`REQUIRES_ID_BEFORE_ME`)
+    pub fn add_requires_id_before(&mut self, identifier: &str) {
+        self.requires_ids_before
+            .get_or_insert_with(Vec::new)
+            .push(String::from(identifier));
+    }
+    /// Adds a module identifier which must be loaded after this module (`REQUIRES_ID_AFTER_ME`)
+    pub fn add_requires_id_after(&mut self, identifier: &str) {
+        self.requires_ids_after
+            .get_or_insert_with(Vec::new)
+            .push(String::from(identifier));
+    }
+    /// Sets the numeric version for the `InfoFile` and recalculates the `object_id`
+    pub fn set_numeric_version(&mut self, numeric_version: u32) {
+        self.numeric_version = numeric_version;
+        self.recalculate_object_id();
+    }
+    /// Sets the `earliest_compatible_numeric_version` for the `InfoFile`
+    pub const fn set_earliest_compatible_numeric_version(&mut self, numeric_version: u32) {
+        self.earliest_compatible_numeric_version = numeric_version;
+    }
+    /// Returns true if a module saved with `numeric_version` can be replaced by this module.
+    ///
+    /// This is the case when `numeric_version` falls between `earliest_compatible_numeric_version`
+    /// and this module's own `numeric_version` (inclusive).
+    #[must_use]
+    pub const fn is_compatible_with_version(&self, numeric_version: u32) -> bool {
+        numeric_version >= self.earliest_compatible_numeric_version
+            && numeric_version <= self.numeric_version
+    }
     /// Returns the directory the `InfoFile` was parsed from
     ///
     /// # Example
@@ -135,6 +135,25 @@ impl Plant {
             .map_or_else(Vec::new, std::clone::Clone::clone)
     }
 
+    /// Get the growths defined on the plant
+    ///
+    /// # Returns
+    ///
+    /// A slice of the growths defined on the plant
+    #[must_use]
+    pub fn get_growths(&self) -> &[PlantGrowth] {
+        self.growths.as_deref().unwrap_or_default()
+    }
+    /// Get the materials defined on the plant
+    ///
+    /// # Returns
+    ///
+    /// A slice of the materials defined on the plant
+    #[must_use]
+    pub fn get_materials(&self) -> &[Material] {
+        self.materials.as_deref().unwrap_or_default()
+    }
+
     #[must_use]
     pub fn get_tags(&self) -> Vec<PlantToken> {
         if self.tags.is_none() {
@@ -323,7 +342,7 @@ impl RawObject for Plant {
                 let growth_type = *PLANT_GROWTH_TYPE_TOKENS
                     .get(value)
                     .unwrap_or(&PlantGrowthTypeToken::None);
-                let growth = PlantGrowth::new(growth_type);
+                let growth = PlantGrowth::with_identifier(value, growth_type);
                 if let Some(growths) = self.growths.as_mut() {
                     growths.push(growth);
                 }
@@ -4,7 +4,7 @@ use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
 use tracing::{error, warn};
 
 use crate::{
-    Name,
+    GrowthPrint, Name, is_in_timing,
     raw_definitions::{PLANT_GROWTH_TOKENS, PLANT_PART_TOKENS},
     tokens::{PlantGrowthToken, PlantGrowthTypeToken, PlantPartToken},
 };
@@ -24,8 +24,11 @@ use crate::{
 )]
 #[serde(rename_all = "camelCase")]
 pub struct PlantGrowth {
-    /// Plant growths are not given an identifier, since they are just supporting
-    /// data for the plant definition. They are defined instead by the type of growth.
+    /// The identifier of the growth, as written in the `GROWTH` token (e.g. `FRUIT`). This is only unique
+    /// within the plant, and is used by `PLANT_GRAPHICS` to give the growth its sprites.
+    #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
+    identifier: String,
+    /// The type of growth, which is determined by the identifier (unknown identifiers have no type).
     growth_type: PlantGrowthTypeToken,
     /// The name of the growth. This is actually defined with `GROWTH_NAME` key in the raws.
     pub name: Name,
@@ -48,10 +51,10 @@ pub struct PlantGrowth {
     /// Currently has no effect.
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     density: Option<u32>,
-    /// Specifies the appearance of the growth. This is defined with `GROWTH_PRINT` key.
-    /// This is a string until we make a proper print structure.
+    /// Specifies the appearance of the growth. This is defined with `GROWTH_PRINT` key, which can be
+    /// used more than once to change the appearance through the year (the stages of the growth).
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
-    print: Option<String>,
+    prints: Option<Vec<GrowthPrint>>,
     /// Specifies at which part of the year the growth appears. Default is all year round.
     /// Minimum: 0, Maximum: `402_200`. This is defined with `GROWTH_TIMING` key.
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
@@ -79,10 +82,65 @@ impl PlantGrowth {
             ..Self::default()
         }
     }
+    /// Create a new plant growth from its `GROWTH` identifier, e.g. `FRUIT`
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The identifier of the growth
+    /// * `growth_type` - The type of growth
+    ///
+    /// # Returns
+    ///
+    /// A new plant growth
+    #[must_use]
+    pub fn with_identifier(identifier: &str, growth_type: PlantGrowthTypeToken) -> Self {
+        Self {
+            identifier: String::from(identifier),
+            growth_type,
+            ..Self::default()
+        }
+    }
+    /// Returns the identifier of the growth, as written in the `GROWTH` token
+    #[must_use]
+    pub fn get_identifier(&self) -> &str {
+        &self.identifier
+    }
     /// Returns the type of growth this is
     pub fn get_growth_type(&self) -> &PlantGrowthTypeToken {
         &self.growth_type
     }
+    /// Returns the part of the year the growth is present, in ticks, or `None` if it is present all year
+    #[must_use]
+    pub const fn get_timing(&self) -> Option<[u32; 2]> {
+        self.timing
+    }
+    /// Returns the appearances of the growth through the year, in the order they were defined
+    #[must_use]
+    pub fn get_prints(&self) -> &[GrowthPrint] {
+        self.prints.as_deref().unwrap_or_default()
+    }
+    /// Returns true if the growth is present on the given day of the year (0 to 335)
+    #[must_use]
+    pub fn is_present_on_day(&self, day_of_year: u32) -> bool {
+        self.timing
+            .is_none_or(|timing| is_in_timing(timing, day_of_year))
+    }
+    /// Returns the stage of the growth on the given day of the year, which is the first print used on
+    /// that day. The stage is the index of the print (so stage 0 is drawn by `GROWTH_1`).
+    ///
+    /// # Returns
+    ///
+    /// The stage and its print, or `None` if the growth isn't present or has no print for that day
+    #[must_use]
+    pub fn get_stage_on_day(&self, day_of_year: u32) -> Option<(usize, &GrowthPrint)> {
+        if !self.is_present_on_day(day_of_year) {
+            return None;
+        }
+        self.get_prints()
+            .iter()
+            .enumerate()
+            .find(|(_, print)| print.is_used_on_day(day_of_year))
+    }
     /// Returns true if tag exists on this plant growth
     pub fn has_tag(&self, tag: &PlantGrowthToken) -> bool {
         if let Some(tags) = &self.tags {
@@ -197,7 +255,9 @@ impl PlantGrowth {
                 self.timing = Some([start, end]);
             }
             PlantGrowthToken::GrowthPrint => {
-                self.print = Some(value.to_string());
+                if let Some(print) = GrowthPrint::from_value(value) {
+                    self.prints.get_or_insert_with(Vec::new).push(print);
+                }
             }
             _ => {
                 // If we don't recognize the tag, we just add it to the list.
@@ -4,7 +4,8 @@ use dfraw_parser_proc_macros::IsEmpty;
 use tracing::{error, warn};
 
 use crate::{
-    Color, SeedMaterial,
+    Color, SeedMaterial, Tile,
+    custom_types::TileCharacter,
     raw_definitions::SHRUB_TOKENS,
     tokens::{SeasonToken, ShrubToken},
 };
@@ -133,6 +134,56 @@ impl Shrub {
             ..Self::default()
         }
     }
+    /// The tile drawn for the plant when it's picked whole or ready to harvest, `231` (`τ`) in dark green
+    /// unless set.
+    #[must_use]
+    pub fn get_picked_tile(&self) -> Tile {
+        map_tile(
+            self.picked_tile.unwrap_or(231),
+            self.picked_color.as_ref(),
+            (2, 0, 0),
+        )
+    }
+    /// The tile drawn for the plant when it was picked whole and has wilted, `169` (`⌐`) in dark gray
+    /// unless set.
+    #[must_use]
+    pub fn get_dead_picked_tile(&self) -> Tile {
+        map_tile(
+            self.dead_picked_tile.unwrap_or(169),
+            self.dead_picked_color.as_ref(),
+            (0, 0, 1),
+        )
+    }
+    /// The tile drawn for the plant when it's wild, alive and without growths, `34` (`"`) in dark green
+    /// unless set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dfraw_parser::Shrub;
+    ///
+    /// let mut shrub = Shrub::new();
+    /// assert_eq!(shrub.get_shrub_tile().to_ansi_string(), "\x1b[38;2;0;128;0;48;2;0;0;0m\"\x1b[0m");
+    /// shrub.parse_tag("SHRUB_TILE", "5");
+    /// assert_eq!(shrub.get_shrub_tile().get_tile_character().map(|chr| chr.to_unicode()), Some('♣'));
+    /// ```
+    #[must_use]
+    pub fn get_shrub_tile(&self) -> Tile {
+        map_tile(
+            self.shrub_tile.unwrap_or(34),
+            self.shrub_color.as_ref(),
+            (2, 0, 0),
+        )
+    }
+    /// The tile drawn for the plant when it's dead in the wild, `34` (`"`) in brown unless set.
+    #[must_use]
+    pub fn get_dead_shrub_tile(&self) -> Tile {
+        map_tile(
+            self.dead_shrub_tile.unwrap_or(34),
+            self.dead_shrub_color.as_ref(),
+            (6, 0, 0),
+        )
+    }
     /// Parses a tag and sets the appropriate field.
     ///
     /// # Arguments
@@ -294,3 +345,9 @@ impl Shrub {
         }
     }
 }
+
+/// The tile drawn for a tile and color set in the raws, using the default for the color if it isn't
+fn map_tile(tile: u8, color: Option<&Color>, default_color: (u8, u8, u8)) -> Tile {
+    let color = color.cloned().unwrap_or_else(|| Color::from(default_color));
+    Tile::from_character_and_color(TileCharacter::from_cp437(tile), color)
+}
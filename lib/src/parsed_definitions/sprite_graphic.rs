@@ -7,7 +7,9 @@ use tracing::warn;
 use crate::{
     Dimensions,
     raw_definitions::{CONDITION_TOKENS, GRAPHIC_TYPE_TOKENS},
-    tokens::{ColorModificationToken, ConditionToken, GraphicTypeToken},
+    tokens::{
+        ColorModificationToken, ConditionToken, GraphicCategory, GraphicTypeToken, GrowthToken,
+    },
 };
 
 /// A struct representing a sprite graphic.
@@ -40,10 +42,24 @@ pub struct SpriteGraphic {
     secondary_condition: Option<ConditionToken>,
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     color_pallet_swap: Option<u32>,
+    /// The variant index given before the tile page of tool sprites, e.g. `3` for `TOOL_GRAPHICS_WOOD_VARIANT:3`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[is_empty(only_if_none)]
+    variant_index: Option<u32>,
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     target_identifier: Option<String>,
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     extra_descriptor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[is_empty(only_if_none)]
+    growth: Option<GrowthToken>,
+    /// The specific graphic type (e.g. `WEAPON_GRAPHICS_UPRIGHT_1T`) of item, building and terrain sprites
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[is_empty(only_if_none)]
+    graphic_type: Option<GraphicTypeToken>,
+    /// The material the sprite is for, e.g. `WOOD` for `TOY_GRAPHICS` or `GLASS_GREEN` for `ROUGH_GEM_GRAPHICS`
+    #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
+    material: Option<String>,
 }
 
 impl SpriteGraphic {
@@ -73,6 +89,44 @@ impl SpriteGraphic {
             None => "",
         }
     }
+    /// Get the growth token the sprite was defined with (e.g. `GROWTH_1`), for the sprites of a growth
+    /// in `PLANT_GRAPHICS`.
+    #[must_use]
+    pub const fn get_growth(&self) -> Option<GrowthToken> {
+        self.growth
+    }
+    /// Set the growth token the sprite was defined with.
+    pub const fn set_growth(&mut self, growth: GrowthToken) {
+        self.growth = Some(growth);
+    }
+    /// Set the identifier of the thing the sprite is for (e.g. the item of a `WEAPON_GRAPHICS_DEFAULT`
+    /// sprite, which is named by the `WEAPON_GRAPHICS` before it).
+    pub fn set_target_identifier(&mut self, target_identifier: &str) {
+        self.target_identifier = Some(String::from(target_identifier));
+    }
+    /// Get the specific graphic type the sprite was defined with, for item, building and terrain sprites
+    /// (e.g. `TOOL_GRAPHICS_WOOD` or `CUSTOM_EDGE_NW`)
+    #[must_use]
+    pub const fn get_graphic_type(&self) -> Option<GraphicTypeToken> {
+        self.graphic_type
+    }
+    /// Get the material the sprite is for, e.g. `WOOD` for `[TOY_GRAPHICS:ITEM_TOY:0:4:ITEM_TOY_MINIFORGE:WOOD]`
+    #[must_use]
+    pub fn get_material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+    /// Get the extra descriptor of the sprite, which is the shape for `TOOL_GRAPHICS_SHAPE` and the gem
+    /// shapes (e.g. `BAGUETTE_CUT_GEM`)
+    #[must_use]
+    pub fn get_extra_descriptor(&self) -> Option<&str> {
+        self.extra_descriptor.as_deref()
+    }
+    /// Get the variant index given before the tile page, e.g. `3` for
+    /// `[TOOL_GRAPHICS_WOOD_VARIANT:3:ITEM_BOOKCASE:9:0]`
+    #[must_use]
+    pub const fn get_variant_index(&self) -> Option<u32> {
+        self.variant_index
+    }
     /// Get the tile page ID.
     ///
     /// # Returns
@@ -95,12 +149,54 @@ impl SpriteGraphic {
     /// An option containing the sprite graphic.
     #[must_use]
     pub fn from_token(key: &str, value: &str, graphic_type: GraphicTypeToken) -> Option<Self> {
-        // Recombine token for parsing
-        let token = format!("{key}:{value}");
         let specific_graphic_type = GRAPHIC_TYPE_TOKENS
             .get(key)
             .copied()
             .unwrap_or(graphic_type);
+        let mut sprite = Self::from_token_with_graphic_type(key, value, specific_graphic_type)?;
+
+        // Keep the specific type of item, building and terrain sprites, since their condition doesn't
+        // tell them apart
+        if matches!(
+            specific_graphic_type.get_category(),
+            GraphicCategory::Item
+                | GraphicCategory::Building
+                | GraphicCategory::Terrain
+                | GraphicCategory::Interface
+        ) && !specific_graphic_type.is_target_header()
+        {
+            sprite.graphic_type = Some(specific_graphic_type);
+        }
+
+        match specific_graphic_type {
+            // .[ROUGH_GEM_GRAPHICS:BOULDERS:0:8:GLASS_GREEN] targets a material instead of an item
+            GraphicTypeToken::RoughGem => {
+                sprite.material = sprite.target_identifier.take();
+            }
+            // .[TOY_GRAPHICS:ITEM_TOY:0:4:ITEM_TOY_MINIFORGE:WOOD] targets an item made of a material
+            GraphicTypeToken::Toy => {
+                if let Some((target, material)) = sprite
+                    .target_identifier
+                    .as_deref()
+                    .and_then(|target| target.split_once(':'))
+                {
+                    sprite.material = Some(String::from(material));
+                    sprite.target_identifier = Some(String::from(target));
+                }
+            }
+            _ => {}
+        }
+
+        Some(sprite)
+    }
+    /// Parses the sprite for a token of a known graphic type.
+    fn from_token_with_graphic_type(
+        key: &str,
+        value: &str,
+        specific_graphic_type: GraphicTypeToken,
+    ) -> Option<Self> {
+        // Recombine token for parsing
+        let token = format!("{key}:{value}");
 
         match specific_graphic_type {
             GraphicTypeToken::Creature
@@ -123,7 +219,7 @@ impl SpriteGraphic {
             | GraphicTypeToken::ToolGlassVariant
             | GraphicTypeToken::ToolMetalVariant
             | GraphicTypeToken::ToolStoneVariant
-            | GraphicTypeToken::ToolDamage => Self::parse_tile_with_color_pallet_from_value(value),
+            | GraphicTypeToken::ToolDamage => Self::parse_tile_with_variant_index_from_value(value),
             GraphicTypeToken::ToolShape
             | GraphicTypeToken::ShapeLargeGem
             | GraphicTypeToken::ShapeSmallGem => {
@@ -216,12 +312,12 @@ impl SpriteGraphic {
         })
     }
     #[tracing::instrument]
-    fn parse_tile_with_color_pallet_from_value(value: &str) -> Option<Self> {
+    fn parse_tile_with_variant_index_from_value(value: &str) -> Option<Self> {
         // .[TOOL_GRAPHICS_WOOD:        1:      ITEM_BOOKCASE:      0:      0]
-        // (     key                color_id    tile_page_id    offset_x   offset_y)
+        // (     key              variant_index tile_page_id    offset_x   offset_y)
         let mut split = value.split(':');
 
-        let color_id: u32 = match split.next() {
+        let variant_index: u32 = match split.next() {
             Some(v) => match v.parse() {
                 Ok(n) => n,
                 Err(_e) => {
@@ -261,7 +357,7 @@ impl SpriteGraphic {
             Ok(n) => n,
             Err(_e) => {
                 warn!(
-                    "parse_tile_with_color_pallet_from_value: Failed to parse {} as offset_x {}",
+                    "parse_tile_with_variant_index_from_value: Failed to parse {} as offset_x {}",
                     tile_offset_x, value
                 );
                 return None;
@@ -272,7 +368,7 @@ impl SpriteGraphic {
             Ok(n) => n,
             Err(_e) => {
                 warn!(
-                    "parse_tile_with_color_pallet_from_value: Failed to parse {} as offset_y {}",
+                    "parse_tile_with_variant_index_from_value: Failed to parse {} as offset_y {}",
                     tile_offset_y, value
                 );
                 return None;
@@ -282,7 +378,7 @@ impl SpriteGraphic {
         Some(Self {
             tile_page_id: tile_sheet,
             offset: Dimensions::from_xy(offset_x, offset_y),
-            color_pallet_swap: Some(color_id),
+            variant_index: Some(variant_index),
             ..Self::default()
         })
     }
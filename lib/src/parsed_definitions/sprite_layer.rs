@@ -35,6 +35,11 @@ pub struct SpriteLayer {
     /// An array of required conditions for this sprite to be visible/used
     #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
     conditions: Option<Vec<(ConditionToken, String)>>,
+    /// The `LAYER_GROUP` this layer belongs to (numbered in order within the graphic), if any.
+    ///
+    /// Only the first matching layer in a group is drawn.
+    #[serde(skip_serializing_if = "crate::traits::IsEmpty::is_empty")]
+    layer_group: Option<u32>,
 }
 
 impl SpriteLayer {
@@ -62,6 +67,20 @@ impl SpriteLayer {
     pub fn get_tile_page_id(&self) -> &str {
         self.tile_page_id.as_str()
     }
+    /// Get the conditions required for this layer to be drawn, in the order they were defined
+    #[must_use]
+    pub fn get_conditions(&self) -> &[(ConditionToken, String)] {
+        self.conditions.as_deref().unwrap_or_default()
+    }
+    /// Get the `LAYER_GROUP` this layer belongs to, if any
+    #[must_use]
+    pub const fn get_layer_group(&self) -> Option<u32> {
+        self.layer_group
+    }
+    /// Set the `LAYER_GROUP` this layer belongs to
+    pub const fn set_layer_group(&mut self, layer_group: Option<u32>) {
+        self.layer_group = layer_group;
+    }
     /// Parse a condition token into a `LayerCondition`.
     ///
     /// # Parameters
@@ -81,6 +81,9 @@ impl SteamData {
             tags.push(String::from(tag));
         }
     }
+    pub fn get_key_value_tags(&self) -> Option<Vec<String>> {
+        self.key_value_tags.clone()
+    }
     pub fn get_tags(&self) -> Option<Vec<String>> {
         self.tags.clone()
     }
@@ -112,4 +115,7 @@ impl SteamData {
             self_metadata.push(String::from(metadata));
         }
     }
+    pub fn get_metadata(&self) -> Option<Vec<String>> {
+        self.metadata.clone()
+    }
 }
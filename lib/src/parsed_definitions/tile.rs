@@ -1,8 +1,10 @@
 //! Tile definition for DF Classic
 
+use std::str::FromStr as _;
+
 use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
 
-use super::color::Color;
+use super::{color::Color, custom_types::TileCharacter};
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(
@@ -32,6 +34,27 @@ pub struct Tile {
 }
 
 impl Tile {
+    /// Creates a tile which draws a character in a color, e.g. for the tiles of trees and shrubs which
+    /// are set by separate tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `character` - The character of the tile
+    /// * `color` - The color of the tile
+    ///
+    /// # Returns
+    ///
+    /// * `Tile` - The tile, with its character stored as the code page 437 index when it has one
+    #[must_use]
+    pub fn from_character_and_color(character: TileCharacter, color: Color) -> Self {
+        Self {
+            character: character
+                .get_cp437_index()
+                .map_or_else(|| character.to_string(), |index| index.to_string()),
+            color: Some(color),
+            ..Self::default()
+        }
+    }
     /// Sets the character of the tile
     ///
     /// # Arguments
@@ -225,4 +248,99 @@ impl Tile {
         self.set_glow_character(character);
         self
     }
+    /// Returns the character of the tile as a [`TileCharacter`], which can give its code page 437
+    /// index and the unicode character the game draws for it.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<TileCharacter>` - The character, or `None` if it isn't set or can't be parsed
+    #[must_use]
+    pub fn get_tile_character(&self) -> Option<TileCharacter> {
+        TileCharacter::from_str(&self.character).ok()
+    }
+    /// Returns the foreground and background RGB values of the tile, from the [`crate::DEFAULT_PALETTE`].
+    ///
+    /// Tiles without a color are drawn light gray on black.
+    ///
+    /// # Returns
+    ///
+    /// * `((u8, u8, u8), (u8, u8, u8))` - The foreground and background colors
+    #[must_use]
+    pub fn get_rgb(&self) -> ((u8, u8, u8), (u8, u8, u8)) {
+        let color = self.render_color();
+        (color.get_foreground_rgb(), color.get_background_rgb())
+    }
+    /// Renders the tile as it looks in the game, as its unicode character in its colors using ANSI
+    /// escape sequences.
+    ///
+    /// Tiles without a character are drawn as a space, and tiles without a color light gray on black.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The character wrapped in the escape sequences, ending with a reset
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dfraw_parser::Tile;
+    ///
+    /// let tile = Tile::default().with_character("236").with_color("4:0:1");
+    /// assert_eq!(tile.to_ansi_string(), "\x1b[38;2;255;0;0;48;2;0;0;0m∞\x1b[0m");
+    /// ```
+    #[must_use]
+    pub fn to_ansi_string(&self) -> String {
+        let character = self
+            .get_tile_character()
+            .map_or(' ', |character| character.to_unicode());
+        format!("{}{character}\x1b[0m", self.render_color().to_ansi_escape())
+    }
+    /// The color to draw the tile with
+    fn render_color(&self) -> Color {
+        self.color.clone().unwrap_or_else(|| Color::from((7, 0, 0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_rgb() {
+        let tile = Tile::default().with_character("'a'").with_color("2:1:1");
+        assert_eq!(tile.get_rgb(), ((0, 255, 0), (0, 0, 128)));
+        // A tile without a color is light gray on black
+        assert_eq!(
+            Tile::default().with_character("1").get_rgb(),
+            ((192, 192, 192), (0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_tile_ansi_string() {
+        let tile = Tile::default().with_character("'A'").with_color("1:7:0");
+        assert_eq!(
+            tile.to_ansi_string(),
+            "\x1b[38;2;0;0;128;48;2;192;192;192mA\x1b[0m"
+        );
+        // A tile without a character is drawn as a space
+        assert_eq!(
+            Tile::default().to_ansi_string(),
+            "\x1b[38;2;192;192;192;48;2;0;0;0m \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_tile_from_character_and_color() {
+        let tile =
+            Tile::from_character_and_color(TileCharacter::from_cp437(24), Color::from((2, 0, 0)));
+        assert_eq!(tile.get_character(), "24");
+        assert_eq!(
+            tile.get_tile_character().map(|chr| chr.to_unicode()),
+            Some('↑')
+        );
+        // Characters outside of code page 437 are kept as they are
+        let tile = Tile::from_character_and_color(TileCharacter { value: '☃' }, Color::default());
+        assert_eq!(tile.get_character(), "☃");
+        assert_eq!(tile.to_ansi_string(), "\x1b[38;2;0;0;0;48;2;0;0;0m☃\x1b[0m");
+    }
 }
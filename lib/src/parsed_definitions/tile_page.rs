@@ -55,6 +55,20 @@ impl TilePage {
     pub fn get_file_path(&self) -> PathBuf {
         self.file.clone()
     }
+    /// Get the path of the image as written in the `FILE` tag, i.e. relative to the directory of the raw
+    /// file which defined the tile page (which is the `graphics` directory of the module).
+    #[must_use]
+    pub fn get_relative_file_path(&self) -> PathBuf {
+        let raw_path = self
+            .metadata
+            .as_ref()
+            .map(|metadata| PathBuf::from(metadata.get_raw_file_path()))
+            .unwrap_or_default();
+        let raw_directory = raw_path.parent().unwrap_or(&raw_path);
+        self.file
+            .strip_prefix(raw_directory)
+            .map_or_else(|_| self.file.clone(), PathBuf::from)
+    }
     #[must_use]
     pub fn get_tile_dimensions(&self) -> Dimensions {
         self.tile_dim
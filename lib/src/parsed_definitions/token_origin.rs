@@ -0,0 +1,129 @@
+//! A record of where a token on a resolved creature came from.
+
+use uuid::Uuid;
+
+use crate::tokens::CreatureVariationRuleToken;
+
+/// Where a token applied during creature resolution came from
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum TokenSource {
+    /// The creature's own definition
+    Body,
+    /// Inserted at the start of the creature with `GO_TO_START`
+    GoToStart,
+    /// Inserted at the end of the creature with `GO_TO_END`
+    GoToEnd,
+    /// Inserted before a tag with `GO_TO_TAG`
+    GoToTag {
+        /// The tag the token was inserted before
+        tag: String,
+    },
+    /// Copied from another creature with `COPY_TAGS_FROM`
+    CopiedFrom {
+        /// The identifier of the creature the token was copied from
+        creature: String,
+        /// The name of the module the copied creature is from
+        module: String,
+        /// The `object_id` of the module the copied creature is from
+        module_object_id: Uuid,
+        /// Where the token came from on the copied creature
+        original: Box<TokenSource>,
+    },
+    /// Applied by a creature variation rule with `APPLY_CREATURE_VARIATION`
+    Variation {
+        /// The identifier of the creature variation
+        variation: String,
+        /// The rule which applied the token (with arguments substituted)
+        rule: CreatureVariationRuleToken,
+        /// The arguments the variation was applied with
+        args: Vec<String>,
+    },
+    /// Applied by a `CV_*` tag in the creature itself with `APPLY_CURRENT_CREATURE_VARIATION`
+    CurrentVariation {
+        /// The rule which applied the token
+        rule: CreatureVariationRuleToken,
+    },
+}
+
+/// What happened to a token during creature resolution
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, specta::Type)]
+pub enum TokenAction {
+    /// The token was added to the creature
+    Added,
+    /// The token was removed from the creature
+    Removed,
+    /// The token was converted (its value was replaced)
+    Converted,
+    /// The token selected the castes which the tokens after it apply to (e.g. `SELECT_CASTE:MALE`)
+    Selected,
+}
+
+/// A single step taken while resolving a creature, recording a token and where it came from.
+///
+/// These are only recorded when [`ParserOptions::trace_creature_resolution`] is enabled.
+///
+/// [`ParserOptions::trace_creature_resolution`]: crate::metadata::ParserOptions::trace_creature_resolution
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenOrigin {
+    /// The token as it appears in the raws (without brackets), e.g. `BODY_SIZE:0:0:500`
+    token: String,
+    /// The castes which were selected when the token was applied
+    castes: Vec<String>,
+    /// What happened to the token
+    action: TokenAction,
+    /// Where the token came from
+    source: TokenSource,
+}
+
+impl TokenOrigin {
+    /// Creates a new record of a token and where it came from
+    #[must_use]
+    pub fn new(token: &str, castes: &[String], action: TokenAction, source: TokenSource) -> Self {
+        Self {
+            token: String::from(token),
+            castes: castes.to_vec(),
+            action,
+            source,
+        }
+    }
+    /// Returns the token as it appears in the raws (without brackets)
+    #[must_use]
+    pub fn get_token(&self) -> &str {
+        &self.token
+    }
+    /// Returns the key of the token (the part before the first `:`)
+    #[must_use]
+    pub fn get_key(&self) -> &str {
+        self.token.split(':').next().unwrap_or_default()
+    }
+    /// Returns the castes which were selected when the token was applied
+    #[must_use]
+    pub fn get_castes(&self) -> &[String] {
+        &self.castes
+    }
+    /// Returns what happened to the token
+    #[must_use]
+    pub const fn get_action(&self) -> TokenAction {
+        self.action
+    }
+    /// Returns where the token came from
+    #[must_use]
+    pub const fn get_source(&self) -> &TokenSource {
+        &self.source
+    }
+    /// Returns a copy of this record as if it was copied from another creature with `COPY_TAGS_FROM`
+    #[must_use]
+    pub fn copied_from(&self, creature: &str, module: &str, module_object_id: Uuid) -> Self {
+        Self {
+            source: TokenSource::CopiedFrom {
+                creature: String::from(creature),
+                module: String::from(module),
+                module_object_id,
+                original: Box::new(self.source.clone()),
+            },
+            ..self.clone()
+        }
+    }
+}
@@ -10,6 +10,7 @@ use crate::{raw_definitions::CONDITION_TOKENS, traits::IsEmpty};
     Clone,
     PartialEq,
     Eq,
+    Hash,
     Default,
     specta::Type,
     Copy,
@@ -5,9 +5,7 @@ use lazy_regex::regex;
 use crate::{
     Creature,
     regex::VARIATION_ARGUMENT_RE,
-    utilities::{
-        apply_new_tag, argument_as_string, convert_tag, remove_tag, replace_args_in_string,
-    },
+    utilities::{CasteSelection, argument_as_string, replace_args_in_string},
 };
 
 /// A variation rule for a creature.
@@ -243,6 +241,9 @@ impl CreatureVariationRuleToken {
     /// Apply the rule to a creature. This will apply the rule to the creature based on the arguments
     /// provided.
     ///
+    /// The rule is applied to the caste currently selected on the creature. Use
+    /// [`Self::apply_to_selection`] to apply it while tracking a caste selection.
+    ///
     /// # Arguments
     ///
     /// * `creature` - The creature to apply the rule to.
@@ -252,18 +253,42 @@ impl CreatureVariationRuleToken {
     ///
     /// This will modify the creature provided.
     pub fn apply(&self, creature: &mut Creature, args: &[&str]) {
+        let mut selection = CasteSelection::current(creature);
+        self.apply_to_selection(creature, args, &mut selection);
+    }
+    /// Apply the rule to the selected castes of a creature, based on the arguments provided.
+    ///
+    /// Caste tags are added, removed or converted on the selected castes. If the rule adds a caste
+    /// selection tag (e.g. `[CV_NEW_TAG:SELECT_CASTE:FEMALE]`) the selection is updated, so later rules
+    /// (and the rest of the creature) apply to the newly selected castes.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature` - The creature to apply the rule to.
+    /// * `args` - The arguments to apply to the rule.
+    /// * `selection` - The castes currently selected on the creature.
+    ///
+    /// # Side Effects
+    ///
+    /// This will modify the creature and selection provided.
+    pub fn apply_to_selection(
+        &self,
+        creature: &mut Creature,
+        args: &[&str],
+        selection: &mut CasteSelection,
+    ) {
         match self.with_args(args) {
             Self::RemoveTag { tag, .. } => {
-                remove_tag(creature, &tag);
+                selection.remove_tag(creature, &tag, "");
             }
             Self::NewTag { tag, value } | Self::AddTag { tag, value } => {
-                apply_new_tag(creature, &tag, value.as_deref());
+                selection.apply_tag(creature, &tag, value.as_deref().unwrap_or_default());
             }
             Self::ConvertTag {
                 tag,
                 target,
                 replacement,
-            } => convert_tag(creature, &tag, target.as_deref(), replacement.as_deref()),
+            } => selection.convert_tag(creature, &tag, target.as_deref(), replacement.as_deref()),
             Self::ConditionalNewTag {
                 tag,
                 value,
@@ -276,20 +301,8 @@ impl CreatureVariationRuleToken {
                 argument_index,
                 argument_requirement,
             } => {
-                // Guard against out of bounds arguments.
-                if args.len() < argument_index {
-                    tracing::warn!(
-                        "Creature Variation Argument index {} is out of bounds for {:?}",
-                        argument_index,
-                        args
-                    );
-                    return;
-                }
-                // Check if the argument matches the requirement.
-                if let Some(argument_value) = args.get(argument_index - 1)
-                    && argument_value == &argument_requirement
-                {
-                    apply_new_tag(creature, &tag, value.as_deref());
+                if Self::argument_matches(args, argument_index, &argument_requirement) {
+                    selection.apply_tag(creature, &tag, value.as_deref().unwrap_or_default());
                 }
             }
             Self::ConditionalRemoveTag {
@@ -298,20 +311,8 @@ impl CreatureVariationRuleToken {
                 argument_requirement,
                 ..
             } => {
-                // Guard against out of bounds arguments.
-                if args.len() < argument_index {
-                    tracing::warn!(
-                        "Creature Variation Argument index {} is out of bounds for {:?}",
-                        argument_index,
-                        args
-                    );
-                    return;
-                }
-                // Check if the argument matches the requirement.
-                if let Some(argument_value) = args.get(argument_index - 1)
-                    && argument_value == &argument_requirement
-                {
-                    remove_tag(creature, &tag);
+                if Self::argument_matches(args, argument_index, &argument_requirement) {
+                    selection.remove_tag(creature, &tag, "");
                 }
             }
             Self::ConditionalConvertTag {
@@ -321,25 +322,32 @@ impl CreatureVariationRuleToken {
                 argument_index,
                 argument_requirement,
             } => {
-                // Guard against out of bounds arguments.
-                if args.len() < argument_index {
-                    tracing::warn!(
-                        "Creature Variation Argument index {} is out of bounds for {:?}",
-                        argument_index,
-                        args
+                if Self::argument_matches(args, argument_index, &argument_requirement) {
+                    selection.convert_tag(
+                        creature,
+                        &tag,
+                        target.as_deref(),
+                        replacement.as_deref(),
                     );
-                    return;
-                }
-                // Check if the argument matches the requirement.
-                if let Some(argument_value) = args.get(argument_index - 1)
-                    && argument_value == &argument_requirement
-                {
-                    convert_tag(creature, &tag, target.as_deref(), replacement.as_deref());
                 }
             }
             Self::Unknown => {}
         }
     }
+    /// Check if the argument at `argument_index` (1-based) matches the requirement.
+    fn argument_matches(args: &[&str], argument_index: usize, argument_requirement: &str) -> bool {
+        // Guard against out of bounds arguments.
+        if argument_index == 0 || args.len() < argument_index {
+            tracing::warn!(
+                "Creature Variation Argument index {} is out of bounds for {:?}",
+                argument_index,
+                args
+            );
+            return false;
+        }
+        args.get(argument_index - 1)
+            .is_some_and(|argument_value| *argument_value == argument_requirement)
+    }
 }
 
 impl std::fmt::Display for CreatureVariationRuleToken {
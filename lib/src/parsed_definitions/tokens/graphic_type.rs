@@ -260,6 +260,205 @@ pub enum GraphicTypeToken {
     WeaponUpright10B,
 }
 
+impl GraphicTypeToken {
+    /// Get the category of things drawn by this graphic type
+    #[must_use]
+    pub const fn get_category(&self) -> GraphicCategory {
+        match self {
+            Self::Creature
+            | Self::CreatureCaste
+            | Self::StatueCreature
+            | Self::StatueCreatureCaste
+            | Self::StatuesSurfaceGiant => GraphicCategory::Creature,
+            Self::Plant => GraphicCategory::Plant,
+            Self::SoilBackground
+            | Self::Grass1
+            | Self::Grass2
+            | Self::Grass3
+            | Self::Grass4
+            | Self::CustomEdging
+            | Self::CustomRamp
+            | Self::CustomEdgeW
+            | Self::CustomEdgeE
+            | Self::CustomEdgeN
+            | Self::CustomEdgeS
+            | Self::CustomEdgeNW
+            | Self::CustomEdgeNE
+            | Self::CustomEdgeSW
+            | Self::CustomEdgeSE => GraphicCategory::Terrain,
+            Self::CustomWorkshop
+            | Self::ToolHiveBuilding
+            | Self::TrapComponentWeaponTrap
+            | Self::TrapComponentUpright1T
+            | Self::TrapComponentUpright2T
+            | Self::TrapComponentUpright3T
+            | Self::TrapComponentUpright4T
+            | Self::TrapComponentUpright5T
+            | Self::TrapComponentUpright6T
+            | Self::TrapComponentUpright7T
+            | Self::TrapComponentUpright8T
+            | Self::TrapComponentUpright9T
+            | Self::TrapComponentUpright10T
+            | Self::TrapComponentUpright1B
+            | Self::TrapComponentUpright2B
+            | Self::TrapComponentUpright3B
+            | Self::TrapComponentUpright4B
+            | Self::TrapComponentUpright5B
+            | Self::TrapComponentUpright6B
+            | Self::TrapComponentUpright7B
+            | Self::TrapComponentUpright8B
+            | Self::TrapComponentUpright9B
+            | Self::TrapComponentUpright10B
+            | Self::WeaponTrap
+            | Self::WeaponUpright1T
+            | Self::WeaponUpright2T
+            | Self::WeaponUpright3T
+            | Self::WeaponUpright4T
+            | Self::WeaponUpright5T
+            | Self::WeaponUpright6T
+            | Self::WeaponUpright7T
+            | Self::WeaponUpright8T
+            | Self::WeaponUpright9T
+            | Self::WeaponUpright10T
+            | Self::WeaponUpright1B
+            | Self::WeaponUpright2B
+            | Self::WeaponUpright3B
+            | Self::WeaponUpright4B
+            | Self::WeaponUpright5B
+            | Self::WeaponUpright6B
+            | Self::WeaponUpright7B
+            | Self::WeaponUpright8B
+            | Self::WeaponUpright9B
+            | Self::WeaponUpright10B => GraphicCategory::Building,
+            Self::ListIcon => GraphicCategory::Interface,
+            Self::AddTool
+            | Self::Ammo
+            | Self::AmmoStraightDefault
+            | Self::AmmoStraightWood
+            | Self::AmmoDiagonalDefault
+            | Self::AmmoDiagonalWood
+            | Self::Armor
+            | Self::Food
+            | Self::Gloves
+            | Self::Helm
+            | Self::Pants
+            | Self::RoughGem
+            | Self::ShapeLargeGem
+            | Self::ShapeSmallGem
+            | Self::Shield
+            | Self::ShieldWooden
+            | Self::Shoes
+            | Self::ShoesMetal
+            | Self::SiegeAmmo
+            | Self::SiegeAmmoStraightDefault
+            | Self::SiegeAmmoStraightWood
+            | Self::SiegeAmmoDiagonalDefault
+            | Self::SiegeAmmoDiagonalWood
+            | Self::Tool
+            | Self::ToolWood
+            | Self::ToolStone
+            | Self::ToolMetal
+            | Self::ToolGlass
+            | Self::ToolShape
+            | Self::ToolGlassVariant
+            | Self::ToolMetalVariant
+            | Self::ToolStoneVariant
+            | Self::ToolWoodVariant
+            | Self::ToolMud
+            | Self::ToolWater
+            | Self::ToolVomit
+            | Self::ToolBlood
+            | Self::ToolDamage
+            | Self::ToolBands
+            | Self::ToolEngraving
+            | Self::ToolStuds
+            | Self::ToolRings
+            | Self::ToolSpikes
+            | Self::Toy
+            | Self::TrapComponent
+            | Self::Weapon
+            | Self::WeaponDefault
+            | Self::WeaponWood
+            | Self::WeaponWoodGrown
+            | Self::WeaponMaterial => GraphicCategory::Item,
+            Self::Tile | Self::Empty | Self::Unknown | Self::Template => GraphicCategory::Other,
+        }
+    }
+    /// Returns true if this graphic type is a variant of the item named before it in a `TILE_GRAPHICS`
+    /// (by a header like `WEAPON_GRAPHICS`, or by the last sprite with a target), e.g.
+    /// `[TOOL_GRAPHICS_WOOD:1:TOOLS:0:11]` or `[WEAPON_GRAPHICS_UPRIGHT_1T:UPRIGHT_WEAPONS:0:3]`.
+    #[must_use]
+    pub const fn is_target_variant(&self) -> bool {
+        match self.get_category() {
+            GraphicCategory::Item | GraphicCategory::Building => {
+                !matches!(
+                    self,
+                    Self::Armor
+                        | Self::Food
+                        | Self::Gloves
+                        | Self::Helm
+                        | Self::Pants
+                        | Self::RoughGem
+                        | Self::ShapeLargeGem
+                        | Self::ShapeSmallGem
+                        | Self::Shield
+                        | Self::ShieldWooden
+                        | Self::Shoes
+                        | Self::ShoesMetal
+                        | Self::Tool
+                        | Self::Toy
+                        | Self::TrapComponent
+                        | Self::CustomWorkshop
+                ) && !self.is_target_header()
+            }
+            _ => false,
+        }
+    }
+    /// Returns true if this graphic type only names the item (or workshop) that the graphic types
+    /// following it in a `TILE_GRAPHICS` belong to, e.g. `[WEAPON_GRAPHICS:ITEM_WEAPON_WHIP]` is
+    /// followed by its `WEAPON_GRAPHICS_DEFAULT` and `WEAPON_GRAPHICS_UPRIGHT_1T` sprites.
+    #[must_use]
+    pub const fn is_target_header(&self) -> bool {
+        matches!(
+            self,
+            Self::AddTool | Self::Ammo | Self::SiegeAmmo | Self::Weapon | Self::CustomWorkshop
+        )
+    }
+}
+
+/// The category of things drawn by a [`GraphicTypeToken`]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    specta::Type,
+    Copy,
+    Hash,
+    strum_macros::EnumIter,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GraphicCategory {
+    /// Creatures, their castes and their statues
+    Creature,
+    /// Plants (trees, shrubs and crops)
+    Plant,
+    /// Items, which are targeted by their item identifier (e.g. `ITEM_TOOL_CAULDRON`)
+    Item,
+    /// Buildings and items placed in buildings (e.g. weapon traps and workshops)
+    Building,
+    /// Terrain, like grass and the edges of custom grasses
+    Terrain,
+    /// Icons for the interface
+    Interface,
+    /// Anything else (tiles without a specific type and templates)
+    #[default]
+    Other,
+}
+
 impl std::fmt::Display for GraphicTypeToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         std::fmt::Debug::fmt(self, f)
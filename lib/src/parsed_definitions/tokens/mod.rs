@@ -55,7 +55,7 @@ pub use environment_class::EnvironmentClassToken;
 pub use fuel_type::FuelTypeToken;
 pub use gait_modifier::GaitModifierToken;
 pub use gait_type::GaitTypeToken;
-pub use graphic_type::GraphicTypeToken;
+pub use graphic_type::{GraphicCategory, GraphicTypeToken};
 pub use growth::GrowthToken;
 pub use inclusion_type::InclusionTypeToken;
 pub use inorganic::InorganicToken;
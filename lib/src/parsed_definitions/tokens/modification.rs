@@ -18,6 +18,10 @@ pub enum ModificationToken {
         identifier: String,
     },
     /// `APPLY_CREATURE_VARIATION` tag
+    ///
+    /// When reading raw files, `APPLY_CREATURE_VARIATION` is kept in place within the raws so that it
+    /// applies to the castes selected at that point. This variant applies the variation to the castes
+    /// selected when it is reached during resolution.
     ApplyCreatureVariation {
         /// The creature to apply the variation from
         identifier: String,
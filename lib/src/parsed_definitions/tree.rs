@@ -4,7 +4,8 @@ use dfraw_parser_proc_macros::{Cleanable, IsEmpty};
 use tracing::{error, warn};
 
 use crate::{
-    Color, Name,
+    Color, Name, Tile,
+    custom_types::TileCharacter,
     raw_definitions::TREE_TOKENS,
     tokens::{TreeToken, TwigPlacementToken},
 };
@@ -172,6 +173,60 @@ impl Tree {
         }
     }
 
+    /// The tile drawn for trees of this type on the map, `24` (`↑`) in dark green unless set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dfraw_parser::Tree;
+    ///
+    /// let mut tree = Tree::new("WOOD");
+    /// assert_eq!(tree.get_tree_tile().get_character(), "24");
+    /// tree.parse_tag("TREE_TILE", "'T'");
+    /// tree.parse_tag("TREE_COLOR", "2:0:1");
+    /// assert_eq!(tree.get_tree_tile().get_character(), "84");
+    /// assert_eq!(tree.get_tree_tile().get_rgb().0, (0, 255, 0));
+    /// ```
+    #[must_use]
+    pub fn get_tree_tile(&self) -> Tile {
+        map_tile(
+            self.tree_tile.as_deref(),
+            24,
+            self.tree_color.as_ref(),
+            (2, 0, 0),
+        )
+    }
+    /// The tile drawn for (un)dead and leafless trees of this type, `198` (`╞`) in dark gray unless set.
+    #[must_use]
+    pub fn get_dead_tree_tile(&self) -> Tile {
+        map_tile(
+            self.dead_tree_tile.as_deref(),
+            198,
+            self.dead_tree_color.as_ref(),
+            (0, 0, 1),
+        )
+    }
+    /// The tile drawn for saplings of this tree, `231` (`τ`) in dark green unless set.
+    #[must_use]
+    pub fn get_sapling_tile(&self) -> Tile {
+        map_tile(
+            self.sapling_tile.as_deref(),
+            231,
+            self.sapling_color.as_ref(),
+            (2, 0, 0),
+        )
+    }
+    /// The tile drawn for dead saplings of this tree, `231` (`τ`) in dark gray unless set.
+    #[must_use]
+    pub fn get_dead_sapling_tile(&self) -> Tile {
+        map_tile(
+            self.dead_sapling_tile.as_deref(),
+            231,
+            self.dead_sapling_color.as_ref(),
+            (0, 0, 1),
+        )
+    }
+
     /// Parse a new tag from the raw file into this raw object.
     ///
     /// # Arguments
@@ -492,3 +547,17 @@ impl Tree {
         }
     }
 }
+
+/// The tile drawn for a tile and color set in the raws, using the defaults for the ones which aren't
+fn map_tile(
+    tile: Option<&str>,
+    default_tile: u8,
+    color: Option<&Color>,
+    default_color: (u8, u8, u8),
+) -> Tile {
+    let character = tile
+        .and_then(|tile| tile.parse().ok())
+        .unwrap_or_else(|| TileCharacter::from_cp437(default_tile));
+    let color = color.cloned().unwrap_or_else(|| Color::from(default_color));
+    Tile::from_character_and_color(character, color)
+}
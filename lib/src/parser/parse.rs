@@ -3,13 +3,14 @@ use std::path::Path;
 use tracing::{error, info};
 
 use crate::{
-    Creature, CreatureVariation, ParserError, legends_export,
+    CreatureVariation, ParserError,
+    legends_export::{self, LegendsWorld},
     metadata::{ParserOptions, RawModuleLocation},
     parser::{parse_location, parse_module},
     reader::{UnprocessedRaw, parse_raw_file},
     tokens::ObjectType,
     traits::RawObject,
-    utilities::{clone_raw_object_box, log_summary, summarize_raws, validate_options},
+    utilities::{log_summary, summarize_raws, validate_options},
 };
 
 use super::{ParseResult, info_file::parse_module_info_files};
@@ -43,6 +44,7 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
     let mut results = ParseResult {
         raws: Vec::new(),
         modules: Vec::new(),
+        legends: LegendsWorld::default(),
     };
     let mut unprocessed_raws: Vec<UnprocessedRaw> = Vec::new();
 
@@ -137,17 +139,28 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
 
     // Finally we can check if any legends exports are specified
     if !options.legends_exports_to_parse.is_empty() {
-        // Parse all legends exports that are specified.
-        for legends_export in &options.legends_exports_to_parse {
-            let target_path = Path::new(&legends_export);
+        // Parse the legends exports of each world, merging the legends-plus exports into the base ones.
+        for world_exports in
+            legends_export::group_legends_exports_by_world(&options.legends_exports_to_parse)
+        {
+            let Some(first_export) = world_exports.first() else {
+                continue;
+            };
+            info!(
+                "Dispatching parse for {} legends export(s) of {:?}",
+                world_exports.len(),
+                legends_export::legends_world_path(first_export)
+                    .file_name()
+                    .unwrap_or_default()
+            );
+            let legends = legends_export::read_merged_legends_exports(&world_exports)?;
 
-            results
-                .raws
-                .extend(legends_export::parse(&target_path, &options)?);
+            results.raws.extend(legends.to_raws(first_export, &options));
+            results.legends.extend(legends.world);
         }
     }
 
-    // Resolve the unprocessed creatures
+    // Resolve the unprocessed raws (creatures, entities, etc.)
     // Prerequisites: build a list of creature variations
     let creature_variations: Vec<CreatureVariation> = results
         .raws
@@ -172,7 +185,7 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
         .collect();
 
     info!(
-        "Resolving {} unprocessed creatures using {} creature variation definitions",
+        "Resolving {} unprocessed raws using {} creature variation definitions",
         unprocessed_raws.len(),
         creature_variations.len()
     );
@@ -195,16 +208,20 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
         }
     }
 
-    // Resolve the simple creatures first
-    let resolved_simple_creatures: Vec<Creature> = simple_unprocessed
-        .iter_mut()
-        .filter(|raw| raw.raw_type() == ObjectType::Creature)
+    // Resolve the simple raws first
+    let resolved_simple_raws: Vec<Box<dyn RawObject>> = simple_unprocessed
+        .iter()
         .filter_map(|raw| {
-            match raw.resolve(creature_variations.as_slice(), results.raws.as_slice()) {
-                Ok(c) => Some(c),
+            match raw.resolve_with_trace(
+                creature_variations.as_slice(),
+                results.raws.as_slice(),
+                options.trace_creature_resolution,
+            ) {
+                Ok(resolved) => Some(resolved),
                 Err(e) => {
                     error!(
-                        "Unable to resolve simple creature {}: {:?}",
+                        "Unable to resolve simple {} {}: {:?}",
+                        raw.raw_type(),
                         raw.get_identifier(),
                         e
                     );
@@ -212,51 +229,37 @@ pub fn parse(options: &ParserOptions) -> Result<ParseResult, ParserError> {
                 }
             }
         })
-        .map(|c| clone_raw_object_box(&c))
-        .filter_map(|c| {
-            c.as_ref().as_any().downcast_ref::<Creature>().map_or_else(
-                || {
-                    error!("Downcast failed for simple creature {}", c.get_identifier());
-                    None
-                },
-                |creature| Some(creature.clone()),
-            )
-        })
         .collect();
 
-    info!(
-        "Resolved {} simple creatures",
-        resolved_simple_creatures.len()
-    );
+    info!("Resolved {} simple raws", resolved_simple_raws.len());
 
-    results.raws.extend(
-        resolved_simple_creatures
-            .iter()
-            .map(|c| Box::new(c.clone()) as Box<dyn RawObject>),
-    );
+    results.raws.extend(resolved_simple_raws);
 
-    // Now we can do the second pass through the unprocessed creatures, but add the complex creatures
+    // Now we can do the second pass through the unprocessed raws, but add the complex raws
     // to the results.raws vector as they are resolved.
-    let mut resolved_complex_creatures = 0_usize;
-    for unprocessed_raw in &mut complex_unprocessed {
-        if unprocessed_raw.raw_type() == ObjectType::Creature {
-            match unprocessed_raw.resolve(creature_variations.as_slice(), results.raws.as_slice()) {
-                Ok(c) => {
-                    resolved_complex_creatures += 1;
-                    results.raws.push(clone_raw_object_box(&c));
-                }
-                Err(e) => {
-                    error!(
-                        "Unable to resolve complex creature {}: {:?}",
-                        unprocessed_raw.get_identifier(),
-                        e
-                    );
-                }
+    let mut resolved_complex_raws = 0_usize;
+    for unprocessed_raw in &complex_unprocessed {
+        match unprocessed_raw.resolve_with_trace(
+            creature_variations.as_slice(),
+            results.raws.as_slice(),
+            options.trace_creature_resolution,
+        ) {
+            Ok(resolved) => {
+                resolved_complex_raws += 1;
+                results.raws.push(resolved);
+            }
+            Err(e) => {
+                error!(
+                    "Unable to resolve complex {} {}: {:?}",
+                    unprocessed_raw.raw_type(),
+                    unprocessed_raw.get_identifier(),
+                    e
+                );
             }
         }
     }
 
-    info!("Resolved {resolved_complex_creatures} complex creatures");
+    info!("Resolved {resolved_complex_raws} complex raws");
 
     // Parse the info modules
     results.modules = parse_module_info_files(&options)?;
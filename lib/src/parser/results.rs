@@ -1,7 +1,16 @@
-
 use uuid::Uuid;
 
-use crate::{ModuleInfo, traits::RawObject};
+use crate::{
+    Creature, Entity, Inorganic, ModuleInfo, Plant, SpriteGraphic,
+    graphics::{
+        GraphicsCoverageReport, GraphicsIssue, SeasonalGrowth, SpriteAtlas, SpriteExtractor,
+        SpriteState, find_best_sprite, graphics_coverage, growths_on_day, validate_graphics,
+    },
+    legends_export::{ExportedArtifact, ExportedEntity, ExportedHistoricalFigure, LegendsWorld},
+    references::{UnresolvedReference, find_unresolved_references},
+    tokens::ObjectType,
+    traits::RawObject,
+};
 
 /// A parsing result that contains the parsed raws and info files.
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -10,6 +19,9 @@ pub struct ParseResult {
     pub raws: Vec<Box<dyn RawObject>>,
     /// The parsed module info files.
     pub modules: Vec<ModuleInfo>,
+    /// The world (historical figures, sites, regions, etc.) from the parsed legends exports.
+    #[serde(default)]
+    pub legends: LegendsWorld,
 }
 
 impl ParseResult {
@@ -25,4 +37,120 @@ impl ParseResult {
     pub fn get_raws_for_module(&self, module: &ModuleInfo) -> Vec<&dyn RawObject> {
         self.get_raws_by_module_id(module.get_object_id())
     }
+    /// Find every reference between the parsed raws which doesn't resolve to a parsed object.
+    ///
+    /// See [`crate::references`] for which references are checked.
+    #[must_use]
+    pub fn find_unresolved_references(&self) -> Vec<UnresolvedReference> {
+        find_unresolved_references(&self.raws)
+    }
+    /// Find the best sprite to show a creature (or other target) in a state.
+    ///
+    /// See [`crate::graphics::find_best_sprite`] for how the sprite is chosen.
+    #[must_use]
+    pub fn find_best_sprite(
+        &self,
+        target_identifier: &str,
+        caste: Option<&str>,
+        state: SpriteState,
+    ) -> Option<SpriteGraphic> {
+        find_best_sprite(&self.raws, target_identifier, caste, state)
+    }
+    /// Check the parsed graphics against the tile pages they use, and the tile page images against their
+    /// declared size.
+    ///
+    /// See [`crate::graphics::validate_graphics`] for what is checked.
+    #[must_use]
+    pub fn validate_graphics(&self) -> Vec<GraphicsIssue> {
+        validate_graphics(&self.raws)
+    }
+    /// Report which creatures, castes, plants and growths have no graphics, and which graphics have
+    /// nothing to show, per module.
+    ///
+    /// See [`crate::graphics::graphics_coverage`] for how graphics are matched.
+    #[must_use]
+    pub fn graphics_coverage(&self) -> GraphicsCoverageReport {
+        graphics_coverage(&self.raws)
+    }
+    /// Pack the sprites of the parsed graphics into a texture atlas, with a manifest of where each sprite
+    /// is.
+    ///
+    /// The tile page images are loaded from the paths they were parsed with. Use [`SpriteAtlas::build`]
+    /// with a [`SpriteExtractor`] to load them from elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `module_object_id` - Only include the graphics of this module
+    /// * `image_name` - The file name of the atlas image, written to the manifest
+    #[must_use]
+    pub fn build_sprite_atlas(
+        &self,
+        module_object_id: Option<Uuid>,
+        image_name: &str,
+    ) -> SpriteAtlas {
+        let mut extractor = SpriteExtractor::new(&self.raws);
+        SpriteAtlas::build(&mut extractor, &self.raws, module_object_id, image_name)
+    }
+    /// Find the growths a plant has on a day of the year (0 to 335), and the sprite to draw each with.
+    ///
+    /// See [`crate::graphics::growths_on_day`] for how the growths and sprites are chosen.
+    ///
+    /// # Returns
+    ///
+    /// The present growths, or `None` if no plant with the identifier was parsed
+    #[must_use]
+    pub fn growths_on_day(
+        &self,
+        plant_identifier: &str,
+        day_of_year: u32,
+    ) -> Option<Vec<SeasonalGrowth>> {
+        self.raws
+            .iter()
+            .filter(|raw| {
+                raw.get_type() == ObjectType::Plant
+                    && raw.get_identifier().eq_ignore_ascii_case(plant_identifier)
+            })
+            .find_map(|raw| raw.as_any().downcast_ref::<Plant>())
+            .map(|plant| growths_on_day(&self.raws, plant, day_of_year))
+    }
+    /// Get the historical figures from the parsed legends exports whose race is a creature.
+    ///
+    /// Combine with [`ExportedHistoricalFigure::is_vampire`] to find e.g. the dwarven vampires of the world.
+    #[must_use]
+    pub fn get_historical_figures_of_creature(
+        &self,
+        creature_identifier: &str,
+    ) -> Vec<&ExportedHistoricalFigure> {
+        self.legends
+            .historical_figures
+            .iter()
+            .filter(|figure| {
+                figure
+                    .get_creature_identifier()
+                    .eq_ignore_ascii_case(creature_identifier)
+            })
+            .collect()
+    }
+    /// Find the parsed creature the race of a historical figure refers to.
+    #[must_use]
+    pub fn get_creature_for_historical_figure(
+        &self,
+        figure: &ExportedHistoricalFigure,
+    ) -> Option<&Creature> {
+        figure.find_creature(&self.raws)
+    }
+    /// Find the parsed entity raw a legends entity was created from, by its race.
+    ///
+    /// See [`ExportedEntity::find_entity`] for how the raw is chosen.
+    #[must_use]
+    pub fn get_entity_raw_for_legends_entity(&self, entity: &ExportedEntity) -> Option<&Entity> {
+        entity.find_entity(&self.raws)
+    }
+    /// Find the parsed inorganic an artifact is made of, by its material.
+    ///
+    /// See [`ExportedArtifact::find_inorganic`] for which artifacts have one.
+    #[must_use]
+    pub fn get_inorganic_for_artifact(&self, artifact: &ExportedArtifact) -> Option<&Inorganic> {
+        artifact.find_inorganic(&self.raws)
+    }
 }
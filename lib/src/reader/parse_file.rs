@@ -1,6 +1,6 @@
 use crate::{
-    CreatureVariation, Entity, Graphic, Inorganic, MaterialTemplate, ModuleInfo, ParserError,
-    Plant, TilePage,
+    CreatureVariation, Graphic, Inorganic, MaterialTemplate, ModuleInfo, ParserError, Plant,
+    TilePage,
     constants::DF_ENCODING,
     metadata::{ParserOptions, RawMetadata, RawModuleLocation},
     raw_definitions::{GRAPHIC_TYPE_TOKENS, OBJECT_TOKEN_MAP},
@@ -31,7 +31,8 @@ use super::{parse_result::FileParseResult, read_raw_file_type};
 ///
 /// # Returns
 ///
-/// Returns a `FileParseResult` containing all parsed objects from the file.
+/// Returns a `FileParseResult` containing all parsed objects from the file. Creatures, and entities which
+/// use `COPY_TAGS_FROM`, are returned as unprocessed raws to be resolved once all the raws are parsed.
 ///
 /// # Errors
 ///
@@ -130,7 +131,6 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
     let mut temp_inorganic = Inorganic::empty();
     let mut temp_graphic = Graphic::empty();
     let mut temp_material_template = MaterialTemplate::empty();
-    let mut temp_entity = Entity::empty();
     let mut temp_creature_variation = CreatureVariation::empty();
     let mut temp_unprocessed_raw = UnprocessedRaw::default();
 
@@ -256,7 +256,7 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                     // The entity object has a CREATURE tag
                     if started && last_parsed_type == ObjectType::Entity {
                         // We need to let the entity parse this tag.
-                        temp_entity.parse_tag(captured_key, captured_value);
+                        current_modification.add_raw(format!("{captured_key}:{captured_value}"));
                         // leave before adding a fake new creature
                         continue;
                     }
@@ -300,7 +300,7 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                     }
                     if started && object_type == ObjectType::Entity {
                         // We need to let the entity parse this tag.
-                        temp_entity.parse_tag(captured_key, captured_value);
+                        current_modification.add_raw(format!("{captured_key}:{captured_value}"));
                         // leave before adding a fake new creature
                         continue;
                     }
@@ -378,15 +378,18 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                     last_parsed_type = ObjectType::TilePage;
                 }
                 "ENTITY" => {
-                    // Starting a new entity, so we can just add an entity to the list.
+                    // Starting a new entity, which (like creatures) is resolved after all raws are parsed.
                     if started {
-                        // We need to add the entity to the list.
-                        created_raws.push(Box::new(temp_entity.clone()));
+                        temp_unprocessed_raw.add_modification(current_modification.clone());
+                        // We need to add the entity to the list of unprocessed raws.
+                        unprocessed_raws.push(temp_unprocessed_raw.clone());
                     } else {
                         started = true;
                     }
                     // We haven't started an entity yet, so we need to start one.
-                    temp_entity = Entity::new(captured_value, &raw_metadata.clone());
+                    temp_unprocessed_raw =
+                        UnprocessedRaw::new(ObjectType::Entity, &raw_metadata, captured_value);
+                    current_modification = ModificationToken::MainRawBody { raws: Vec::new() };
                     last_parsed_type = ObjectType::Entity;
                 }
                 "GO_TO_END" => {
@@ -421,13 +424,10 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                     });
                 }
                 "APPLY_CREATURE_VARIATION" => {
-                    trace!("began tracking ApplyCreatureVariation:{captured_value} modification");
-                    // Push the ApplyCreatureVariation modification to the unprocessed raw
-                    temp_unprocessed_raw.add_modification(
-                        ModificationToken::ApplyCreatureVariation {
-                            identifier: captured_value.to_string(),
-                        },
-                    );
+                    trace!("tracking ApplyCreatureVariation:{captured_value} in place");
+                    // Variations apply to the castes selected where they appear, so keep them in
+                    // place with the rest of the raws.
+                    current_modification.add_raw(format!("{captured_key}:{captured_value}"));
                 }
                 _ => {
                     // This should be a tag for the current object.
@@ -462,10 +462,11 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                             ObjectType::Graphics => {
                                 // We have a graphic, so we can add a tag to it.
                                 if temp_graphic.get_graphic_type() == GraphicTypeToken::Tile {
-                                    // Update graphic type (every line should have a graphic type tag)
+                                    // Each line of a tile graphic names its own graphic type; lines which
+                                    // don't (like a plain tile) are parsed as tiles of the graphic
                                     last_graphic_type = *GRAPHIC_TYPE_TOKENS
                                         .get(captured_key)
-                                        .unwrap_or(&GraphicTypeToken::Unknown);
+                                        .unwrap_or(&GraphicTypeToken::Tile);
                                 }
 
                                 temp_graphic.parse_sprite_from_tag(
@@ -480,7 +481,8 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
                             }
                             ObjectType::Entity => {
                                 // We have an entity, so we can add a tag to it.
-                                temp_entity.parse_tag(captured_key, captured_value);
+                                current_modification
+                                    .add_raw(format!("{captured_key}:{captured_value}"));
                             }
                             _ => {
                                 // We don't have a known raw yet. So do nothing.
@@ -512,14 +514,21 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
         if !temp_tile_page.is_empty() {
             created_raws.push(Box::new(temp_tile_page.clone()));
         }
-        if !temp_entity.is_empty() {
-            created_raws.push(Box::new(temp_entity.clone()));
-        }
         if !temp_creature_variation.is_empty() {
             created_raws.push(Box::new(temp_creature_variation.clone()));
         }
     }
 
+    // Entities which don't copy tags from another entity don't depend on any other raws, so they can be
+    // resolved straight away. The rest are resolved with the creatures once all raws are parsed.
+    let (simple_entities, unprocessed_raws): (Vec<UnprocessedRaw>, Vec<UnprocessedRaw>) =
+        unprocessed_raws
+            .into_iter()
+            .partition(|raw| raw.raw_type() == ObjectType::Entity && raw.is_simple());
+    for entity in &simple_entities {
+        created_raws.push(entity.resolve(&[], &[])?);
+    }
+
     debug!(
         "parse_raw_file_with_info: parsed {} raws from {}",
         created_raws.len(),
@@ -531,3 +540,182 @@ pub fn parse_raw_file_with_info<P: AsRef<Path>>(
         unprocessed_raws,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Entity, SpriteGraphic, tokens::GraphicCategory};
+
+    /// Lines taken from the vanilla item graphics (`vanilla_items_graphics`)
+    const ITEM_GRAPHICS: &str = "graphics_items_test
+
+[OBJECT:GRAPHICS]
+
+[TILE_GRAPHICS:TOOLS]
+    [TOOL_GRAPHICS:TOOLS:0:0:ITEM_TOOL_CAULDRON]
+    [ADD_TOOL_GRAPHICS:ITEM_TOOL_LARGE_POT]
+        [TOOL_GRAPHICS_WOOD:1:TOOLS:0:11]
+        [TOOL_GRAPHICS_STONE:1:TOOLS:1:11]
+    [TOOL_GRAPHICS:ITEM_BOOKCASE:0:0:ITEM_TOOL_BOOKCASE]
+        [TOOL_GRAPHICS_WOOD_VARIANT:3:ITEM_BOOKCASE:9:0]
+        [TOOL_GRAPHICS_BLOOD:ITEM_BOOKCASE:12:0]
+
+[TILE_GRAPHICS:WEAPONS]
+    [WEAPON_GRAPHICS:ITEM_WEAPON_WHIP]
+        [WEAPON_GRAPHICS_DEFAULT:WEAPONS:0:0]
+        [WEAPON_GRAPHICS_WOOD_GROWN:WEAPONS:1:0]
+        [WEAPON_GRAPHICS_UPRIGHT_1T:UPRIGHT_WEAPONS:0:3]
+    [AMMO_GRAPHICS:ITEM_AMMO_BOLTS]
+        [AMMO_GRAPHICS_DIAGONAL_WOOD:ITEM_AMMO:0:1]
+
+[TILE_GRAPHICS:MISC]
+    [TOY_GRAPHICS:ITEM_TOY:0:4:ITEM_TOY_MINIFORGE:WOOD]
+    [ROUGH_GEM_GRAPHICS:BOULDERS:0:8:GLASS_GREEN]
+    [SHAPE_GRAPHICS_LARGE_GEM:BAGUETTE_CUT_GEM:GEMS:1:0]
+    [ARMOR_GRAPHICS:ITEMS3:1:15:ITEM_ARMOR_BREASTPLATE]
+";
+
+    const ENTITIES: &str = "entity_test
+
+[OBJECT:ENTITY]
+
+[ENTITY:MOUNTAIN]
+    [CREATURE:DWARF]
+    [TRANSLATION:DWARF]
+
+[ENTITY:HILL]
+    [COPY_TAGS_FROM:MOUNTAIN]
+    [TRANSLATION:HUMAN]
+";
+
+    fn parse_test_file(name: &str, contents: &str) -> FileParseResult {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let path = temp_dir.path().join(format!("{name}.txt"));
+        std::fs::write(&path, contents).expect("Failed to write test raw file");
+        super::super::parse_raw_file(&path, &ParserOptions::default())
+            .expect("Failed to parse test raw file")
+    }
+
+    fn parse_graphics(name: &str, contents: &str) -> Vec<Graphic> {
+        parse_test_file(name, contents)
+            .parsed_raws
+            .iter()
+            .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>())
+            .cloned()
+            .collect()
+    }
+
+    fn summary(sprite: &SpriteGraphic) -> (Option<GraphicTypeToken>, &str, Option<u32>) {
+        (
+            sprite.get_graphic_type(),
+            sprite.get_target_identifier(),
+            sprite.get_variant_index(),
+        )
+    }
+
+    #[test]
+    fn test_tile_graphics_targets() {
+        let graphics = parse_graphics("tile_graphics", ITEM_GRAPHICS);
+        assert_eq!(graphics.len(), 3);
+
+        let tools = graphics[0].get_sprites();
+        assert_eq!(
+            tools.iter().map(summary).collect::<Vec<_>>(),
+            vec![
+                (Some(GraphicTypeToken::Tool), "ITEM_TOOL_CAULDRON", None),
+                (
+                    Some(GraphicTypeToken::ToolWood),
+                    "ITEM_TOOL_LARGE_POT",
+                    Some(1)
+                ),
+                (
+                    Some(GraphicTypeToken::ToolStone),
+                    "ITEM_TOOL_LARGE_POT",
+                    Some(1)
+                ),
+                (Some(GraphicTypeToken::Tool), "ITEM_TOOL_BOOKCASE", None),
+                (
+                    Some(GraphicTypeToken::ToolWoodVariant),
+                    "ITEM_TOOL_BOOKCASE",
+                    Some(3)
+                ),
+                (
+                    Some(GraphicTypeToken::ToolBlood),
+                    "ITEM_TOOL_BOOKCASE",
+                    None
+                ),
+            ]
+        );
+
+        let variant = serde_json::to_value(&tools[4]).expect("Failed to serialize sprite");
+        assert_eq!(variant["variantIndex"], 3);
+        assert!(variant.get("colorPalletSwap").is_none());
+
+        // The headers only name the item, so they aren't sprites themselves
+        let weapons = graphics[1].get_sprites();
+        assert_eq!(weapons.len(), 4);
+        assert_eq!(
+            graphics[1].get_sprites_for_target("ITEM_WEAPON_WHIP").len(),
+            3
+        );
+        assert_eq!(
+            weapons[2].get_graphic_type().map(|t| t.get_category()),
+            Some(GraphicCategory::Building)
+        );
+        assert_eq!(weapons[3].get_target_identifier(), "ITEM_AMMO_BOLTS");
+
+        let misc = graphics[2].get_sprites();
+        assert_eq!(misc[0].get_target_identifier(), "ITEM_TOY_MINIFORGE");
+        assert_eq!(misc[0].get_material(), Some("WOOD"));
+        assert_eq!(misc[1].get_target_identifier(), "");
+        assert_eq!(misc[1].get_material(), Some("GLASS_GREEN"));
+        assert_eq!(misc[2].get_extra_descriptor(), Some("BAGUETTE_CUT_GEM"));
+        assert_eq!(misc[2].get_target_identifier(), "");
+        assert_eq!(misc[3].get_target_identifier(), "ITEM_ARMOR_BREASTPLATE");
+    }
+
+    #[test]
+    fn test_tile_graphics_round_trip() {
+        for graphic in parse_graphics("tile_graphics_round_trip", ITEM_GRAPHICS) {
+            let json = serde_json::to_value(&graphic).expect("Failed to serialize graphic");
+            let round_trip: Graphic =
+                serde_json::from_value(json.clone()).expect("Failed to deserialize graphic");
+            assert_eq!(
+                serde_json::to_value(&round_trip).expect("Failed to serialize graphic"),
+                json
+            );
+            assert_eq!(
+                round_trip
+                    .get_sprites()
+                    .iter()
+                    .map(summary)
+                    .collect::<Vec<_>>(),
+                graphic
+                    .get_sprites()
+                    .iter()
+                    .map(summary)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_entities_are_parsed() {
+        let result = parse_test_file("entities", ENTITIES);
+
+        // Entities without `COPY_TAGS_FROM` are resolved with the file
+        let entities: Vec<&Entity> = result
+            .parsed_raws
+            .iter()
+            .filter_map(|raw| raw.as_any().downcast_ref::<Entity>())
+            .collect();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].get_identifier(), "MOUNTAIN");
+        assert_eq!(entities[0].get_creature(), Some("DWARF"));
+
+        // The others have to wait until the entity they copy is parsed
+        assert_eq!(result.unprocessed_raws.len(), 1);
+        assert_eq!(result.unprocessed_raws[0].get_identifier(), "HILL");
+        assert_eq!(result.unprocessed_raws[0].raw_type(), ObjectType::Entity);
+    }
+}
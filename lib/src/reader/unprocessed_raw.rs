@@ -1,14 +1,17 @@
 use tracing::{debug, trace};
 
 use crate::{
-    Creature, CreatureVariation, ParserError,
+    Creature, CreatureVariation, Entity, ParserError, TokenAction, TokenOrigin, TokenSource,
     metadata::RawMetadata,
     tokens::{ModificationToken, ObjectType},
-    traits::RawObject,
-    utilities::singularly_apply_creature_variation,
+    traits::{RawObject, RawToken as _, Resolvable},
+    utilities::{CasteSelection, apply_creature_variation_to_selection},
 };
 
 /// An unprocessed raw object
+///
+/// Raw objects which use deferred resolution (see [`Resolvable`]) are collected as these while the raw
+/// files are read, and resolved into the final object once all the raws have been parsed.
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct UnprocessedRaw {
@@ -211,60 +214,153 @@ impl UnprocessedRaw {
     ///
     /// # Errors
     ///
-    /// * `ParserError::NotYetImplemented` - If the raw type is not yet implemented
+    /// * `ParserError::NotYetImplemented` - If the raw type does not support deferred resolution
+    ///   (currently creatures and entities do, see [`Resolvable`])
     pub fn resolve(
-        &mut self,
+        &self,
         creature_variations: &[CreatureVariation],
         all_raws: &[Box<dyn RawObject>],
     ) -> Result<Box<dyn RawObject>, ParserError> {
-        if self.raw_type != ObjectType::Creature {
-            return Err(ParserError::NotYetImplemented);
+        self.resolve_with_trace(creature_variations, all_raws, false)
+    }
+
+    /// Resolves the raws into the final object, optionally recording where each tag came from.
+    ///
+    /// When `trace` is true, the resolved creature has a resolution trace (see
+    /// [`Creature::get_resolution_trace`]) with a [`TokenOrigin`] for every tag applied to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature_variations` - all possible creature variations to apply
+    /// * `all_raws` - All the raws to use when resolving the raws
+    /// * `trace` - Whether to record where each tag came from
+    ///
+    /// # Returns
+    ///
+    /// The resolved raw object or an error if there was a problem
+    ///
+    /// # Errors
+    ///
+    /// * `ParserError::NotYetImplemented` - If the raw type does not support deferred resolution
+    ///   (currently creatures and entities do, see [`Resolvable`])
+    pub fn resolve_with_trace(
+        &self,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+        trace: bool,
+    ) -> Result<Box<dyn RawObject>, ParserError> {
+        // Each type which opts into deferred resolution (see `Resolvable`)
+        const RESOLVERS: [(ObjectType, Resolver); 2] = [
+            (Creature::OBJECT_TYPE, resolve_boxed::<Creature>),
+            (Entity::OBJECT_TYPE, resolve_boxed::<Entity>),
+        ];
+
+        RESOLVERS
+            .iter()
+            .find(|(object_type, _)| *object_type == self.raw_type)
+            .map(|(_, resolver)| resolver(self, creature_variations, all_raws, trace))
+            .ok_or(ParserError::NotYetImplemented)
+    }
+
+    /// Resolves the raws into any [`Resolvable`] object.
+    ///
+    /// `COPY_TAGS_FROM` is applied first, then the body of the object (with any `GO_TO_*` insertions
+    /// in place) is parsed into it.
+    pub(crate) fn resolve_object<T: Resolvable>(&self, all_raws: &[Box<dyn RawObject>]) -> T {
+        let mut object = T::new_unresolved(&self.identifier, &self.metadata);
+
+        for modification in &self.modifications {
+            if let ModificationToken::CopyTagsFrom { identifier } = modification {
+                if let Some(source) = self.find_copy_source::<T>(identifier, all_raws) {
+                    object = object.copy_tags_from(&source);
+                } else {
+                    debug!(
+                        "Unable to find {} with identifier `{}` to copy tags from",
+                        self.raw_type, identifier
+                    );
+                }
+            }
+        }
+
+        for (raw_string, _) in self.collapsed_body() {
+            // Split the raw into the key and value (rest of the string)
+            let (key, value) = raw_string.split_once(':').unwrap_or((&raw_string, ""));
+            object.parse_tag(key, value);
         }
 
-        // We need to pre-process the modifications to collapse `AddBeforeTag`, `AddToBeginning`,
-        // `AddToEnding`, and `MainRawBody` into one modification.
-        self.collapse_modifications();
+        object
+    }
+
+    /// Finds the object of the same type to copy tags from with `COPY_TAGS_FROM`.
+    ///
+    /// If more than one object has the identifier, the one with the newest module version is used.
+    fn find_copy_source<T: Resolvable>(
+        &self,
+        identifier: &str,
+        all_raws: &[Box<dyn RawObject>],
+    ) -> Option<T> {
+        let mut source_options = all_raws
+            .iter()
+            .filter(|raw| {
+                raw.get_type() == self.raw_type
+                    && raw.get_identifier().to_lowercase() == identifier.to_lowercase()
+            })
+            .filter_map(|raw| raw.as_any().downcast_ref::<T>().cloned())
+            .collect::<Vec<T>>();
 
+        if source_options.len() > 1 {
+            debug!(
+                "Found {} {} with identifier `{}` to copy tags from. Using the newest one.",
+                source_options.len(),
+                self.raw_type,
+                identifier
+            );
+            source_options.sort_by(|a, b| {
+                a.get_metadata()
+                    .get_module_version()
+                    .cmp(b.get_metadata().get_module_version())
+            });
+        }
+
+        source_options.into_iter().next()
+    }
+
+    /// Resolves the raws into a creature, applying creature variations and caste selections.
+    pub(crate) fn resolve_creature(
+        &self,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+        trace: bool,
+    ) -> Creature {
         let mut creature = Creature::new(&self.identifier, &self.metadata);
+        // The castes selected at this point in the creature definition
+        let mut selection = if trace {
+            CasteSelection::with_trace()
+        } else {
+            CasteSelection::default()
+        };
+        // Any `CV_*` tags in the creature body, applied by `APPLY_CURRENT_CREATURE_VARIATION`
+        let mut current_variation = CreatureVariation::empty();
+        // The references used while resolving, kept on the creature so they can be checked later
+        let mut copied_from: Option<String> = None;
+        let mut applied_variations: Vec<String> = Vec::new();
 
+        // `COPY_TAGS_FROM` and `APPLY_CREATURE_VARIATION` are applied first, in order
         for modification in &self.modifications {
             match modification {
                 ModificationToken::CopyTagsFrom { identifier } => {
-                    // Get the creature we are copying from. If we find more than one, we just use the newest one. (But we log a warning)
-                    let mut source_creature_options = all_raws
-                        .iter()
-                        .filter_map(|raw| {
-                            if raw.get_type() == ObjectType::Creature
-                                && raw.get_identifier().to_lowercase() == identifier.to_lowercase()
-                            {
-                                Some(
-                                    raw.as_any()
-                                        .downcast_ref::<Creature>()
-                                        .unwrap_or(&Creature::empty())
-                                        .clone(),
-                                )
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<Creature>>();
-
-                    if source_creature_options.len() > 1 {
-                        debug!(
-                            "Found {} creatures with identifier `{}` to copy tags from. Using the newest one.",
-                            source_creature_options.len(),
-                            identifier
-                        );
-                        source_creature_options.sort_by(|a, b| {
-                            a.get_metadata()
-                                .get_module_version()
-                                .cmp(b.get_metadata().get_module_version())
-                        });
-                    }
-
-                    if let Some(source_creature) = source_creature_options.first() {
+                    copied_from = Some(identifier.clone());
+                    if let Some(source_creature) =
+                        self.find_copy_source::<Creature>(identifier, all_raws)
+                    {
                         // We found a creature to copy tags from, so we can copy the tags
-                        creature = Creature::copy_tags_from(&creature, source_creature);
+                        creature = Creature::copy_tags_from(&creature, &source_creature);
+                        if selection.is_tracing() {
+                            selection.extend_trace(copied_origins(&source_creature));
+                        }
+                        // The copied creature may have left any caste selected
+                        selection.reset();
+                        creature.select_caste("ALL");
                     } else {
                         debug!(
                             "Unable to find creature with identifier `{}` to copy tags from",
@@ -273,100 +369,115 @@ impl UnprocessedRaw {
                     }
                 }
                 ModificationToken::ApplyCreatureVariation { identifier } => {
-                    if let Some(updated_creature) = singularly_apply_creature_variation(
-                        &creature,
+                    applied_variations.push(identifier.clone());
+                    apply_creature_variation_to_selection(
+                        &mut creature,
                         identifier,
                         creature_variations,
-                    ) {
-                        creature = updated_creature;
-                    }
-                }
-                ModificationToken::MainRawBody { raws } => {
-                    for raw_string in raws {
-                        // Split the raw into the key and value (rest of the string)
-                        let mut split = raw_string.split(':');
-                        let key = split.next().unwrap_or("");
-                        let value = split.collect::<Vec<&str>>().join(":");
-
-                        match key {
-                            "CASTE" | "SELECT_CASTE" => creature.select_caste(&value),
-                            _ => creature.parse_tag(key, &value),
-                        }
-                    }
-                }
-                _ => {
-                    // Since we've already collapsed the modifications, we don't need to do anything with the
-                    // other modification types. If we end up here, its a bug.
-                    debug!("Unexpectedly found {:?} modification", modification);
+                        &mut selection,
+                    );
                 }
+                _ => {}
             }
         }
 
-        Ok(Box::new(creature))
-    }
+        // Then the body of the creature, with any `GO_TO_*` insertions in place
+        for (raw_string, source) in self.collapsed_body() {
+            selection.set_source(source);
+            // Split the raw into the key and value (rest of the string)
+            let mut split = raw_string.split(':');
+            let key = split.next().unwrap_or("");
+            let value = split.collect::<Vec<&str>>().join(":");
 
-    fn collapse_modifications(&mut self) {
-        // Grab the base raws first
-        let mut collapsed_raws: Vec<String> = Vec::new();
-        for modification in &self.modifications {
-            if let ModificationToken::MainRawBody { raws } = modification {
-                collapsed_raws.extend(raws.clone());
-                trace!("collapsed {} base raws", raws.len());
+            match key {
+                "APPLY_CREATURE_VARIATION" => {
+                    applied_variations.push(value.clone());
+                    apply_creature_variation_to_selection(
+                        &mut creature,
+                        &value,
+                        creature_variations,
+                        &mut selection,
+                    );
+                }
+                "APPLY_CURRENT_CREATURE_VARIATION" => {
+                    // Apply the `CV_*` tags gathered from the creature body so far
+                    for rule in current_variation.get_rules() {
+                        if selection.is_tracing() {
+                            selection
+                                .set_source(TokenSource::CurrentVariation { rule: rule.clone() });
+                        }
+                        rule.apply_to_selection(&mut creature, &[], &mut selection);
+                    }
+                    current_variation = CreatureVariation::empty();
+                }
+                _ if key.starts_with("CV_") || key.starts_with("CVCT_") => {
+                    current_variation.parse_tag(key, &value);
+                }
+                _ => selection.apply_tag(&mut creature, key, &value),
             }
         }
 
-        // Now we can remove all the `MainRawBody` modifications
-        self.modifications
-            .retain(|m| !matches!(m, ModificationToken::MainRawBody { .. }));
+        creature.set_resolution_trace(selection.take_trace());
+        creature.set_resolved_references(copied_from, applied_variations);
 
-        // Next process the `AddToEnding` modifications
-        let mut add_to_ending: Vec<String> = Vec::new();
-        for modification in &self.modifications {
-            if let ModificationToken::AddToEnding { raws } = modification {
-                add_to_ending.extend(raws.clone());
-                trace!("collapsed {} add to ending raws", raws.len());
-            }
-        }
+        creature
+    }
 
-        // Now we can remove all the `AddToEnding` modifications
-        self.modifications
-            .retain(|m| !matches!(m, ModificationToken::AddToEnding { .. }));
+    /// Collapses the `MainRawBody`, `AddToBeginning`, `AddToEnding`, and `AddBeforeTag` modifications
+    /// into the final body of the creature.
+    ///
+    /// # Returns
+    ///
+    /// Each raw in the body, in order, with the modification it came from
+    fn collapsed_body(&self) -> Vec<(String, TokenSource)> {
+        let mut add_to_beginning: Vec<(String, TokenSource)> = Vec::new();
+        let mut base_raws: Vec<(String, TokenSource)> = Vec::new();
+        let mut add_to_ending: Vec<(String, TokenSource)> = Vec::new();
 
-        // Next process the `AddToBeginning` modifications
-        let mut add_to_beginning: Vec<String> = Vec::new();
         for modification in &self.modifications {
-            if let ModificationToken::AddToBeginning { raws } = modification {
-                add_to_beginning.extend(raws.clone());
-                trace!("collapsed {} add to beginning raws", raws.len());
+            match modification {
+                ModificationToken::MainRawBody { raws } => {
+                    base_raws.extend(raws.iter().map(|r| (r.clone(), TokenSource::Body)));
+                    trace!("collapsed {} base raws", raws.len());
+                }
+                ModificationToken::AddToBeginning { raws } => {
+                    add_to_beginning
+                        .extend(raws.iter().map(|r| (r.clone(), TokenSource::GoToStart)));
+                    trace!("collapsed {} add to beginning raws", raws.len());
+                }
+                ModificationToken::AddToEnding { raws } => {
+                    add_to_ending.extend(raws.iter().map(|r| (r.clone(), TokenSource::GoToEnd)));
+                    trace!("collapsed {} add to ending raws", raws.len());
+                }
+                _ => {}
             }
         }
 
-        // Now we can remove all the `AddToBeginning` modifications
-        self.modifications
-            .retain(|m| !matches!(m, ModificationToken::AddToBeginning { .. }));
-
         // Combine the raws into [add_to_beginning, raws, add_to_ending] (order matters)
         debug!(
             "collapsed {} total raws ({} base, {} add to beginning, {} add to ending)",
-            collapsed_raws.len() + add_to_beginning.len() + add_to_ending.len(),
-            collapsed_raws.len(),
+            base_raws.len() + add_to_beginning.len() + add_to_ending.len(),
+            base_raws.len(),
             add_to_beginning.len(),
             add_to_ending.len()
         );
 
-        collapsed_raws.splice(0..0, add_to_beginning);
+        let mut collapsed_raws = add_to_beginning;
+        collapsed_raws.extend(base_raws);
         collapsed_raws.extend(add_to_ending);
 
         // Finally process the `AddBeforeTag` modifications
         // These have to get inserted before the tag, so we need to find where to insert first
         for modification in &self.modifications {
             if let ModificationToken::AddBeforeTag { tag, raws } = modification {
+                let source = TokenSource::GoToTag { tag: tag.clone() };
+                let tagged_raws = raws.iter().map(|r| (r.clone(), source.clone()));
                 // Find the index of the tag
-                let index = collapsed_raws.iter().position(|r| r.starts_with(tag));
+                let index = collapsed_raws.iter().position(|(r, _)| r.starts_with(tag));
 
                 // If we found the index, insert the raws before the tag (without replacing)
                 if let Some(index) = index {
-                    collapsed_raws.splice(index..index, raws.clone());
+                    collapsed_raws.splice(index..index, tagged_raws);
                     trace!(
                         "collapsed {} add before tag raws, before tag {}",
                         raws.len(),
@@ -374,7 +485,7 @@ impl UnprocessedRaw {
                     );
                 } else {
                     // If we didn't find the index, just add the raws to the end
-                    collapsed_raws.extend(raws.clone());
+                    collapsed_raws.extend(tagged_raws);
                     debug!(
                         "resolve: Unable to find tag `{}` to add raws before. Adding raws to end instead.",
                         tag
@@ -383,9 +494,366 @@ impl UnprocessedRaw {
             }
         }
 
-        // Add the collapsed raws back as a `MainRawBody` modification
-        self.modifications.push(ModificationToken::MainRawBody {
-            raws: collapsed_raws,
+        collapsed_raws
+    }
+}
+
+/// Resolves an unprocessed raw with a type's [`Resolvable::resolve`]
+type Resolver =
+    fn(&UnprocessedRaw, &[CreatureVariation], &[Box<dyn RawObject>], bool) -> Box<dyn RawObject>;
+
+fn resolve_boxed<T: Resolvable + 'static>(
+    unprocessed: &UnprocessedRaw,
+    creature_variations: &[CreatureVariation],
+    all_raws: &[Box<dyn RawObject>],
+    trace: bool,
+) -> Box<dyn RawObject> {
+    Box::new(T::resolve(
+        unprocessed,
+        creature_variations,
+        all_raws,
+        trace,
+    ))
+}
+
+/// The trace entries for tags copied from a creature with `COPY_TAGS_FROM`.
+///
+/// If the copied creature was not traced itself (i.e. it didn't need resolving), its tags are all
+/// attributed to its own body.
+fn copied_origins(source_creature: &Creature) -> Vec<TokenOrigin> {
+    let metadata = source_creature.get_metadata();
+    let identifier = source_creature.get_identifier();
+    let module = metadata.get_module_name();
+    let module_object_id = metadata.get_module_object_id();
+
+    if let Some(origins) = source_creature.get_resolution_trace() {
+        return origins
+            .iter()
+            .map(|origin| origin.copied_from(identifier, module, module_object_id))
+            .collect();
+    }
+
+    let body_origin = |token: String, castes: &[String]| {
+        // `to_raw_token` includes the brackets, the trace does not
+        let token = token.trim_start_matches('[').trim_end_matches(']');
+        TokenOrigin::new(token, castes, TokenAction::Added, TokenSource::Body).copied_from(
+            identifier,
+            module,
+            module_object_id,
+        )
+    };
+    let all = [String::from("ALL")];
+    let mut origins: Vec<TokenOrigin> = source_creature
+        .get_tags()
+        .iter()
+        .map(|tag| body_origin(tag.to_raw_token(), &all))
+        .collect();
+    for caste in source_creature.get_castes() {
+        let castes = [String::from(caste.get_identifier())];
+        origins.extend(
+            caste
+                .get_tags()
+                .iter()
+                .map(|tag| body_origin(tag.to_raw_token(), &castes)),
+        );
+    }
+    origins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Caste, tokens::CasteToken};
+
+    fn variation(identifier: &str, tags: &[(&str, &str)]) -> CreatureVariation {
+        let mut variation = CreatureVariation::new(identifier, &RawMetadata::default());
+        for (key, value) in tags {
+            variation.parse_tag(key, value);
+        }
+        variation
+    }
+
+    fn resolve(
+        identifier: &str,
+        modifications: Vec<ModificationToken>,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+    ) -> Creature {
+        resolve_traced(
+            identifier,
+            modifications,
+            creature_variations,
+            all_raws,
+            false,
+        )
+    }
+
+    fn resolve_traced(
+        identifier: &str,
+        modifications: Vec<ModificationToken>,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+        trace: bool,
+    ) -> Creature {
+        let mut unprocessed =
+            UnprocessedRaw::new(ObjectType::Creature, &RawMetadata::default(), identifier);
+        for modification in modifications {
+            unprocessed.add_modification(modification);
+        }
+        let resolved = unprocessed
+            .resolve_with_trace(creature_variations, all_raws, trace)
+            .expect("Creature should resolve");
+        resolved
+            .as_any()
+            .downcast_ref::<Creature>()
+            .expect("Resolved raw should be a creature")
+            .clone()
+    }
+
+    fn body(raws: &[&str]) -> ModificationToken {
+        ModificationToken::MainRawBody {
+            raws: raws.iter().map(|r| String::from(*r)).collect(),
+        }
+    }
+
+    fn caste<'a>(creature: &'a Creature, identifier: &str) -> &'a Caste {
+        creature
+            .get_castes()
+            .iter()
+            .find(|c| c.get_identifier() == identifier)
+            .expect("Caste should exist")
+    }
+
+    #[test]
+    fn test_giant_variation_applies_to_all_castes_after_copy() {
+        // A toad whose last selected caste is MALE, like the vanilla definition
+        let toad = resolve(
+            "TOAD",
+            vec![body(&[
+                "PET",
+                "CASTE:FEMALE",
+                "FEMALE",
+                "PET",
+                "CASTE:MALE",
+                "MALE",
+            ])],
+            &[],
+            &[],
+        );
+        let all_raws: Vec<Box<dyn RawObject>> = vec![Box::new(toad)];
+        // Shaped like the vanilla GIANT variation: drops pet status and scales the body
+        let giant = variation(
+            "GIANT",
+            &[
+                ("CV_REMOVE_TAG", "PET"),
+                ("CV_NEW_TAG", "CHANGE_BODY_SIZE_PERC:!ARG1"),
+            ],
+        );
+
+        let giant_toad = resolve(
+            "GIANT_TOAD",
+            vec![
+                ModificationToken::CopyTagsFrom {
+                    identifier: String::from("TOAD"),
+                },
+                body(&["APPLY_CREATURE_VARIATION:GIANT:500", "LARGE_PREDATOR"]),
+            ],
+            &[giant],
+            &all_raws,
+        );
+
+        let all = caste(&giant_toad, "ALL");
+        assert!(all.has_tag(&CasteToken::ChangeBodySizePercent { percent: 0 }));
+        assert!(all.has_tag(&CasteToken::LargePredator));
+        assert!(!all.has_tag(&CasteToken::Pet));
+        // Removing a tag with ALL selected also removes it from the specific castes
+        assert!(!caste(&giant_toad, "FEMALE").has_tag(&CasteToken::Pet));
+        assert!(!caste(&giant_toad, "MALE").has_tag(&CasteToken::LargePredator));
+    }
+
+    #[test]
+    fn test_animal_person_variation_uses_selected_caste() {
+        // Shaped like the vanilla ANIMAL_PERSON variation, with a caste-specific section
+        let animal_person = variation(
+            "ANIMAL_PERSON",
+            &[
+                ("CV_NEW_TAG", "CAN_LEARN"),
+                ("CV_NEW_TAG", "SELECT_CASTE:MALE"),
+                ("CV_NEW_TAG", "EQUIPS"),
+            ],
+        );
+
+        let bat_man = resolve(
+            "BAT_MAN",
+            vec![body(&[
+                "CASTE:FEMALE",
+                "FEMALE",
+                "CASTE:MALE",
+                "MALE",
+                "SELECT_CASTE:FEMALE",
+                "APPLY_CREATURE_VARIATION:ANIMAL_PERSON",
+                "CAN_SPEAK",
+            ])],
+            &[animal_person],
+            &[],
+        );
+
+        let female = caste(&bat_man, "FEMALE");
+        let male = caste(&bat_man, "MALE");
+        let all = caste(&bat_man, "ALL");
+        assert!(female.has_tag(&CasteToken::CanLearn));
+        assert!(!female.has_tag(&CasteToken::Equips));
+        // The variation's caste selection carries on to the rest of the creature
+        assert!(male.has_tag(&CasteToken::Equips));
+        assert!(male.has_tag(&CasteToken::CanSpeak));
+        assert!(!all.has_tag(&CasteToken::CanLearn));
+    }
+
+    #[test]
+    fn test_current_variation_applies_to_additional_castes() {
+        let creature = resolve(
+            "BEAK_DOG_MAN",
+            vec![body(&[
+                "CASTE:FEMALE",
+                "CASTE:MALE",
+                "SELECT_CASTE:FEMALE",
+                "SELECT_ADDITIONAL_CASTE:MALE",
+                "CV_NEW_TAG:BENIGN",
+                "CV_NEW_CTAG:1:NONE:LARGE_PREDATOR",
+                "APPLY_CURRENT_CREATURE_VARIATION",
+            ])],
+            &[],
+            &[],
+        );
+
+        assert!(caste(&creature, "FEMALE").has_tag(&CasteToken::Benign));
+        assert!(caste(&creature, "MALE").has_tag(&CasteToken::Benign));
+        assert!(!caste(&creature, "ALL").has_tag(&CasteToken::Benign));
+        // Conditional tags without arguments are never applied
+        assert!(!caste(&creature, "MALE").has_tag(&CasteToken::LargePredator));
+    }
+
+    #[test]
+    fn test_resolution_trace_explains_tags() {
+        let toad = resolve_traced(
+            "TOAD",
+            vec![body(&["PET", "CASTE:FEMALE", "FEMALE"])],
+            &[],
+            &[],
+            true,
+        );
+        let all_raws: Vec<Box<dyn RawObject>> = vec![Box::new(toad)];
+        let giant = variation(
+            "GIANT",
+            &[
+                ("CV_REMOVE_TAG", "PET"),
+                ("CV_NEW_TAG", "SELECT_CASTE:FEMALE"),
+            ],
+        );
+
+        let giant_toad = resolve_traced(
+            "GIANT_TOAD",
+            vec![
+                ModificationToken::CopyTagsFrom {
+                    identifier: String::from("TOAD"),
+                },
+                body(&["APPLY_CREATURE_VARIATION:GIANT", "LARGE_PREDATOR"]),
+                ModificationToken::AddToEnding {
+                    raws: vec![String::from("BENIGN")],
+                },
+            ],
+            &[giant],
+            &all_raws,
+            true,
+        );
+
+        let pet = giant_toad.explain_token("PET");
+        assert_eq!(pet.len(), 2);
+        assert_eq!(pet[0].get_action(), TokenAction::Added);
+        assert!(matches!(
+            pet[0].get_source(),
+            TokenSource::CopiedFrom { creature, original, .. }
+                if creature == "TOAD" && **original == TokenSource::Body
+        ));
+        assert_eq!(pet[1].get_action(), TokenAction::Removed);
+        assert!(matches!(
+            pet[1].get_source(),
+            TokenSource::Variation { variation, .. } if variation == "GIANT"
+        ));
+
+        let large_predator = giant_toad.explain_token("LARGE_PREDATOR");
+        assert_eq!(large_predator.len(), 1);
+        assert_eq!(large_predator[0].get_source(), &TokenSource::Body);
+        assert_eq!(
+            giant_toad.explain_token("BENIGN")[0].get_source(),
+            &TokenSource::GoToEnd
+        );
+
+        // Caste selections are recorded as selections, with the castes they select
+        let caste_selection = giant_toad.explain_token("CASTE");
+        assert_eq!(caste_selection.len(), 1);
+        assert_eq!(caste_selection[0].get_action(), TokenAction::Selected);
+        assert_eq!(caste_selection[0].get_castes(), ["FEMALE"]);
+        assert!(matches!(
+            caste_selection[0].get_source(),
+            TokenSource::CopiedFrom { creature, .. } if creature == "TOAD"
+        ));
+        let female = giant_toad.explain_token("FEMALE");
+        assert_eq!(female[0].get_action(), TokenAction::Added);
+        assert_eq!(female[0].get_castes(), ["FEMALE"]);
+        let select_caste = giant_toad.explain_token("SELECT_CASTE");
+        assert_eq!(select_caste.len(), 1);
+        assert_eq!(select_caste[0].get_action(), TokenAction::Selected);
+        assert!(matches!(
+            select_caste[0].get_source(),
+            TokenSource::Variation { variation, .. } if variation == "GIANT"
+        ));
+        // The variation's selection carries on to the tags after it
+        assert_eq!(large_predator[0].get_castes(), ["FEMALE"]);
+
+        // Nothing is recorded unless asked for
+        let untraced = resolve("GIANT_TOAD", vec![body(&["BENIGN"])], &[], &[]);
+        assert!(untraced.get_resolution_trace().is_none());
+    }
+
+    #[test]
+    fn test_entity_copies_tags_from_entity() {
+        let mut mountain =
+            UnprocessedRaw::new(ObjectType::Entity, &RawMetadata::default(), "MOUNTAIN");
+        mountain.add_modification(body(&["CREATURE:DWARF", "TRANSLATION:DWARF"]));
+        let mountain = mountain.resolve(&[], &[]).expect("Entity should resolve");
+        let all_raws: Vec<Box<dyn RawObject>> = vec![mountain];
+
+        let mut hill = UnprocessedRaw::new(ObjectType::Entity, &RawMetadata::default(), "HILL");
+        hill.add_modification(ModificationToken::CopyTagsFrom {
+            identifier: String::from("MOUNTAIN"),
+        });
+        hill.add_modification(body(&[
+            "TRANSLATION:HUMAN",
+            "CREATURE:HUMAN",
+            "CREATURE:HUMAN",
+        ]));
+        hill.add_modification(ModificationToken::AddToBeginning {
+            raws: vec![String::from("BABYSNATCHER")],
         });
+        let hill = hill.resolve(&[], &all_raws).expect("Entity should resolve");
+        let hill = hill
+            .as_any()
+            .downcast_ref::<Entity>()
+            .expect("Resolved raw should be an entity");
+
+        assert_eq!(hill.get_identifier(), "HILL");
+        let tags: Vec<String> = hill
+            .get_tags()
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect();
+        // Tags with a single value replace the copied ones, the rest (including every `CREATURE`) are added
+        assert_eq!(tags, vec!["DWARF", "HUMAN", "", "HUMAN", "HUMAN"]);
+        assert_eq!(hill.get_creature(), Some("HUMAN"));
+
+        // Types which haven't opted in can't be resolved
+        let plant = UnprocessedRaw::new(ObjectType::Plant, &RawMetadata::default(), "OAK");
+        assert!(plant.resolve(&[], &[]).is_err());
     }
 }
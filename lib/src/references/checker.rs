@@ -0,0 +1,389 @@
+//! Finds the references between raw objects which can't be resolved.
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::{
+    Creature, Entity, Graphic, Inorganic, Material, Plant,
+    tokens::{ConditionToken, EntityToken, GraphicTypeToken, ObjectType},
+    traits::RawObject,
+};
+
+use super::UnresolvedReference;
+
+/// The identifiers of every parsed object, by type
+struct KnownIdentifiers {
+    identifiers: HashMap<ObjectType, HashSet<String>>,
+}
+
+impl KnownIdentifiers {
+    fn new(raws: &[Box<dyn RawObject>]) -> Self {
+        let mut identifiers: HashMap<ObjectType, HashSet<String>> = HashMap::new();
+        for raw in raws {
+            identifiers
+                .entry(raw.get_type())
+                .or_default()
+                .insert(raw.get_identifier().to_uppercase());
+        }
+        Self { identifiers }
+    }
+
+    /// Returns true if the identifier is missing, i.e. objects of the type were parsed but none of them
+    /// have the identifier
+    fn is_missing(&self, object_type: ObjectType, identifier: &str) -> bool {
+        self.identifiers
+            .get(&object_type)
+            .is_some_and(|known| !known.contains(&identifier.to_uppercase()))
+    }
+}
+
+/// Collects the unresolved references, only reporting each one once per object
+struct References<'a> {
+    known: &'a KnownIdentifiers,
+    found: Vec<UnresolvedReference>,
+    seen: HashSet<(Uuid, String, String)>,
+}
+
+impl References<'_> {
+    fn check(
+        &mut self,
+        source: &dyn RawObject,
+        field: &str,
+        target_type: ObjectType,
+        identifier: &str,
+    ) {
+        if identifier.is_empty() || !self.known.is_missing(target_type, identifier) {
+            return;
+        }
+        let key = (
+            source.get_object_id(),
+            String::from(field),
+            identifier.to_uppercase(),
+        );
+        if !self.seen.insert(key) {
+            return;
+        }
+        self.found.push(UnresolvedReference {
+            source_type: source.get_type(),
+            source_identifier: String::from(source.get_identifier()),
+            source_object_id: source.get_object_id(),
+            module: String::from(source.get_metadata().get_module_name()),
+            field: String::from(field),
+            target_type,
+            expected_identifier: String::from(identifier),
+        });
+    }
+
+    fn check_creature(&mut self, creature: &Creature) {
+        self.check(
+            creature,
+            "COPY_TAGS_FROM",
+            ObjectType::Creature,
+            creature.get_copy_tags_from(),
+        );
+        for variation in creature.get_variations_to_apply() {
+            // Variations are stored with their arguments, e.g. `STANDARD_WALK_CRAWL_GAITS:900:711`
+            let identifier = variation.split(':').next().unwrap_or_default();
+            self.check(
+                creature,
+                "APPLY_CREATURE_VARIATION",
+                ObjectType::CreatureVariation,
+                identifier,
+            );
+        }
+    }
+
+    fn check_entity(&mut self, entity: &Entity) {
+        for (token, value) in entity.get_tags() {
+            if token == EntityToken::Creature {
+                self.check(entity, "CREATURE", ObjectType::Creature, &value);
+            }
+        }
+    }
+
+    fn check_graphic(&mut self, graphic: &Graphic) {
+        // Caste graphics are identified like `CREATURE:CASTE`
+        let target = graphic
+            .get_identifier()
+            .split(':')
+            .next()
+            .unwrap_or_default();
+        match graphic.get_graphic_type() {
+            GraphicTypeToken::Creature
+            | GraphicTypeToken::CreatureCaste
+            | GraphicTypeToken::StatueCreature
+            | GraphicTypeToken::StatueCreatureCaste => {
+                self.check(graphic, "CREATURE_GRAPHICS", ObjectType::Creature, target);
+            }
+            GraphicTypeToken::Plant => {
+                self.check(graphic, "PLANT_GRAPHICS", ObjectType::Plant, target);
+            }
+            _ => {}
+        }
+
+        for sprite in graphic.get_sprites() {
+            // Templates are stored with the template token in place of the tile page
+            if sprite.get_primary_condition() == ConditionToken::CopyOfTemplate {
+                continue;
+            }
+            self.check(
+                graphic,
+                "TILE_PAGE",
+                ObjectType::TilePage,
+                sprite.get_tile_page_id(),
+            );
+        }
+        for (layer_set, layers) in graphic.get_layers() {
+            for layer in layers {
+                self.check(
+                    graphic,
+                    &format!("LAYER_SET:{layer_set}"),
+                    ObjectType::TilePage,
+                    layer.get_tile_page_id(),
+                );
+            }
+        }
+        for (growth, sprites) in graphic.get_growths() {
+            for sprite in sprites {
+                self.check(
+                    graphic,
+                    &format!("GROWTH:{growth}"),
+                    ObjectType::TilePage,
+                    sprite.get_tile_page_id(),
+                );
+            }
+        }
+    }
+
+    /// Check the templates of materials, allowing materials based on other materials of the same object
+    fn check_materials(&mut self, source: &dyn RawObject, materials: &[&Material]) {
+        let local_materials: HashSet<String> = materials
+            .iter()
+            .filter_map(|material| material.get_name())
+            .map(str::to_uppercase)
+            .collect();
+
+        for material in materials {
+            if let Some(template) = material.get_template_identifier()
+                && !local_materials.contains(&template.to_uppercase())
+            {
+                self.check(
+                    source,
+                    "USE_MATERIAL_TEMPLATE",
+                    ObjectType::MaterialTemplate,
+                    template,
+                );
+            }
+        }
+    }
+}
+
+/// Finds every reference between the raws which doesn't resolve to a parsed object.
+///
+/// See the [module documentation](crate::references) for which references are checked.
+///
+/// # Arguments
+///
+/// * `raws` - All the parsed raws
+///
+/// # Returns
+///
+/// Each dangling reference, in the order of the raws which have them
+#[must_use]
+pub fn find_unresolved_references(raws: &[Box<dyn RawObject>]) -> Vec<UnresolvedReference> {
+    let known = KnownIdentifiers::new(raws);
+    let mut references = References {
+        known: &known,
+        found: Vec::new(),
+        seen: HashSet::new(),
+    };
+
+    for raw in raws {
+        let any = raw.as_any();
+        match raw.get_type() {
+            ObjectType::Creature => {
+                if let Some(creature) = any.downcast_ref::<Creature>() {
+                    references.check_creature(creature);
+                }
+            }
+            ObjectType::Entity => {
+                if let Some(entity) = any.downcast_ref::<Entity>() {
+                    references.check_entity(entity);
+                }
+            }
+            ObjectType::Graphics => {
+                if let Some(graphic) = any.downcast_ref::<Graphic>() {
+                    references.check_graphic(graphic);
+                }
+            }
+            ObjectType::Plant => {
+                if let Some(plant) = any.downcast_ref::<Plant>() {
+                    let materials: Vec<&Material> = plant.get_materials().iter().collect();
+                    references.check_materials(plant, &materials);
+                }
+            }
+            ObjectType::Inorganic => {
+                if let Some(inorganic) = any.downcast_ref::<Inorganic>() {
+                    references.check_materials(inorganic, &[inorganic.get_material()]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references.found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        MaterialTemplate, TilePage,
+        metadata::{ParserOptions, RawMetadata},
+    };
+
+    const CREATURE_RAWS: &str = "creature_test
+
+[OBJECT:CREATURE]
+
+[CREATURE:TOAD]
+    [NAME:toad:toads:toad]
+
+[CREATURE:GIANT_TOAD]
+    [COPY_TAGS_FROM:TOAD]
+    [APPLY_CREATURE_VARIATION:GIANT]
+
+[CREATURE:TOAD_MAN]
+    [COPY_TAGS_FROM:TOAD]
+    [APPLY_CREATURE_VARIATION:ANIMAL_PERSON]
+
+[CREATURE:LOST_FROG]
+    [COPY_TAGS_FROM:FROG]
+";
+
+    const VARIATION_RAWS: &str = "c_variation_test
+
+[OBJECT:CREATURE_VARIATION]
+
+[CREATURE_VARIATION:GIANT]
+    [CV_REMOVE_TAG:PET]
+";
+
+    #[test]
+    fn test_dangling_references_are_reported() {
+        let metadata = RawMetadata::default();
+
+        let mut dwarf = Creature::new("DWARF", &metadata);
+        dwarf.parse_tag("COPY_TAGS_FROM", "MISSING_CREATURE");
+        let mut mountain = Entity::new("MOUNTAIN", &metadata);
+        mountain.parse_tag("CREATURE", "DWARF");
+        mountain.parse_tag("CREATURE", "ELF");
+        let mut graphics = Graphic::new("DWARF", &metadata, GraphicTypeToken::Creature);
+        graphics.parse_sprite_from_tag(
+            "DEFAULT",
+            "DWARVES:0:0:AS_IS:DEFAULT",
+            GraphicTypeToken::Creature,
+        );
+        graphics.parse_sprite_from_tag(
+            "CHILD",
+            "MISSING_PAGE:1:0:AS_IS:DEFAULT",
+            GraphicTypeToken::Creature,
+        );
+        let page = TilePage::new("DWARVES", &metadata);
+
+        let raws: Vec<Box<dyn RawObject>> = vec![
+            Box::new(dwarf),
+            Box::new(mountain),
+            Box::new(graphics),
+            Box::new(page),
+        ];
+        let references = find_unresolved_references(&raws);
+
+        let found: Vec<(&str, &str, &str)> = references
+            .iter()
+            .map(|r| {
+                (
+                    r.source_identifier.as_str(),
+                    r.field.as_str(),
+                    r.expected_identifier.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("DWARF", "COPY_TAGS_FROM", "MISSING_CREATURE"),
+                ("MOUNTAIN", "CREATURE", "ELF"),
+                ("DWARF", "TILE_PAGE", "MISSING_PAGE"),
+            ]
+        );
+        assert_eq!(references[2].source_type, ObjectType::Graphics);
+        assert_eq!(references[2].target_type, ObjectType::TilePage);
+    }
+
+    #[test]
+    fn test_unparsed_types_and_local_materials_are_not_reported() {
+        let metadata = RawMetadata::default();
+
+        let mut plant = Plant::new("OAK", &metadata);
+        plant.parse_tag(
+            "USE_MATERIAL_TEMPLATE",
+            "STRUCTURAL:STRUCTURAL_PLANT_TEMPLATE",
+        );
+        plant.parse_tag("USE_MATERIAL", "WOOD:STRUCTURAL");
+        // No material templates or creature variations were parsed, so these can't be checked
+        let mut creature = Creature::new("GIANT_TOAD", &metadata);
+        creature.parse_tag("APPLY_CREATURE_VARIATION", "GIANT:500");
+
+        let template = MaterialTemplate::new("STRUCTURAL_PLANT_TEMPLATE", &metadata);
+
+        let raws: Vec<Box<dyn RawObject>> =
+            vec![Box::new(plant), Box::new(creature), Box::new(template)];
+        assert!(find_unresolved_references(&raws).is_empty());
+    }
+
+    #[test]
+    fn test_references_consumed_while_resolving_are_reported() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create test directory");
+        let module_path = temp_dir.path().join("references_test");
+        let objects_path = module_path.join("objects");
+        std::fs::create_dir_all(&objects_path).expect("Failed to create test module");
+        std::fs::write(
+            module_path.join("info.txt"),
+            "[ID:references_test]\n[NUMERIC_VERSION:1]\n[NAME:References Test]\n",
+        )
+        .expect("Failed to write info.txt");
+        std::fs::write(objects_path.join("creature_test.txt"), CREATURE_RAWS)
+            .expect("Failed to write creature raws");
+        std::fs::write(objects_path.join("c_variation_test.txt"), VARIATION_RAWS)
+            .expect("Failed to write creature variation raws");
+
+        let mut options = ParserOptions::default();
+        options.add_raw_module_to_parse(&module_path);
+        let result = crate::parse(&options);
+
+        let mut found: Vec<(String, String, String)> = result
+            .expect("Failed to parse test module")
+            .find_unresolved_references()
+            .into_iter()
+            .map(|r| (r.source_identifier, r.field, r.expected_identifier))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    String::from("LOST_FROG"),
+                    String::from("COPY_TAGS_FROM"),
+                    String::from("FROG")
+                ),
+                (
+                    String::from("TOAD_MAN"),
+                    String::from("APPLY_CREATURE_VARIATION"),
+                    String::from("ANIMAL_PERSON")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Checks the references between parsed raw objects.
+//!
+//! Raw objects refer to each other by identifier, and Dwarf Fortress will fail to load (or crash) when
+//! one of those identifiers doesn't exist. The references which are checked are:
+//!
+//! * Creatures: `COPY_TAGS_FROM` (creature) and `APPLY_CREATURE_VARIATION` (creature variation), which
+//!   are recorded on the creature while it is resolved
+//! * Entities: `CREATURE` (creature)
+//! * Graphics: the creature or plant the graphics are for, and the tile page of each sprite or layer
+//! * Plants and inorganics: the material template each material is based on (or, for `USE_MATERIAL`, a
+//!   material defined on the same plant)
+//!
+//! A reference is only checked when at least one object of the referenced type was parsed, so that
+//! parsing a subset of the raws doesn't report everything as missing. References to tissues, bodies,
+//! interactions, translations and reactions are not checked, because those objects aren't parsed.
+//!
+//! Use [`find_unresolved_references`] (or [`crate::ParseResult::find_unresolved_references`]) to get
+//! a list of [`UnresolvedReference`]s.
+
+mod checker;
+mod unresolved;
+
+pub use checker::find_unresolved_references;
+pub use unresolved::UnresolvedReference;
@@ -0,0 +1,41 @@
+//! A reference from one raw object to another which doesn't exist.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tokens::ObjectType;
+
+/// A reference from a raw object to an identifier which doesn't exist in the parsed raws
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedReference {
+    /// The type of the object with the reference
+    pub source_type: ObjectType,
+    /// The identifier of the object with the reference
+    pub source_identifier: String,
+    /// The `object_id` of the object with the reference
+    pub source_object_id: Uuid,
+    /// The name of the module the object with the reference is from
+    pub module: String,
+    /// The field (or tag) which holds the reference, e.g. `COPY_TAGS_FROM`
+    pub field: String,
+    /// The type of object which was expected
+    pub target_type: ObjectType,
+    /// The identifier which was expected to exist
+    pub expected_identifier: String,
+}
+
+impl std::fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} ({}) refers to missing {} {} in {}",
+            self.source_type,
+            self.source_identifier,
+            self.module,
+            self.target_type,
+            self.expected_identifier,
+            self.field
+        )
+    }
+}
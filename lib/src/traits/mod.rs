@@ -5,6 +5,7 @@
 //! - [`TokenParser`] - Helper trait for parsing raw file tokens
 //! - [`TagOperations`] - Operations for manipulating tags
 //! - [`CreatureVariationRequirements`] - Handling creature variation requirements
+//! - [`Resolvable`] - Opting into deferred resolution (e.g. for `COPY_TAGS_FROM`)
 //!
 
 mod cleanable;
@@ -13,6 +14,7 @@ mod is_empty;
 mod numeric_tokens;
 mod raw_object;
 mod raw_token;
+mod resolvable;
 mod tag_operations;
 mod to_raw_string;
 mod token_parser;
@@ -23,6 +25,7 @@ pub use is_empty::IsEmpty;
 pub use numeric_tokens::NumericTokenTransform;
 pub use raw_object::RawObject;
 pub use raw_token::RawToken;
+pub use resolvable::Resolvable;
 pub use tag_operations::TagOperations;
 pub use to_raw_string::ToRawFileString;
 pub use token_parser::TokenParser;
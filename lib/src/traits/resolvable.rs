@@ -0,0 +1,58 @@
+//! Trait for raw objects which are resolved after all the raws have been parsed.
+
+use crate::{
+    CreatureVariation, UnprocessedRaw, metadata::RawMetadata, tokens::ObjectType, traits::RawObject,
+};
+
+/// A raw object which opts into deferred resolution.
+///
+/// Objects of these types are first collected as unprocessed raws with their modifications
+/// (`COPY_TAGS_FROM`, `GO_TO_START`, `GO_TO_END` and `GO_TO_TAG`), and only built once all the raws
+/// have been parsed, so that they can copy tags from objects defined in other files or modules.
+///
+/// To resolve an object, a new object is created with [`Resolvable::new_unresolved`], the tags of any
+/// objects named by `COPY_TAGS_FROM` are copied with [`Resolvable::copy_tags_from`], and then the
+/// object's own tags are applied (in order, with the `GO_TO_*` insertions in place) with
+/// [`RawObject::parse_tag`]. Types which need more than that (like creatures, which apply creature
+/// variations) can override [`Resolvable::resolve`].
+///
+/// To opt a type in, implement this trait and add it to the resolvers in
+/// [`UnprocessedRaw::resolve_with_trace`].
+pub trait Resolvable: RawObject + Clone {
+    /// The type of raw object which is resolved into this
+    const OBJECT_TYPE: ObjectType;
+    /// Create a new object with no tags, to resolve the raws into
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The identifier of the object
+    /// * `metadata` - The metadata of the object
+    fn new_unresolved(identifier: &str, metadata: &RawMetadata) -> Self;
+    /// Create a copy of this object which includes the tags from `source` (for `COPY_TAGS_FROM`).
+    ///
+    /// The identifier, metadata and object id of `self` are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The object to copy the tags from
+    #[must_use]
+    fn copy_tags_from(&self, source: &Self) -> Self;
+    /// Resolve an unprocessed raw into this object.
+    ///
+    /// # Arguments
+    ///
+    /// * `unprocessed` - The unprocessed raw to resolve
+    /// * `creature_variations` - All possible creature variations to apply
+    /// * `all_raws` - All the raws to use when resolving the raws (e.g. to copy tags from)
+    /// * `trace` - Whether to record where each tag came from (if the object supports it)
+    #[must_use]
+    fn resolve(
+        unprocessed: &UnprocessedRaw,
+        creature_variations: &[CreatureVariation],
+        all_raws: &[Box<dyn RawObject>],
+        trace: bool,
+    ) -> Self {
+        let _ = (creature_variations, trace);
+        unprocessed.resolve_object(all_raws)
+    }
+}
@@ -0,0 +1,244 @@
+//! Tracks which castes tags are applied to while resolving a creature.
+
+use crate::{
+    Creature, TokenAction, TokenOrigin, TokenSource,
+    raw_definitions::CASTE_TOKENS,
+    traits::{CreatureVariationRequirements as _, RawObject as _},
+};
+
+/// The castes currently selected while applying tags to a creature.
+///
+/// This follows how the game reads a creature definition:
+///
+/// * `CASTE` and `SELECT_CASTE` replace the selection with a single caste
+/// * `SELECT_ADDITIONAL_CASTE` adds a caste to the selection
+///
+/// Creature variations use the selection which is active where they are applied, and any selection
+/// tags they add (e.g. `[CV_NEW_TAG:SELECT_CASTE:FEMALE]`) carry on to the tags which follow.
+///
+/// A selection created with [`Self::with_trace`] also records every tag it adds, removes, converts or
+/// selects castes with (see [`TokenOrigin`]), attributed to the source set with [`Self::set_source`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CasteSelection {
+    castes: Vec<String>,
+    /// Where the tags being applied are coming from
+    source: TokenSource,
+    /// The recorded tags, if tracing is enabled
+    trace: Option<Vec<TokenOrigin>>,
+}
+
+impl Default for CasteSelection {
+    fn default() -> Self {
+        Self {
+            castes: vec![String::from("ALL")],
+            source: TokenSource::Body,
+            trace: None,
+        }
+    }
+}
+
+impl CasteSelection {
+    /// Creates a selection of the caste which is currently selected on the creature (the last caste).
+    #[must_use]
+    pub fn current(creature: &Creature) -> Self {
+        creature
+            .get_castes()
+            .last()
+            .map_or_else(Self::default, |caste| Self {
+                castes: vec![String::from(caste.get_identifier())],
+                ..Self::default()
+            })
+    }
+
+    /// Creates a selection of the `ALL` caste which records every tag it applies.
+    #[must_use]
+    pub fn with_trace() -> Self {
+        Self {
+            trace: Some(Vec::new()),
+            ..Self::default()
+        }
+    }
+
+    /// Selects the `ALL` caste again, keeping the source and anything recorded so far.
+    pub fn reset(&mut self) {
+        self.castes = vec![String::from("ALL")];
+    }
+
+    /// Returns true if this selection is recording the tags it applies
+    #[must_use]
+    pub const fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Returns where the tags being applied are coming from
+    #[must_use]
+    pub const fn get_source(&self) -> &TokenSource {
+        &self.source
+    }
+
+    /// Sets where the tags applied from now on are coming from.
+    ///
+    /// # Returns
+    ///
+    /// The previous source, so it can be restored afterwards
+    pub fn set_source(&mut self, source: TokenSource) -> TokenSource {
+        std::mem::replace(&mut self.source, source)
+    }
+
+    /// Adds already recorded tags to the trace (e.g. the trace of a copied creature).
+    ///
+    /// Does nothing if tracing is not enabled.
+    pub fn extend_trace(&mut self, origins: impl IntoIterator<Item = TokenOrigin>) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.extend(origins);
+        }
+    }
+
+    /// Takes the recorded tags, leaving the trace empty.
+    ///
+    /// # Returns
+    ///
+    /// The recorded tags, or `None` if tracing is not enabled
+    pub fn take_trace(&mut self) -> Option<Vec<TokenOrigin>> {
+        self.trace.as_mut().map(std::mem::take)
+    }
+
+    fn record(&mut self, key: &str, value: &str, action: TokenAction) {
+        if let Some(trace) = self.trace.as_mut() {
+            let token = if value.is_empty() {
+                String::from(key)
+            } else {
+                format!("{key}:{value}")
+            };
+            trace.push(TokenOrigin::new(
+                &token,
+                &self.castes,
+                action,
+                self.source.clone(),
+            ));
+        }
+    }
+
+    /// Returns the identifiers of the selected castes
+    #[must_use]
+    pub fn get_castes(&self) -> &[String] {
+        &self.castes
+    }
+
+    /// Returns true if the `ALL` caste is selected
+    #[must_use]
+    pub fn is_all(&self) -> bool {
+        self.castes.iter().any(|caste| caste == "ALL")
+    }
+
+    /// Updates the selection if the tag is a caste selection tag.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the tag was a caste selection tag (and was handled)
+    pub fn update(&mut self, creature: &mut Creature, key: &str, value: &str) -> bool {
+        match key {
+            "CASTE" | "SELECT_CASTE" => {
+                creature.select_caste(value);
+                self.castes = vec![String::from(value)];
+            }
+            "SELECT_ADDITIONAL_CASTE" => {
+                creature.select_caste(value);
+                if !self.castes.iter().any(|caste| caste == value) {
+                    self.castes.push(String::from(value));
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Applies a tag to the creature.
+    ///
+    /// Caste tags are added to every selected caste, other tags are added to the creature itself.
+    /// Caste selection tags update the selection (and are recorded with the castes they select).
+    pub fn apply_tag(&mut self, creature: &mut Creature, key: &str, value: &str) {
+        if self.update(creature, key, value) {
+            self.record(key, value, TokenAction::Selected);
+            return;
+        }
+        self.record(key, value, TokenAction::Added);
+        self.add_tag(creature, key, value);
+    }
+
+    fn add_tag(&self, creature: &mut Creature, key: &str, value: &str) {
+        if CASTE_TOKENS.contains_key(key) {
+            for caste in &self.castes {
+                creature.add_tag_and_value_for_caste(key, value, caste);
+            }
+        } else {
+            creature.add_tag_and_value(key, value);
+        }
+    }
+
+    /// Removes a tag (with the given value) from the creature.
+    ///
+    /// Caste tags are removed from every selected caste. When the `ALL` caste is selected, they are
+    /// removed from every caste the creature has, since those tags would all follow the `ALL` caste.
+    pub fn remove_tag(&mut self, creature: &mut Creature, key: &str, value: &str) {
+        self.record(key, value, TokenAction::Removed);
+        self.take_tag(creature, key, value);
+    }
+
+    fn take_tag(&self, creature: &mut Creature, key: &str, value: &str) {
+        if CASTE_TOKENS.contains_key(key) {
+            let castes: Vec<String> = if self.is_all() {
+                creature
+                    .get_castes()
+                    .iter()
+                    .map(|caste| String::from(caste.get_identifier()))
+                    .collect()
+            } else {
+                self.castes.clone()
+            };
+            for caste in &castes {
+                creature.remove_tag_and_value_for_caste(key, value, caste);
+            }
+            // Leave the creature with the same caste selected as before
+            if let Some(caste) = self.castes.last() {
+                creature.select_caste(caste);
+            }
+        } else {
+            creature.remove_tag_and_value(key, value);
+        }
+    }
+
+    /// Converts a tag on the creature, as `CV_CONVERT_TAG` does.
+    ///
+    /// * `tag` - the tag to convert (`CVCT_MASTER`)
+    /// * `target` - the value to convert (`CVCT_TARGET`)
+    /// * `replacement` - the value to replace it with (`CVCT_REPLACEMENT`)
+    pub fn convert_tag(
+        &mut self,
+        creature: &mut Creature,
+        tag: &str,
+        target: Option<&str>,
+        replacement: Option<&str>,
+    ) {
+        tracing::trace!(
+            "Converting tag {}:{} to {}:{} on creature {} for castes {:?}",
+            tag,
+            target.unwrap_or_default(),
+            tag,
+            replacement.unwrap_or_default(),
+            creature.get_identifier(),
+            self.castes
+        );
+        if let Some(target) = target {
+            let replacement = replacement.unwrap_or(target);
+            self.record(tag, replacement, TokenAction::Converted);
+            self.take_tag(creature, tag, target);
+            self.add_tag(creature, tag, replacement);
+        } else {
+            let replacement = replacement.unwrap_or_default();
+            self.record(replacement, "", TokenAction::Converted);
+            self.take_tag(creature, tag, "");
+            self.add_tag(creature, replacement, "");
+        }
+    }
+}
@@ -20,13 +20,15 @@ use walkdir::WalkDir;
 
 use crate::{
     Creature, CreatureVariation, Entity, Graphic, Inorganic, MaterialTemplate, ParserError, Plant,
-    SelectCreature, TilePage,
+    SelectCreature, TilePage, TokenSource,
     metadata::{ParserOptions, RawModuleLocation},
     regex::VARIATION_ARGUMENT_RE,
     tokens::ObjectType,
-    traits::{CreatureVariationRequirements, IsEmpty, RawObject},
+    traits::{IsEmpty, RawObject},
 };
 
+use super::CasteSelection;
+
 #[tracing::instrument]
 /// Get a vec of subdirectories for a given directory
 ///
@@ -251,6 +253,7 @@ pub fn validate_options(options: &ParserOptions) -> Result<ParserOptions, Parser
         object_types_to_parse: options.object_types_to_parse.clone(),
         skip_apply_copy_tags_from: options.skip_apply_copy_tags_from,
         skip_apply_creature_variations: options.skip_apply_creature_variations,
+        trace_creature_resolution: options.trace_creature_resolution,
         locations: options.locations.clone(),
         ..Default::default()
     };
@@ -986,72 +989,39 @@ pub fn replace_args_in_string(string: &str, args: &[&str]) -> String {
 
 /// ADD or NEW tags can simply be applied by the parsing logic that already exists.
 ///
+/// The tag is applied to the caste currently selected on the creature.
+///
 /// ## Arguments
 ///
 /// * `creature` - The creature to apply the tag to.
 /// * `tag` - The tag to apply.
 /// * `value` - The value to apply to the tag.
 pub fn apply_new_tag(creature: &mut Creature, tag: &str, value: Option<&str>) {
-    (creature as &mut dyn CreatureVariationRequirements)
-        .add_tag_and_value(tag, value.unwrap_or_default());
+    CasteSelection::current(creature).apply_tag(creature, tag, value.unwrap_or_default());
 }
 
 /// Removes a tag from a creature.
 ///
+/// The tag is removed from the caste currently selected on the creature.
+///
 /// ## Arguments
 ///
 /// * `creature` - The creature to remove the tag from.
 /// * `tag` - The tag to remove.
 pub fn remove_tag(creature: &mut Creature, tag: &str) {
-    (creature as &mut dyn CreatureVariationRequirements).remove_tag(tag);
+    CasteSelection::current(creature).remove_tag(creature, tag, "");
 }
 
 /// Converts a tag on a creature.
+///
+/// The tag is converted on the caste currently selected on the creature.
 pub fn convert_tag(
     creature: &mut Creature,
     tag: &str,
     target: Option<&str>,
     replacement: Option<&str>,
 ) {
-    if let Some(target) = target {
-        if let Some(replacement) = replacement {
-            tracing::trace!(
-                "Converting tag {}:{} to {}:{} on creature {}",
-                tag,
-                target,
-                tag,
-                replacement,
-                creature.get_identifier()
-            );
-            // Convert the tag to the target value.
-            (creature as &mut dyn CreatureVariationRequirements).remove_tag_and_value(tag, target);
-            (creature as &mut dyn CreatureVariationRequirements)
-                .add_tag_and_value(tag, replacement);
-        } else {
-            tracing::trace!(
-                "Converting tag {}:{} to {}:{} on creature {}",
-                tag,
-                target,
-                replacement.unwrap_or_default(),
-                target,
-                creature.get_identifier(),
-            );
-            // Convert the tag to the target value.
-            (creature as &mut dyn CreatureVariationRequirements).remove_tag_and_value(tag, target);
-            (creature as &mut dyn CreatureVariationRequirements).add_tag_and_value(tag, target);
-        }
-    } else {
-        tracing::trace!(
-            "Converting tag {} to {} on creature {}",
-            tag,
-            replacement.unwrap_or_default(),
-            creature.get_identifier()
-        );
-        // Convert the tag to the replacement value.
-        (creature as &mut dyn CreatureVariationRequirements).remove_tag(tag);
-        (creature as &mut dyn CreatureVariationRequirements)
-            .add_tag(replacement.unwrap_or_default());
-    }
+    CasteSelection::current(creature).convert_tag(creature, tag, target, replacement);
 }
 
 /// Returns the argument which matches the given capture group.
@@ -1149,6 +1119,8 @@ pub fn apply_creature_variations(all_raws: &mut [Box<dyn RawObject>]) {
 
 /// Apply a single creature variation to a creature.
 ///
+/// The variation is applied to the `ALL` caste (unless the variation selects a caste itself).
+///
 /// # Arguments
 ///
 /// * `creature` - The creature to apply the variation to.
@@ -1164,6 +1136,43 @@ pub fn singularly_apply_creature_variation(
     variation: &str,
     creature_variations: &[CreatureVariation],
 ) -> Option<Creature> {
+    let mut updated_creature = creature.clone();
+
+    // Reset to `ALL` caste; some variations contain caste-specific rules but do not specify a caste
+    let mut selection = CasteSelection::default();
+    updated_creature.select_caste("ALL");
+
+    apply_creature_variation_to_selection(
+        &mut updated_creature,
+        variation,
+        creature_variations,
+        &mut selection,
+    )
+    .then_some(updated_creature)
+}
+
+/// Apply a single creature variation to the selected castes of a creature.
+///
+/// This is how `APPLY_CREATURE_VARIATION` behaves inside a creature definition: the variation's
+/// rules apply to whichever castes are selected at that point, and any caste selection made by the
+/// variation carries on after it.
+///
+/// # Arguments
+///
+/// * `creature` - The creature to apply the variation to.
+/// * `variation` - The variation to apply, with its arguments (e.g. `"STANDARD_WALK_CRAWL_GAITS:6561:6115"`)
+/// * `creature_variations` - The list of creature variations to apply.
+/// * `selection` - The castes currently selected on the creature.
+///
+/// # Returns
+///
+/// `true` if the variation was found and applied.
+pub fn apply_creature_variation_to_selection(
+    creature: &mut Creature,
+    variation: &str,
+    creature_variations: &[CreatureVariation],
+    selection: &mut CasteSelection,
+) -> bool {
     // The variation comes back like this:
     // "STANDARD_WALK_CRAWL_GAITS:6561:6115:5683:1755:7456:8567"
     // We need to split it into the variation id and the args (if any)
@@ -1174,29 +1183,39 @@ pub fn singularly_apply_creature_variation(
     let Some(creature_variation) = creature_variations
         .iter()
         .find(|r| r.get_identifier().to_uppercase() == variation_identifier.to_uppercase())
-        .cloned()
     else {
         warn!("Failed to find creature variation {}", variation_identifier);
         debug!("args: {:?}", variation_args);
-        return None;
+        return false;
     };
 
-    let mut updated_creature = creature.clone();
     debug!(
-        "Applying variation {} to {}",
+        "Applying variation {} to {} (castes {:?})",
         variation_identifier,
-        creature.get_identifier()
+        creature.get_identifier(),
+        selection.get_castes()
     );
 
-    // Reset to `ALL` caste; some variations contain caste-specific rules but do not specify a caste
-    updated_creature.select_caste("ALL");
-
     // Apply variation to creature
     for rule in creature_variation.get_rules() {
-        rule.apply(&mut updated_creature, variation_args);
+        if selection.is_tracing() {
+            // Attribute the tags to this rule, then go back to wherever the variation was applied from
+            let previous = selection.set_source(TokenSource::Variation {
+                variation: String::from(variation_identifier),
+                rule: rule.with_args(variation_args),
+                args: variation_args
+                    .iter()
+                    .map(|arg| String::from(*arg))
+                    .collect(),
+            });
+            rule.apply_to_selection(creature, variation_args, selection);
+            selection.set_source(previous);
+        } else {
+            rule.apply_to_selection(creature, variation_args, selection);
+        }
     }
 
-    Some(updated_creature)
+    true
 }
 
 #[cfg(test)]
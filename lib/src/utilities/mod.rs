@@ -19,12 +19,14 @@
 //! }
 //! ```
 
+mod caste_selection;
 mod file_operations;
 mod object_id;
 mod searchable;
 mod steam_directory_lookup;
 mod user_directory_lookup;
 
+pub use caste_selection::CasteSelection;
 pub use file_operations::*;
 pub use object_id::*;
 pub use searchable::*;
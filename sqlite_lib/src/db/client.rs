@@ -1,7 +1,13 @@
-use std::path::Path;
+use std::{io::BufRead, path::Path};
 
 use chrono::{TimeDelta, prelude::*};
+use dfraw_parser::dependencies::DependencyIssue;
+use dfraw_parser::graphics::SpriteState;
+use dfraw_parser::legends_export::{
+    ExportedHistoricalEvent, ExportedHistoricalFigure, ExportedSite, LegendsReader, LegendsWorld,
+};
 use dfraw_parser::metadata::{ParserOptions, RawModuleLocation};
+use dfraw_parser::tokens::ObjectType;
 use dfraw_parser::traits::RawObject;
 use dfraw_parser::{ModuleInfo, ParseResult};
 use rusqlite::{Connection, Result};
@@ -19,7 +25,10 @@ use crate::db::migrate::{apply_migrations, migrate_down};
 use crate::db::migrations::LATEST_SCHEMA_VERSION;
 use crate::db::queries::{self, init_constant_tables, insert_module_and_data};
 use crate::db::util::get_current_schema_version;
-use crate::models::{SpriteGraphicData, TilePageData};
+use crate::models::{
+    GraphicPaletteData, GrowthSpriteData, LegendsWorldData, SpriteGraphicData, SpriteLayerData,
+    TilePageData,
+};
 use crate::search_query::DEFAULT_SEARCH_LIMIT;
 use crate::{SearchQuery, SearchResults};
 
@@ -552,6 +561,41 @@ impl DbClient {
         queries::get_raw_by_object_id(&self.conn, object_id)
     }
 
+    /// Retrieves all raw objects of the given types, optionally only those from one module.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_raws_by_type(
+        &self,
+        raw_types: &[ObjectType],
+        module_object_id: Option<Uuid>,
+    ) -> Result<Vec<Box<dyn RawObject>>> {
+        queries::get_raws_by_type(&self.conn, raw_types, module_object_id)
+    }
+
+    /// Retrieves the tile pages and graphics (optionally only those from one module), e.g. to build a
+    /// [`dfraw_parser::graphics::SpriteAtlas`] from.
+    ///
+    /// The tile pages of every module are always included, since graphics can use another module's tile
+    /// pages.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_graphics_raws(
+        &self,
+        module_object_id: Option<Uuid>,
+    ) -> Result<Vec<Box<dyn RawObject>>> {
+        let mut raws = queries::get_raws_by_type(&self.conn, &[ObjectType::TilePage], None)?;
+        raws.extend(queries::get_raws_by_type(
+            &self.conn,
+            &[ObjectType::Graphics],
+            module_object_id,
+        )?);
+        Ok(raws)
+    }
+
     /// Retrieves the top result for a module id matching the data in the raw's metadata.
     ///
     /// # Errors
@@ -763,6 +807,28 @@ impl DbClient {
         )
     }
 
+    /// Get the best sprite graphic to show a target in a state (e.g. a child or a corpse).
+    ///
+    /// This follows the game's fallbacks between primary and secondary conditions, and prefers the
+    /// caste's sprites when a caste is given. See [`SpriteState`] for the states.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_best_sprite_graphic_for_target_identifier(
+        &self,
+        target_identifier: &str,
+        target_caste: Option<&str>,
+        state: SpriteState,
+    ) -> Result<Option<SpriteGraphicData>> {
+        queries::get_best_sprite_graphic_for_target_identifier(
+            &self.conn,
+            target_identifier,
+            target_caste,
+            state,
+        )
+    }
+
     /// Get a sprite graphic by its target identifier and include any caste-specific matches
     ///
     /// # Errors
@@ -779,6 +845,60 @@ impl DbClient {
         )
     }
 
+    /// Get the layers (from every layer set) for a target identifier, in the order they are drawn
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_sprite_layers_for_target_identifier(
+        &self,
+        target_identifier: &str,
+    ) -> Result<Vec<SpriteLayerData>> {
+        queries::get_sprite_layers_for_target_identifier(&self.conn, target_identifier)
+    }
+
+    /// Get the layers of one layer set (e.g. `DEFAULT` or `PORTRAIT`) for a target identifier, in the
+    /// order they are drawn
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_sprite_layer_set_for_target_identifier(
+        &self,
+        target_identifier: &str,
+        layer_set: &str,
+    ) -> Result<Vec<SpriteLayerData>> {
+        queries::get_sprite_layer_set_for_target_identifier(
+            &self.conn,
+            target_identifier,
+            layer_set,
+        )
+    }
+
+    /// Get the growth sprites for a target (plant) identifier
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_growth_sprites_for_target_identifier(
+        &self,
+        target_identifier: &str,
+    ) -> Result<Vec<GrowthSpriteData>> {
+        queries::get_growth_sprites_for_target_identifier(&self.conn, target_identifier)
+    }
+
+    /// Get the palettes defined in the graphics for a target identifier
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_graphic_palettes_for_target_identifier(
+        &self,
+        target_identifier: &str,
+    ) -> Result<Vec<GraphicPaletteData>> {
+        queries::get_graphic_palettes_for_target_identifier(&self.conn, target_identifier)
+    }
+
     /// Insert a module with its supporting data, returning its id in the database.
     ///
     /// This inserts the module along with its dependency chain and steam tag data.
@@ -853,4 +973,236 @@ impl DbClient {
     ) -> Result<Option<i64>> {
         queries::try_get_module_id_by_metadata(&self.conn, identifier, numeric_version, location)
     }
+
+    /// Get the database ids of the modules which have a steam tag (ignoring case)
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_module_ids_by_steam_tag(&self, tag: &str) -> Result<Vec<i64>> {
+        queries::get_module_ids_by_steam_tag(&self.conn, tag)
+    }
+
+    /// Get the database ids of the modules published to the steam workshop with a file id
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_module_ids_by_steam_file_id(&self, file_id: u64) -> Result<Vec<i64>> {
+        queries::get_module_ids_by_steam_file_id(&self.conn, file_id)
+    }
+
+    /// Get every steam tag used by the modules in the database
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_steam_tags(&self) -> Result<Vec<String>> {
+        queries::get_steam_tags(&self.conn)
+    }
+
+    /// Get the steam tags of a module
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_steam_tags_for_module(&self, module_id: i64) -> Result<Vec<String>> {
+        queries::get_steam_tags_for_module(&self.conn, module_id)
+    }
+
+    /// Get the steam key/value tags of a module as (key, value)
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_steam_key_value_tags_for_module(
+        &self,
+        module_id: i64,
+    ) -> Result<Vec<(String, Option<String>)>> {
+        queries::get_steam_key_value_tags_for_module(&self.conn, module_id)
+    }
+
+    /// Get the steam metadata of a module
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_steam_metadata_for_module(&self, module_id: i64) -> Result<Vec<String>> {
+        queries::get_steam_metadata_for_module(&self.conn, module_id)
+    }
+
+    /// Set the load order of the modules in the database, replacing any stored load order.
+    ///
+    /// The modules are given by their database ids, in the order they are loaded. An empty slice clears
+    /// the load order.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - a module id is not in the database
+    /// - a module id is given more than once
+    pub fn set_load_order(&mut self, module_ids: &[i64]) -> Result<()> {
+        queries::set_load_order(&mut self.conn, module_ids)
+    }
+
+    /// Get the database ids of the modules in the stored load order, in the order they are loaded
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_load_order(&self) -> Result<Vec<i64>> {
+        queries::get_load_order(&self.conn)
+    }
+
+    /// Check the stored load order against the dependencies declared by its modules.
+    ///
+    /// Reports required modules which are missing from the load order, conflicting modules which are both
+    /// in it, and modules which are loaded before a module they must be loaded after.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn validate_load_order(&self) -> Result<Vec<DependencyIssue>> {
+        queries::validate_load_order(&self.conn)
+    }
+
+    /// Insert a world parsed from legends exports (its historical figures, sites and historical events)
+    /// under a name, returning the id of the stored world.
+    ///
+    /// The same world can be inserted more than once; each insertion is stored as a separate world.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - serialization error
+    pub fn insert_legends_world(&mut self, name: &str, world: &LegendsWorld) -> Result<i64> {
+        info!(
+            "Inserting legends world {name} ({} historical figures, {} sites, {} historical events)",
+            world.historical_figures.len(),
+            world.sites.len(),
+            world.historical_events.len()
+        );
+        queries::insert_legends_world(&mut self.conn, name, world)
+    }
+
+    /// Insert a world from a legends export file under a name, returning the id of the stored world.
+    ///
+    /// The historical figures, sites and historical events are stored as they are read from the export,
+    /// so (unlike [`Self::insert_legends_world`]) the world is never held in memory.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - serialization error
+    /// - the export can't be opened or read
+    pub fn insert_legends_export<P: AsRef<Path>>(
+        &mut self,
+        name: &str,
+        input_path: &P,
+    ) -> Result<i64> {
+        let reader = LegendsReader::open(input_path)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        self.insert_legends_reader(name, reader)
+    }
+
+    /// Insert a world under a name from a reader of a legends export, returning the id of the stored world.
+    ///
+    /// The historical figures, sites and historical events are stored as they are read, and every other
+    /// record is dropped.
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - serialization error
+    /// - the export can't be read
+    pub fn insert_legends_reader<R: BufRead>(
+        &mut self,
+        name: &str,
+        reader: LegendsReader<R>,
+    ) -> Result<i64> {
+        info!("Inserting legends world {name} from a legends export");
+        queries::insert_legends_records(&mut self.conn, name, reader)
+    }
+
+    /// Delete a stored legends world and everything stored for it
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn delete_legends_world(&mut self, world_id: i64) -> Result<()> {
+        queries::delete_legends_world(&mut self.conn, world_id)
+    }
+
+    /// Get all the stored legends worlds
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    pub fn get_legends_worlds(&self) -> Result<Vec<LegendsWorldData>> {
+        queries::get_legends_worlds(&self.conn)
+    }
+
+    /// Get a historical figure of a stored legends world by its id in the export
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - deserialization error
+    pub fn get_legends_historical_figure(
+        &self,
+        world_id: i64,
+        historical_figure_id: i32,
+    ) -> Result<Option<ExportedHistoricalFigure>> {
+        queries::get_legends_historical_figure(&self.conn, world_id, historical_figure_id)
+    }
+
+    /// Get a page of the historical figures of a stored legends world of a race (e.g. `DWARF`)
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - deserialization error
+    pub fn get_legends_historical_figures_by_race(
+        &self,
+        world_id: i64,
+        race: &str,
+        limit: u32,
+        page: u32,
+    ) -> Result<Vec<ExportedHistoricalFigure>> {
+        queries::get_legends_historical_figures_by_race(&self.conn, world_id, race, limit, page)
+    }
+
+    /// Get a page of the historical events of a stored legends world which happened between two years
+    /// (inclusive), in the order they happened
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - deserialization error
+    pub fn get_legends_historical_events_between(
+        &self,
+        world_id: i64,
+        start_year: i32,
+        end_year: i32,
+        limit: u32,
+        page: u32,
+    ) -> Result<Vec<ExportedHistoricalEvent>> {
+        queries::get_legends_historical_events_between(
+            &self.conn, world_id, start_year, end_year, limit, page,
+        )
+    }
+
+    /// Get the sites of a stored legends world which belong to, or are currently held by, an entity
+    ///
+    /// # Errors
+    ///
+    /// - database error
+    /// - deserialization error
+    pub fn get_legends_sites_owned_by(
+        &self,
+        world_id: i64,
+        entity_id: i32,
+    ) -> Result<Vec<ExportedSite>> {
+        queries::get_legends_sites_owned_by(&self.conn, world_id, entity_id)
+    }
 }
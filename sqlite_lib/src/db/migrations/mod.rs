@@ -5,25 +5,40 @@ mod sql_003_graphics;
 mod sql_004_db_metadata;
 mod sql_005_unique_module_raw_ident;
 mod sql_006_object_id_cols;
+mod sql_007_graphics_layers;
+mod sql_008_legends;
+mod sql_009_steam_data;
+mod sql_010_sprite_condition_keys;
+mod sql_011_nullable_birth_year;
 
 /// The highest (and most recent) schema version.
-pub const LATEST_SCHEMA_VERSION: i32 = 5;
+pub const LATEST_SCHEMA_VERSION: i32 = 11;
 
 /// Migrations forward in the format (`schema_version`, SQL), in order of ascending schema version.
-pub(super) const UP_MIGRATIONS: [(i32, &str); 6] = [
+pub(super) const UP_MIGRATIONS: [(i32, &str); 11] = [
     (1, sql_001_initial::UP),
     (2, sql_002_names::UP),
     (3, sql_003_graphics::UP),
     (4, sql_004_db_metadata::UP),
     (5, sql_005_unique_module_raw_ident::UP),
     (6, sql_006_object_id_cols::UP),
+    (7, sql_007_graphics_layers::UP),
+    (8, sql_008_legends::UP),
+    (9, sql_009_steam_data::UP),
+    (10, sql_010_sprite_condition_keys::UP),
+    (11, sql_011_nullable_birth_year::UP),
 ];
 /// Migrations backward in in the format (`previous_schema_version`, SQL), in order of ascending schema version.
-pub(super) const DOWN_MIGRATIONS: [(i32, &str); 6] = [
+pub(super) const DOWN_MIGRATIONS: [(i32, &str); 11] = [
     (0, sql_001_initial::DOWN),
     (1, sql_002_names::DOWN),
     (2, sql_003_graphics::DOWN),
     (3, sql_004_db_metadata::DOWN),
     (4, sql_005_unique_module_raw_ident::DOWN),
     (5, sql_006_object_id_cols::DOWN),
+    (6, sql_007_graphics_layers::DOWN),
+    (7, sql_008_legends::DOWN),
+    (8, sql_009_steam_data::DOWN),
+    (9, sql_010_sprite_condition_keys::DOWN),
+    (10, sql_011_nullable_birth_year::DOWN),
 ];
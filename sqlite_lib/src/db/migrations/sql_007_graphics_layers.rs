@@ -0,0 +1,80 @@
+pub const UP: &str = r"
+BEGIN;
+
+-- Stores the layers of the layer sets (e.g. DEFAULT, PORTRAIT) of a graphics raw, in the order they
+-- are drawn within their set
+CREATE TABLE sprite_layers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    raw_id INTEGER NOT NULL, -- The GRAPHICS raw object this belongs to
+    target_identifier TEXT NOT NULL, -- The identifier of the creature/item this represents
+    layer_set TEXT NOT NULL,         -- e.g. DEFAULT, PORTRAIT, CORPSE, etc.
+    layer_index INTEGER NOT NULL,    -- The position of the layer within its layer set
+    layer_name TEXT NOT NULL,
+    layer_group INTEGER,             -- (optional) the LAYER_GROUP, only the first matching layer is drawn
+    tile_page_identifier TEXT NOT NULL,
+    offset_x INTEGER NOT NULL,
+    offset_y INTEGER NOT NULL,
+    offset_x_2 INTEGER,
+    offset_y_2 INTEGER,
+    FOREIGN KEY(raw_id) REFERENCES raw_definitions(id) ON DELETE CASCADE
+);
+
+-- Stores the conditions which must be met for a layer to be drawn
+CREATE TABLE sprite_layer_conditions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    layer_id INTEGER NOT NULL,
+    condition_index INTEGER NOT NULL, -- The position of the condition within the layer
+    condition TEXT NOT NULL,          -- e.g. CONDITION_BP, CONDITION_MATERIAL_FLAG, etc.
+    value TEXT NOT NULL,              -- The value given to the condition (may be empty)
+    FOREIGN KEY(layer_id) REFERENCES sprite_layers(id) ON DELETE CASCADE
+);
+
+-- Stores the sprites for each stage of the growths of a plant graphics raw
+CREATE TABLE growth_sprites (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    raw_id INTEGER NOT NULL, -- The GRAPHICS raw object this belongs to
+    target_identifier TEXT NOT NULL, -- The identifier of the plant this represents
+    growth_identifier TEXT NOT NULL, -- e.g. FRUIT, LEAVES, FLOWERS, etc.
+    growth_stage TEXT,               -- (optional) e.g. GROWTH_1, GROWTH_2, etc.
+    tile_page_identifier TEXT NOT NULL,
+    offset_x INTEGER NOT NULL,
+    offset_y INTEGER NOT NULL,
+    offset_x_2 INTEGER,
+    offset_y_2 INTEGER,
+    FOREIGN KEY(raw_id) REFERENCES raw_definitions(id) ON DELETE CASCADE
+);
+
+-- Stores the palettes (LS_PALETTE) defined in a graphics raw
+CREATE TABLE graphic_palettes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    raw_id INTEGER NOT NULL, -- The GRAPHICS raw object this belongs to
+    target_identifier TEXT NOT NULL, -- The identifier of the creature/item this represents
+    name TEXT NOT NULL,
+    file_path TEXT NOT NULL,
+    default_row INTEGER NOT NULL,
+    FOREIGN KEY(raw_id) REFERENCES raw_definitions(id) ON DELETE CASCADE
+);
+
+-- Indexes for fast lookups when building the UI
+CREATE INDEX idx_sprite_layers_target ON sprite_layers(target_identifier);
+CREATE INDEX idx_sprite_layers_raw ON sprite_layers(raw_id);
+CREATE INDEX idx_sprite_layer_conditions_layer ON sprite_layer_conditions(layer_id);
+CREATE INDEX idx_growth_sprites_target ON growth_sprites(target_identifier);
+CREATE INDEX idx_graphic_palettes_target ON graphic_palettes(target_identifier);
+
+COMMIT;
+";
+
+pub const DOWN: &str = r"
+BEGIN;
+DROP INDEX IF EXISTS idx_graphic_palettes_target;
+DROP INDEX IF EXISTS idx_growth_sprites_target;
+DROP INDEX IF EXISTS idx_sprite_layer_conditions_layer;
+DROP INDEX IF EXISTS idx_sprite_layers_raw;
+DROP INDEX IF EXISTS idx_sprite_layers_target;
+DROP TABLE IF EXISTS graphic_palettes;
+DROP TABLE IF EXISTS growth_sprites;
+DROP TABLE IF EXISTS sprite_layer_conditions;
+DROP TABLE IF EXISTS sprite_layers;
+COMMIT;
+";
@@ -0,0 +1,72 @@
+pub const UP: &str = r"
+BEGIN;
+
+-- Stores the worlds read from legends exports
+CREATE TABLE legends_worlds (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL
+);
+
+-- Stores the historical figures of a legends world
+CREATE TABLE legends_historical_figures (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    world_id INTEGER NOT NULL,
+    historical_figure_id INTEGER NOT NULL, -- The id of the figure in the export
+    name TEXT NOT NULL,
+    race TEXT NOT NULL,         -- The identifier of the creature of the race, e.g. DWARF
+    caste TEXT NOT NULL,
+    birth_year INTEGER NOT NULL,
+    death_year INTEGER,         -- (optional) only set if the figure has died
+    data_blob BLOB NOT NULL,    -- The JSON of the full historical figure
+    FOREIGN KEY(world_id) REFERENCES legends_worlds(id) ON DELETE CASCADE,
+    UNIQUE(world_id, historical_figure_id)
+);
+
+-- Stores the sites of a legends world
+CREATE TABLE legends_sites (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    world_id INTEGER NOT NULL,
+    site_id INTEGER NOT NULL,   -- The id of the site in the export
+    name TEXT NOT NULL,
+    site_type TEXT NOT NULL,    -- e.g. fortress, dark fortress, hamlet, etc.
+    civ_id INTEGER,             -- (optional) the civilization the site belongs to
+    current_owner_id INTEGER,   -- (optional) the entity currently holding the site
+    data_blob BLOB NOT NULL,    -- The JSON of the full site
+    FOREIGN KEY(world_id) REFERENCES legends_worlds(id) ON DELETE CASCADE,
+    UNIQUE(world_id, site_id)
+);
+
+-- Stores the historical events of a legends world
+CREATE TABLE legends_historical_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    world_id INTEGER NOT NULL,
+    event_id INTEGER NOT NULL,  -- The id of the event in the export
+    year INTEGER NOT NULL,
+    seconds72 INTEGER,          -- (optional) the time within the year
+    event_type TEXT NOT NULL,   -- e.g. hf died, change hf state, etc.
+    data_blob BLOB NOT NULL,    -- The JSON of the full historical event
+    FOREIGN KEY(world_id) REFERENCES legends_worlds(id) ON DELETE CASCADE,
+    UNIQUE(world_id, event_id)
+);
+
+-- Indexes for the common legends queries
+CREATE INDEX idx_legends_historical_figures_race ON legends_historical_figures(world_id, race);
+CREATE INDEX idx_legends_sites_civ ON legends_sites(world_id, civ_id);
+CREATE INDEX idx_legends_sites_owner ON legends_sites(world_id, current_owner_id);
+CREATE INDEX idx_legends_historical_events_year ON legends_historical_events(world_id, year, seconds72);
+
+COMMIT;
+";
+
+pub const DOWN: &str = r"
+BEGIN;
+DROP INDEX IF EXISTS idx_legends_historical_events_year;
+DROP INDEX IF EXISTS idx_legends_sites_owner;
+DROP INDEX IF EXISTS idx_legends_sites_civ;
+DROP INDEX IF EXISTS idx_legends_historical_figures_race;
+DROP TABLE IF EXISTS legends_historical_events;
+DROP TABLE IF EXISTS legends_sites;
+DROP TABLE IF EXISTS legends_historical_figures;
+DROP TABLE IF EXISTS legends_worlds;
+COMMIT;
+";
@@ -0,0 +1,28 @@
+pub const UP: &str = r"
+BEGIN;
+
+-- The value given to a key of a module's STEAM_KEY_VALUE_TAG
+ALTER TABLE module_steam_key_value_pairs ADD COLUMN value TEXT;
+
+-- Tags and keys are shared between modules, so they are only stored once
+CREATE UNIQUE INDEX idx_steam_tags_name ON steam_tags(name);
+CREATE UNIQUE INDEX idx_steam_key_value_keys_name ON steam_key_value_keys(name);
+
+-- Indexes for searching modules by their steam data
+CREATE INDEX idx_module_steam_tags_tag ON module_steam_tags(steam_tag_id);
+CREATE INDEX idx_steam_metadata_module ON steam_metadata(module_id);
+CREATE INDEX idx_modules_steam_file_id ON modules(steam_file_id);
+
+COMMIT;
+";
+
+pub const DOWN: &str = r"
+BEGIN;
+DROP INDEX IF EXISTS idx_modules_steam_file_id;
+DROP INDEX IF EXISTS idx_steam_metadata_module;
+DROP INDEX IF EXISTS idx_module_steam_tags_tag;
+DROP INDEX IF EXISTS idx_steam_key_value_keys_name;
+DROP INDEX IF EXISTS idx_steam_tags_name;
+ALTER TABLE module_steam_key_value_pairs DROP COLUMN value;
+COMMIT;
+";
@@ -0,0 +1,295 @@
+pub const UP: &str = r"
+BEGIN;
+
+-- Sprite conditions inserted one raw at a time were stored by their enum names (e.g. ChildPrime)
+-- instead of their tokens (e.g. CHILD), so convert them to the tokens.
+UPDATE sprite_graphics SET primary_condition = CASE primary_condition
+    WHEN 'Default' THEN 'DEFAULT'
+    WHEN 'Portrait' THEN 'PORTRAIT'
+    WHEN 'Animated' THEN 'ANIMATED'
+    WHEN 'Corpse' THEN 'CORPSE'
+    WHEN 'ChildPrime' THEN 'CHILD'
+    WHEN 'TrainedWar' THEN 'TRAINED_WAR'
+    WHEN 'TrainedHunter' THEN 'TRAINED_HUNTER'
+    WHEN 'ListIcon' THEN 'LIST_ICON'
+    WHEN 'Skeleton' THEN 'SKELETON'
+    WHEN 'SkeletonWithSkull' THEN 'SKELETON_WITH_SKULL'
+    WHEN 'Zombie' THEN 'ZOMBIE'
+    WHEN 'Necromancer' THEN 'NECROMANCER'
+    WHEN 'Male' THEN 'MALE'
+    WHEN 'Female' THEN 'FEMALE'
+    WHEN 'VampireCursed' THEN 'VAMPCURSE'
+    WHEN 'Ghoul' THEN 'GHOUL'
+    WHEN 'Ghost' THEN 'GHOST'
+    WHEN 'DisturbedDead' THEN 'DISTURBED_DEAD'
+    WHEN 'Remains' THEN 'REMAINS'
+    WHEN 'Vermin' THEN 'VERMIN'
+    WHEN 'LightVermin' THEN 'LIGHT_VERMIN'
+    WHEN 'Hive' THEN 'HIVE'
+    WHEN 'SwarmSmall' THEN 'SWARM_SMALL'
+    WHEN 'SwarmMedium' THEN 'SWARM_MEDIUM'
+    WHEN 'SwarmLarge' THEN 'SWARM_LARGE'
+    WHEN 'NotArtifact' THEN 'NOT_ARTIFACT'
+    WHEN 'CraftedArtifact' THEN 'IS_CRAFTED_ARTIFACT'
+    WHEN 'Shrub' THEN 'SHRUB'
+    WHEN 'Picked' THEN 'PICKED'
+    WHEN 'Seed' THEN 'SEED'
+    WHEN 'Crop' THEN 'CROP'
+    WHEN 'CropSprout' THEN 'CROP_SPROUT'
+    WHEN 'CropL' THEN 'CROP_L'
+    WHEN 'CropM' THEN 'CROP_M'
+    WHEN 'CropR' THEN 'CROP_R'
+    WHEN 'ShrubDead' THEN 'SHRUB_DEAD'
+    WHEN 'Sapling' THEN 'SAPLING'
+    WHEN 'NotChild' THEN 'CONDITION_NOT_CHILD'
+    WHEN 'Class' THEN 'CONDITION_CLASS'
+    WHEN 'Child' THEN 'CONDITION_CHILD'
+    WHEN 'Baby' THEN 'CONDITION_BABY'
+    WHEN 'HaulCountMin' THEN 'CONDITION_HAUL_COUNT_MIN'
+    WHEN 'HaulCountMax' THEN 'CONDITION_HAUL_COUNT_MAX'
+    WHEN 'ItemWorn' THEN 'CONDITION_ITEM_WORN'
+    WHEN 'ProfessionCategory' THEN 'CONDITION_PROFESSION_CATEGORY'
+    WHEN 'SyndromeClass' THEN 'CONDITION_SYN_CLASS'
+    WHEN 'Caste' THEN 'CONDITION_CASTE'
+    WHEN 'TissueLayer' THEN 'CONDITION_TISSUE_LAYER'
+    WHEN 'MaterialFlag' THEN 'CONDITION_MATERIAL_FLAG'
+    WHEN 'MaterialType' THEN 'CONDITION_MATERIAL_TYPE'
+    WHEN 'Dye' THEN 'CONDITION_DYE'
+    WHEN 'NotDyed' THEN 'CONDITION_NOT_DYED'
+    WHEN 'ShutOffIfItemPresent' THEN 'SHUT_OFF_IF_ITEM_PRESENT'
+    WHEN 'RandomPartIndex' THEN 'CONDITION_RANDOM_PART_INDEX'
+    WHEN 'TissueMayHaveColor' THEN 'TISSUE_MAY_HAVE_COLOR'
+    WHEN 'TissueMinLength' THEN 'TISSUE_MIN_LENGTH'
+    WHEN 'TissueMaxLength' THEN 'TISSUE_MAX_LENGTH'
+    WHEN 'TissueMayHaveShaping' THEN 'TISSUE_MAY_HAVE_SHAPING'
+    WHEN 'TissueNotShaped' THEN 'TISSUE_NOT_SHAPED'
+    WHEN 'TissueSwap' THEN 'TISSUE_SWAP'
+    WHEN 'TissueMinCurly' THEN 'TISSUE_MIN_CURLY'
+    WHEN 'TissueMaxCurly' THEN 'TISSUE_MAX_CURLY'
+    WHEN 'Condition' THEN 'CONDITION'
+    WHEN 'Hammerman' THEN 'HAMMERMAN'
+    WHEN 'MasterHammerman' THEN 'MASTER_HAMMERMAN'
+    WHEN 'Spearman' THEN 'SPEARMAN'
+    WHEN 'MasterSpearman' THEN 'MASTER_SPEARMAN'
+    WHEN 'Wrestler' THEN 'WRESTLER'
+    WHEN 'MasterWrestler' THEN 'MASTER_WRESTLER'
+    WHEN 'Axeman' THEN 'AXEMAN'
+    WHEN 'MasterAxeman' THEN 'MASTER_AXEMAN'
+    WHEN 'Swordsman' THEN 'SWORDSMAN'
+    WHEN 'MasterSwordsman' THEN 'MASTER_SWORDSMAN'
+    WHEN 'Maceman' THEN 'MACEMAN'
+    WHEN 'MasterMaceman' THEN 'MASTER_MACEMAN'
+    WHEN 'Pikeman' THEN 'PIKEMAN'
+    WHEN 'MasterPikeman' THEN 'MASTER_PIKEMAN'
+    WHEN 'Recruit' THEN 'RECRUIT'
+    WHEN 'Thief' THEN 'THIEF'
+    WHEN 'MasterThief' THEN 'MASTER_THIEF'
+    WHEN 'Lasher' THEN 'LASHER'
+    WHEN 'MasterLasher' THEN 'MASTER_LASHER'
+    WHEN 'MonsterSlayer' THEN 'MONSTER_SLAYER'
+    WHEN 'Crossbowman' THEN 'CROSSBOWMAN'
+    WHEN 'MasterCrossbowman' THEN 'MASTER_CROSSBOWMAN'
+    WHEN 'Bowman' THEN 'BOWMAN'
+    WHEN 'MasterBowman' THEN 'MASTER_BOWMAN'
+    WHEN 'Blowgunman' THEN 'BLOWGUNMAN'
+    WHEN 'MasterBlowgunman' THEN 'MASTER_BLOWGUNMAN'
+    WHEN 'BeastHunter' THEN 'BEAST_HUNTER'
+    WHEN 'Scout' THEN 'SCOUT'
+    WHEN 'Ranger' THEN 'RANGER'
+    WHEN 'Hunter' THEN 'HUNTER'
+    WHEN 'Sage' THEN 'SAGE'
+    WHEN 'Scholar' THEN 'SCHOLAR'
+    WHEN 'Philosopher' THEN 'PHILOSOPHER'
+    WHEN 'Mathematician' THEN 'MATHEMATICIAN'
+    WHEN 'Historian' THEN 'HISTORIAN'
+    WHEN 'Astronomer' THEN 'ASTRONOMER'
+    WHEN 'Naturalist' THEN 'NATURALIST'
+    WHEN 'Chemist' THEN 'CHEMIST'
+    WHEN 'Geographer' THEN 'GEOGRAPHER'
+    WHEN 'Scribe' THEN 'SCRIBE'
+    WHEN 'Bookbinder' THEN 'BOOKBINDER'
+    WHEN 'Performer' THEN 'PERFORMER'
+    WHEN 'Poet' THEN 'POET'
+    WHEN 'Bard' THEN 'BARD'
+    WHEN 'Dancer' THEN 'DANCER'
+    WHEN 'LayerSetPalette' THEN 'LS_PALETTE'
+    WHEN 'LayerSetPaletteFile' THEN 'LS_PALETTE_FILE'
+    WHEN 'LayerSetPaletteDefault' THEN 'LS_PALETTE_DEFAULT'
+    WHEN 'VerminAlt' THEN 'VERMIN_ALT'
+    WHEN 'LightVerminAlt' THEN 'LIGHT_VERMIN_ALT'
+    WHEN 'LightSwarmSmall' THEN 'LIGHT_SWARM_SMALL'
+    WHEN 'LightSwarmMedium' THEN 'LIGHT_SWARM_MEDIUM'
+    WHEN 'LightSwarmLarge' THEN 'LIGHT_SWARM_LARGE'
+    WHEN 'ItemQuality' THEN 'ITEM_QUALITY'
+    WHEN 'UsePalette' THEN 'USE_PALETTE'
+    WHEN 'UseStandardPaletteFromItem' THEN 'USE_STANDARD_PALETTE_FROM_ITEM'
+    WHEN 'BodyPartAppearanceModifierRange' THEN 'BP_APPEARANCE_MODIFIER_RANGE'
+    WHEN 'BodyPart' THEN 'CONDITION_BP'
+    WHEN 'LayerGroupBodyPart' THEN 'LG_CONDITION_BP'
+    WHEN 'TissueMinDensity' THEN 'TISSUE_MIN_DENSITY'
+    WHEN 'TissueMaxDensity' THEN 'TISSUE_MAX_DENSITY'
+    WHEN 'BodySizeMin' THEN 'CONDITION_BODY_SIZE_MIN'
+    WHEN 'BodySizeMax' THEN 'CONDITION_BODY_SIZE_MAX'
+    WHEN 'Glow' THEN 'GLOW'
+    WHEN 'GlowChild' THEN 'GLOW_CHILD'
+    WHEN 'GlowLeftGone' THEN 'GLOW_LEFT_GONE'
+    WHEN 'GlowRightGone' THEN 'GLOW_RIGHT_GONE'
+    WHEN 'LawEnforcement' THEN 'LAW_ENFORCE'
+    WHEN 'TaxEscort' THEN 'TAX_ESCORT'
+    WHEN 'Adventurer' THEN 'ADVENTURER'
+    WHEN 'Egg' THEN 'EGG'
+    WHEN 'CdiListIcon' THEN 'CDI_LIST_ICON'
+    WHEN 'BabyPrime' THEN 'BABY'
+    ELSE primary_condition
+END;
+
+UPDATE sprite_graphics SET secondary_condition = NULL WHERE secondary_condition IN ('None', '');
+UPDATE sprite_graphics SET secondary_condition = CASE secondary_condition
+    WHEN 'Default' THEN 'DEFAULT'
+    WHEN 'Portrait' THEN 'PORTRAIT'
+    WHEN 'Animated' THEN 'ANIMATED'
+    WHEN 'Corpse' THEN 'CORPSE'
+    WHEN 'ChildPrime' THEN 'CHILD'
+    WHEN 'TrainedWar' THEN 'TRAINED_WAR'
+    WHEN 'TrainedHunter' THEN 'TRAINED_HUNTER'
+    WHEN 'ListIcon' THEN 'LIST_ICON'
+    WHEN 'Skeleton' THEN 'SKELETON'
+    WHEN 'SkeletonWithSkull' THEN 'SKELETON_WITH_SKULL'
+    WHEN 'Zombie' THEN 'ZOMBIE'
+    WHEN 'Necromancer' THEN 'NECROMANCER'
+    WHEN 'Male' THEN 'MALE'
+    WHEN 'Female' THEN 'FEMALE'
+    WHEN 'VampireCursed' THEN 'VAMPCURSE'
+    WHEN 'Ghoul' THEN 'GHOUL'
+    WHEN 'Ghost' THEN 'GHOST'
+    WHEN 'DisturbedDead' THEN 'DISTURBED_DEAD'
+    WHEN 'Remains' THEN 'REMAINS'
+    WHEN 'Vermin' THEN 'VERMIN'
+    WHEN 'LightVermin' THEN 'LIGHT_VERMIN'
+    WHEN 'Hive' THEN 'HIVE'
+    WHEN 'SwarmSmall' THEN 'SWARM_SMALL'
+    WHEN 'SwarmMedium' THEN 'SWARM_MEDIUM'
+    WHEN 'SwarmLarge' THEN 'SWARM_LARGE'
+    WHEN 'NotArtifact' THEN 'NOT_ARTIFACT'
+    WHEN 'CraftedArtifact' THEN 'IS_CRAFTED_ARTIFACT'
+    WHEN 'Shrub' THEN 'SHRUB'
+    WHEN 'Picked' THEN 'PICKED'
+    WHEN 'Seed' THEN 'SEED'
+    WHEN 'Crop' THEN 'CROP'
+    WHEN 'CropSprout' THEN 'CROP_SPROUT'
+    WHEN 'CropL' THEN 'CROP_L'
+    WHEN 'CropM' THEN 'CROP_M'
+    WHEN 'CropR' THEN 'CROP_R'
+    WHEN 'ShrubDead' THEN 'SHRUB_DEAD'
+    WHEN 'Sapling' THEN 'SAPLING'
+    WHEN 'NotChild' THEN 'CONDITION_NOT_CHILD'
+    WHEN 'Class' THEN 'CONDITION_CLASS'
+    WHEN 'Child' THEN 'CONDITION_CHILD'
+    WHEN 'Baby' THEN 'CONDITION_BABY'
+    WHEN 'HaulCountMin' THEN 'CONDITION_HAUL_COUNT_MIN'
+    WHEN 'HaulCountMax' THEN 'CONDITION_HAUL_COUNT_MAX'
+    WHEN 'ItemWorn' THEN 'CONDITION_ITEM_WORN'
+    WHEN 'ProfessionCategory' THEN 'CONDITION_PROFESSION_CATEGORY'
+    WHEN 'SyndromeClass' THEN 'CONDITION_SYN_CLASS'
+    WHEN 'Caste' THEN 'CONDITION_CASTE'
+    WHEN 'TissueLayer' THEN 'CONDITION_TISSUE_LAYER'
+    WHEN 'MaterialFlag' THEN 'CONDITION_MATERIAL_FLAG'
+    WHEN 'MaterialType' THEN 'CONDITION_MATERIAL_TYPE'
+    WHEN 'Dye' THEN 'CONDITION_DYE'
+    WHEN 'NotDyed' THEN 'CONDITION_NOT_DYED'
+    WHEN 'ShutOffIfItemPresent' THEN 'SHUT_OFF_IF_ITEM_PRESENT'
+    WHEN 'RandomPartIndex' THEN 'CONDITION_RANDOM_PART_INDEX'
+    WHEN 'TissueMayHaveColor' THEN 'TISSUE_MAY_HAVE_COLOR'
+    WHEN 'TissueMinLength' THEN 'TISSUE_MIN_LENGTH'
+    WHEN 'TissueMaxLength' THEN 'TISSUE_MAX_LENGTH'
+    WHEN 'TissueMayHaveShaping' THEN 'TISSUE_MAY_HAVE_SHAPING'
+    WHEN 'TissueNotShaped' THEN 'TISSUE_NOT_SHAPED'
+    WHEN 'TissueSwap' THEN 'TISSUE_SWAP'
+    WHEN 'TissueMinCurly' THEN 'TISSUE_MIN_CURLY'
+    WHEN 'TissueMaxCurly' THEN 'TISSUE_MAX_CURLY'
+    WHEN 'Condition' THEN 'CONDITION'
+    WHEN 'Hammerman' THEN 'HAMMERMAN'
+    WHEN 'MasterHammerman' THEN 'MASTER_HAMMERMAN'
+    WHEN 'Spearman' THEN 'SPEARMAN'
+    WHEN 'MasterSpearman' THEN 'MASTER_SPEARMAN'
+    WHEN 'Wrestler' THEN 'WRESTLER'
+    WHEN 'MasterWrestler' THEN 'MASTER_WRESTLER'
+    WHEN 'Axeman' THEN 'AXEMAN'
+    WHEN 'MasterAxeman' THEN 'MASTER_AXEMAN'
+    WHEN 'Swordsman' THEN 'SWORDSMAN'
+    WHEN 'MasterSwordsman' THEN 'MASTER_SWORDSMAN'
+    WHEN 'Maceman' THEN 'MACEMAN'
+    WHEN 'MasterMaceman' THEN 'MASTER_MACEMAN'
+    WHEN 'Pikeman' THEN 'PIKEMAN'
+    WHEN 'MasterPikeman' THEN 'MASTER_PIKEMAN'
+    WHEN 'Recruit' THEN 'RECRUIT'
+    WHEN 'Thief' THEN 'THIEF'
+    WHEN 'MasterThief' THEN 'MASTER_THIEF'
+    WHEN 'Lasher' THEN 'LASHER'
+    WHEN 'MasterLasher' THEN 'MASTER_LASHER'
+    WHEN 'MonsterSlayer' THEN 'MONSTER_SLAYER'
+    WHEN 'Crossbowman' THEN 'CROSSBOWMAN'
+    WHEN 'MasterCrossbowman' THEN 'MASTER_CROSSBOWMAN'
+    WHEN 'Bowman' THEN 'BOWMAN'
+    WHEN 'MasterBowman' THEN 'MASTER_BOWMAN'
+    WHEN 'Blowgunman' THEN 'BLOWGUNMAN'
+    WHEN 'MasterBlowgunman' THEN 'MASTER_BLOWGUNMAN'
+    WHEN 'BeastHunter' THEN 'BEAST_HUNTER'
+    WHEN 'Scout' THEN 'SCOUT'
+    WHEN 'Ranger' THEN 'RANGER'
+    WHEN 'Hunter' THEN 'HUNTER'
+    WHEN 'Sage' THEN 'SAGE'
+    WHEN 'Scholar' THEN 'SCHOLAR'
+    WHEN 'Philosopher' THEN 'PHILOSOPHER'
+    WHEN 'Mathematician' THEN 'MATHEMATICIAN'
+    WHEN 'Historian' THEN 'HISTORIAN'
+    WHEN 'Astronomer' THEN 'ASTRONOMER'
+    WHEN 'Naturalist' THEN 'NATURALIST'
+    WHEN 'Chemist' THEN 'CHEMIST'
+    WHEN 'Geographer' THEN 'GEOGRAPHER'
+    WHEN 'Scribe' THEN 'SCRIBE'
+    WHEN 'Bookbinder' THEN 'BOOKBINDER'
+    WHEN 'Performer' THEN 'PERFORMER'
+    WHEN 'Poet' THEN 'POET'
+    WHEN 'Bard' THEN 'BARD'
+    WHEN 'Dancer' THEN 'DANCER'
+    WHEN 'LayerSetPalette' THEN 'LS_PALETTE'
+    WHEN 'LayerSetPaletteFile' THEN 'LS_PALETTE_FILE'
+    WHEN 'LayerSetPaletteDefault' THEN 'LS_PALETTE_DEFAULT'
+    WHEN 'VerminAlt' THEN 'VERMIN_ALT'
+    WHEN 'LightVerminAlt' THEN 'LIGHT_VERMIN_ALT'
+    WHEN 'LightSwarmSmall' THEN 'LIGHT_SWARM_SMALL'
+    WHEN 'LightSwarmMedium' THEN 'LIGHT_SWARM_MEDIUM'
+    WHEN 'LightSwarmLarge' THEN 'LIGHT_SWARM_LARGE'
+    WHEN 'ItemQuality' THEN 'ITEM_QUALITY'
+    WHEN 'UsePalette' THEN 'USE_PALETTE'
+    WHEN 'UseStandardPaletteFromItem' THEN 'USE_STANDARD_PALETTE_FROM_ITEM'
+    WHEN 'BodyPartAppearanceModifierRange' THEN 'BP_APPEARANCE_MODIFIER_RANGE'
+    WHEN 'BodyPart' THEN 'CONDITION_BP'
+    WHEN 'LayerGroupBodyPart' THEN 'LG_CONDITION_BP'
+    WHEN 'TissueMinDensity' THEN 'TISSUE_MIN_DENSITY'
+    WHEN 'TissueMaxDensity' THEN 'TISSUE_MAX_DENSITY'
+    WHEN 'BodySizeMin' THEN 'CONDITION_BODY_SIZE_MIN'
+    WHEN 'BodySizeMax' THEN 'CONDITION_BODY_SIZE_MAX'
+    WHEN 'Glow' THEN 'GLOW'
+    WHEN 'GlowChild' THEN 'GLOW_CHILD'
+    WHEN 'GlowLeftGone' THEN 'GLOW_LEFT_GONE'
+    WHEN 'GlowRightGone' THEN 'GLOW_RIGHT_GONE'
+    WHEN 'LawEnforcement' THEN 'LAW_ENFORCE'
+    WHEN 'TaxEscort' THEN 'TAX_ESCORT'
+    WHEN 'Adventurer' THEN 'ADVENTURER'
+    WHEN 'Egg' THEN 'EGG'
+    WHEN 'CdiListIcon' THEN 'CDI_LIST_ICON'
+    WHEN 'BabyPrime' THEN 'BABY'
+    ELSE secondary_condition
+END
+WHERE secondary_condition IS NOT NULL;
+
+COMMIT;
+";
+
+// The tokens are what both insertion paths store now, so there is nothing to undo.
+pub const DOWN: &str = r"
+BEGIN;
+COMMIT;
+";
@@ -0,0 +1,66 @@
+pub const UP: &str = r"
+BEGIN;
+
+-- The birth year of a historical figure is optional (like the death year), so rebuild the table with a
+-- nullable column. SQLite can't drop a NOT NULL constraint in place.
+CREATE TABLE legends_historical_figures_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    world_id INTEGER NOT NULL,
+    historical_figure_id INTEGER NOT NULL, -- The id of the figure in the export
+    name TEXT NOT NULL,
+    race TEXT NOT NULL,         -- The identifier of the creature of the race, e.g. DWARF
+    caste TEXT NOT NULL,
+    birth_year INTEGER,         -- (optional) only set if the birth year was exported
+    death_year INTEGER,         -- (optional) only set if the figure has died
+    data_blob BLOB NOT NULL,    -- The JSON of the full historical figure
+    FOREIGN KEY(world_id) REFERENCES legends_worlds(id) ON DELETE CASCADE,
+    UNIQUE(world_id, historical_figure_id)
+);
+
+-- Unknown birth years were stored as 0, the stored figure knows whether it had one
+INSERT INTO legends_historical_figures_new
+    (id, world_id, historical_figure_id, name, race, caste, birth_year, death_year, data_blob)
+SELECT
+    id, world_id, historical_figure_id, name, race, caste,
+    json_extract(data_blob, '$.birthYear'), death_year, data_blob
+FROM legends_historical_figures;
+
+DROP INDEX IF EXISTS idx_legends_historical_figures_race;
+DROP TABLE legends_historical_figures;
+ALTER TABLE legends_historical_figures_new RENAME TO legends_historical_figures;
+CREATE INDEX idx_legends_historical_figures_race ON legends_historical_figures(world_id, race);
+
+COMMIT;
+";
+
+pub const DOWN: &str = r"
+BEGIN;
+
+CREATE TABLE legends_historical_figures_old (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    world_id INTEGER NOT NULL,
+    historical_figure_id INTEGER NOT NULL, -- The id of the figure in the export
+    name TEXT NOT NULL,
+    race TEXT NOT NULL,         -- The identifier of the creature of the race, e.g. DWARF
+    caste TEXT NOT NULL,
+    birth_year INTEGER NOT NULL,
+    death_year INTEGER,         -- (optional) only set if the figure has died
+    data_blob BLOB NOT NULL,    -- The JSON of the full historical figure
+    FOREIGN KEY(world_id) REFERENCES legends_worlds(id) ON DELETE CASCADE,
+    UNIQUE(world_id, historical_figure_id)
+);
+
+INSERT INTO legends_historical_figures_old
+    (id, world_id, historical_figure_id, name, race, caste, birth_year, death_year, data_blob)
+SELECT
+    id, world_id, historical_figure_id, name, race, caste,
+    COALESCE(birth_year, 0), death_year, data_blob
+FROM legends_historical_figures;
+
+DROP INDEX IF EXISTS idx_legends_historical_figures_race;
+DROP TABLE legends_historical_figures;
+ALTER TABLE legends_historical_figures_old RENAME TO legends_historical_figures;
+CREATE INDEX idx_legends_historical_figures_race ON legends_historical_figures(world_id, race);
+
+COMMIT;
+";
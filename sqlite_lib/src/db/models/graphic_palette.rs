@@ -0,0 +1,17 @@
+/// A simplified struct for a palette (`LS_PALETTE`) defined in a graphics raw
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphicPaletteData {
+    /// database id for this palette
+    pub id: i64,
+    /// linked raw id (of graphics raw) this belongs to
+    pub raw_id: i64,
+    /// the identifier of the thing using this palette
+    pub target_identifier: String,
+    /// the name of the palette
+    pub name: String,
+    /// path to the palette image
+    pub file_path: String,
+    /// the row of the palette image used by default
+    pub default_row: u32,
+}
@@ -0,0 +1,25 @@
+/// A simplified struct for the sprite of a plant growth
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GrowthSpriteData {
+    /// database id for this growth sprite
+    pub id: i64,
+    /// linked raw id (of graphics raw) this belongs to
+    pub raw_id: i64,
+    /// the identifier of the plant this sprite displays
+    pub target_identifier: String,
+    /// the identifier of the growth, e.g. `FRUIT`
+    pub growth_identifier: String,
+    /// the stage of the growth this sprite is for, e.g. `GROWTH_1`
+    pub growth_stage: Option<String>,
+    /// identifier of tile page sprite is on
+    pub tile_page_identifier: String,
+    /// sprite offset x1
+    pub offset_x: i32,
+    /// sprite offset y1
+    pub offset_y: i32,
+    /// for large sprites, offset x2
+    pub offset_x_2: Option<i32>,
+    /// for large sprites, offset y2
+    pub offset_y_2: Option<i32>,
+}
@@ -0,0 +1,15 @@
+/// A simplified struct for a world read from a legends export
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendsWorldData {
+    /// database id for this world
+    pub id: i64,
+    /// the name the world was stored with
+    pub name: String,
+    /// how many historical figures are stored for the world
+    pub historical_figure_count: u32,
+    /// how many sites are stored for the world
+    pub site_count: u32,
+    /// how many historical events are stored for the world
+    pub historical_event_count: u32,
+}
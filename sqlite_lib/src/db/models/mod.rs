@@ -1,7 +1,15 @@
 //! Models for handling data types of various queries.
 
+mod graphic_palette;
+mod growth_sprite;
+mod legends_world;
 mod sprite_graphic;
+mod sprite_layer;
 mod tile_page;
 
+pub use graphic_palette::GraphicPaletteData;
+pub use growth_sprite::GrowthSpriteData;
+pub use legends_world::LegendsWorldData;
 pub use sprite_graphic::SpriteGraphicData;
+pub use sprite_layer::{SpriteLayerConditionData, SpriteLayerData};
 pub use tile_page::TilePageData;
@@ -0,0 +1,41 @@
+/// A simplified struct for a layer of a layer set in a graphics raw
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteLayerData {
+    /// database id for this layer
+    pub id: i64,
+    /// linked raw id (of graphics raw) this belongs to
+    pub raw_id: i64,
+    /// the identifier of the thing this layer displays
+    pub target_identifier: String,
+    /// the layer set this layer belongs to, e.g. `DEFAULT` or `PORTRAIT`
+    pub layer_set: String,
+    /// the position of the layer within its layer set (layers are drawn in this order)
+    pub layer_index: u32,
+    /// the name of the layer
+    pub layer_name: String,
+    /// the `LAYER_GROUP` of the layer, if any (only the first matching layer in a group is drawn)
+    pub layer_group: Option<u32>,
+    /// identifier of tile page sprite is on
+    pub tile_page_identifier: String,
+    /// sprite offset x1
+    pub offset_x: i32,
+    /// sprite offset y1
+    pub offset_y: i32,
+    /// for large sprites, offset x2
+    pub offset_x_2: Option<i32>,
+    /// for large sprites, offset y2
+    pub offset_y_2: Option<i32>,
+    /// the conditions which must be met for the layer to be drawn, in the order they were defined
+    pub conditions: Vec<SpriteLayerConditionData>,
+}
+
+/// A condition which must be met for a layer to be drawn
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteLayerConditionData {
+    /// the condition token, e.g. `CONDITION_BP`
+    pub condition: String,
+    /// the value given to the condition (empty if it has none)
+    pub value: String,
+}
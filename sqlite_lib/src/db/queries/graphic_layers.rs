@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use dfraw_parser::{Graphic, traits::RawObject as _, traits::RawToken as _};
+use rusqlite::{Connection, Result, Row, params};
+
+use crate::models::{
+    GraphicPaletteData, GrowthSpriteData, SpriteLayerConditionData, SpriteLayerData,
+};
+
+use super::table_inserts::{
+    INSERT_GRAPHIC_PALETTE, INSERT_GROWTH_SPRITE, INSERT_SPRITE_LAYER_CONDITION,
+    INSERT_SPRITE_LAYER_RETURN_ID,
+};
+
+/// Inserts the layer sets (with their conditions), growth sprites and palettes of a graphics raw.
+///
+/// # Errors
+///
+/// - database error
+pub fn insert_graphic_layers(conn: &Connection, raw_id: i64, graphic: &Graphic) -> Result<()> {
+    let mut insert_layer_stmt = conn.prepare_cached(INSERT_SPRITE_LAYER_RETURN_ID)?;
+    let mut insert_condition_stmt = conn.prepare_cached(INSERT_SPRITE_LAYER_CONDITION)?;
+    let mut insert_growth_stmt = conn.prepare_cached(INSERT_GROWTH_SPRITE)?;
+    let mut insert_palette_stmt = conn.prepare_cached(INSERT_GRAPHIC_PALETTE)?;
+    let target_identifier = graphic.get_identifier();
+
+    // layers are (LAYER_SET: [LAYER DEFINITIONS..])
+    for (layer_set, layers) in &graphic.get_layers() {
+        for (layer_index, layer) in (0_u32..).zip(layers) {
+            let offset = layer.get_offset();
+            let offset_2 = layer.get_offset2();
+            let layer_id: i64 = insert_layer_stmt.query_row(
+                params![
+                    raw_id,
+                    target_identifier,
+                    layer_set,
+                    layer_index,
+                    layer.get_name(),
+                    layer.get_layer_group(),
+                    layer.get_tile_page_id(),
+                    offset.x,
+                    offset.y,
+                    offset_2.map(|o| o.x),
+                    offset_2.map(|o| o.y)
+                ],
+                |row| row.get(0),
+            )?;
+            for (condition_index, (condition, value)) in (0_u32..).zip(layer.get_conditions()) {
+                insert_condition_stmt.execute(params![
+                    layer_id,
+                    condition_index,
+                    condition.get_key().unwrap_or_default(),
+                    value
+                ])?;
+            }
+        }
+    }
+
+    // growths are (GROWTH_IDENTIFIER: [SPRITES..])
+    for (growth_identifier, sprites) in &graphic.get_growths() {
+        for sprite in sprites {
+            let offset = sprite.get_offset();
+            let offset_2 = sprite.get_offset2();
+            insert_growth_stmt.execute(params![
+                raw_id,
+                target_identifier,
+                growth_identifier,
+                sprite.get_growth().and_then(|growth| growth.get_key()),
+                sprite.get_tile_page_id(),
+                offset.x,
+                offset.y,
+                offset_2.map(|o| o.x),
+                offset_2.map(|o| o.y)
+            ])?;
+        }
+    }
+
+    for palette in graphic.get_palettes() {
+        insert_palette_stmt.execute(params![
+            raw_id,
+            target_identifier,
+            palette.get_name(),
+            palette.get_file(),
+            palette.get_default_row()
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Get all the layers (from every layer set) for a target identifier, in the order they are drawn.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_sprite_layers_for_target_identifier(
+    conn: &Connection,
+    target_identifier: &str,
+) -> Result<Vec<SpriteLayerData>> {
+    const GET_SPRITE_LAYERS_FOR_TARGET_IDENTIFIER: &str = r"
+    SELECT
+        id, raw_id, target_identifier, layer_set, layer_index, layer_name, layer_group,
+        tile_page_identifier, offset_x, offset_y, offset_x_2, offset_y_2
+    FROM sprite_layers
+    WHERE
+        target_identifier = ?1
+    ORDER BY raw_id, id;
+    ";
+
+    let mut stmt = conn.prepare(GET_SPRITE_LAYERS_FOR_TARGET_IDENTIFIER)?;
+    let mut rows = stmt.query(params![target_identifier])?;
+    let mut layers = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        layers.push(sprite_layer_from_row(row)?);
+    }
+
+    add_sprite_layer_conditions(conn, &mut layers, target_identifier, None)?;
+    Ok(layers)
+}
+
+/// Get the layers of one layer set (e.g. `DEFAULT` or `PORTRAIT`) for a target identifier, in the order
+/// they are drawn.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_sprite_layer_set_for_target_identifier(
+    conn: &Connection,
+    target_identifier: &str,
+    layer_set: &str,
+) -> Result<Vec<SpriteLayerData>> {
+    const GET_SPRITE_LAYER_SET_FOR_TARGET_IDENTIFIER: &str = r"
+    SELECT
+        id, raw_id, target_identifier, layer_set, layer_index, layer_name, layer_group,
+        tile_page_identifier, offset_x, offset_y, offset_x_2, offset_y_2
+    FROM sprite_layers
+    WHERE
+        target_identifier = ?1
+    AND layer_set = ?2 COLLATE NOCASE
+    ORDER BY raw_id, layer_index;
+    ";
+
+    let mut stmt = conn.prepare(GET_SPRITE_LAYER_SET_FOR_TARGET_IDENTIFIER)?;
+    let mut rows = stmt.query(params![target_identifier, layer_set])?;
+    let mut layers = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        layers.push(sprite_layer_from_row(row)?);
+    }
+
+    add_sprite_layer_conditions(conn, &mut layers, target_identifier, Some(layer_set))?;
+    Ok(layers)
+}
+
+/// Get the growth sprites for a target (plant) identifier
+///
+/// # Errors
+///
+/// - database error
+pub fn get_growth_sprites_for_target_identifier(
+    conn: &Connection,
+    target_identifier: &str,
+) -> Result<Vec<GrowthSpriteData>> {
+    const GET_GROWTH_SPRITES_FOR_TARGET_IDENTIFIER: &str = r"
+    SELECT
+        id, raw_id, target_identifier, growth_identifier, growth_stage,
+        tile_page_identifier, offset_x, offset_y, offset_x_2, offset_y_2
+    FROM growth_sprites
+    WHERE
+        target_identifier = ?1
+    ORDER BY raw_id, id;
+    ";
+
+    let mut stmt = conn.prepare(GET_GROWTH_SPRITES_FOR_TARGET_IDENTIFIER)?;
+    let mut rows = stmt.query(params![target_identifier])?;
+    let mut sprites = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        sprites.push(GrowthSpriteData {
+            id: row.get(0)?,
+            raw_id: row.get(1)?,
+            target_identifier: row.get(2)?,
+            growth_identifier: row.get(3)?,
+            growth_stage: row.get(4)?,
+            tile_page_identifier: row.get(5)?,
+            offset_x: row.get(6)?,
+            offset_y: row.get(7)?,
+            offset_x_2: row.get(8)?,
+            offset_y_2: row.get(9)?,
+        });
+    }
+
+    Ok(sprites)
+}
+
+/// Get the palettes defined in the graphics for a target identifier
+///
+/// # Errors
+///
+/// - database error
+pub fn get_graphic_palettes_for_target_identifier(
+    conn: &Connection,
+    target_identifier: &str,
+) -> Result<Vec<GraphicPaletteData>> {
+    const GET_GRAPHIC_PALETTES_FOR_TARGET_IDENTIFIER: &str = r"
+    SELECT
+        id, raw_id, target_identifier, name, file_path, default_row
+    FROM graphic_palettes
+    WHERE
+        target_identifier = ?1
+    ORDER BY raw_id, id;
+    ";
+
+    let mut stmt = conn.prepare(GET_GRAPHIC_PALETTES_FOR_TARGET_IDENTIFIER)?;
+    let mut rows = stmt.query(params![target_identifier])?;
+    let mut palettes = Vec::new();
+
+    while let Some(row) = rows.next()? {
+        palettes.push(GraphicPaletteData {
+            id: row.get(0)?,
+            raw_id: row.get(1)?,
+            target_identifier: row.get(2)?,
+            name: row.get(3)?,
+            file_path: row.get(4)?,
+            default_row: row.get(5)?,
+        });
+    }
+
+    Ok(palettes)
+}
+
+/// Builds a layer from a row of `sprite_layers` (without its conditions)
+fn sprite_layer_from_row(row: &Row) -> Result<SpriteLayerData> {
+    Ok(SpriteLayerData {
+        id: row.get(0)?,
+        raw_id: row.get(1)?,
+        target_identifier: row.get(2)?,
+        layer_set: row.get(3)?,
+        layer_index: row.get(4)?,
+        layer_name: row.get(5)?,
+        layer_group: row.get(6)?,
+        tile_page_identifier: row.get(7)?,
+        offset_x: row.get(8)?,
+        offset_y: row.get(9)?,
+        offset_x_2: row.get(10)?,
+        offset_y_2: row.get(11)?,
+        conditions: Vec::new(),
+    })
+}
+
+/// Load the conditions of the layers of a target identifier (in one layer set, or all of them) in one
+/// query, and add them to their layers in the order they were defined
+fn add_sprite_layer_conditions(
+    conn: &Connection,
+    layers: &mut [SpriteLayerData],
+    target_identifier: &str,
+    layer_set: Option<&str>,
+) -> Result<()> {
+    const GET_SPRITE_LAYER_CONDITIONS_FOR_TARGET_IDENTIFIER: &str = r"
+    SELECT
+        c.layer_id, c.condition, c.value
+    FROM sprite_layer_conditions c
+    JOIN sprite_layers l ON l.id = c.layer_id
+    WHERE
+        l.target_identifier = ?1
+    AND (?2 IS NULL OR l.layer_set = ?2 COLLATE NOCASE)
+    ORDER BY c.layer_id, c.condition_index;
+    ";
+
+    if layers.is_empty() {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare_cached(GET_SPRITE_LAYER_CONDITIONS_FOR_TARGET_IDENTIFIER)?;
+    let mut rows = stmt.query(params![target_identifier, layer_set])?;
+    let mut conditions: HashMap<i64, Vec<SpriteLayerConditionData>> = HashMap::new();
+
+    while let Some(row) = rows.next()? {
+        conditions
+            .entry(row.get(0)?)
+            .or_default()
+            .push(SpriteLayerConditionData {
+                condition: row.get(1)?,
+                value: row.get(2)?,
+            });
+    }
+
+    for layer in layers {
+        layer.conditions = conditions.remove(&layer.id).unwrap_or_default();
+    }
+    Ok(())
+}
@@ -0,0 +1,334 @@
+use std::io::BufRead;
+
+use dfraw_parser::legends_export::{
+    ExportedHistoricalEvent, ExportedHistoricalFigure, ExportedSite, LegendsReader, LegendsRecord,
+    LegendsWorld,
+};
+use rusqlite::{Connection, OptionalExtension as _, Result, Transaction, params};
+use serde::de::DeserializeOwned;
+
+use crate::models::LegendsWorldData;
+
+use super::table_inserts::{
+    INSERT_LEGENDS_HISTORICAL_EVENT, INSERT_LEGENDS_HISTORICAL_FIGURE, INSERT_LEGENDS_SITE,
+    INSERT_LEGENDS_WORLD_RETURN_ID,
+};
+
+/// Inserts a legends world with its historical figures, sites and historical events, returning the id
+/// of the world.
+///
+/// # Errors
+///
+/// - Database error (will not commit transaction if error)
+/// - Serialization error
+pub fn insert_legends_world(
+    conn: &mut Connection,
+    name: &str,
+    world: &LegendsWorld,
+) -> Result<i64> {
+    let tx = conn.transaction()?;
+    let world_id = insert_world_name(&tx, name)?;
+
+    for figure in &world.historical_figures {
+        insert_historical_figure(&tx, world_id, figure)?;
+    }
+    for site in &world.sites {
+        insert_site(&tx, world_id, site)?;
+    }
+    for event in &world.historical_events {
+        insert_historical_event(&tx, world_id, event)?;
+    }
+
+    tx.commit()?;
+    Ok(world_id)
+}
+
+/// Inserts a legends world from the records of a legends export as they are read, returning the id of
+/// the world.
+///
+/// Only the historical figures, sites and historical events are stored, and every other record is
+/// dropped as soon as it is read, so the export is never held in memory.
+///
+/// # Errors
+///
+/// - Database error (will not commit transaction if error)
+/// - Serialization error
+/// - The export can't be read (will not commit transaction)
+pub fn insert_legends_records<R: BufRead>(
+    conn: &mut Connection,
+    name: &str,
+    records: LegendsReader<R>,
+) -> Result<i64> {
+    let tx = conn.transaction()?;
+    let world_id = insert_world_name(&tx, name)?;
+
+    for record in records {
+        match record.map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))? {
+            LegendsRecord::HistoricalFigure(figure) => {
+                insert_historical_figure(&tx, world_id, &figure)?;
+            }
+            LegendsRecord::Site(site) => insert_site(&tx, world_id, &site)?,
+            LegendsRecord::HistoricalEvent(event) => {
+                insert_historical_event(&tx, world_id, &event)?;
+            }
+            _ => {}
+        }
+    }
+
+    tx.commit()?;
+    Ok(world_id)
+}
+
+fn insert_world_name(tx: &Transaction, name: &str) -> Result<i64> {
+    tx.query_row(INSERT_LEGENDS_WORLD_RETURN_ID, params![name], |row| {
+        row.get(0)
+    })
+}
+
+fn insert_historical_figure(
+    tx: &Transaction,
+    world_id: i64,
+    figure: &ExportedHistoricalFigure,
+) -> Result<()> {
+    tx.prepare_cached(INSERT_LEGENDS_HISTORICAL_FIGURE)?
+        .execute(params![
+            world_id,
+            figure.get_id(),
+            figure.get_name(),
+            figure.get_creature_identifier(),
+            figure.get_caste(),
+            figure.get_birth_year(),
+            figure.get_death_year(),
+            to_json(figure)?
+        ])?;
+    Ok(())
+}
+
+fn insert_site(tx: &Transaction, world_id: i64, site: &ExportedSite) -> Result<()> {
+    tx.prepare_cached(INSERT_LEGENDS_SITE)?.execute(params![
+        world_id,
+        site.get_id(),
+        site.get_name(),
+        site.get_site_type(),
+        site.get_civ_id(),
+        site.get_current_owner_id(),
+        to_json(site)?
+    ])?;
+    Ok(())
+}
+
+fn insert_historical_event(
+    tx: &Transaction,
+    world_id: i64,
+    event: &ExportedHistoricalEvent,
+) -> Result<()> {
+    tx.prepare_cached(INSERT_LEGENDS_HISTORICAL_EVENT)?
+        .execute(params![
+            world_id,
+            event.get_id(),
+            event.get_year(),
+            event.get_seconds72(),
+            event.get_event_type(),
+            to_json(event)?
+        ])?;
+    Ok(())
+}
+
+/// Deletes a legends world and everything stored for it.
+///
+/// # Errors
+///
+/// - Database error (will not commit transaction if error)
+pub fn delete_legends_world(conn: &mut Connection, world_id: i64) -> Result<()> {
+    let tx = conn.transaction()?;
+    // foreign keys are not guaranteed to be enforced on the connection, so remove the rows explicitly
+    tx.execute(
+        "DELETE FROM legends_historical_events WHERE world_id = ?1",
+        params![world_id],
+    )?;
+    tx.execute(
+        "DELETE FROM legends_sites WHERE world_id = ?1",
+        params![world_id],
+    )?;
+    tx.execute(
+        "DELETE FROM legends_historical_figures WHERE world_id = ?1",
+        params![world_id],
+    )?;
+    tx.execute(
+        "DELETE FROM legends_worlds WHERE id = ?1",
+        params![world_id],
+    )?;
+    tx.commit()
+}
+
+/// Get all the legends worlds stored, with how many records are stored for each.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_legends_worlds(conn: &Connection) -> Result<Vec<LegendsWorldData>> {
+    const GET_LEGENDS_WORLDS: &str = r"
+    SELECT
+        w.id, w.name,
+        (SELECT COUNT(*) FROM legends_historical_figures WHERE world_id = w.id),
+        (SELECT COUNT(*) FROM legends_sites WHERE world_id = w.id),
+        (SELECT COUNT(*) FROM legends_historical_events WHERE world_id = w.id)
+    FROM legends_worlds w
+    ORDER BY w.id;
+    ";
+
+    let mut stmt = conn.prepare(GET_LEGENDS_WORLDS)?;
+    let rows = stmt.query_map([], |row| {
+        Ok(LegendsWorldData {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            historical_figure_count: row.get(2)?,
+            site_count: row.get(3)?,
+            historical_event_count: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Get a historical figure of a legends world by its id in the export.
+///
+/// # Errors
+///
+/// - database error
+/// - deserialization error
+pub fn get_legends_historical_figure(
+    conn: &Connection,
+    world_id: i64,
+    historical_figure_id: i32,
+) -> Result<Option<ExportedHistoricalFigure>> {
+    const GET_LEGENDS_HISTORICAL_FIGURE: &str = r"
+    SELECT json(data_blob)
+    FROM legends_historical_figures
+    WHERE world_id = ?1 AND historical_figure_id = ?2;
+    ";
+
+    conn.query_row(
+        GET_LEGENDS_HISTORICAL_FIGURE,
+        params![world_id, historical_figure_id],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()?
+    .map(|json_str| from_json(&json_str))
+    .transpose()
+}
+
+/// Get a page of the historical figures of a legends world of a race, ordered by their id.
+///
+/// The race is matched against the creature identifier (e.g. `DWARF`), ignoring case.
+///
+/// # Errors
+///
+/// - database error
+/// - deserialization error
+pub fn get_legends_historical_figures_by_race(
+    conn: &Connection,
+    world_id: i64,
+    race: &str,
+    limit: u32,
+    page: u32,
+) -> Result<Vec<ExportedHistoricalFigure>> {
+    const GET_LEGENDS_HISTORICAL_FIGURES_BY_RACE: &str = r"
+    SELECT json(data_blob)
+    FROM legends_historical_figures
+    WHERE world_id = ?1 AND race = ?2
+    ORDER BY historical_figure_id
+    LIMIT ?3 OFFSET ?4;
+    ";
+
+    let race = race.to_uppercase().replace(' ', "_");
+    query_json_rows(
+        conn,
+        GET_LEGENDS_HISTORICAL_FIGURES_BY_RACE,
+        params![world_id, race, limit, page_offset(limit, page)],
+    )
+}
+
+/// Get a page of the historical events of a legends world which happened between two years (inclusive),
+/// in the order they happened.
+///
+/// # Errors
+///
+/// - database error
+/// - deserialization error
+pub fn get_legends_historical_events_between(
+    conn: &Connection,
+    world_id: i64,
+    start_year: i32,
+    end_year: i32,
+    limit: u32,
+    page: u32,
+) -> Result<Vec<ExportedHistoricalEvent>> {
+    const GET_LEGENDS_HISTORICAL_EVENTS_BETWEEN: &str = r"
+    SELECT json(data_blob)
+    FROM legends_historical_events
+    WHERE world_id = ?1 AND year BETWEEN ?2 AND ?3
+    ORDER BY year, seconds72, event_id
+    LIMIT ?4 OFFSET ?5;
+    ";
+
+    query_json_rows(
+        conn,
+        GET_LEGENDS_HISTORICAL_EVENTS_BETWEEN,
+        params![
+            world_id,
+            start_year,
+            end_year,
+            limit,
+            page_offset(limit, page)
+        ],
+    )
+}
+
+/// Get the sites of a legends world which belong to, or are currently held by, an entity.
+///
+/// # Errors
+///
+/// - database error
+/// - deserialization error
+pub fn get_legends_sites_owned_by(
+    conn: &Connection,
+    world_id: i64,
+    entity_id: i32,
+) -> Result<Vec<ExportedSite>> {
+    const GET_LEGENDS_SITES_OWNED_BY: &str = r"
+    SELECT json(data_blob)
+    FROM legends_sites
+    WHERE world_id = ?1 AND (civ_id = ?2 OR current_owner_id = ?2)
+    ORDER BY site_id;
+    ";
+
+    query_json_rows(
+        conn,
+        GET_LEGENDS_SITES_OWNED_BY,
+        params![world_id, entity_id],
+    )
+}
+
+/// The number of rows to skip to get to a (1-based) page
+const fn page_offset(limit: u32, page: u32) -> u32 {
+    page.saturating_sub(1).saturating_mul(limit)
+}
+
+/// Runs a query selecting a single JSON column and deserializes each row.
+fn query_json_rows<T: DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<T>> {
+    let mut stmt = conn.prepare_cached(sql)?;
+    let rows = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+    rows.map(|json_str| from_json(&json_str?)).collect()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|_| rusqlite::Error::InvalidQuery)
+}
+
+fn from_json<T: DeserializeOwned>(json_str: &str) -> Result<T> {
+    serde_json::from_str(json_str).map_err(|_| rusqlite::Error::InvalidQuery)
+}
@@ -0,0 +1,120 @@
+use std::collections::{BTreeSet, HashMap};
+
+use dfraw_parser::dependencies::DependencyIssue;
+use rusqlite::{Connection, Result, params};
+use tracing::info;
+
+/// Replaces the stored load order with the given modules, in the order they are loaded.
+///
+/// # Errors
+///
+/// - Database error (will not commit transaction if error)
+/// - A module id is not in the database (`QueryReturnedNoRows`)
+/// - A module id is given more than once
+pub fn set_load_order(conn: &mut Connection, module_ids: &[i64]) -> Result<()> {
+    const INSERT_LOAD_ORDER: &str = r"
+    INSERT INTO load_order (position, module_id)
+    SELECT ?1, id FROM modules WHERE id = ?2;
+    ";
+
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM load_order", [])?;
+    {
+        let mut insert_stmt = tx.prepare_cached(INSERT_LOAD_ORDER)?;
+        for (position, module_id) in (0_i64..).zip(module_ids) {
+            if insert_stmt.execute(params![position, module_id])? == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+        }
+    }
+    info!("Set load order of {} modules", module_ids.len());
+    tx.commit()
+}
+
+/// Gets the ids of the modules in the stored load order, in the order they are loaded.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_load_order(conn: &Connection) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare_cached("SELECT module_id FROM load_order ORDER BY position")?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Checks the stored load order against the `module_dependencies` of its modules.
+///
+/// The modules a module requires to be loaded before or after it must also be in the load order.
+///
+/// # Errors
+///
+/// - database error
+pub fn validate_load_order(conn: &Connection) -> Result<Vec<DependencyIssue>> {
+    const GET_LOAD_ORDER_IDENTIFIERS: &str = r"
+    SELECT m.id, m.identifier
+    FROM load_order lo
+    JOIN modules m ON lo.module_id = m.id
+    ORDER BY lo.position;
+    ";
+    const GET_MODULE_DEPENDENCIES: &str = r"
+    SELECT d.target_identifier, r.name
+    FROM module_dependencies d
+    JOIN module_restriction_rules r ON d.restriction_type_id = r.id
+    WHERE d.module_id = ?1
+    ORDER BY r.id, d.target_identifier;
+    ";
+
+    let modules: Vec<(i64, String)> = conn
+        .prepare(GET_LOAD_ORDER_IDENTIFIERS)?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_>>()?;
+
+    // When an identifier is in the load order more than once, the first one decides the position
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for (position, (_, identifier)) in modules.iter().enumerate() {
+        positions.entry(identifier.as_str()).or_insert(position);
+    }
+
+    let mut dependency_stmt = conn.prepare_cached(GET_MODULE_DEPENDENCIES)?;
+    let mut issues = Vec::new();
+    for (position, (module_id, identifier)) in modules.iter().enumerate() {
+        let dependencies: Vec<(String, String)> = dependency_stmt
+            .query_map(params![module_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_>>()?;
+
+        let mut reported: BTreeSet<&str> = BTreeSet::new();
+        for (target, restriction) in &dependencies {
+            let target_position = positions.get(target.as_str()).copied();
+            match (restriction.as_str(), target_position) {
+                ("CONFLICTS", Some(_)) if target != identifier => {
+                    issues.push(DependencyIssue::Conflict {
+                        module: identifier.clone(),
+                        conflicts_with: target.clone(),
+                    });
+                }
+                ("CONFLICTS", _) => (),
+                (_, None) if reported.insert(target) => {
+                    issues.push(DependencyIssue::MissingRequirement {
+                        module: identifier.clone(),
+                        required: target.clone(),
+                    });
+                }
+                ("BEFORE", Some(target_position)) if target_position > position => {
+                    issues.push(DependencyIssue::OutOfOrder {
+                        module: identifier.clone(),
+                        must_load_after: target.clone(),
+                    });
+                }
+                ("AFTER", Some(target_position)) if target_position < position => {
+                    issues.push(DependencyIssue::OutOfOrder {
+                        module: target.clone(),
+                        must_load_after: identifier.clone(),
+                    });
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(issues)
+}
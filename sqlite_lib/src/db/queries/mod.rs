@@ -1,5 +1,8 @@
 mod get_set_db_metadata;
+mod graphic_layers;
 mod initialization;
+mod legends;
+mod load_order;
 mod module_locations;
 mod modules;
 mod process_raw_insertions;
@@ -10,7 +13,10 @@ pub(super) mod table_inserts;
 mod tile_pages;
 
 pub(super) use get_set_db_metadata::*;
+pub(super) use graphic_layers::*;
 pub(super) use initialization::*;
+pub(super) use legends::*;
+pub(super) use load_order::*;
 pub(super) use module_locations::*;
 pub(super) use modules::*;
 pub(super) use process_raw_insertions::process_raw_insertions;
@@ -4,6 +4,10 @@ use tracing::info;
 use uuid::Uuid;
 
 use super::super::rusqlite_extensions::OptionalResultExtension;
+use super::table_inserts::{
+    INSERT_MODULE_STEAM_KEY_VALUE_PAIR, INSERT_MODULE_STEAM_TAG, INSERT_STEAM_KEY_VALUE_KEY,
+    INSERT_STEAM_METADATA, INSERT_STEAM_TAG,
+};
 
 /// Returns true if the module exists in the database.
 ///
@@ -186,9 +190,10 @@ pub fn create_module(
         insert_module_record(&tx, module)?
     };
 
-    // 2. Process Dependencies (only if module is new)
+    // 2. Process Dependencies and Steam data (only if module is new)
     if existing_module_id.is_none() {
         insert_module_dependencies(&tx, module_db_id, module)?;
+        insert_module_steam_data(&tx, module_db_id, module)?;
     }
 
     tx.commit()?;
@@ -242,6 +247,141 @@ pub fn insert_module_dependencies(
     Ok(())
 }
 
+/// Insert the steam tags, key/value tags and metadata of a module into their tables
+///
+/// Key/value tags are stored as `key:value`; a tag without a value is stored with a `NULL` value.
+///
+/// # Error
+///
+/// - on database error
+pub fn insert_module_steam_data(
+    tx: &Transaction,
+    module_db_id: i64,
+    info: &ModuleInfo,
+) -> Result<()> {
+    let Some(steam_data) = info.get_steam_data() else {
+        return Ok(());
+    };
+    info!("Inserting steam data for {}", info.get_identifier());
+
+    let mut tag_stmt = tx.prepare_cached(INSERT_STEAM_TAG)?;
+    let mut module_tag_stmt = tx.prepare_cached(INSERT_MODULE_STEAM_TAG)?;
+    for tag in steam_data.get_tags().unwrap_or_default() {
+        tag_stmt.execute(params![tag])?;
+        module_tag_stmt.execute(params![module_db_id, tag])?;
+    }
+
+    let mut key_stmt = tx.prepare_cached(INSERT_STEAM_KEY_VALUE_KEY)?;
+    let mut module_key_value_stmt = tx.prepare_cached(INSERT_MODULE_STEAM_KEY_VALUE_PAIR)?;
+    for tag in steam_data.get_key_value_tags().unwrap_or_default() {
+        let (key, value) = tag
+            .split_once(':')
+            .map_or((tag.as_str(), None), |(key, value)| (key, Some(value)));
+        key_stmt.execute(params![key])?;
+        module_key_value_stmt.execute(params![module_db_id, key, value])?;
+    }
+
+    let mut metadata_stmt = tx.prepare_cached(INSERT_STEAM_METADATA)?;
+    for metadata in steam_data.get_metadata().unwrap_or_default() {
+        metadata_stmt.execute(params![module_db_id, metadata])?;
+    }
+
+    Ok(())
+}
+
+/// Get the ids of the modules which have a steam tag (ignoring case), ordered by id
+///
+/// # Errors
+///
+/// - database error
+pub fn get_module_ids_by_steam_tag(conn: &Connection, tag: &str) -> Result<Vec<i64>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT mt.module_id FROM module_steam_tags mt
+         JOIN steam_tags t ON mt.steam_tag_id = t.id
+         WHERE t.name = ?1 COLLATE NOCASE
+         ORDER BY mt.module_id",
+    )?;
+    let rows = stmt.query_map(params![tag], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Get the ids of the modules published to the steam workshop with a file id, ordered by id
+///
+/// More than one version of a module can share a file id.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_module_ids_by_steam_file_id(conn: &Connection, file_id: u64) -> Result<Vec<i64>> {
+    let mut stmt =
+        conn.prepare_cached("SELECT id FROM modules WHERE steam_file_id = ?1 ORDER BY id")?;
+    let rows = stmt.query_map(params![file_id.cast_signed()], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Get every steam tag used by the modules, in alphabetical order
+///
+/// # Errors
+///
+/// - database error
+pub fn get_steam_tags(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT name FROM steam_tags
+         WHERE id IN (SELECT steam_tag_id FROM module_steam_tags)
+         ORDER BY name COLLATE NOCASE, name",
+    )?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Get the steam tags of a module, in alphabetical order
+///
+/// # Errors
+///
+/// - database error
+pub fn get_steam_tags_for_module(conn: &Connection, module_id: i64) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT t.name FROM module_steam_tags mt
+         JOIN steam_tags t ON mt.steam_tag_id = t.id
+         WHERE mt.module_id = ?1
+         ORDER BY t.name COLLATE NOCASE, t.name",
+    )?;
+    let rows = stmt.query_map(params![module_id], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Get the steam key/value tags of a module as (key, value), ordered by key
+///
+/// # Errors
+///
+/// - database error
+pub fn get_steam_key_value_tags_for_module(
+    conn: &Connection,
+    module_id: i64,
+) -> Result<Vec<(String, Option<String>)>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT k.name, kv.value FROM module_steam_key_value_pairs kv
+         JOIN steam_key_value_keys k ON kv.steam_key_value_id = k.id
+         WHERE kv.module_id = ?1
+         ORDER BY k.name",
+    )?;
+    let rows = stmt.query_map(params![module_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+/// Get the steam metadata of a module, in the order it was declared
+///
+/// # Errors
+///
+/// - database error
+pub fn get_steam_metadata_for_module(conn: &Connection, module_id: i64) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT metadata FROM steam_metadata WHERE module_id = ?1 ORDER BY rowid",
+    )?;
+    let rows = stmt.query_map(params![module_id], |row| row.get(0))?;
+    rows.collect()
+}
+
 /// Clear the side tables (search indices and lookup tables) for a given raw id.
 ///
 /// The other relations are not touched (and rely on the cascade delete); e.g. tiles or sprites.
@@ -254,6 +394,10 @@ pub fn clear_side_tables_for_module_id(conn: &Connection, id: i64) -> Result<()>
     const DELETE_COMMON_NUMERIC_FLAGS_FOR_ID: &str = "DELETE FROM common_raw_flags_with_numeric_value WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
     const DELETE_SEARCH_IDX_FOR_ID: &str = "DELETE FROM raw_search_index WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
     const DELETE_NAME_SEARCH_IDX_FOR_ID: &str = "DELETE FROM raw_names WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
+    const DELETE_SPRITE_LAYER_CONDITIONS_FOR_ID: &str = "DELETE FROM sprite_layer_conditions WHERE layer_id IN (SELECT id FROM sprite_layers WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1))";
+    const DELETE_SPRITE_LAYERS_FOR_ID: &str = "DELETE FROM sprite_layers WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
+    const DELETE_GROWTH_SPRITES_FOR_ID: &str = "DELETE FROM growth_sprites WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
+    const DELETE_GRAPHIC_PALETTES_FOR_ID: &str = "DELETE FROM graphic_palettes WHERE raw_id IN (SELECT id FROM raw_definitions WHERE module_id = ?1)";
 
     // Clear side tables (CASCADE handles tile_pages, sprite_graphics, raw_names, flags; in this
     // case we explicitly remove the raw_names and flags in order to allow them to be updated if
@@ -262,6 +406,11 @@ pub fn clear_side_tables_for_module_id(conn: &Connection, id: i64) -> Result<()>
     conn.execute(DELETE_COMMON_NUMERIC_FLAGS_FOR_ID, params![id])?;
     conn.execute(DELETE_SEARCH_IDX_FOR_ID, params![id])?;
     conn.execute(DELETE_NAME_SEARCH_IDX_FOR_ID, params![id])?;
+    // The graphic layers are rebuilt from the raw on insert, so they are removed as well
+    conn.execute(DELETE_SPRITE_LAYER_CONDITIONS_FOR_ID, params![id])?;
+    conn.execute(DELETE_SPRITE_LAYERS_FOR_ID, params![id])?;
+    conn.execute(DELETE_GROWTH_SPRITES_FOR_ID, params![id])?;
+    conn.execute(DELETE_GRAPHIC_PALETTES_FOR_ID, params![id])?;
 
     Ok(())
 }
@@ -281,6 +281,21 @@ fn process_raw_insertions_inner(
                             }
                         }
                     }
+                    // Insert the full layer sets, growth sprites and palettes
+                    #[cfg(debug_assertions)]
+                    let graphic_start = Utc::now();
+                    queries::insert_graphic_layers(tx, raw_db_id, g).inspect_err(|e| {
+                        tracing::error!(
+                            "Failed inserting graphic layers for raw_id:{} target:{}: {e}",
+                            raw_db_id,
+                            g.get_identifier()
+                        );
+                    })?;
+                    #[cfg(debug_assertions)]
+                    {
+                        let graphic_duration = Utc::now().signed_duration_since(graphic_start);
+                        total_graphic_time += graphic_duration;
+                    }
                 }
             }
             _ => {}
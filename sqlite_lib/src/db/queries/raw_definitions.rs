@@ -1,8 +1,12 @@
-use dfraw_parser::{Creature, Graphic, TilePage, tokens::ObjectType, traits::RawObject};
+use dfraw_parser::{
+    Creature, Graphic, TilePage,
+    tokens::ObjectType,
+    traits::{RawObject, RawToken as _},
+};
 use rusqlite::{Connection, Result, params};
 use uuid::Uuid;
 
-use crate::db::queries::get_id_for_module_location;
+use crate::db::queries::{get_id_for_module_location, insert_graphic_layers};
 
 use super::super::rusqlite_extensions::OptionalResultExtension;
 use super::table_inserts::{
@@ -213,6 +217,43 @@ pub fn get_raw(conn: &Connection, id: i64) -> Result<Box<dyn RawObject>> {
     serde_json::from_str(&json_str).map_err(|_| rusqlite::Error::InvalidQuery)
 }
 
+/// Retrieves all raw objects of the given types, optionally only those from one module.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_raws_by_type(
+    conn: &Connection,
+    raw_types: &[ObjectType],
+    module_object_id: Option<Uuid>,
+) -> Result<Vec<Box<dyn RawObject>>> {
+    let type_placeholders: Vec<String> = (0..raw_types.len())
+        .map(|i| format!("?{}", i + 2))
+        .collect();
+    let sql = format!(
+        "SELECT json(r.data_blob) FROM raw_definitions r
+             JOIN modules m ON r.module_id = m.id
+             WHERE (?1 IS NULL OR m.object_id = ?1)
+             AND r.raw_type_id IN (SELECT id FROM raw_types WHERE name IN ({}))
+             ORDER BY r.id",
+        type_placeholders.join(", ")
+    );
+
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> =
+        vec![Box::new(module_object_id.map(|id| id.as_bytes().to_vec()))];
+    for raw_type in raw_types {
+        // Object types stored in database by "key", i.e. all caps: CREATURE, PLANT, etc
+        params_vec.push(Box::new(raw_type.get_key()));
+    }
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(params_vec.iter()), |row| {
+        row.get::<_, String>(0)
+    })?;
+    rows.map(|json_str| serde_json::from_str(&json_str?).map_err(|_| rusqlite::Error::InvalidQuery))
+        .collect()
+}
+
 /// Retrieves a raw object by its object id.
 ///
 /// # Errors
@@ -276,6 +317,10 @@ pub fn clear_side_tables_for_raw_id(conn: &Connection, id: i64) -> Result<()> {
         "DELETE FROM common_raw_flags_with_numeric_value WHERE raw_id = ?1";
     const DELETE_SEARCH_IDX_FOR_ID: &str = "DELETE FROM raw_search_index WHERE raw_id = ?1";
     const DELETE_NAME_SEARCH_IDX_FOR_ID: &str = "DELETE FROM raw_names WHERE raw_id = ?1";
+    const DELETE_SPRITE_LAYER_CONDITIONS_FOR_ID: &str = "DELETE FROM sprite_layer_conditions WHERE layer_id IN (SELECT id FROM sprite_layers WHERE raw_id = ?1)";
+    const DELETE_SPRITE_LAYERS_FOR_ID: &str = "DELETE FROM sprite_layers WHERE raw_id = ?1";
+    const DELETE_GROWTH_SPRITES_FOR_ID: &str = "DELETE FROM growth_sprites WHERE raw_id = ?1";
+    const DELETE_GRAPHIC_PALETTES_FOR_ID: &str = "DELETE FROM graphic_palettes WHERE raw_id = ?1";
 
     // Clear side tables (CASCADE handles tile_pages, sprite_graphics, raw_names, flags; in this
     // case we explicitly remove the raw_names and flags in order to allow them to be updated if
@@ -284,6 +329,11 @@ pub fn clear_side_tables_for_raw_id(conn: &Connection, id: i64) -> Result<()> {
     conn.execute(DELETE_COMMON_NUMERIC_FLAGS_FOR_ID, params![id])?;
     conn.execute(DELETE_SEARCH_IDX_FOR_ID, params![id])?;
     conn.execute(DELETE_NAME_SEARCH_IDX_FOR_ID, params![id])?;
+    // The graphic layers are rebuilt from the raw on insert, so they are removed as well
+    conn.execute(DELETE_SPRITE_LAYER_CONDITIONS_FOR_ID, params![id])?;
+    conn.execute(DELETE_SPRITE_LAYERS_FOR_ID, params![id])?;
+    conn.execute(DELETE_GROWTH_SPRITES_FOR_ID, params![id])?;
+    conn.execute(DELETE_GRAPHIC_PALETTES_FOR_ID, params![id])?;
 
     Ok(())
 }
@@ -348,7 +398,7 @@ pub fn create_raw_with_module(
     let raw_id: i64 = conn.query_row(
         INSERT_RAW_DEFINITION_NO_UPDATE_RETURN_ID,
         params![
-            u32::from(raw.get_type()),
+            raw.get_type().to_string().to_uppercase().replace(' ', "_"),
             raw.get_identifier(),
             module_id,
             json_payload,
@@ -412,8 +462,8 @@ fn populate_side_tables(conn: &Connection, raw_id: i64, raw: &Box<dyn RawObject>
                                 s_offset.y,
                                 s_offset_2.x,
                                 s_offset_2.y,
-                                &s.get_primary_condition().to_string(),
-                                &s.get_secondary_condition().to_string(),
+                                s.get_primary_condition().get_key().unwrap_or_default(),
+                                s.get_secondary_condition().get_key(),
                                 g.get_identifier()
                             ],
                         )?;
@@ -425,13 +475,14 @@ fn populate_side_tables(conn: &Connection, raw_id: i64, raw: &Box<dyn RawObject>
                                 s.get_tile_page_id(),
                                 s_offset.x,
                                 s_offset.y,
-                                &s.get_primary_condition().to_string(),
-                                &s.get_secondary_condition().to_string(),
+                                s.get_primary_condition().get_key().unwrap_or_default(),
+                                s.get_secondary_condition().get_key(),
                                 g.get_identifier()
                             ],
                         )?;
                     }
                 }
+                insert_graphic_layers(conn, raw_id, g)?;
             }
         }
         _ => {}
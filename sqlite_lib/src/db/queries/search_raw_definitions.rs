@@ -69,6 +69,10 @@ pub fn search_raws(conn: &Connection, query: &SearchQuery) -> Result<SearchResul
         add_favorite_raw_restriction(query, &mut conditions, &favorite_raw_list);
     }
 
+    if query.effective_only {
+        add_effective_definition_restriction(&mut conditions);
+    }
+
     // Identifier Filter
     add_identifier_filter(query, &mut conditions, &mut params_vec);
 
@@ -186,6 +190,22 @@ fn add_favorite_raw_restriction(
     conditions.push(format!("r.id IN ({})", favorites.iter().format(",")));
 }
 
+/// Internal function to restrict the raws to those which are the effective definition of their identifier
+///
+/// Of the raws sharing a type and identifier, only the one from the module latest in the `load_order` is
+/// kept. Raws from modules which are not in the `load_order` are never effective.
+fn add_effective_definition_restriction(conditions: &mut Vec<String>) {
+    conditions.push(String::from(
+        "r.id IN (SELECT id FROM (
+            SELECT ed.id, ROW_NUMBER() OVER (
+                PARTITION BY ed.raw_type_id, ed.identifier ORDER BY lo.position DESC
+            ) AS load_rank
+            FROM raw_definitions ed
+            JOIN load_order lo ON ed.module_id = lo.module_id
+        ) WHERE load_rank = 1)",
+    ));
+}
+
 /// Internal function to add the `RawModuleLocation` filter into `params_vec` and `conditions`
 ///
 /// Will return early if `query.locations` is empty (no locations to filter on)
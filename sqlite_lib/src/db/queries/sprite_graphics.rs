@@ -1,3 +1,4 @@
+use dfraw_parser::graphics::SpriteState;
 use rusqlite::{Connection, Result, params};
 
 use crate::models::SpriteGraphicData;
@@ -186,3 +187,50 @@ pub fn get_sprite_graphics_for_target_identifier_and_any_castes(
 
     Ok(sprites)
 }
+
+/// Get the best sprite graphic to show a target in a state, following the game's fallbacks between the
+/// primary and secondary conditions (see [`SpriteState::fallbacks`]).
+///
+/// When a caste is given, the sprites for the caste are used if any of them match, otherwise the sprites
+/// for the target itself are used. When more than one sprite matches equally well, the one inserted first
+/// is chosen.
+///
+/// # Errors
+///
+/// - database error
+pub fn get_best_sprite_graphic_for_target_identifier(
+    conn: &Connection,
+    target_identifier: &str,
+    target_caste: Option<&str>,
+    state: SpriteState,
+) -> Result<Option<SpriteGraphicData>> {
+    let best = |sprites: Vec<SpriteGraphicData>| {
+        sprites
+            .into_iter()
+            .filter_map(|sprite| {
+                state
+                    .rank_keys(
+                        &sprite.primary_condition,
+                        Some(sprite.secondary_condition.as_str()),
+                    )
+                    .map(|rank| (rank, sprite.id, sprite))
+            })
+            .min_by_key(|(rank, id, _)| (*rank, *id))
+            .map(|(_, _, sprite)| sprite)
+    };
+
+    if let Some(target_caste) = target_caste
+        && let Some(sprite) = best(get_sprite_graphics_for_target_identifier_and_caste(
+            conn,
+            target_identifier,
+            target_caste,
+        )?)
+    {
+        return Ok(Some(sprite));
+    }
+
+    Ok(best(get_sprite_graphics_for_target_identifier(
+        conn,
+        target_identifier,
+    )?))
+}
@@ -74,6 +74,76 @@ VALUES
         ?7, ?8, ?9);
 ";
 
+/// Requires 11 params:
+///
+/// * `raw_id`
+/// * `target_identifier`
+/// * `layer_set`
+/// * `layer_index`
+/// * `layer_name`
+/// * `layer_group`
+/// * `tile_page_identifier`
+/// * `offset_x`
+/// * `offset_y`
+/// * `offset_x_2`
+/// * `offset_y_2`
+pub(super) const INSERT_SPRITE_LAYER_RETURN_ID: &str = r"
+INSERT INTO sprite_layers
+    (raw_id, target_identifier, layer_set, layer_index, layer_name, layer_group,
+        tile_page_identifier, offset_x, offset_y, offset_x_2, offset_y_2)
+VALUES
+    (?1, ?2, ?3, ?4, ?5, ?6,
+        ?7, ?8, ?9, ?10, ?11)
+RETURNING id;
+";
+
+/// Requires 4 params:
+///
+/// * `layer_id`
+/// * `condition_index`
+/// * `condition`
+/// * `value`
+pub(super) const INSERT_SPRITE_LAYER_CONDITION: &str = r"
+INSERT INTO sprite_layer_conditions
+    (layer_id, condition_index, condition, value)
+VALUES
+    (?1, ?2, ?3, ?4);
+";
+
+/// Requires 9 params:
+///
+/// * `raw_id`
+/// * `target_identifier`
+/// * `growth_identifier`
+/// * `growth_stage`
+/// * `tile_page_identifier`
+/// * `offset_x`
+/// * `offset_y`
+/// * `offset_x_2`
+/// * `offset_y_2`
+pub(super) const INSERT_GROWTH_SPRITE: &str = r"
+INSERT INTO growth_sprites
+    (raw_id, target_identifier, growth_identifier, growth_stage,
+        tile_page_identifier, offset_x, offset_y, offset_x_2, offset_y_2)
+VALUES
+    (?1, ?2, ?3, ?4,
+        ?5, ?6, ?7, ?8, ?9);
+";
+
+/// Requires 5 params:
+///
+/// * `raw_id`
+/// * `target_identifier`
+/// * `name`
+/// * `file_path`
+/// * `default_row`
+pub(super) const INSERT_GRAPHIC_PALETTE: &str = r"
+INSERT INTO graphic_palettes
+    (raw_id, target_identifier, name, file_path, default_row)
+VALUES
+    (?1, ?2, ?3, ?4, ?5);
+";
+
 /// Requires 2 params:
 ///
 /// * `raw_id`
@@ -129,3 +199,103 @@ VALUES
 ON CONFLICT(module_id, identifier) DO NOTHING
 RETURNING id;
 ";
+
+/// Returns the `world_id`
+///
+/// Requires 1 param:
+///
+/// * `name`
+pub(super) const INSERT_LEGENDS_WORLD_RETURN_ID: &str = r"
+INSERT INTO legends_worlds (name) VALUES (?1) RETURNING id;
+";
+
+/// Requires 8 params:
+///
+/// * `world_id`
+/// * `historical_figure_id`
+/// * `name`
+/// * `race` as creature identifier
+/// * `caste`
+/// * `birth_year`
+/// * `death_year`
+/// * `data_blob` as raw JSON string
+pub(super) const INSERT_LEGENDS_HISTORICAL_FIGURE: &str = r"
+INSERT OR REPLACE INTO legends_historical_figures
+    (world_id, historical_figure_id, name, race, caste, birth_year, death_year, data_blob)
+VALUES
+    (?1, ?2, ?3, ?4, ?5, ?6, ?7, jsonb(?8));
+";
+
+/// Requires 7 params:
+///
+/// * `world_id`
+/// * `site_id`
+/// * `name`
+/// * `site_type`
+/// * `civ_id`
+/// * `current_owner_id`
+/// * `data_blob` as raw JSON string
+pub(super) const INSERT_LEGENDS_SITE: &str = r"
+INSERT OR REPLACE INTO legends_sites
+    (world_id, site_id, name, site_type, civ_id, current_owner_id, data_blob)
+VALUES
+    (?1, ?2, ?3, ?4, ?5, ?6, jsonb(?7));
+";
+
+/// Requires 6 params:
+///
+/// * `world_id`
+/// * `event_id`
+/// * `year`
+/// * `seconds72`
+/// * `event_type`
+/// * `data_blob` as raw JSON string
+pub(super) const INSERT_LEGENDS_HISTORICAL_EVENT: &str = r"
+INSERT OR REPLACE INTO legends_historical_events
+    (world_id, event_id, year, seconds72, event_type, data_blob)
+VALUES
+    (?1, ?2, ?3, ?4, ?5, jsonb(?6));
+";
+
+/// Inserts the tag if it isn't already stored
+///
+/// Requires 1 param:
+///
+/// * `name`
+pub(super) const INSERT_STEAM_TAG: &str = "INSERT OR IGNORE INTO steam_tags (name) VALUES (?1);";
+
+/// Requires 2 params:
+///
+/// * `module_id`
+/// * `name` of the steam tag
+pub(super) const INSERT_MODULE_STEAM_TAG: &str = r"
+INSERT OR IGNORE INTO module_steam_tags
+    (module_id, steam_tag_id)
+SELECT ?1, id FROM steam_tags WHERE name = ?2;
+";
+
+/// Inserts the key if it isn't already stored
+///
+/// Requires 1 param:
+///
+/// * `name`
+pub(super) const INSERT_STEAM_KEY_VALUE_KEY: &str =
+    "INSERT OR IGNORE INTO steam_key_value_keys (name) VALUES (?1);";
+
+/// Requires 3 params:
+///
+/// * `module_id`
+/// * `name` of the key
+/// * `value`
+pub(super) const INSERT_MODULE_STEAM_KEY_VALUE_PAIR: &str = r"
+INSERT OR REPLACE INTO module_steam_key_value_pairs
+    (module_id, steam_key_value_id, value)
+SELECT ?1, id, ?3 FROM steam_key_value_keys WHERE name = ?2;
+";
+
+/// Requires 2 params:
+///
+/// * `module_id`
+/// * `metadata`
+pub(super) const INSERT_STEAM_METADATA: &str =
+    "INSERT INTO steam_metadata (module_id, metadata) VALUES (?1, ?2);";
@@ -45,6 +45,13 @@ pub struct SearchQuery {
     /// Specify using the module's `object_id` which can be found from the raw:
     ///     `raw.metadata.module_object_it`
     pub in_modules: Vec<Uuid>,
+    /// Limit results to the effective definition of each raw under the stored load order, i.e. the
+    /// definition from the last loaded module which defines that identifier.
+    ///
+    /// Raws from modules which are not in the load order are not returned.
+    ///
+    /// Default: false
+    pub effective_only: bool,
 }
 
 impl SearchQuery {
@@ -100,6 +107,7 @@ impl Default for SearchQuery {
             page: 1,
             favorites_only: false,
             in_modules: Vec::new(),
+            effective_only: false,
         }
     }
 }
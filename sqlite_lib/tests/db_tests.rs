@@ -1044,6 +1044,40 @@ fn verify_tile_page_lookups() {
     }
 }
 
+#[test]
+fn verify_graphic_layer_lookups() {
+    setup_tracing();
+    let client_mutex = get_test_client();
+
+    // Dwarves are drawn with layered portraits
+    let layers = client_mutex
+        .lock()
+        .expect("Failed to lock DbClient")
+        .get_sprite_layers_for_target_identifier("DWARF")
+        .expect("Failed to get sprite layers");
+    assert!(!layers.is_empty(), "Dwarves should have sprite layers");
+    assert!(
+        layers.iter().any(|layer| !layer.conditions.is_empty()),
+        "Some dwarf layers should have conditions"
+    );
+
+    // Looking up a single layer set only returns its layers, in drawing order
+    let layer_set = layers[0].layer_set.clone();
+    let set_layers = client_mutex
+        .lock()
+        .expect("Failed to lock DbClient")
+        .get_sprite_layer_set_for_target_identifier("DWARF", &layer_set)
+        .expect("Failed to get sprite layer set");
+    assert!(!set_layers.is_empty());
+    assert!(set_layers.iter().all(|layer| layer.layer_set == layer_set));
+    assert!(
+        set_layers
+            .windows(2)
+            .all(|w| w[0].raw_id != w[1].raw_id || w[0].layer_index <= w[1].layer_index),
+        "Layers should be in drawing order"
+    );
+}
+
 #[test]
 fn get_raw_from_object_id() {
     let hydra_obj_id: Uuid = Uuid::parse_str("0617fc81-77b0-508a-947b-3899d1aebfd6")
@@ -0,0 +1,251 @@
+//! Tests for verifying that legends worlds can be stored and queried.
+
+use dfraw_parser::legends_export::{
+    ExportedHistoricalEvent, ExportedHistoricalFigure, ExportedSite, parse_legends_world,
+};
+use dfraw_parser_sqlite_lib::{ClientOptions, DbClient};
+
+use crate::common::setup_tracing;
+
+mod common;
+
+const LEGENDS: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<sites>
+<site>
+	<id>1</id>
+	<type>fortress</type>
+	<name>boltstone</name>
+	<civ_id>12</civ_id>
+	<cur_owner_id>14</cur_owner_id>
+</site>
+<site>
+	<id>2</id>
+	<type>hamlet</type>
+	<name>fieldfolds</name>
+	<civ_id>14</civ_id>
+</site>
+<site>
+	<id>3</id>
+	<type>cave</type>
+	<name>the dark hole</name>
+</site>
+</sites>
+<historical_figures>
+<historical_figure>
+	<id>0</id>
+	<name>ngitha the lashes of dread</name>
+	<race>DWARF</race>
+	<caste>FEMALE</caste>
+	<birth_year>-112</birth_year>
+	<death_year>-1</death_year>
+</historical_figure>
+<historical_figure>
+	<id>1</id>
+	<name>urist</name>
+	<race>DWARF</race>
+	<caste>MALE</caste>
+	<birth_year>5</birth_year>
+	<death_year>40</death_year>
+</historical_figure>
+<historical_figure>
+	<id>2</id>
+	<name>stasost</name>
+	<race>GOBLIN</race>
+	<caste>MALE</caste>
+	<birth_year>10</birth_year>
+	<death_year>-1</death_year>
+</historical_figure>
+</historical_figures>
+<historical_events>
+<historical_event>
+	<id>0</id>
+	<year>20</year>
+	<seconds72>1000</seconds72>
+	<type>hf died</type>
+	<hfid>1</hfid>
+</historical_event>
+<historical_event>
+	<id>1</id>
+	<year>20</year>
+	<seconds72>500</seconds72>
+	<type>created site</type>
+	<site_id>1</site_id>
+</historical_event>
+<historical_event>
+	<id>2</id>
+	<year>3</year>
+	<seconds72>500</seconds72>
+	<type>change hf state</type>
+	<hfid>0</hfid>
+</historical_event>
+</historical_events>
+</df_world>
+"#;
+
+#[test]
+fn stores_and_queries_legends_world() {
+    setup_tracing();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let export_path = dir.join("region1-00050-01-01-legends.xml");
+    std::fs::write(&export_path, LEGENDS).expect("Failed to write legends export");
+    let db_path = dir.join("legends.db");
+
+    let world = parse_legends_world(&export_path).expect("Failed to parse legends export");
+    let mut client = DbClient::init_db(
+        db_path.to_str().expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+
+    let world_id = client
+        .insert_legends_world("region1", &world)
+        .expect("Failed to insert legends world");
+
+    let worlds = client.get_legends_worlds().expect("Failed to list worlds");
+    assert_eq!(worlds.len(), 1);
+    assert_eq!(worlds[0].name, "region1");
+    assert_eq!(worlds[0].historical_figure_count, 3);
+    assert_eq!(worlds[0].site_count, 3);
+    assert_eq!(worlds[0].historical_event_count, 3);
+
+    let dwarves = client
+        .get_legends_historical_figures_by_race(world_id, "dwarf", 10, 1)
+        .expect("Failed to query figures by race");
+    assert_eq!(
+        dwarves
+            .iter()
+            .map(ExportedHistoricalFigure::get_id)
+            .collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    let second_page = client
+        .get_legends_historical_figures_by_race(world_id, "DWARF", 1, 2)
+        .expect("Failed to query figures by race");
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].get_name(), "urist");
+    assert_eq!(second_page[0].get_death_year(), Some(40));
+
+    let events = client
+        .get_legends_historical_events_between(world_id, 0, 20, 10, 1)
+        .expect("Failed to query events by year");
+    assert_eq!(
+        events
+            .iter()
+            .map(ExportedHistoricalEvent::get_id)
+            .collect::<Vec<_>>(),
+        vec![2, 1, 0],
+        "Events should be in the order they happened"
+    );
+    let events = client
+        .get_legends_historical_events_between(world_id, 10, 19, 10, 1)
+        .expect("Failed to query events by year");
+    assert!(events.is_empty());
+
+    let sites = client
+        .get_legends_sites_owned_by(world_id, 14)
+        .expect("Failed to query sites by owner");
+    assert_eq!(
+        sites.iter().map(ExportedSite::get_id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    let figure = client
+        .get_legends_historical_figure(world_id, 2)
+        .expect("Failed to get figure");
+    assert_eq!(
+        figure.as_ref().map(ExportedHistoricalFigure::get_race),
+        Some("GOBLIN")
+    );
+    assert!(
+        client
+            .get_legends_historical_figure(world_id, 99)
+            .expect("Failed to get figure")
+            .is_none()
+    );
+
+    client
+        .delete_legends_world(world_id)
+        .expect("Failed to delete world");
+    assert!(
+        client
+            .get_legends_worlds()
+            .expect("Failed to list worlds")
+            .is_empty()
+    );
+    assert!(
+        client
+            .get_legends_historical_figures_by_race(world_id, "DWARF", 10, 1)
+            .expect("Failed to query figures by race")
+            .is_empty()
+    );
+}
+
+/// A historical figure without a birth year, from an export which doesn't include them
+const UNKNOWN_BIRTH: &str = r#"<?xml version="1.0" encoding='UTF-8'?>
+<df_world>
+<historical_figures>
+<historical_figure>
+	<id>7</id>
+	<name>the nameless</name>
+	<race>GOBLIN</race>
+	<caste>FEMALE</caste>
+</historical_figure>
+</historical_figures>
+</df_world>
+"#;
+
+#[test]
+fn stores_legends_export_as_it_is_read() {
+    setup_tracing();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let export_path = dir.join("region1-00050-01-01-legends.xml");
+    std::fs::write(&export_path, LEGENDS).expect("Failed to write legends export");
+    let unknown_birth_path = dir.join("region2-00050-01-01-legends.xml");
+    std::fs::write(&unknown_birth_path, UNKNOWN_BIRTH).expect("Failed to write legends export");
+    let db_path = dir.join("legends.db");
+
+    let mut client = DbClient::init_db(
+        db_path.to_str().expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+    let world_id = client
+        .insert_legends_export("region1", &export_path)
+        .expect("Failed to insert legends export");
+
+    let worlds = client.get_legends_worlds().expect("Failed to list worlds");
+    assert_eq!(worlds[0].historical_figure_count, 3);
+    assert_eq!(worlds[0].site_count, 3);
+    assert_eq!(worlds[0].historical_event_count, 3);
+    let figure = client
+        .get_legends_historical_figure(world_id, 0)
+        .expect("Failed to get figure")
+        .expect("Expected the figure");
+    assert_eq!(figure.get_birth_year(), Some(-112));
+
+    // Pages far past the end are empty instead of overflowing
+    assert!(
+        client
+            .get_legends_historical_figures_by_race(world_id, "DWARF", u32::MAX, u32::MAX)
+            .expect("Failed to query figures by race")
+            .is_empty()
+    );
+
+    // An unknown birth year is stored as NULL, not as year 0
+    let unknown_world_id = client
+        .insert_legends_export("region2", &unknown_birth_path)
+        .expect("Failed to insert legends export");
+    drop(client);
+    let conn = rusqlite::Connection::open(&db_path).expect("Failed to open database");
+    let birth_year: Option<i32> = conn
+        .query_row(
+            "SELECT birth_year FROM legends_historical_figures WHERE world_id = ?1",
+            [unknown_world_id],
+            |row| row.get(0),
+        )
+        .expect("Failed to query birth year");
+    assert_eq!(birth_year, None);
+}
@@ -0,0 +1,166 @@
+//! Tests for verifying that the load order can be stored, validated and used in searches.
+
+use dfraw_parser::{
+    Inorganic, ModuleInfo, ParseResult,
+    dependencies::DependencyIssue,
+    legends_export::LegendsWorld,
+    metadata::{RawMetadata, RawModuleLocation},
+    tokens::ObjectType,
+    traits::RawObject,
+};
+use dfraw_parser_sqlite_lib::{ClientOptions, DbClient, SearchQuery};
+use dfraw_parser_test_util::json_helpers::identifier_from_json_blob;
+use tempfile::TempDir;
+
+use crate::common::setup_tracing;
+
+mod common;
+
+fn module(identifier: &str) -> ModuleInfo {
+    ModuleInfo::new(identifier, RawModuleLocation::InstalledMods, "mods")
+}
+
+fn inorganic(module: &ModuleInfo, identifier: &str) -> Box<dyn RawObject> {
+    let metadata = RawMetadata::new(
+        module,
+        ObjectType::Inorganic,
+        identifier,
+        &"inorganic_metal.txt",
+        true,
+    );
+    Box::new(Inorganic::new(identifier, &metadata))
+}
+
+/// Creates a database with three modules: a base module, an overhaul of it and a rival overhaul.
+///
+/// Returns the directory the database is in (removed when dropped), the client, and the modules.
+fn setup_modules() -> (TempDir, DbClient, [ModuleInfo; 3]) {
+    setup_tracing();
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db_path = dir.path().join("load_order.db");
+
+    let base = module("base_metals");
+    let mut overhaul = module("metal_overhaul");
+    overhaul.add_requires_id_before("base_metals");
+    overhaul.add_requires_id("metal_graphics");
+    let mut rival = module("rival_overhaul");
+    rival.add_conflicts_with_id("metal_overhaul");
+    rival.add_requires_id_after("metal_overhaul");
+
+    let parse_results = ParseResult {
+        raws: vec![
+            inorganic(&base, "STEEL"),
+            inorganic(&base, "IRON"),
+            inorganic(&overhaul, "STEEL"),
+            inorganic(&rival, "STEEL"),
+        ],
+        modules: vec![base.clone(), overhaul.clone(), rival.clone()],
+        legends: LegendsWorld::default(),
+    };
+
+    let mut client = DbClient::init_db(
+        db_path.to_str().expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+    client
+        .insert_parse_results(&parse_results)
+        .expect("Failed to insert parse results");
+
+    (dir, client, [base, overhaul, rival])
+}
+
+fn module_id(client: &DbClient, module: &ModuleInfo) -> i64 {
+    client
+        .try_get_module_id(module)
+        .expect("Failed to get module id")
+        .expect("Module was not inserted")
+}
+
+#[test]
+fn stores_and_validates_load_order() {
+    let (_dir, mut client, [base, overhaul, rival]) = setup_modules();
+    let (base_id, overhaul_id, rival_id) = (
+        module_id(&client, &base),
+        module_id(&client, &overhaul),
+        module_id(&client, &rival),
+    );
+
+    assert!(
+        client
+            .get_load_order()
+            .expect("Failed to get load order")
+            .is_empty()
+    );
+
+    client
+        .set_load_order(&[overhaul_id, base_id, rival_id])
+        .expect("Failed to set load order");
+    assert_eq!(
+        client.get_load_order().expect("Failed to get load order"),
+        vec![overhaul_id, base_id, rival_id]
+    );
+
+    let issues = client
+        .validate_load_order()
+        .expect("Failed to validate load order");
+    assert!(issues.contains(&DependencyIssue::OutOfOrder {
+        module: String::from("metal_overhaul"),
+        must_load_after: String::from("base_metals"),
+    }));
+    assert!(issues.contains(&DependencyIssue::MissingRequirement {
+        module: String::from("metal_overhaul"),
+        required: String::from("metal_graphics"),
+    }));
+    assert!(issues.contains(&DependencyIssue::Conflict {
+        module: String::from("rival_overhaul"),
+        conflicts_with: String::from("metal_overhaul"),
+    }));
+    assert!(issues.contains(&DependencyIssue::OutOfOrder {
+        module: String::from("metal_overhaul"),
+        must_load_after: String::from("rival_overhaul"),
+    }));
+    assert_eq!(issues.len(), 4, "Unexpected issues: {issues:?}");
+
+    assert!(client.set_load_order(&[base_id, 9999]).is_err());
+    assert_eq!(
+        client.get_load_order().expect("Failed to get load order"),
+        vec![overhaul_id, base_id, rival_id],
+        "A failed update should keep the previous load order"
+    );
+}
+
+#[test]
+fn searches_effective_definitions_under_load_order() {
+    let (_dir, mut client, [base, overhaul, _]) = setup_modules();
+    let base_id = module_id(&client, &base);
+    let overhaul_id = module_id(&client, &overhaul);
+
+    // The steel of the last loaded module is the effective one
+    client
+        .set_load_order(&[base_id, overhaul_id])
+        .expect("Failed to set load order");
+    let query = SearchQuery {
+        effective_only: true,
+        limit: 10,
+        ..Default::default()
+    };
+    let results = client.search_raws(&query).expect("Search failed");
+    assert_eq!(results.total_count, 2);
+    let steel = results
+        .results
+        .iter()
+        .find(|r| identifier_from_json_blob(&r.data) == "STEEL")
+        .expect("Expected STEEL to be effective");
+    let steel_raw = client.get_raw(steel.id).expect("Failed to get raw");
+    assert_eq!(steel_raw.get_module_object_id(), overhaul.get_object_id());
+
+    // Without the load order restriction every definition is returned
+    let results = client
+        .search_raws(&SearchQuery {
+            limit: 10,
+            ..Default::default()
+        })
+        .expect("Search failed");
+    assert_eq!(results.total_count, 4);
+}
@@ -0,0 +1,105 @@
+//! Tests for verifying that the best sprite for a creature can be found in the database.
+
+use dfraw_parser::{
+    Graphic, ModuleInfo, ParseResult,
+    graphics::SpriteState,
+    legends_export::LegendsWorld,
+    metadata::{RawMetadata, RawModuleLocation},
+    tokens::{GraphicTypeToken, ObjectType},
+    traits::RawObject,
+};
+use dfraw_parser_sqlite_lib::{ClientOptions, DbClient};
+
+use crate::common::setup_tracing;
+
+mod common;
+
+/// Creates the `CREATURE_GRAPHICS` for a creature with an adult, child and skeleton sprite.
+fn creature_graphics(module: &ModuleInfo, identifier: &str) -> Box<dyn RawObject> {
+    let metadata = RawMetadata::new(
+        module,
+        ObjectType::Graphics,
+        identifier,
+        &"graphics_creatures.txt",
+        true,
+    );
+    let mut graphic = Graphic::new(identifier, &metadata, GraphicTypeToken::Creature);
+    for sprite in [
+        "DEFAULT:CREATURES:0:0:AS_IS:DEFAULT",
+        "CHILD:CREATURES:1:0:AS_IS:DEFAULT",
+        "DEFAULT:CREATURES:2:0:AS_IS:SKELETON",
+        "CORPSE:CREATURES:3:0:AS_IS",
+    ] {
+        let (key, value) = sprite.split_once(':').unwrap_or_default();
+        graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+    }
+    Box::new(graphic)
+}
+
+fn best_offset_x(client: &DbClient, identifier: &str, state: SpriteState) -> Option<i32> {
+    client
+        .get_best_sprite_graphic_for_target_identifier(identifier, None, state)
+        .expect("Failed to get best sprite")
+        .map(|sprite| sprite.offset_x)
+}
+
+#[test]
+fn finds_best_sprite_for_state() {
+    setup_tracing();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+
+    let module = ModuleInfo::new(
+        "creature_graphics",
+        RawModuleLocation::InstalledMods,
+        "mods",
+    );
+    let mut client = DbClient::init_db(
+        dir.join("sprite_graphics.db")
+            .to_str()
+            .expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+    client
+        .insert_parse_results(&ParseResult {
+            raws: vec![creature_graphics(&module, "DOG")],
+            modules: vec![module.clone()],
+            legends: LegendsWorld::default(),
+        })
+        .expect("Failed to insert parse results");
+
+    // Raws created one at a time go through a different insert than parse results
+    let module_id = client
+        .try_get_module_id(&module)
+        .expect("Failed to get module id")
+        .expect("Module was not inserted");
+    client
+        .create_raw_with_module(module_id, &creature_graphics(&module, "CAT"))
+        .expect("Failed to create raw");
+
+    for identifier in ["DOG", "CAT"] {
+        assert_eq!(
+            best_offset_x(&client, identifier, SpriteState::Default),
+            Some(0)
+        );
+        assert_eq!(
+            best_offset_x(&client, identifier, SpriteState::Child),
+            Some(1)
+        );
+        assert_eq!(
+            best_offset_x(&client, identifier, SpriteState::Skeleton),
+            Some(2)
+        );
+        // A sprite without a secondary condition counts as DEFAULT
+        assert_eq!(
+            best_offset_x(&client, identifier, SpriteState::Corpse),
+            Some(3)
+        );
+        assert_eq!(
+            best_offset_x(&client, identifier, SpriteState::Zombie),
+            Some(0)
+        );
+    }
+    assert_eq!(best_offset_x(&client, "COW", SpriteState::Default), None);
+}
@@ -0,0 +1,131 @@
+//! Tests for verifying that the layer sets of a graphic are stored and loaded with their conditions.
+
+use dfraw_parser::{
+    Graphic, ModuleInfo, ParseResult,
+    legends_export::LegendsWorld,
+    metadata::{RawMetadata, RawModuleLocation},
+    tokens::{GraphicTypeToken, ObjectType},
+};
+use dfraw_parser_sqlite_lib::{ClientOptions, DbClient, models::SpriteLayerData};
+
+use crate::common::setup_tracing;
+
+mod common;
+
+/// Creates the `CREATURE_GRAPHICS` for a creature with a default and a portrait layer set
+fn layered_graphics(module: &ModuleInfo, identifier: &str) -> Graphic {
+    let metadata = RawMetadata::new(
+        module,
+        ObjectType::Graphics,
+        identifier,
+        &"graphics_creatures_layered.txt",
+        true,
+    );
+    let mut graphic = Graphic::new(identifier, &metadata, GraphicTypeToken::Creature);
+    for (key, value) in [
+        ("LAYER_SET", "DEFAULT"),
+        ("LAYER", "BODY:PAGE:0:0"),
+        ("LAYER", "BEARD:PAGE:1:0"),
+        ("CONDITION_CASTE", "MALE"),
+        ("CONDITION_NOT_CHILD", ""),
+        ("LAYER", "HELM:PAGE:2:0"),
+        (
+            "CONDITION_ITEM_WORN",
+            "BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM",
+        ),
+        ("LAYER_SET", "PORTRAIT"),
+        ("LAYER", "HEAD:PORTRAIT_PAGE:0:0"),
+        ("CONDITION_CASTE", "FEMALE"),
+    ] {
+        graphic.parse_sprite_from_tag(key, value, GraphicTypeToken::Creature);
+    }
+    graphic
+}
+
+/// Each layer as its name and its conditions (as `CONDITION:value`)
+fn summary(layers: &[SpriteLayerData]) -> Vec<(String, Vec<String>)> {
+    layers
+        .iter()
+        .map(|layer| {
+            (
+                layer.layer_name.clone(),
+                layer
+                    .conditions
+                    .iter()
+                    .map(|condition| format!("{}:{}", condition.condition, condition.value))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn loads_layers_with_their_conditions() {
+    setup_tracing();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+
+    let module = ModuleInfo::new("layered_graphics", RawModuleLocation::InstalledMods, "mods");
+    let mut client = DbClient::init_db(
+        dir.join("sprite_layers.db")
+            .to_str()
+            .expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+    client
+        .insert_parse_results(&ParseResult {
+            raws: vec![
+                Box::new(layered_graphics(&module, "DWARF")),
+                Box::new(layered_graphics(&module, "ELF")),
+            ],
+            modules: vec![module.clone()],
+            legends: LegendsWorld::default(),
+        })
+        .expect("Failed to insert parse results");
+
+    let layers = client
+        .get_sprite_layers_for_target_identifier("DWARF")
+        .expect("Failed to get sprite layers");
+    assert_eq!(
+        summary(&layers),
+        vec![
+            (String::from("BODY"), vec![]),
+            (
+                String::from("BEARD"),
+                vec![
+                    String::from("CONDITION_CASTE:MALE"),
+                    String::from("CONDITION_NOT_CHILD:")
+                ]
+            ),
+            (
+                String::from("HELM"),
+                vec![String::from(
+                    "CONDITION_ITEM_WORN:BY_CATEGORY:HEAD:HELM:ITEM_HELM_HELM"
+                )]
+            ),
+            (
+                String::from("HEAD"),
+                vec![String::from("CONDITION_CASTE:FEMALE")]
+            ),
+        ]
+    );
+
+    // Only the conditions of the layers in the set are loaded
+    let portrait = client
+        .get_sprite_layer_set_for_target_identifier("DWARF", "portrait")
+        .expect("Failed to get sprite layer set");
+    assert_eq!(
+        summary(&portrait),
+        vec![(
+            String::from("HEAD"),
+            vec![String::from("CONDITION_CASTE:FEMALE")]
+        )]
+    );
+    assert!(
+        client
+            .get_sprite_layer_set_for_target_identifier("DWARF", "CORPSE")
+            .expect("Failed to get sprite layer set")
+            .is_empty()
+    );
+}
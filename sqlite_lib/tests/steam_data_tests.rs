@@ -0,0 +1,129 @@
+//! Tests for verifying that the steam data of modules is stored and searchable.
+
+use dfraw_parser::{ModuleInfo, ParseResult, legends_export::LegendsWorld};
+use dfraw_parser_sqlite_lib::{ClientOptions, DbClient};
+
+use crate::common::setup_tracing;
+
+mod common;
+
+const WORKSHOP_CREATURES_INFO: &str = r"[ID:workshop_creatures]
+[NUMERIC_VERSION:3]
+[NAME:Workshop Creatures]
+[STEAM_FILE_ID:2887593419]
+[STEAM_TAG:Creatures]
+[STEAM_TAG:Graphics]
+[STEAM_KEY_VALUE_TAG:difficulty:hard]
+[STEAM_KEY_VALUE_TAG:standalone]
+[STEAM_METADATA:made for 50.x]
+[STEAM_METADATA:requires graphics]
+";
+
+const WORKSHOP_PLANTS_INFO: &str = r"[ID:workshop_plants]
+[NUMERIC_VERSION:1]
+[NAME:Workshop Plants]
+[STEAM_FILE_ID:2887593420]
+[STEAM_TAG:Plants]
+[STEAM_TAG:graphics]
+";
+
+/// Writes an `info.txt` for a module and parses it
+fn parse_module_info(dir: &std::path::Path, directory: &str, info: &str) -> ModuleInfo {
+    let module_dir = dir.join(directory);
+    std::fs::create_dir_all(&module_dir).expect("Failed to create module dir");
+    let info_path = module_dir.join("info.txt");
+    std::fs::write(&info_path, info).expect("Failed to write info.txt");
+    ModuleInfo::parse(&info_path, false).expect("Failed to parse info.txt")
+}
+
+#[test]
+fn stores_and_searches_steam_data() {
+    setup_tracing();
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let creatures = parse_module_info(dir, "workshop_creatures", WORKSHOP_CREATURES_INFO);
+    let plants = parse_module_info(dir, "workshop_plants", WORKSHOP_PLANTS_INFO);
+
+    let mut client = DbClient::init_db(
+        dir.join("steam_data.db")
+            .to_str()
+            .expect("Temp path is not valid UTF-8"),
+        ClientOptions::default(),
+    )
+    .expect("Failed to open database");
+    client
+        .insert_parse_results(&ParseResult {
+            raws: Vec::new(),
+            modules: vec![creatures.clone(), plants.clone()],
+            legends: LegendsWorld::default(),
+        })
+        .expect("Failed to insert parse results");
+
+    let creatures_id = client
+        .try_get_module_id(&creatures)
+        .expect("Failed to get module id")
+        .expect("Module was not inserted");
+    let plants_id = client
+        .try_get_module_id(&plants)
+        .expect("Failed to get module id")
+        .expect("Module was not inserted");
+
+    assert_eq!(
+        client
+            .get_module_ids_by_steam_tag("creatures")
+            .expect("Failed to search by tag"),
+        vec![creatures_id]
+    );
+    let mut graphics = client
+        .get_module_ids_by_steam_tag("Graphics")
+        .expect("Failed to search by tag");
+    graphics.sort_unstable();
+    let mut both = vec![creatures_id, plants_id];
+    both.sort_unstable();
+    assert_eq!(graphics, both);
+    assert!(
+        client
+            .get_module_ids_by_steam_tag("Items")
+            .expect("Failed to search by tag")
+            .is_empty()
+    );
+
+    assert_eq!(
+        client
+            .get_module_ids_by_steam_file_id(2_887_593_420)
+            .expect("Failed to search by file id"),
+        vec![plants_id]
+    );
+
+    assert_eq!(
+        client.get_steam_tags().expect("Failed to get tags"),
+        vec!["Creatures", "Graphics", "graphics", "Plants"]
+    );
+    assert_eq!(
+        client
+            .get_steam_tags_for_module(creatures_id)
+            .expect("Failed to get module tags"),
+        vec!["Creatures", "Graphics"]
+    );
+    assert_eq!(
+        client
+            .get_steam_key_value_tags_for_module(creatures_id)
+            .expect("Failed to get key/value tags"),
+        vec![
+            (String::from("difficulty"), Some(String::from("hard"))),
+            (String::from("standalone"), None),
+        ]
+    );
+    assert_eq!(
+        client
+            .get_steam_metadata_for_module(creatures_id)
+            .expect("Failed to get metadata"),
+        vec!["made for 50.x", "requires graphics"]
+    );
+    assert!(
+        client
+            .get_steam_metadata_for_module(plants_id)
+            .expect("Failed to get metadata")
+            .is_empty()
+    );
+}
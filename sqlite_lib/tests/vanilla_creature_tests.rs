@@ -0,0 +1,120 @@
+//! Tests for verifying that the vanilla creatures which use creature variations resolve to the tags the
+//! variations produce.
+//!
+//! These use the vanilla raws, which are downloaded (once) by the test util.
+
+use dfraw_parser::{
+    Caste, Creature, TokenAction, TokenSource,
+    metadata::ParserOptions,
+    tokens::{CasteToken, CreatureToken},
+    traits::RawObject as _,
+};
+use dfraw_parser_test_util::ensure_vanilla_raws;
+
+use crate::common::setup_tracing;
+
+mod common;
+
+/// Parse the vanilla creatures (and their variations from `c_variation_default.txt`), and get the
+/// creature with an identifier
+fn vanilla_creature(identifier: &str) -> Creature {
+    let module_path = ensure_vanilla_raws()
+        .join("data")
+        .join("vanilla")
+        .join("vanilla_creatures");
+    let mut options = ParserOptions::default();
+    options.add_raw_module_to_parse(&module_path);
+    options.trace_creature_resolution();
+    dfraw_parser::parse(&options)
+        .expect("Failed to parse the vanilla creatures")
+        .raws
+        .iter()
+        .filter_map(|raw| raw.as_any().downcast_ref::<Creature>())
+        .find(|creature| creature.get_identifier() == identifier)
+        .expect("Vanilla creature should exist")
+        .clone()
+}
+
+fn caste<'a>(creature: &'a Creature, identifier: &str) -> &'a Caste {
+    creature
+        .get_castes()
+        .iter()
+        .find(|caste| caste.get_identifier() == identifier)
+        .expect("Caste should exist")
+}
+
+/// True if the trace shows the token being removed by the creature variation
+fn removed_by(creature: &Creature, key: &str, variation_identifier: &str) -> bool {
+    creature.explain_token(key).iter().any(|origin| {
+        origin.get_action() == TokenAction::Removed
+            && matches!(
+                origin.get_source(),
+                TokenSource::Variation { variation, .. } if variation == variation_identifier
+            )
+    })
+}
+
+#[test]
+fn vanilla_giant_creature() {
+    setup_tracing();
+    let giant_toad = vanilla_creature("GIANT_TOAD");
+    assert_eq!(giant_toad.get_copy_tags_from(), "TOAD");
+
+    // GIANT removes what the toad has as a small animal
+    assert!(removed_by(&giant_toad, "PET_EXOTIC", "GIANT"));
+    assert!(removed_by(&giant_toad, "PETVALUE", "GIANT"));
+    let all = caste(&giant_toad, "ALL");
+    assert!(!all.has_tag(&CasteToken::PetValue { pet_value: 0 }));
+
+    // GIANT is applied after the toad's castes are copied, so it lands on all of them
+    for origin in giant_toad.get_resolution_trace().unwrap_or_default() {
+        if matches!(origin.get_source(), TokenSource::Variation { variation, .. } if variation == "GIANT")
+        {
+            assert_eq!(origin.get_castes(), ["ALL"], "{origin:?}");
+        }
+    }
+    assert!(giant_toad.has_tag(&CreatureToken::LargeRoaming));
+    assert!(giant_toad.has_tag(&CreatureToken::Savage));
+
+    // The size GIANT gives is removed by the giant toad, which sets its own
+    assert_eq!(
+        all.get_tags()
+            .iter()
+            .filter_map(|tag| match tag {
+                CasteToken::ChangeBodySizePercent { percent } => Some(*percent),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        vec![100_700]
+    );
+}
+
+#[test]
+fn vanilla_animal_person() {
+    setup_tracing();
+    let toad_man = vanilla_creature("TOAD_MAN");
+    assert_eq!(toad_man.get_copy_tags_from(), "TOAD");
+
+    assert!(removed_by(&toad_man, "PET_EXOTIC", "ANIMAL_PERSON"));
+    let all = caste(&toad_man, "ALL");
+    assert!(!all.has_tag(&CasteToken::PetExotic));
+    assert!(!all.has_tag(&CasteToken::PetValue { pet_value: 0 }));
+
+    // The tags ANIMAL_PERSON adds after its `SELECT_CASTE:ALL` land on every caste
+    assert!(all.has_tag(&CasteToken::CanLearn));
+    assert!(all.has_tag(&CasteToken::Equips));
+    assert!(
+        all.get_tags()
+            .contains(&CasteToken::GravitateBodySize { target: 70_000 })
+    );
+
+    // The castes selected after the variation get their own names
+    for (identifier, name) in [("MALE", "toad man"), ("FEMALE", "toad woman")] {
+        assert_eq!(
+            caste(&toad_man, identifier)
+                .get_caste_name()
+                .map(|caste_name| caste_name.get_singular().to_string()),
+            Some(String::from(name))
+        );
+    }
+}
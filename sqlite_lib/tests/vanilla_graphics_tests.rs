@@ -0,0 +1,131 @@
+//! Tests for verifying that the item, building and terrain sprites in the vanilla graphics raws are parsed.
+//!
+//! These use the vanilla raws, which are downloaded (once) by the test util.
+
+use std::path::PathBuf;
+
+use dfraw_parser::{
+    Graphic, SpriteGraphic,
+    metadata::ParserOptions,
+    parse_raw_file,
+    tokens::{GraphicCategory, GraphicTypeToken},
+};
+use dfraw_parser_test_util::ensure_vanilla_raws;
+
+use crate::common::setup_tracing;
+
+mod common;
+
+/// The raw files in the `graphics` folders of the vanilla modules
+fn vanilla_graphics_files() -> Vec<PathBuf> {
+    let vanilla_dir = ensure_vanilla_raws().join("data").join("vanilla");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&vanilla_dir)
+        .expect("Failed to read the vanilla raws")
+        .filter_map(Result::ok)
+        .map(|module| module.path().join("graphics"))
+        .filter(|graphics_dir| graphics_dir.is_dir())
+        .flat_map(|graphics_dir| {
+            std::fs::read_dir(graphics_dir)
+                .expect("Failed to read a graphics folder")
+                .filter_map(Result::ok)
+                .map(|file| file.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn vanilla_graphics() -> Vec<Graphic> {
+    let options = ParserOptions::default();
+    vanilla_graphics_files()
+        .iter()
+        .flat_map(|path| {
+            parse_raw_file(path, &options)
+                .expect("Failed to parse a vanilla graphics file")
+                .parsed_raws
+        })
+        .filter_map(|raw| raw.as_any().downcast_ref::<Graphic>().cloned())
+        .collect()
+}
+
+fn summary(sprite: &SpriteGraphic) -> (Option<GraphicTypeToken>, String, Option<String>) {
+    (
+        sprite.get_graphic_type(),
+        sprite.get_target_identifier().to_string(),
+        sprite.get_material().map(String::from),
+    )
+}
+
+#[test]
+fn vanilla_tile_graphics_have_targets() {
+    setup_tracing();
+    let graphics = vanilla_graphics();
+    assert!(!graphics.is_empty(), "Expected vanilla graphics");
+
+    let tile_sprites: Vec<SpriteGraphic> = graphics
+        .iter()
+        .filter(|graphic| graphic.get_graphic_type() == GraphicTypeToken::Tile)
+        .flat_map(Graphic::get_sprites)
+        .collect();
+    assert!(!tile_sprites.is_empty(), "Expected TILE_GRAPHICS sprites");
+
+    // The variants of an item (e.g. `TOOL_GRAPHICS_WOOD`) get the item named before them
+    let variants: Vec<&SpriteGraphic> = tile_sprites
+        .iter()
+        .filter(|sprite| {
+            sprite
+                .get_graphic_type()
+                .is_some_and(|graphic_type| graphic_type.is_target_variant())
+        })
+        .collect();
+    assert!(!variants.is_empty(), "Expected item variant sprites");
+    for sprite in variants {
+        assert!(
+            !sprite.get_target_identifier().is_empty(),
+            "Variant sprite without a target: {sprite:?}"
+        );
+    }
+
+    for category in [GraphicCategory::Item, GraphicCategory::Building] {
+        assert!(
+            tile_sprites.iter().any(|sprite| sprite
+                .get_graphic_type()
+                .is_some_and(|graphic_type| graphic_type.get_category() == category)),
+            "Expected {category:?} sprites"
+        );
+    }
+    assert!(
+        graphics.iter().any(|graphic| !graphic
+            .get_sprites_for_target("ITEM_WEAPON_SWORD_SHORT")
+            .is_empty()),
+        "Expected sprites for the short sword"
+    );
+}
+
+#[test]
+fn vanilla_graphics_round_trip() {
+    setup_tracing();
+    for graphic in vanilla_graphics() {
+        let json = serde_json::to_value(&graphic).expect("Failed to serialize graphic");
+        let round_trip: Graphic =
+            serde_json::from_value(json.clone()).expect("Failed to deserialize graphic");
+        assert_eq!(
+            serde_json::to_value(&round_trip).expect("Failed to serialize graphic"),
+            json
+        );
+        assert_eq!(
+            round_trip
+                .get_sprites()
+                .iter()
+                .map(summary)
+                .collect::<Vec<_>>(),
+            graphic
+                .get_sprites()
+                .iter()
+                .map(summary)
+                .collect::<Vec<_>>()
+        );
+    }
+}